@@ -91,6 +91,7 @@ pub(crate) fn sample_request_network_message() -> NetworkMessage {
         ext_budget: None,
         ext_timeout: None,
         ext_ts_stack: None,
+        ext_trace_context: None,
         payload: RequestBody::from(Query::default()),
     };
     NetworkBody::Request(request).into()
@@ -114,6 +115,7 @@ pub(crate) fn sample_response_network_message() -> NetworkMessage {
         ext_tstamp: None,
         ext_respid: None,
         ext_ts_stack: None,
+        ext_trace_context: None,
     };
     NetworkBody::Response(response).into()
 }
@@ -240,6 +242,8 @@ pub(crate) fn sample_init_syn() -> InitSyn {
         ext_compression: None,
         ext_patch: init::ext::PatchType::NONE,
         ext_region_name: None,
+        ext_fec: None,
+        ext_compression_algorithm: None,
     }
 }
 
@@ -260,6 +264,8 @@ pub(crate) fn sample_init_ack() -> InitAck {
         ext_compression: None,
         ext_patch: init::ext::PatchType::NONE,
         ext_region_name: None,
+        ext_fec: None,
+        ext_compression_algorithm: None,
     }
 }
 
@@ -304,6 +310,7 @@ pub(crate) fn sample_join() -> Join {
         next_sn: PrioritySn::DEFAULT,
         ext_qos: None,
         ext_shm: None,
+        ext_nack: None,
         ext_patch: join::ext::PatchType::NONE,
     }
 }
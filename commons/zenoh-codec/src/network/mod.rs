@@ -18,6 +18,7 @@ mod push;
 mod request;
 mod response;
 mod timestamp_stack;
+mod trace_context;
 
 use zenoh_buffers::{
     reader::{BacktrackableReader, DidntRead, Reader},
@@ -42,6 +42,7 @@ where
             ext_tstamp,
             ext_nodeid,
             ext_ts_stack,
+            ext_trace_context,
             payload,
         } = x;
 
@@ -50,7 +51,8 @@ where
         let mut n_exts = ((ext_qos != &ext::QoSType::DEFAULT) as u8)
             + (ext_tstamp.is_some() as u8)
             + ((ext_nodeid != &ext::NodeIdType::DEFAULT) as u8)
-            + (ext_ts_stack.is_some() as u8);
+            + (ext_ts_stack.is_some() as u8)
+            + (ext_trace_context.is_some() as u8);
         if n_exts != 0 {
             header |= flag::Z;
         }
@@ -82,6 +84,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (ts_stack, n_exts != 0))?;
         }
+        if let Some(trace_context) = ext_trace_context.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (trace_context, n_exts != 0))?;
+        }
         // Payload
         self.write(&mut *writer, payload)?;
 
@@ -128,6 +134,7 @@ where
         let mut ext_tstamp = None;
         let mut ext_nodeid = ext::NodeIdType::DEFAULT;
         let mut ext_ts_stack = None;
+        let mut ext_trace_context = None;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -154,6 +161,11 @@ where
                     ext_ts_stack = Some(ts);
                     has_ext = ext;
                 }
+                ext::TraceContext::ID => {
+                    let (tc, ext): (ext::TraceContextType, bool) = eodec.read(&mut *reader)?;
+                    ext_trace_context = Some(tc);
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "Push", ext)?;
                 }
@@ -170,6 +182,7 @@ where
             ext_tstamp,
             ext_nodeid,
             ext_ts_stack,
+            ext_trace_context,
         })
     }
 }
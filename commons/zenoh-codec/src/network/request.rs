@@ -85,6 +85,7 @@ where
             ext_budget,
             ext_timeout,
             ext_ts_stack,
+            ext_trace_context,
             payload,
         } = x;
 
@@ -96,7 +97,8 @@ where
             + (ext_budget.is_some() as u8)
             + (ext_timeout.is_some() as u8)
             + ((ext_nodeid != &ext::NodeIdType::DEFAULT) as u8)
-            + (ext_ts_stack.is_some() as u8);
+            + (ext_ts_stack.is_some() as u8)
+            + (ext_trace_context.is_some() as u8);
         if n_exts != 0 {
             header |= flag::Z;
         }
@@ -143,6 +145,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (ts_stack, n_exts != 0))?;
         }
+        if let Some(trace_context) = ext_trace_context.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (trace_context, n_exts != 0))?;
+        }
 
         // Payload
         self.write(&mut *writer, payload)?;
@@ -194,6 +200,7 @@ where
         let mut ext_limit = None;
         let mut ext_timeout = None;
         let mut ext_ts_stack = None;
+        let mut ext_trace_context = None;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -235,6 +242,11 @@ where
                     ext_ts_stack = Some(ts);
                     has_ext = ext;
                 }
+                ext::TraceContext::ID => {
+                    let (tc, ext): (ext::TraceContextType, bool) = eodec.read(&mut *reader)?;
+                    ext_trace_context = Some(tc);
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "Request", ext)?;
                 }
@@ -255,6 +267,7 @@ where
             ext_budget: ext_limit,
             ext_timeout,
             ext_ts_stack,
+            ext_trace_context,
         })
     }
 }
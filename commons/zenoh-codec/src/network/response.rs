@@ -46,6 +46,7 @@ where
             ext_tstamp,
             ext_respid,
             ext_ts_stack,
+            ext_trace_context,
         } = x;
 
         // Header
@@ -53,7 +54,8 @@ where
         let mut n_exts = ((ext_qos != &ext::QoSType::DEFAULT) as u8)
             + (ext_tstamp.is_some() as u8)
             + (ext_respid.is_some() as u8)
-            + (ext_ts_stack.is_some() as u8);
+            + (ext_ts_stack.is_some() as u8)
+            + (ext_trace_context.is_some() as u8);
         if n_exts != 0 {
             header |= flag::Z;
         }
@@ -86,6 +88,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (ts_stack, n_exts != 0))?;
         }
+        if let Some(trace_context) = ext_trace_context.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (trace_context, n_exts != 0))?;
+        }
 
         // Payload
         self.write(&mut *writer, payload)?;
@@ -134,6 +140,7 @@ where
         let mut ext_tstamp = None;
         let mut ext_respid = None;
         let mut ext_ts_stack = None;
+        let mut ext_trace_context = None;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -160,6 +167,11 @@ where
                     ext_ts_stack = Some(ts);
                     has_ext = ext;
                 }
+                ext::TraceContext::ID => {
+                    let (tc, ext): (ext::TraceContextType, bool) = eodec.read(&mut *reader)?;
+                    ext_trace_context = Some(tc);
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "Response", ext)?;
                 }
@@ -177,6 +189,7 @@ where
             ext_tstamp,
             ext_respid,
             ext_ts_stack,
+            ext_trace_context,
         })
     }
 }
@@ -0,0 +1,81 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use alloc::string::String;
+
+use zenoh_buffers::{
+    reader::{DidntRead, Reader},
+    writer::{DidntWrite, Writer},
+};
+use zenoh_protocol::{common::ZExtZBufHeader, network::trace_context::TraceContextType};
+
+use crate::{LCodec, RCodec, WCodec, Zenoh080, Zenoh080Header};
+
+impl<const ID: u8> LCodec<&TraceContextType<{ ID }>> for Zenoh080 {
+    fn w_len(self, x: &TraceContextType<{ ID }>) -> usize {
+        self.w_len(&x.traceparent) + self.w_len(&x.tracestate)
+    }
+}
+
+impl<W, const ID: u8> WCodec<(&TraceContextType<{ ID }>, bool), &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: (&TraceContextType<{ ID }>, bool)) -> Self::Output {
+        let (x, more) = x;
+        let header: ZExtZBufHeader<{ ID }> = ZExtZBufHeader::new(self.w_len(x));
+        self.write(&mut *writer, (&header, more))?;
+        self.write(&mut *writer, &x.traceparent)?;
+        self.write(&mut *writer, &x.tracestate)
+    }
+}
+
+impl<R, const ID: u8> RCodec<(TraceContextType<{ ID }>, bool), &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<(TraceContextType<{ ID }>, bool), Self::Error> {
+        let header: u8 = self.read(&mut *reader)?;
+        let codec = Zenoh080Header::new(header);
+        codec.read(reader)
+    }
+}
+
+impl<R, const ID: u8> RCodec<(TraceContextType<{ ID }>, bool), &mut R> for Zenoh080Header
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<(TraceContextType<{ ID }>, bool), Self::Error> {
+        let (header, more): (ZExtZBufHeader<{ ID }>, bool) = self.read(&mut *reader)?;
+        // The body must be exactly of the declared length
+        let mut body = reader.read_zslice(header.len)?;
+        let traceparent: String = self.codec.read(&mut body)?;
+        let tracestate: String = self.codec.read(&mut body)?;
+        if body.can_read() {
+            return Err(DidntRead);
+        }
+        Ok((
+            TraceContextType {
+                traceparent,
+                tracestate,
+            },
+            more,
+        ))
+    }
+}
@@ -54,6 +54,8 @@ where
             ext_compression,
             ext_patch,
             ext_region_name,
+            ext_fec,
+            ext_compression_algorithm,
         } = x;
 
         // Header
@@ -68,7 +70,9 @@ where
             + (ext_lowlatency.is_some() as u8)
             + (ext_compression.is_some() as u8)
             + (*ext_patch != ext::PatchType::NONE) as u8
-            + (ext_region_name.is_some() as u8);
+            + (ext_region_name.is_some() as u8)
+            + (ext_fec.is_some() as u8)
+            + (ext_compression_algorithm.is_some() as u8);
 
         #[cfg(feature = "shared-memory")]
         {
@@ -137,6 +141,14 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (region_name, n_exts != 0))?;
         }
+        if let Some(fec) = ext_fec.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (fec, n_exts != 0))?;
+        }
+        if let Some(algorithm) = ext_compression_algorithm.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (algorithm, n_exts != 0))?;
+        }
 
         Ok(())
     }
@@ -200,6 +212,8 @@ where
         let mut ext_compression = None;
         let mut ext_patch = ext::PatchType::NONE;
         let mut ext_northtag = None;
+        let mut ext_fec = None;
+        let mut ext_compression_algorithm = None;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -252,6 +266,16 @@ where
                     ext_northtag = Some(p);
                     has_ext = ext;
                 }
+                ext::Fec::ID => {
+                    let (f, ext): (ext::Fec, bool) = eodec.read(&mut *reader)?;
+                    ext_fec = Some(f);
+                    has_ext = ext;
+                }
+                ext::CompressionAlgorithm::ID => {
+                    let (a, ext): (ext::CompressionAlgorithm, bool) = eodec.read(&mut *reader)?;
+                    ext_compression_algorithm = Some(a);
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "InitSyn", ext)?;
                 }
@@ -274,6 +298,8 @@ where
             ext_compression,
             ext_patch,
             ext_region_name: ext_northtag,
+            ext_fec,
+            ext_compression_algorithm,
         })
     }
 }
@@ -303,6 +329,8 @@ where
             ext_compression,
             ext_patch,
             ext_region_name,
+            ext_fec,
+            ext_compression_algorithm,
         } = x;
 
         // Header
@@ -317,7 +345,9 @@ where
             + (ext_lowlatency.is_some() as u8)
             + (ext_compression.is_some() as u8)
             + (*ext_patch != ext::PatchType::NONE) as u8
-            + (ext_region_name.is_some() as u8);
+            + (ext_region_name.is_some() as u8)
+            + (ext_fec.is_some() as u8)
+            + (ext_compression_algorithm.is_some() as u8);
 
         #[cfg(feature = "shared-memory")]
         {
@@ -389,6 +419,14 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (region_name, n_exts != 0))?;
         }
+        if let Some(fec) = ext_fec.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (fec, n_exts != 0))?;
+        }
+        if let Some(algorithm) = ext_compression_algorithm.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (algorithm, n_exts != 0))?;
+        }
 
         Ok(())
     }
@@ -455,6 +493,8 @@ where
         let mut ext_compression = None;
         let mut ext_patch = ext::PatchType::NONE;
         let mut ext_region_name = None;
+        let mut ext_fec = None;
+        let mut ext_compression_algorithm = None;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -507,6 +547,16 @@ where
                     ext_region_name = Some(q);
                     has_ext = ext;
                 }
+                ext::Fec::ID => {
+                    let (f, ext): (ext::Fec, bool) = eodec.read(&mut *reader)?;
+                    ext_fec = Some(f);
+                    has_ext = ext;
+                }
+                ext::CompressionAlgorithm::ID => {
+                    let (a, ext): (ext::CompressionAlgorithm, bool) = eodec.read(&mut *reader)?;
+                    ext_compression_algorithm = Some(a);
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "InitAck", ext)?;
                 }
@@ -530,6 +580,8 @@ where
             ext_compression,
             ext_patch,
             ext_region_name,
+            ext_fec,
+            ext_compression_algorithm,
         })
     }
 }
@@ -132,6 +132,53 @@ where
     }
 }
 
+impl<W> WCodec<(&ext::NackType, bool), &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: (&ext::NackType, bool)) -> Self::Output {
+        let (x, more) = x;
+
+        // Header
+        let len = x.iter().fold(0, |acc, sn| acc + self.w_len(*sn));
+        let header = ZExtZBufHeader::<{ ext::Nack::ID }>::new(len);
+        self.write(&mut *writer, (&header, more))?;
+
+        // Body
+        for sn in x.iter() {
+            self.write(&mut *writer, *sn)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> RCodec<(ext::NackType, bool), &mut R> for Zenoh080Header
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<(ext::NackType, bool), Self::Error> {
+        // Header
+        let (header, more): (ZExtZBufHeader<{ ext::Nack::ID }>, bool) = self.read(&mut *reader)?;
+
+        // Body, which must be exactly of the declared length
+        let mut body = reader.read_zslice(header.len)?;
+        let mut ext_nack: ext::NackType = Box::new([0; Priority::NUM]);
+        for sn in ext_nack.iter_mut() {
+            *sn = self.codec.read(&mut body)?;
+        }
+        if body.can_read() {
+            return Err(DidntRead);
+        }
+
+        Ok((ext_nack, more))
+    }
+}
+
 // Join
 impl<W> WCodec<&Join, &mut W> for Zenoh080
 where
@@ -150,6 +197,7 @@ where
             next_sn,
             ext_qos,
             ext_shm,
+            ext_nack,
             ext_patch,
         } = x;
 
@@ -163,6 +211,7 @@ where
         }
         let mut n_exts = (ext_qos.is_some() as u8)
             + (ext_shm.is_some() as u8)
+            + (ext_nack.is_some() as u8)
             + (*ext_patch != ext::PatchType::NONE) as u8;
         if n_exts != 0 {
             header |= flag::Z;
@@ -204,6 +253,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (shm, n_exts != 0))?;
         }
+        if let Some(nack) = ext_nack.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (nack, n_exts != 0))?;
+        }
         if *ext_patch != ext::PatchType::NONE {
             n_exts -= 1;
             self.write(&mut *writer, (*ext_patch, n_exts != 0))?;
@@ -271,6 +324,7 @@ where
         // Extensions
         let mut ext_qos = None;
         let mut ext_shm = None;
+        let mut ext_nack = None;
         let mut ext_patch = ext::PatchType::NONE;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
@@ -288,6 +342,11 @@ where
                     ext_shm = Some(s);
                     has_ext = ext;
                 }
+                ext::Nack::ID => {
+                    let (n, ext): (ext::NackType, bool) = eodec.read(&mut *reader)?;
+                    ext_nack = Some(n);
+                    has_ext = ext;
+                }
                 ext::Patch::ID => {
                     let (p, ext): (ext::PatchType, bool) = eodec.read(&mut *reader)?;
                     ext_patch = p;
@@ -309,6 +368,7 @@ where
             next_sn,
             ext_qos,
             ext_shm,
+            ext_nack,
             ext_patch,
         })
     }
@@ -573,6 +573,61 @@ fn codec_push() {
     run!(Push, Push::rand());
 }
 
+#[test]
+fn codec_trace_context_length() {
+    use zenoh_buffers::writer::Writer;
+
+    let codec = Zenoh080::new();
+    let x = network::push::ext::TraceContextType::rand();
+    let len = codec.w_len(&x);
+    // The body must be exactly of the declared length
+    for (declared, trailing) in [(len - 1, 0), (len + 1, 1)] {
+        let mut buff = vec![];
+        let mut writer = buff.writer();
+        let header: ZExtZBufHeader<{ network::push::ext::TraceContext::ID }> =
+            ZExtZBufHeader::new(declared);
+        codec.write(&mut writer, (&header, false)).unwrap();
+        codec.write(&mut writer, &x.traceparent).unwrap();
+        codec.write(&mut writer, &x.tracestate).unwrap();
+        for _ in 0..trailing {
+            writer.write_u8(0).unwrap();
+        }
+
+        let mut reader = buff.reader();
+        let res: Result<(network::push::ext::TraceContextType, bool), _> = codec.read(&mut reader);
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn codec_join_nack_length() {
+    use zenoh_buffers::writer::Writer;
+
+    let codec = Zenoh080::new();
+    let x: transport::join::ext::NackType = Box::new([1, 2, 3, 4, 5, 6, 7, 8]);
+    let len = x.iter().fold(0, |acc, sn| acc + codec.w_len(*sn));
+    // The body must be exactly of the declared length
+    for (declared, trailing) in [(len - 1, 0), (len + 1, 1)] {
+        let mut buff = vec![];
+        let mut writer = buff.writer();
+        let header: ZExtZBufHeader<{ transport::join::ext::Nack::ID }> =
+            ZExtZBufHeader::new(declared);
+        codec.write(&mut writer, (&header, false)).unwrap();
+        for sn in x.iter() {
+            codec.write(&mut writer, *sn).unwrap();
+        }
+        for _ in 0..trailing {
+            writer.write_u8(0).unwrap();
+        }
+
+        let mut reader = buff.reader();
+        let header: u8 = codec.read(&mut reader).unwrap();
+        let res: Result<(transport::join::ext::NackType, bool), _> =
+            Zenoh080Header::new(header).read(&mut reader);
+        assert!(res.is_err());
+    }
+}
+
 #[test]
 fn codec_request() {
     run!(Request, Request::rand());
@@ -150,6 +150,33 @@ pub mod timestamping {
 #[allow(dead_code)]
 pub const queries_default_timeout: u64 = 10000;
 
+#[allow(non_upper_case_globals)]
+#[allow(dead_code)]
+pub mod stats {
+    pub mod latency {
+        pub const enabled: bool = false;
+        pub const sampling: f64 = 0.01;
+        pub const prefix_chunks: usize = 1;
+        pub const max_series: usize = 1000;
+    }
+    pub mod top {
+        pub const enabled: bool = false;
+        pub const prefix_chunks: usize = 2;
+        pub const max_keys: usize = 1000;
+        pub const n: usize = 10;
+    }
+    pub mod scrape {
+        pub const per_transport: bool = true;
+        pub const per_link: bool = true;
+        pub const per_key: bool = true;
+        pub const disconnected: bool = false;
+        pub mod federation {
+            pub const enabled: bool = false;
+            pub const timeout_ms: u64 = 1000;
+        }
+    }
+}
+
 #[allow(non_upper_case_globals)]
 #[allow(dead_code)]
 pub mod routing {
@@ -206,6 +233,7 @@ impl Default for TransportUnicastConf {
             lowlatency: false,
             qos: QoSUnicastConf::default(),
             compression: CompressionUnicastConf::default(),
+            fec: FecUnicastConf::default(),
         }
     }
 }
@@ -217,6 +245,8 @@ impl Default for TransportMulticastConf {
             max_sessions: Some(1000),
             qos: QoSMulticastConf::default(),
             compression: CompressionMulticastConf::default(),
+            fec: FecMulticastConf::default(),
+            reliability: ReliabilityMulticastConf::default(),
         }
     }
 }
@@ -234,17 +264,56 @@ impl Default for QoSMulticastConf {
     }
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for CompressionUnicastConf {
     fn default() -> Self {
-        Self { enabled: false }
+        Self {
+            enabled: false,
+            algorithm: CompressionAlgorithmConf::Lz4,
+            level: 3,
+            dictionary: None,
+            excluded_priorities: vec![],
+        }
     }
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for CompressionMulticastConf {
     fn default() -> Self {
-        Self { enabled: false }
+        Self {
+            enabled: false,
+            algorithm: CompressionAlgorithmConf::Lz4,
+            excluded_priorities: vec![],
+        }
+    }
+}
+
+impl Default for FecUnicastConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ratio: 0.25,
+        }
+    }
+}
+
+impl Default for FecMulticastConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ratio: 0.25,
+        }
+    }
+}
+
+impl Default for ReliabilityMulticastConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retransmission_window: 256,
+            nack_interval: 20,
+            gap_timeout: 1000,
+            max_pending: 1024,
+            history: 0,
+        }
     }
 }
 
@@ -351,6 +420,20 @@ impl Default for AclConfig {
             rules: None,
             subjects: None,
             policies: None,
+            audit: AclAuditConf::default(),
+        }
+    }
+}
+
+impl Default for AclAuditConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            log_allowed: false,
+            file: None,
+            max_file_size: None,
+            max_files: 5,
+            adminspace_records: 1000,
         }
     }
 }
@@ -46,7 +46,7 @@ use std::{
 
 use include::recursive_include;
 use nonempty_collections::NEVec;
-use qos::{PublisherQoSConfList, QosFilter, QosOverwriteMessage, QosOverwrites};
+use qos::{PriorityConf, PublisherQoSConfList, QosFilter, QosOverwriteMessage, QosOverwrites};
 use secrecy::{CloneableSecret, DebugSecret, Secret, SerializableSecret, Zeroize};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -122,7 +122,7 @@ pub enum DataMessage {
     Reply,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct DownsamplingRuleConf {
     /// A list of key-expressions to which the downsampling will be applied.
@@ -132,7 +132,7 @@ pub struct DownsamplingRuleConf {
     pub freq: f64,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct DownsamplingItemConf {
     /// Optional identifier for the downsampling configuration item
@@ -151,7 +151,7 @@ pub struct DownsamplingItemConf {
     pub flows: Option<NEVec<InterceptorFlow>>,
 }
 
-#[derive(Serialize, Debug, Deserialize, Clone)]
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct LowPassFilterConf {
     pub id: Option<String>,
@@ -163,6 +163,124 @@ pub struct LowPassFilterConf {
     pub size_limit: usize,
 }
 
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadFilterAttachmentConf {
+    /// Key that must be present in the attachment.
+    pub key: String,
+    /// Value the key must be associated to. Any value is accepted if None.
+    pub value: Option<String>,
+}
+
+#[derive(Serialize, Debug, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadFilterConf {
+    pub id: Option<String>,
+    pub interfaces: Option<NEVec<String>>,
+    pub link_protocols: Option<NEVec<InterceptorLink>>,
+    pub flows: Option<NEVec<InterceptorFlow>>,
+    pub messages: NEVec<DataMessage>,
+    pub key_exprs: NEVec<OwnedKeyExpr>,
+    /// Encodings admitted by the filter. An encoding without schema admits all its schemas.
+    /// Any encoding is admitted if None.
+    pub encodings: Option<NEVec<String>>,
+    /// Entries that must all be present in the attachment, which must be serialized as a
+    /// map of strings. Any attachment is admitted if None.
+    pub attachment: Option<NEVec<PayloadFilterAttachmentConf>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KeyExprRemappingRuleConf {
+    /// Key expression as seen by the remote node, either a key without wildcards or
+    /// a key without wildcards followed by `/**`.
+    pub remote: OwnedKeyExpr,
+    /// Key expression as seen by the local node, of the same form as `remote`.
+    pub local: OwnedKeyExpr,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KeyExprRemappingConf {
+    /// Optional identifier for the remapping configuration item.
+    pub id: Option<String>,
+    /// A list of interfaces to which the remapping will be applied.
+    /// Remapping will be applied for all interfaces if the parameter is None.
+    pub interfaces: Option<NEVec<String>>,
+    /// A list of ZIDs to which the remapping will be applied.
+    /// Remapping will be applied for all ZIDs if the parameter is None.
+    pub zids: Option<NEVec<ZenohId>>,
+    /// Remapping flow directions: ingress (remote to local) and/or egress (local to remote).
+    pub flows: Option<NEVec<InterceptorFlow>>,
+    /// A list of remapping rules, the first one matching a key expression is applied.
+    pub rules: NEVec<KeyExprRemappingRuleConf>,
+}
+
+/// Behavior of the rate limiter when a message exceeds the configured budget.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitAction {
+    /// Drop the messages exceeding the budget.
+    #[default]
+    Drop,
+    /// Hold back the put and delete messages exceeding the budget until it is replenished,
+    /// dropping them if they would be held back for more than `max_burst_ms`.
+    /// The other messages exceeding the budget are dropped.
+    Delay,
+}
+
+/// Algorithm used to compress the batches on a link.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithmConf {
+    /// lz4, fast and supported by every peer.
+    #[default]
+    Lz4,
+    /// zstd, better compression ratio at a higher CPU cost.
+    Zstd,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitRuleConf {
+    /// Key expression to which the budget applies.
+    pub key_expr: OwnedKeyExpr,
+    /// Maximum number of payload bytes (serialized payload + serialized attachment) per second.
+    pub bytes_per_sec: Option<u64>,
+    /// Maximum number of messages per second.
+    pub msgs_per_sec: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitItemConf {
+    /// Optional identifier for the rate limit configuration item.
+    pub id: Option<String>,
+    /// A list of interfaces to which the rate limit will be applied.
+    /// Rate limit will be applied for all interfaces if the parameter is None.
+    pub interfaces: Option<NEVec<Interface>>,
+    /// A list of certificate common names to which the rate limit will be applied.
+    pub cert_common_names: Option<NEVec<CertCommonName>>,
+    /// A list of usernames to which the rate limit will be applied.
+    pub usernames: Option<NEVec<Username>>,
+    /// A list of link types, transports having one of those link types will have the rate limit applied.
+    /// Rate limit will be applied for all link types if the parameter is None.
+    pub link_protocols: Option<NEVec<InterceptorLink>>,
+    /// A list of ZIDs to which the rate limit will be applied.
+    pub zids: Option<NEVec<ZenohId>>,
+    /// Rate limit flow directions: egress and/or ingress.
+    pub flows: Option<NEVec<InterceptorFlow>>,
+    /// List of message types on which the rate limit will be applied.
+    pub messages: NEVec<DataMessage>,
+    /// A list of rate limit rules: key expression and budgets.
+    pub rules: NEVec<RateLimitRuleConf>,
+    /// Behavior when a message exceeds the budget: "drop" (default) or "delay".
+    #[serde(default)]
+    pub action: RateLimitAction,
+    /// Maximum time in milliseconds a message is held back with the "delay" action.
+    pub max_burst_ms: Option<u64>,
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct AclConfigRule {
@@ -258,7 +376,7 @@ impl<'a> serde::Deserialize<'a> for ConfRange {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct QosOverwriteItemConf {
     /// Optional identifier for the qos modification configuration item.
@@ -391,6 +509,32 @@ pub struct StatsFilterConfig {
     pub key: OwnedKeyExpr,
 }
 
+/// A fraction, e.g. of the messages which are sampled. Unlike `f64`, it implements `Eq`, by
+/// comparing the bits of the values, so that the configurations holding it do too.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ratio(f64);
+
+impl Ratio {
+    pub const fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl PartialEq for Ratio {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for Ratio {}
+
+impl From<f64> for Ratio {
+    fn from(ratio: f64) -> Self {
+        Self(ratio)
+    }
+}
+
 pub trait ConfigValidator: Send + Sync {
     fn check_config(
         &self,
@@ -674,6 +818,21 @@ validated_struct::validator! {
                     /// You must compile zenoh with "transport_compression" feature to be able to enable compression.
                     /// When enabled is true, batches will be sent compressed. (default `false`).
                     enabled: bool,
+                    /// The compression algorithm, either `"lz4"` or `"zstd"`. (default `"lz4"`).
+                    algorithm: CompressionAlgorithmConf,
+                    /// The zstd compression level, in the [-7, 22] range. Negative levels trade ratio for speed. (default `3`).
+                    level: i8 where (compression_level_validator),
+                    /// Path to a pre-shared zstd dictionary, only used with the `"zstd"` algorithm. (default `null`).
+                    dictionary: Option<String>,
+                    /// Priorities whose batches are never compressed, e.g. the ones carrying already compressed video. (default `[]`).
+                    excluded_priorities: Vec<PriorityConf>,
+                },
+                pub fec: FecUnicastConf {
+                    /// Whether forward error correction is enabled or not on datagram links. (default `false`).
+                    enabled: bool,
+                    /// Ratio of parity batches over data batches, in the (0.0, 1.0] range. (default `0.25`).
+                    /// It can be overridden per endpoint with the `fec` metadata, e.g. `udp/192.168.1.1:7447?fec=0.5`.
+                    ratio: f64 where (fec_ratio_validator),
                 },
             },
             pub multicast: TransportMulticastConf {
@@ -690,6 +849,35 @@ validated_struct::validator! {
                     /// You must compile zenoh with "transport_compression" feature to be able to enable compression.
                     /// When enabled is true, batches will be sent compressed. (default `false`).
                     enabled: bool,
+                    /// The compression algorithm, only `"lz4"` is supported since the algorithm is not advertised
+                    /// to the other nodes of the group. (default `"lz4"`).
+                    algorithm: CompressionAlgorithmConf where (multicast_compression_algorithm_validator),
+                    /// Priorities whose batches are never compressed, e.g. the ones carrying already compressed video. (default `[]`).
+                    excluded_priorities: Vec<PriorityConf>,
+                },
+                pub fec: FecMulticastConf {
+                    /// Whether forward error correction is enabled or not. (default `false`).
+                    enabled: bool,
+                    /// Ratio of parity batches over data batches, in the (0.0, 1.0] range. (default `0.25`).
+                    /// It can be overridden per endpoint with the `fec` metadata.
+                    ratio: f64 where (fec_ratio_validator),
+                },
+                pub reliability: ReliabilityMulticastConf {
+                    /// Whether NACK-based reliability is enabled or not.
+                    /// When enabled, receivers request the retransmission of missing reliable batches
+                    /// and senders keep the recently sent reliable batches to serve those requests. (default `false`).
+                    enabled: bool,
+                    /// Number of reliable batches kept per priority for retransmission. (default `256`).
+                    /// With 0, no batch is kept and the NACKs of the other nodes are not served.
+                    retransmission_window: usize,
+                    /// Minimum interval in milliseconds between two NACKs for the same gap, greater than 0. (default `20`).
+                    nack_interval: u64 where (nack_interval_validator),
+                    /// Time in milliseconds after which a gap that has not been repaired is skipped. (default `1000`).
+                    gap_timeout: u64,
+                    /// Maximum number of reliable messages per priority buffered out of order while waiting for a gap to be repaired. (default `1024`).
+                    max_pending: usize,
+                    /// Number of past reliable sequence numbers per priority to request from a newly discovered peer. (default `0`).
+                    history: usize,
                 },
             },
             pub link: #[derive(Default)]
@@ -914,14 +1102,84 @@ validated_struct::validator! {
             pub rules: Option<Vec<AclConfigRule>>,
             pub subjects: Option<Vec<AclConfigSubjects>>,
             pub policies: Option<Vec<AclConfigPolicyEntry>>,
+            /// Configuration of the audit log of the access control decisions
+            pub audit: #[derive(PartialEq, Eq)] AclAuditConf {
+                pub enabled: bool,
+                /// Whether allow decisions are recorded too (only deny decisions are recorded by default)
+                pub log_allowed: bool,
+                /// Path of a file to which the records are appended as JSON lines.
+                /// The file settings can only be set at startup.
+                pub file: Option<String>,
+                /// Size in bytes beyond which the file is rotated, if any
+                pub max_file_size: Option<u64>,
+                /// Number of rotated files kept next to the file, as `<file>.1`, `<file>.2`, ...
+                pub max_files: usize,
+                /// Number of most recent records kept in memory and served on the adminspace
+                pub adminspace_records: usize,
+            },
         },
 
         /// Configuration of the low-pass filter
         pub low_pass_filter: Vec<LowPassFilterConf>,
 
+        /// Configuration of the payload filter
+        pub payload_filter: Vec<PayloadFilterConf>,
+
+        /// Configuration of the rate limit
+        pub rate_limit: Vec<RateLimitItemConf>,
+
+        /// Configuration of the key expressions remapping
+        pub keyexpr_remapping: Vec<KeyExprRemappingConf>,
+
         /// Configuration of the stats per keyexpr
         pub stats: #[derive(Default, PartialEq, Eq)] StatsConfig {
             filters: Vec<StatsFilterConfig>,
+            /// Per-hop latency analytics of the timestamp stacks carried by received messages
+            pub latency: #[derive(Default, PartialEq, Eq)]
+            StatsLatencyConf {
+                /// Whether the timestamp stacks of received messages are analyzed.
+                enabled: Option<bool>,
+                /// The fraction of instrumented messages which are analyzed, in ]0, 1].
+                sampling: Option<Ratio>,
+                /// The number of chunks of the key expression prefix by which latencies are aggregated.
+                prefix_chunks: Option<usize>,
+                /// The maximum number of (source, router, prefix) series, beyond which latencies are aggregated.
+                max_series: Option<usize>,
+            },
+            /// Per key expression prefix traffic statistics, reported as top talkers
+            pub top: #[derive(Default, PartialEq, Eq)]
+            StatsTopConf {
+                /// Whether message count and byte volume are tracked per key expression prefix.
+                enabled: Option<bool>,
+                /// The number of chunks of the key expression prefix by which traffic is aggregated.
+                prefix_chunks: Option<usize>,
+                /// The maximum number of tracked prefixes. Traffic of other prefixes is aggregated together.
+                max_keys: Option<usize>,
+                /// The default number of entries of each top list.
+                n: Option<usize>,
+            },
+            /// Embedded HTTP listener serving the metrics to Prometheus scrapers
+            pub scrape: #[derive(Default, PartialEq, Eq)]
+            StatsScrapeConf {
+                /// The socket address on which metrics are served at `/metrics`. The listener is disabled if not set.
+                listen: Option<String>,
+                /// Whether metrics are detailed per transport.
+                per_transport: Option<bool>,
+                /// Whether metrics are detailed per link.
+                per_link: Option<bool>,
+                /// Whether metrics are detailed per key expression filter.
+                per_key: Option<bool>,
+                /// Whether metrics of disconnected transports are kept.
+                disconnected: Option<bool>,
+                /// Scraping of the metrics of all the reachable nodes
+                pub federation: #[derive(Default, PartialEq, Eq)]
+                StatsFederationConf {
+                    /// Whether the metrics of the reachable nodes are served along with the local ones.
+                    enabled: Option<bool>,
+                    /// The timeout in milliseconds of the metrics queries to the reachable nodes.
+                    timeout_ms: Option<u64>,
+                },
+            },
         },
 
         /// A list of directories where plugins may be searched for if no `__path__` was specified for them.
@@ -1253,6 +1511,22 @@ fn config_deser() {
         })
     );
 
+    let config = Config::from_deserializer(
+        &mut json5::Deserializer::from_str(
+            r#"{
+              transport: {
+                multicast: {
+                  reliability: {
+                    nack_interval: 0,
+                  },
+                },
+              }
+            }"#,
+        )
+        .unwrap(),
+    );
+    assert!(config.is_err());
+
     dbg!(Config::from_file("../../DEFAULT_CONFIG.json5").unwrap());
 }
 
@@ -1590,6 +1864,22 @@ fn sequence_number_resolution_validator(b: &Bits) -> bool {
     b <= &Bits::from(TransportSn::MAX)
 }
 
+fn compression_level_validator(l: &i8) -> bool {
+    (-7..=22).contains(l)
+}
+
+fn multicast_compression_algorithm_validator(a: &CompressionAlgorithmConf) -> bool {
+    *a == CompressionAlgorithmConf::Lz4
+}
+
+fn fec_ratio_validator(r: &f64) -> bool {
+    *r > 0.0 && *r <= 1.0
+}
+
+fn nack_interval_validator(i: &u64) -> bool {
+    *i > 0
+}
+
 fn queue_size_validator(q: &QueueSizeConf) -> bool {
     fn check(size: &usize) -> bool {
         (QueueSizeConf::MIN..=QueueSizeConf::MAX).contains(size)
@@ -217,7 +217,7 @@ pub struct QosFilter {
     pub reliability: Option<ReliabilityConf>,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
 pub struct QosOverwrites {
     pub congestion_control: Option<CongestionControlConf>,
     pub priority: Option<PriorityUpdateConf>,
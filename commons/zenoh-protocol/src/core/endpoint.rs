@@ -197,6 +197,7 @@ impl<'a> Metadata<'a> {
     pub const PRIORITIES: &'static str = "prio";
     pub const MULTISTREAM: &'static str = "multistream";
     pub const MIXED_RELIABILITY: &'static str = "mixed_rel";
+    pub const FEC: &'static str = "fec";
 
     pub fn as_str(&self) -> &'a str {
         self.0
@@ -18,6 +18,7 @@ pub mod push;
 pub mod request;
 pub mod response;
 pub mod timestamp_stack;
+pub mod trace_context;
 
 use core::fmt;
 
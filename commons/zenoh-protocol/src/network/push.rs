@@ -49,6 +49,7 @@ pub struct Push {
     pub ext_tstamp: Option<ext::TimestampType>,
     pub ext_nodeid: ext::NodeIdType,
     pub ext_ts_stack: Option<ext::TsStackType>,
+    pub ext_trace_context: Option<ext::TraceContextType>,
     pub payload: PushBody,
 }
 
@@ -66,6 +67,10 @@ pub mod ext {
 
     pub type TsStack = zextzbuf!(0x7, false);
     pub type TsStackType = crate::network::timestamp_stack::TsStackType<{ TsStack::ID }>;
+
+    pub type TraceContext = zextzbuf!(0x8, false);
+    pub type TraceContextType =
+        crate::network::trace_context::TraceContextType<{ TraceContext::ID }>;
 }
 
 impl Push {
@@ -90,6 +95,7 @@ impl Push {
         let ext_tstamp = rng.gen_bool(0.5).then(ext::TimestampType::rand);
         let ext_nodeid = ext::NodeIdType::rand();
         let ext_ts_stack = rng.gen_bool(0.5).then(ext::TsStackType::rand);
+        let ext_trace_context = rng.gen_bool(0.5).then(ext::TraceContextType::rand);
 
         Self {
             wire_expr,
@@ -98,6 +104,7 @@ impl Push {
             ext_qos,
             ext_nodeid,
             ext_ts_stack,
+            ext_trace_context,
         }
     }
 }
@@ -110,6 +117,7 @@ impl From<PushBody> for Push {
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType::DEFAULT,
             ext_ts_stack: None,
+            ext_trace_context: None,
             payload: value,
         }
     }
@@ -64,6 +64,7 @@ pub struct Request {
     pub ext_budget: Option<ext::BudgetType>,
     pub ext_timeout: Option<ext::TimeoutType>,
     pub ext_ts_stack: Option<ext::TsStackType>,
+    pub ext_trace_context: Option<ext::TraceContextType>,
     pub payload: RequestBody,
 }
 
@@ -133,6 +134,10 @@ pub mod ext {
 
     pub type TsStack = zextzbuf!(0x7, false);
     pub type TsStackType = crate::network::timestamp_stack::TsStackType<{ TsStack::ID }>;
+
+    pub type TraceContext = zextzbuf!(0x8, false);
+    pub type TraceContextType =
+        crate::network::trace_context::TraceContextType<{ TraceContext::ID }>;
 }
 
 impl Request {
@@ -171,6 +176,7 @@ impl Request {
             None
         };
         let ext_ts_stack = rng.gen_bool(0.5).then(ext::TsStackType::rand);
+        let ext_trace_context = rng.gen_bool(0.5).then(ext::TraceContextType::rand);
 
         Self {
             wire_expr,
@@ -183,6 +189,7 @@ impl Request {
             ext_budget,
             ext_timeout,
             ext_ts_stack,
+            ext_trace_context,
         }
     }
 }
@@ -57,6 +57,7 @@ pub struct Response {
     pub ext_tstamp: Option<ext::TimestampType>,
     pub ext_respid: Option<ext::ResponderIdType>,
     pub ext_ts_stack: Option<ext::TsStackType>,
+    pub ext_trace_context: Option<ext::TraceContextType>,
 }
 
 pub mod ext {
@@ -72,6 +73,10 @@ pub mod ext {
 
     pub type TsStack = zextzbuf!(0x7, false);
     pub type TsStackType = crate::network::timestamp_stack::TsStackType<{ TsStack::ID }>;
+
+    pub type TraceContext = zextzbuf!(0x8, false);
+    pub type TraceContextType =
+        crate::network::trace_context::TraceContextType<{ TraceContext::ID }>;
 }
 
 impl Response {
@@ -100,6 +105,7 @@ impl Response {
         let ext_tstamp = rng.gen_bool(0.5).then(ext::TimestampType::rand);
         let ext_respid = rng.gen_bool(0.5).then(ext::ResponderIdType::rand);
         let ext_ts_stack = rng.gen_bool(0.5).then(ext::TsStackType::rand);
+        let ext_trace_context = rng.gen_bool(0.5).then(ext::TraceContextType::rand);
 
         Self {
             rid,
@@ -109,6 +115,7 @@ impl Response {
             ext_tstamp,
             ext_respid,
             ext_ts_stack,
+            ext_trace_context,
         }
     }
 }
@@ -0,0 +1,63 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use alloc::string::String;
+
+/// A [W3C trace context](https://www.w3.org/TR/trace-context/) carried as a message extension,
+/// allowing distributed traces to cross the boundaries of zenoh sessions.
+///
+/// The `const ID: u8` parameter encodes the extension's wire ID, ensuring
+/// type-safety across different message contexts.
+///
+/// ```text
+///  7 6 5 4 3 2 1 0
+/// +-+-+-+-+-+-+-+-+
+/// ~  traceparent  ~ -- <u8;z64>
+/// +---------------+
+/// ~  tracestate   ~ -- <u8;z64>
+/// +---------------+
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContextType<const ID: u8> {
+    /// The `traceparent` header, e.g. `00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01`.
+    pub traceparent: String,
+    /// The `tracestate` header, possibly empty.
+    pub tracestate: String,
+}
+
+impl<const ID: u8> TraceContextType<{ ID }> {
+    #[cfg(feature = "test")]
+    #[doc(hidden)]
+    pub fn rand() -> Self {
+        use alloc::format;
+
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+
+        let traceparent = format!(
+            "00-{:032x}-{:016x}-{:02x}",
+            rng.gen::<u128>(),
+            rng.gen::<u64>(),
+            rng.gen::<u8>() & 1
+        );
+        let tracestate = if rng.gen_bool(0.5) {
+            format!("zenoh={:x}", rng.gen::<u32>())
+        } else {
+            String::new()
+        };
+        Self {
+            traceparent,
+            tracestate,
+        }
+    }
+}
@@ -133,6 +133,8 @@ pub struct InitSyn {
     pub ext_compression: Option<ext::Compression>,
     pub ext_patch: ext::PatchType,
     pub ext_region_name: Option<ext::RegionName>,
+    pub ext_fec: Option<ext::Fec>,
+    pub ext_compression_algorithm: Option<ext::CompressionAlgorithm>,
 }
 
 // Extensions
@@ -176,6 +178,16 @@ pub mod ext {
     ///
     /// See [`crate::core::RegionName`].
     pub type RegionName = zextzbuf!(0x8, false);
+
+    /// # Fec extension
+    /// Used to negotiate the use of forward error correction on datagram links.
+    /// The value is the number of data batches protected by a parity batch.
+    pub type Fec = zextz64!(0x9, false);
+
+    /// # CompressionAlgorithm extension
+    /// Used to negotiate the compression algorithm, level and dictionary on the link.
+    /// If absent while the Compression extension is present, then lz4 is used.
+    pub type CompressionAlgorithm = zextz64!(0xa, false);
 }
 
 impl InitSyn {
@@ -203,6 +215,8 @@ impl InitSyn {
         let ext_compression = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_patch = ext::PatchType::rand();
         let ext_region_name = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
+        let ext_fec = rng.gen_bool(0.5).then_some(ZExtZ64::rand());
+        let ext_compression_algorithm = rng.gen_bool(0.5).then_some(ZExtZ64::rand());
 
         Self {
             version,
@@ -220,6 +234,8 @@ impl InitSyn {
             ext_compression,
             ext_patch,
             ext_region_name,
+            ext_fec,
+            ext_compression_algorithm,
         }
     }
 }
@@ -252,6 +268,8 @@ pub struct InitAck {
     pub ext_compression: Option<ext::Compression>,
     pub ext_patch: ext::PatchType,
     pub ext_region_name: Option<ext::RegionName>,
+    pub ext_fec: Option<ext::Fec>,
+    pub ext_compression_algorithm: Option<ext::CompressionAlgorithm>,
 }
 
 impl InitAck {
@@ -284,6 +302,8 @@ impl InitAck {
         let ext_compression = rng.gen_bool(0.5).then_some(ZExtUnit::rand());
         let ext_patch = ext::PatchType::rand();
         let ext_region_name = rng.gen_bool(0.5).then_some(ZExtZBuf::rand());
+        let ext_fec = rng.gen_bool(0.5).then_some(ZExtZ64::rand());
+        let ext_compression_algorithm = rng.gen_bool(0.5).then_some(ZExtZ64::rand());
 
         Self {
             version,
@@ -302,6 +322,8 @@ impl InitAck {
             ext_compression,
             ext_patch,
             ext_region_name,
+            ext_fec,
+            ext_compression_algorithm,
         }
     }
 }
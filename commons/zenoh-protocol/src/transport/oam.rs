@@ -15,6 +15,13 @@ use crate::common::ZExtBody;
 
 pub type OamId = u16;
 
+pub mod id {
+    use super::OamId;
+
+    /// Negative acknowledgement of missing reliable sequence numbers on a multicast group.
+    pub const OAM_NACK: OamId = 0x0001;
+}
+
 pub mod flag {
     pub const T: u8 = 1 << 5; // 0x20 Transport
                               // pub const X: u8 = 1 << 6; // 0x40 Reserved
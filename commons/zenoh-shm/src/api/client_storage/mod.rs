@@ -20,10 +20,12 @@ use std::{
 use static_init::dynamic;
 use zenoh_result::{bail, ZResult};
 
+#[cfg(target_os = "linux")]
+use crate::api::protocol_implementations::memfd::memfd_shm_client::MemfdShmClient;
 use crate::{
     api::{
         client::{shm_client::ShmClient, shm_segment::ShmSegment},
-        common::{types::ProtocolID, with_id::WithProtocolID},
+        common::types::ProtocolID,
         protocol_implementations::posix::posix_shm_client::PosixShmClient,
     },
     reader::{ClientStorage, GlobalDataSegmentId},
@@ -67,9 +69,12 @@ impl ShmClientSetBuilder {
     /// Include default clients
     #[zenoh_macros::unstable_doc]
     pub fn with_default_client_set(self) -> ShmClientStorageBuilder {
-        let client = PosixShmClient {};
-        let clients = BTreeMap::from([(client.id(), Arc::new(client) as Arc<dyn ShmClient>)]);
-        ShmClientStorageBuilder::new(clients)
+        let clients: Vec<Arc<dyn ShmClient>> = vec![
+            Arc::new(PosixShmClient {}),
+            #[cfg(target_os = "linux")]
+            Arc::new(MemfdShmClient {}),
+        ];
+        self.with_clients(&clients)
     }
 }
 
@@ -0,0 +1,182 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Exchange of the file descriptors of memfd segments over Unix socket links.
+//!
+//! The segments known by this process, either created by it or received from its peers, are
+//! registered here. A link sends the segments created by this process that its peer doesn't have
+//! yet along with the data it writes, and registers the segments it receives for as long as it is
+//! open. The segments received from a peer are never forwarded to the others.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    os::fd::{AsRawFd, OwnedFd},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
+
+use nix::{
+    fcntl::{fcntl, FcntlArg, SealFlag},
+    sys::stat::fstat,
+};
+use zenoh_core::zlock;
+
+use crate::api::common::types::SegmentID;
+
+/// The prefix of the name of the memfd segments, followed by their ID.
+pub(crate) const SEGMENT_NAME_PREFIX: &str = "zenoh-shm-";
+
+/// The seals the segments must have, so that they can't be truncated once mapped.
+const REQUIRED_SEALS: SealFlag = SealFlag::F_SEAL_SHRINK.union(SealFlag::F_SEAL_SEAL);
+
+#[derive(Debug)]
+struct Segment {
+    fd: Weak<OwnedFd>,
+    // Whether the segment was created by this process, rather than received from a peer
+    created: bool,
+}
+
+/// The segments known by this process.
+static SEGMENTS: Mutex<BTreeMap<SegmentID, Segment>> = Mutex::new(BTreeMap::new());
+/// Incremented each time a segment is created, so that the links notice the new segments
+/// without locking the registry.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Registers a segment, unless another live segment is registered under the same ID.
+pub(crate) fn register(id: SegmentID, fd: &Arc<OwnedFd>, created: bool) -> bool {
+    let mut segments = zlock!(SEGMENTS);
+    segments.retain(|_, segment| segment.fd.strong_count() > 0);
+    if segments.contains_key(&id) {
+        return false;
+    }
+    segments.insert(
+        id,
+        Segment {
+            fd: Arc::downgrade(fd),
+            created,
+        },
+    );
+    if created {
+        GENERATION.fetch_add(1, Ordering::Release);
+    }
+    true
+}
+
+/// Returns the file descriptor of a registered segment.
+pub(crate) fn get(id: SegmentID) -> Option<Arc<OwnedFd>> {
+    zlock!(SEGMENTS)
+        .get(&id)
+        .and_then(|segment| segment.fd.upgrade())
+}
+
+/// The segments already sent over a link.
+#[zenoh_macros::unstable_doc]
+#[derive(Debug, Default)]
+pub struct SentSegments {
+    generation: u64,
+    sent: HashSet<SegmentID>,
+}
+
+impl SentSegments {
+    /// Returns at most `max` segments not sent over the link yet, and marks them as sent.
+    ///
+    /// Only the segments created by this process are sent, not those received from its peers.
+    #[zenoh_macros::unstable_doc]
+    pub fn take_pending(&mut self, max: usize) -> Vec<Arc<OwnedFd>> {
+        let generation = GENERATION.load(Ordering::Acquire);
+        if generation == self.generation {
+            return vec![];
+        }
+        let segments = zlock!(SEGMENTS);
+        self.sent.retain(|id| segments.contains_key(id));
+        let mut pending = vec![];
+        for (id, segment) in segments.iter().filter(|(_, segment)| segment.created) {
+            if pending.len() == max {
+                // the others are sent next time
+                return pending;
+            }
+            if let Some(fd) = segment.fd.upgrade() {
+                if self.sent.insert(*id) {
+                    pending.push(fd);
+                }
+            }
+        }
+        self.generation = generation;
+        pending
+    }
+}
+
+/// The segments received over a link, kept for as long as it is open.
+#[zenoh_macros::unstable_doc]
+#[derive(Debug, Default)]
+pub struct ReceivedSegments {
+    fds: Vec<Arc<OwnedFd>>,
+}
+
+impl ReceivedSegments {
+    /// Registers a file descriptor received over the link.
+    ///
+    /// The ID of the segment is read from the name of the memfd file. The file descriptors not
+    /// referring to a memfd segment, or to a segment whose size may shrink, are closed.
+    #[zenoh_macros::unstable_doc]
+    pub fn receive(&mut self, fd: OwnedFd) {
+        let Some(id) = segment_id(&fd) else {
+            tracing::debug!("Ignoring a file descriptor not referring to a memfd segment");
+            return;
+        };
+        // a segment truncated once mapped would make the accesses to it fault
+        if !is_sealed(&fd) {
+            tracing::warn!("Ignoring memfd segment {id}: its size is not sealed");
+            return;
+        }
+        let fd = Arc::new(fd);
+        if register(id, &fd, false) {
+            tracing::trace!("Received memfd segment {id}");
+            self.fds.push(fd);
+            return;
+        }
+        // the segment may have been received over another link already
+        match get(id) {
+            Some(known) if same_file(&known, &fd) => self.fds.push(known),
+            _ => tracing::warn!("Ignoring memfd segment {id}: another segment has the same ID"),
+        }
+    }
+}
+
+/// Reads the ID of a segment from the name of its memfd file.
+fn segment_id(fd: &OwnedFd) -> Option<SegmentID> {
+    // the link to a memfd file reads "/memfd:<name> (deleted)"
+    let path = std::fs::read_link(format!("/proc/self/fd/{}", fd.as_raw_fd())).ok()?;
+    path.to_str()?
+        .strip_prefix("/memfd:")?
+        .strip_prefix(SEGMENT_NAME_PREFIX)?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn is_sealed(fd: &OwnedFd) -> bool {
+    fcntl(fd.as_raw_fd(), FcntlArg::F_GET_SEALS)
+        .is_ok_and(|seals| SealFlag::from_bits_truncate(seals).contains(REQUIRED_SEALS))
+}
+
+fn same_file(a: &OwnedFd, b: &OwnedFd) -> bool {
+    match (fstat(a.as_raw_fd()), fstat(b.as_raw_fd())) {
+        (Ok(a), Ok(b)) => a.st_dev == b.st_dev && a.st_ino == b.st_ino,
+        _ => false,
+    }
+}
@@ -0,0 +1,46 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::sync::Arc;
+
+use zenoh_result::ZResult;
+
+use super::memfd_shm_segment::MemfdShmSegment;
+use crate::api::{
+    client::{shm_client::ShmClient, shm_segment::ShmSegment},
+    common::{
+        types::{ProtocolID, SegmentID},
+        with_id::WithProtocolID,
+    },
+    protocol_implementations::memfd::protocol_id::MEMFD_PROTOCOL_ID,
+};
+
+/// Client attaching to the memfd segments of other processes, received over Unix socket links
+#[zenoh_macros::unstable_doc]
+#[derive(Debug)]
+pub struct MemfdShmClient;
+
+impl WithProtocolID for MemfdShmClient {
+    fn id(&self) -> ProtocolID {
+        MEMFD_PROTOCOL_ID
+    }
+}
+
+impl ShmClient for MemfdShmClient {
+    /// Attach to particular shared memory segment
+    #[zenoh_macros::unstable_doc]
+    fn attach(&self, segment: SegmentID) -> ZResult<Arc<dyn ShmSegment>> {
+        Ok(Arc::new(MemfdShmSegment::open(segment)?))
+    }
+}
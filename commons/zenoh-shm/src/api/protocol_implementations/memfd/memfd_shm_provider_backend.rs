@@ -0,0 +1,205 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{
+    alloc::Layout,
+    ptr::NonNull,
+    slice,
+    sync::{Arc, Mutex},
+};
+
+use talc::{ErrOnOom, Talc};
+use zenoh_core::{zlock, Resolvable, Wait};
+use zenoh_result::ZResult;
+
+use super::memfd_shm_segment::MemfdShmSegment;
+use crate::api::{
+    common::{types::ProtocolID, with_id::WithProtocolID},
+    protocol_implementations::memfd::protocol_id::MEMFD_PROTOCOL_ID,
+    provider::{
+        chunk::ChunkDescriptor,
+        memory_layout::MemoryLayout,
+        shm_provider_backend::ShmProviderBackend,
+        types::{AllocAlignment, ChunkAllocResult, ZAllocError, ZLayoutError},
+    },
+};
+
+/// Builder to create memfd SHM provider
+#[zenoh_macros::unstable_doc]
+pub struct MemfdShmProviderBackendBuilder<Layout> {
+    layout: Layout,
+    huge_pages: bool,
+}
+
+impl<Layout> std::fmt::Debug for MemfdShmProviderBackendBuilder<Layout> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemfdShmProviderBackendBuilder")
+            .field("layout", &"..")
+            .field("huge_pages", &self.huge_pages)
+            .finish()
+    }
+}
+
+impl<Layout> MemfdShmProviderBackendBuilder<Layout> {
+    /// Back the segment with huge pages (`MFD_HUGETLB`) to reduce the TLB misses on large buffers.
+    ///
+    /// The size of the segment is rounded up to the default huge page size, and the system must
+    /// have enough huge pages reserved (see `/proc/sys/vm/nr_hugepages`).
+    #[zenoh_macros::unstable_doc]
+    pub fn huge_pages(mut self, huge_pages: bool) -> Self {
+        self.huge_pages = huge_pages;
+        self
+    }
+}
+
+#[zenoh_macros::unstable_doc]
+impl<Layout> Resolvable for MemfdShmProviderBackendBuilder<Layout> {
+    type To = ZResult<MemfdShmProviderBackend>;
+}
+
+#[zenoh_macros::unstable_doc]
+impl<Layout: TryInto<MemoryLayout>> Wait for MemfdShmProviderBackendBuilder<Layout>
+where
+    Layout::Error: Into<ZLayoutError>,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        MemfdShmProviderBackend::new(
+            &self.layout.try_into().map_err(Into::into)?,
+            self.huge_pages,
+        )
+    }
+}
+
+/// A talc backend based on Linux memfd shared memory.
+///
+/// The segment is an anonymous file: it doesn't appear in `/dev/shm` and is released by the
+/// kernel once no process maps it anymore, even if the processes using it crash. Its file
+/// descriptor is handed to the readers of the same user through an abstract Unix socket, so both
+/// sides must share the same network namespace.
+#[zenoh_macros::unstable_doc]
+pub struct MemfdShmProviderBackend {
+    segment: Arc<MemfdShmSegment>,
+    talc: Mutex<Talc<ErrOnOom>>,
+    alignment: AllocAlignment,
+}
+
+impl std::fmt::Debug for MemfdShmProviderBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemfdShmProviderBackend")
+            .field("segment", &"..")
+            .field("talc", &"..")
+            .field("alignment", &self.alignment)
+            .finish()
+    }
+}
+
+impl MemfdShmProviderBackend {
+    /// Get the builder to construct a new instance
+    #[zenoh_macros::unstable_doc]
+    pub fn builder<Layout>(layout: Layout) -> MemfdShmProviderBackendBuilder<Layout> {
+        MemfdShmProviderBackendBuilder {
+            layout,
+            huge_pages: false,
+        }
+    }
+
+    fn new(layout: &MemoryLayout, huge_pages: bool) -> ZResult<Self> {
+        let segment = Arc::new(MemfdShmSegment::create(layout.size(), huge_pages)?);
+
+        // with huge pages, the segment may be larger than requested, so in order to utilize
+        // additional memory we use its real size
+        let real_size = segment.len().get();
+        // SAFETY: the segment is guaranteed to be valid and the index 0 is always valid.
+        let ptr = unsafe { segment.elem_mut(0) };
+
+        let mut talc = Talc::new(ErrOnOom);
+
+        // SAFETY: the pointer and size are guaranteed to be valid as they represent the whole segment.
+        unsafe {
+            talc.claim(slice::from_raw_parts_mut(ptr, real_size).into())
+                .map_err(|_| "Error initializing Talc backend!")?;
+        }
+
+        tracing::trace!(
+            "Created MemfdShmProviderBackend id {}, layout {:?}",
+            segment.id(),
+            layout
+        );
+
+        Ok(Self {
+            segment,
+            talc: Mutex::new(talc),
+            alignment: layout.alignment(),
+        })
+    }
+}
+
+impl WithProtocolID for MemfdShmProviderBackend {
+    fn id(&self) -> ProtocolID {
+        MEMFD_PROTOCOL_ID
+    }
+}
+
+impl ShmProviderBackend for MemfdShmProviderBackend {
+    fn alloc(&self, layout: &MemoryLayout) -> ChunkAllocResult {
+        tracing::trace!("MemfdShmProviderBackend::alloc({:?})", layout);
+
+        // SAFETY: layout is guaranteed to be valid as it's passed from `MemoryLayout`.
+        let alloc_layout = unsafe {
+            Layout::from_size_align_unchecked(
+                layout.size().get(),
+                layout.alignment().get_alignment_value().get(),
+            )
+        };
+
+        let alloc = {
+            let mut lock = zlock!(self.talc);
+            // SAFETY: layout is guaranteed to be valid.
+            unsafe { lock.malloc(alloc_layout) }
+        };
+
+        match alloc {
+            Ok(buf) => Ok(self.segment.clone().allocated_chunk(buf, layout)),
+            Err(_) => Err(ZAllocError::OutOfMemory),
+        }
+    }
+
+    fn free(&self, chunk: &ChunkDescriptor) {
+        // SAFETY: chunk descriptor is guaranteed to be valid and belong to the segment.
+        let alloc_layout = unsafe {
+            Layout::from_size_align_unchecked(
+                chunk.len.get(),
+                self.alignment.get_alignment_value().get(),
+            )
+        };
+
+        // SAFETY: chunk descriptor is guaranteed to be valid and belong to the segment.
+        let ptr = unsafe { self.segment.elem_mut(chunk.chunk) };
+
+        // SAFETY: ptr and layout are guaranteed to be valid as they are passed from `ChunkDescriptor`.
+        unsafe { zlock!(self.talc).free(NonNull::new_unchecked(ptr), alloc_layout) };
+    }
+
+    fn defragment(&self) -> usize {
+        0
+    }
+
+    fn available(&self) -> usize {
+        0
+    }
+
+    fn layout_for(&self, layout: MemoryLayout) -> Result<MemoryLayout, ZLayoutError> {
+        layout.extend(self.alignment)
+    }
+}
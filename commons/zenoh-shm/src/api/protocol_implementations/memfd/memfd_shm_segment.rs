@@ -0,0 +1,217 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{
+    ffi::CString,
+    num::NonZeroUsize,
+    os::fd::{AsRawFd, OwnedFd},
+    ptr::NonNull,
+    sync::Arc,
+};
+
+use nix::{
+    fcntl::{fcntl, FcntlArg, SealFlag},
+    sys::{
+        memfd::{memfd_create, MemFdCreateFlag},
+        mman::{mmap, munmap, MapFlags, ProtFlags},
+        stat::fstat,
+    },
+    unistd::{ftruncate, sysconf, SysconfVar},
+};
+use rand::Rng;
+use zenoh_result::{bail, zerror, ZResult};
+
+use super::fd_exchange;
+use crate::api::{
+    client::shm_segment::ShmSegment,
+    common::types::{ChunkID, PtrInSegment, SegmentID},
+    provider::{
+        chunk::{AllocatedChunk, ChunkDescriptor},
+        memory_layout::MemoryLayout,
+    },
+};
+
+/// The number of random segment IDs tried before giving up creating a segment.
+const CREATE_ATTEMPTS: usize = 100;
+
+/// A memfd segment mapped in the memory of this process.
+///
+/// The segment created by this process keeps its file descriptor registered, so that the Unix
+/// socket links hand it over to their peers.
+#[derive(Debug)]
+pub(crate) struct MemfdShmSegment {
+    id: SegmentID,
+    data: NonNull<u8>,
+    len: NonZeroUsize,
+    _fd: Option<Arc<OwnedFd>>,
+}
+
+// SAFETY: the mapping is shared memory, valid for the lifetime of the segment.
+unsafe impl Send for MemfdShmSegment {}
+// SAFETY: the mapping is shared memory, valid for the lifetime of the segment.
+unsafe impl Sync for MemfdShmSegment {}
+
+impl MemfdShmSegment {
+    pub(crate) fn create(len: NonZeroUsize, huge_pages: bool) -> ZResult<Self> {
+        let mut flags = MemFdCreateFlag::MFD_CLOEXEC | MemFdCreateFlag::MFD_ALLOW_SEALING;
+        // the segment is mapped by whole pages, so we make use of the whole last one
+        let page_size = if huge_pages {
+            flags |= MemFdCreateFlag::MFD_HUGETLB;
+            huge_page_size()?
+        } else {
+            page_size()?
+        };
+        let len = NonZeroUsize::new(len.get().div_ceil(page_size) * page_size)
+            .ok_or_else(|| zerror!("Invalid memfd segment size"))?;
+        if len.get() - 1 > ChunkID::MAX as usize {
+            bail!("Unable to create memfd segment of {len} bytes: out of range for ChunkID!");
+        }
+
+        for _ in 0..CREATE_ATTEMPTS {
+            let id: SegmentID = rand::thread_rng().gen();
+            let name = CString::new(format!("{}{id}", fd_exchange::SEGMENT_NAME_PREFIX))?;
+            let fd = memfd_create(&name, flags)
+                .map_err(|e| zerror!("Unable to create memfd segment: {e}"))?;
+            ftruncate(&fd, len.get() as _)
+                .map_err(|e| zerror!("Unable to resize memfd segment to {len} bytes: {e}"))?;
+            // the size of the segment is sealed, so that no process may truncate it under the others
+            fcntl(
+                fd.as_raw_fd(),
+                FcntlArg::F_ADD_SEALS(
+                    SealFlag::F_SEAL_SHRINK | SealFlag::F_SEAL_GROW | SealFlag::F_SEAL_SEAL,
+                ),
+            )?;
+
+            let fd = Arc::new(fd);
+            if !fd_exchange::register(id, &fd, true) {
+                continue;
+            }
+            tracing::trace!("Created memfd segment {id} of {len} bytes (huge pages: {huge_pages})");
+            return Ok(Self {
+                id,
+                data: map(&fd, len)?,
+                len,
+                _fd: Some(fd),
+            });
+        }
+        bail!("Unable to find a free memfd segment ID in {CREATE_ATTEMPTS} attempts!")
+    }
+
+    pub(crate) fn open(id: SegmentID) -> ZResult<Self> {
+        let fd = fd_exchange::get(id).ok_or_else(|| {
+            zerror!("Memfd segment {id} was not received over a Unix socket link")
+        })?;
+        // the registered segments may not shrink, so the mapping remains within the file
+        let len = usize::try_from(fstat(fd.as_raw_fd())?.st_size)?;
+        let len = NonZeroUsize::new(len).ok_or_else(|| zerror!("Empty memfd segment {id}"))?;
+        // the mapping keeps the segment alive once the link it was received over is closed
+        Ok(Self {
+            id,
+            data: map(&fd, len)?,
+            len,
+            _fd: None,
+        })
+    }
+
+    pub(crate) fn id(&self) -> SegmentID {
+        self.id
+    }
+
+    pub(crate) fn len(&self) -> NonZeroUsize {
+        self.len
+    }
+
+    /// # Safety
+    /// The `chunk` must be within the segment.
+    pub(crate) unsafe fn elem_mut(&self, chunk: ChunkID) -> *mut u8 {
+        self.data.as_ptr().add(chunk as usize)
+    }
+
+    /// # Safety
+    /// The `ptr` must point within the segment.
+    pub(crate) unsafe fn index(&self, ptr: *const u8) -> ChunkID {
+        ptr.offset_from(self.data.as_ptr()) as ChunkID
+    }
+
+    pub(crate) fn allocated_chunk(
+        self: Arc<Self>,
+        buf: NonNull<u8>,
+        layout: &MemoryLayout,
+    ) -> AllocatedChunk {
+        AllocatedChunk {
+            descriptor: ChunkDescriptor::new(
+                self.id,
+                // SAFETY: buf is guaranteed to belong to the segment.
+                unsafe { self.index(buf.as_ptr()) },
+                layout.size(),
+            ),
+            data: PtrInSegment::new(buf.as_ptr(), self),
+        }
+    }
+}
+
+impl ShmSegment for MemfdShmSegment {
+    fn map(&self, chunk: ChunkID) -> ZResult<*mut u8> {
+        if chunk as usize >= self.len.get() {
+            bail!("Chunk {chunk} is out of memfd segment {}", self.id);
+        }
+        // SAFETY: the chunk is within the segment.
+        Ok(unsafe { self.elem_mut(chunk) })
+    }
+}
+
+impl Drop for MemfdShmSegment {
+    fn drop(&mut self) {
+        // SAFETY: the mapping is owned by the segment, and not used past its lifetime.
+        if let Err(e) = unsafe { munmap(self.data.cast(), self.len.get()) } {
+            tracing::debug!("Unable to unmap memfd segment {}: {e}", self.id);
+        }
+    }
+}
+
+fn map(fd: &OwnedFd, len: NonZeroUsize) -> ZResult<NonNull<u8>> {
+    // SAFETY: the file is at least `len` bytes long and may not shrink: the segments are sealed
+    // when created, and the received ones are rejected otherwise.
+    let data = unsafe {
+        mmap(
+            None,
+            len,
+            ProtFlags::PROT_READ | ProtFlags::PROT_WRITE,
+            MapFlags::MAP_SHARED,
+            fd,
+            0,
+        )
+    }
+    .map_err(|e| zerror!("Unable to map memfd segment of {len} bytes: {e}"))?;
+    Ok(data.cast())
+}
+
+fn page_size() -> ZResult<usize> {
+    sysconf(SysconfVar::PAGE_SIZE)?
+        .and_then(|size| usize::try_from(size).ok())
+        .ok_or_else(|| zerror!("Unable to get the page size").into())
+}
+
+/// Returns the size of the default huge pages.
+fn huge_page_size() -> ZResult<usize> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo")?;
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("Hugepagesize:"))
+        .and_then(|size| size.trim().strip_suffix("kB"))
+        .and_then(|size| size.trim().parse::<usize>().ok())
+        .filter(|size| *size > 0)
+        .map(|size| size * 1024)
+        .ok_or_else(|| zerror!("Huge pages are not supported by the system").into())
+}
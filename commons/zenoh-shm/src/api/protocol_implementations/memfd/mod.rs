@@ -0,0 +1,30 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Shared memory backed by anonymous `memfd_create` files (Linux only).
+//!
+//! Unlike POSIX shared memory, memfd segments have no name in `/dev/shm`: they can't collide
+//! with the segments of other containers sharing it, and they are released by the kernel as soon
+//! as the last process mapping them is gone. The file descriptor of a segment is handed to the
+//! peers over the `unixsock-stream` links, along with the data, so memfd segments are shared only
+//! with the processes connected over such a link. The segments may be backed by huge pages to
+//! reduce the TLB misses on large buffers.
+
+pub mod fd_exchange;
+pub mod memfd_shm_client;
+pub mod memfd_shm_provider_backend;
+
+pub mod protocol_id;
+
+pub(crate) mod memfd_shm_segment;
@@ -0,0 +1,19 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use crate::api::common::types::ProtocolID;
+
+/// Protocol identifier to use when creating ShmProvider with a memfd backend
+#[zenoh_macros::unstable_doc]
+pub const MEMFD_PROTOCOL_ID: ProtocolID = 1;
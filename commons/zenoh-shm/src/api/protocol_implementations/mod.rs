@@ -12,4 +12,6 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
+#[cfg(target_os = "linux")]
+pub mod memfd;
 pub mod posix;
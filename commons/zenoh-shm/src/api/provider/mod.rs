@@ -14,6 +14,7 @@
 
 pub mod chunk;
 pub mod memory_layout;
+pub mod shm_buffer_pool;
 pub mod shm_provider;
 pub mod shm_provider_backend;
 pub mod types;
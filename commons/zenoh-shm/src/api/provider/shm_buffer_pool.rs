@@ -0,0 +1,277 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{collections::HashMap, marker::PhantomData, num::NonZeroUsize};
+
+use crossbeam_queue::ArrayQueue;
+use zenoh_core::{Resolvable, Wait};
+use zenoh_result::{bail, ZResult};
+
+use super::{
+    chunk::{AllocatedChunk, ChunkDescriptor},
+    memory_layout::MemoryLayout,
+    shm_provider::{
+        AllocLayout, BlockOn, ConstPolicy, GarbageCollect, ShmProvider, ShmProviderBuilder,
+    },
+    shm_provider_backend::ShmProviderBackend,
+    types::{ChunkAllocResult, ZAllocError, ZLayoutError},
+};
+use crate::api::common::{
+    types::{ChunkID, ProtocolID, SegmentID},
+    with_id::WithProtocolID,
+};
+
+/// Builder to create a [`ShmBufferPool`]
+#[zenoh_macros::unstable_doc]
+pub struct ShmBufferPoolBuilder<Backend, Layout> {
+    backend: Backend,
+    layout: Layout,
+    count: usize,
+}
+
+impl<Backend, Layout> std::fmt::Debug for ShmBufferPoolBuilder<Backend, Layout> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShmBufferPoolBuilder")
+            .field("backend", &"..")
+            .field("layout", &"..")
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+#[zenoh_macros::unstable_doc]
+impl<Backend, Layout> Resolvable for ShmBufferPoolBuilder<Backend, Layout> {
+    type To = ZResult<ShmBufferPool<Backend, Layout>>;
+}
+
+#[zenoh_macros::unstable_doc]
+impl<Backend: ShmProviderBackend, Layout: AllocLayout> Wait
+    for ShmBufferPoolBuilder<Backend, Layout>
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        ShmBufferPool::new(self.backend, self.layout.memory_layout()?, self.count)
+    }
+}
+
+/// A pool of fixed-size buffers preallocated in shared memory.
+///
+/// The pool allocates all its chunks from the backend once, and then hands them out as buffers
+/// in a ring: a chunk is reused as soon as all the subscribers holding its previous buffer have
+/// released it, in the order of the releases. Taking a buffer from the pool costs neither a
+/// backend allocation nor a defragmentation, which makes it suitable for streaming large
+/// messages of constant size, such as video frames.
+///
+/// When all the chunks are in use, the allocations fail with [`ZAllocError::OutOfMemory`]: this
+/// is the backpressure signal of the pool, meaning that the subscribers don't keep up with the
+/// publisher.
+///
+/// The buffers are typed if the pool is built with a [`TypedLayout`](super::memory_layout::TypedLayout).
+#[zenoh_macros::unstable_doc]
+pub struct ShmBufferPool<Backend, Layout> {
+    provider: ShmProvider<PoolBackend<Backend>>,
+    size: NonZeroUsize,
+    chunk_layout: MemoryLayout,
+    _phantom: PhantomData<fn() -> Layout>,
+}
+
+impl<Backend, Layout> std::fmt::Debug for ShmBufferPool<Backend, Layout> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShmBufferPool")
+            .field("chunk_layout", &self.chunk_layout)
+            .finish()
+    }
+}
+
+impl<Backend, Layout> ShmBufferPool<Backend, Layout> {
+    /// Get the builder of a pool of `count` buffers of layout `layout`, allocated from `backend`
+    #[zenoh_macros::unstable_doc]
+    pub fn builder(
+        backend: Backend,
+        layout: Layout,
+        count: usize,
+    ) -> ShmBufferPoolBuilder<Backend, Layout> {
+        ShmBufferPoolBuilder {
+            backend,
+            layout,
+            count,
+        }
+    }
+}
+
+impl<Backend, Layout> ShmBufferPool<Backend, Layout>
+where
+    Backend: ShmProviderBackend,
+    Layout: AllocLayout,
+{
+    fn new(backend: Backend, layout: MemoryLayout, count: usize) -> ZResult<Self> {
+        let size = layout.size();
+        let backend = PoolBackend::new(backend, layout, count)?;
+        let chunk_layout = backend.chunk_layout;
+        Ok(Self {
+            provider: ShmProviderBuilder::backend(backend).wait(),
+            size,
+            chunk_layout,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Take a buffer from the pool.
+    ///
+    /// Fails with [`ZAllocError::OutOfMemory`] if all the buffers are in use.
+    #[zenoh_macros::unstable_doc]
+    pub fn alloc(&self) -> Result<Layout::Buffer, ZAllocError> {
+        let buffer =
+            self.provider
+                .alloc_inner(self.size, &self.chunk_layout, &<GarbageCollect>::NEW)?;
+        // SAFETY: the chunks of the pool have the layout of the pool.
+        Ok(unsafe { Layout::wrap_buffer(buffer) })
+    }
+
+    /// Take a buffer from the pool, waiting for one to be released if all are in use.
+    #[zenoh_macros::unstable_doc]
+    pub async fn alloc_async(&self) -> Result<Layout::Buffer, ZAllocError>
+    where
+        Backend: Sync,
+    {
+        let buffer = self
+            .provider
+            .alloc_inner_async(
+                self.size,
+                &self.chunk_layout,
+                &<BlockOn<GarbageCollect>>::NEW,
+            )
+            .await?;
+        // SAFETY: the chunks of the pool have the layout of the pool.
+        Ok(unsafe { Layout::wrap_buffer(buffer) })
+    }
+
+    /// Reclaim the buffers released by their holders.
+    ///
+    /// This is done by the allocations when all the buffers are in use, and allows to get an
+    /// accurate [`Self::available`] count. Returns the number of buffers reclaimed.
+    #[zenoh_macros::unstable_doc]
+    pub fn reclaim(&self) -> usize {
+        let before = self.available();
+        self.provider.garbage_collect();
+        // the buffers may be taken concurrently by other threads
+        self.available().saturating_sub(before)
+    }
+
+    /// The number of buffers of the pool
+    #[zenoh_macros::unstable_doc]
+    pub fn capacity(&self) -> usize {
+        self.provider.backend().chunks.len()
+    }
+
+    /// The number of buffers which may be taken from the pool without reclaiming the released ones
+    #[zenoh_macros::unstable_doc]
+    pub fn available(&self) -> usize {
+        self.provider.backend().free.len()
+    }
+}
+
+/// The backend handing out the preallocated chunks of a pool.
+struct PoolBackend<Backend> {
+    backend: Backend,
+    chunk_layout: MemoryLayout,
+    chunks: Vec<AllocatedChunk>,
+    index: HashMap<(SegmentID, ChunkID), usize>,
+    free: ArrayQueue<usize>,
+}
+
+impl<Backend: ShmProviderBackend> PoolBackend<Backend> {
+    fn new(backend: Backend, layout: MemoryLayout, count: usize) -> ZResult<Self> {
+        if count == 0 {
+            bail!("A SHM buffer pool must have at least one buffer!");
+        }
+        let chunk_layout = backend.layout_for(layout)?;
+        let mut chunks = Vec::with_capacity(count);
+        for _ in 0..count {
+            match backend.alloc(&chunk_layout) {
+                Ok(chunk) => chunks.push(chunk),
+                Err(e) => {
+                    for chunk in &chunks {
+                        backend.free(&chunk.descriptor);
+                    }
+                    bail!(
+                        "Unable to preallocate {count} buffers of {:?}: {e}",
+                        chunk_layout
+                    );
+                }
+            }
+        }
+        let index = chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| ((chunk.descriptor.segment, chunk.descriptor.chunk), i))
+            .collect();
+        let free = ArrayQueue::new(count);
+        for i in 0..count {
+            let _ = free.push(i);
+        }
+        Ok(Self {
+            backend,
+            chunk_layout,
+            chunks,
+            index,
+            free,
+        })
+    }
+}
+
+impl<Backend: WithProtocolID> WithProtocolID for PoolBackend<Backend> {
+    fn id(&self) -> ProtocolID {
+        self.backend.id()
+    }
+}
+
+impl<Backend: ShmProviderBackend> ShmProviderBackend for PoolBackend<Backend> {
+    fn alloc(&self, layout: &MemoryLayout) -> ChunkAllocResult {
+        if *layout != self.chunk_layout {
+            return Err(ZAllocError::Other);
+        }
+        let chunk = &self.chunks[self.free.pop().ok_or(ZAllocError::OutOfMemory)?];
+        Ok(AllocatedChunk::new(
+            chunk.descriptor.clone(),
+            chunk.data.clone(),
+        ))
+    }
+
+    fn free(&self, chunk: &ChunkDescriptor) {
+        match self.index.get(&(chunk.segment, chunk.chunk)) {
+            Some(i) => {
+                let _ = self.free.push(*i);
+            }
+            None => tracing::error!("Freeing a chunk which is not in the pool: {:?}", chunk),
+        }
+    }
+
+    fn defragment(&self) -> usize {
+        0
+    }
+
+    fn available(&self) -> usize {
+        self.free.len() * self.chunk_layout.size().get()
+    }
+
+    fn layout_for(&self, layout: MemoryLayout) -> Result<MemoryLayout, ZLayoutError> {
+        if layout.size() <= self.chunk_layout.size()
+            && layout.alignment() <= self.chunk_layout.alignment()
+        {
+            Ok(self.chunk_layout)
+        } else {
+            Err(ZLayoutError::ProviderIncompatibleLayout)
+        }
+    }
+}
@@ -922,13 +922,17 @@ impl<Backend> ShmProvider<Backend> {
             busy_list: Default::default(),
         }
     }
+
+    pub(crate) fn backend(&self) -> &Backend {
+        &self.backend
+    }
 }
 
 impl<Backend> ShmProvider<Backend>
 where
     Backend: ShmProviderBackend,
 {
-    fn alloc_inner<Policy>(
+    pub(crate) fn alloc_inner<Policy>(
         &self,
         size: NonZeroUsize,
         layout: &MemoryLayout,
@@ -1017,7 +1021,7 @@ impl<Backend> ShmProvider<Backend>
 where
     Backend: ShmProviderBackend + Sync,
 {
-    async fn alloc_inner_async<Policy>(
+    pub(crate) async fn alloc_inner_async<Policy>(
         &self,
         size: NonZeroUsize,
         backend_layout: &MemoryLayout,
@@ -0,0 +1,228 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(target_os = "linux")]
+
+use std::{
+    collections::HashSet,
+    ffi::CString,
+    fs::File,
+    os::{
+        fd::{AsFd, AsRawFd, FromRawFd, OwnedFd},
+        unix::fs::MetadataExt,
+    },
+};
+
+use zenoh_core::Wait;
+use zenoh_shm::api::{
+    client::shm_client::ShmClient,
+    protocol_implementations::memfd::{
+        fd_exchange::{ReceivedSegments, SentSegments},
+        memfd_shm_client::MemfdShmClient,
+        memfd_shm_provider_backend::MemfdShmProviderBackend,
+    },
+    provider::{
+        memory_layout::MemoryLayout, shm_provider_backend::ShmProviderBackend,
+        types::AllocAlignment,
+    },
+};
+
+static BUFFER_NUM: usize = 100;
+static BUFFER_SIZE: usize = 1000;
+
+#[test]
+fn memfd_shm_provider_create() {
+    let _backend = MemfdShmProviderBackend::builder(1024)
+        .wait()
+        .expect("Error creating MemfdShmProviderBackend!");
+}
+
+#[test]
+fn memfd_shm_provider_open() {
+    let backend = MemfdShmProviderBackend::builder(1024)
+        .wait()
+        .expect("Error creating MemfdShmProviderBackend!");
+
+    let layout = MemoryLayout::new(100, AllocAlignment::default()).unwrap();
+
+    let mut buf = backend
+        .alloc(&layout)
+        .expect("MemfdShmProviderBackend: error allocating buffer");
+    // SAFETY: the buffer is 100 bytes long and not shared yet.
+    unsafe { buf.data.ptr_mut().write_bytes(42, 100) };
+
+    let client = MemfdShmClient {};
+
+    let segment = client
+        .attach(buf.descriptor.segment)
+        .expect("Error attaching to segment");
+    let data = segment
+        .map(buf.descriptor.chunk)
+        .expect("Error mapping chunk");
+
+    // the attached segment is a distinct mapping of the same memory
+    assert_ne!(data.cast_const(), buf.data.ptr());
+    // SAFETY: the chunk is 100 bytes long.
+    assert_eq!(unsafe { std::slice::from_raw_parts(data, 100) }, &[42; 100]);
+
+    // the segment remains attached once released by its provider
+    drop(buf);
+    drop(backend);
+    // SAFETY: the chunk is 100 bytes long.
+    assert_eq!(unsafe { std::slice::from_raw_parts(data, 100) }, &[42; 100]);
+}
+
+#[test]
+fn memfd_shm_provider_attach_unknown_segment() {
+    let backend = MemfdShmProviderBackend::builder(1024)
+        .wait()
+        .expect("Error creating MemfdShmProviderBackend!");
+    let layout = MemoryLayout::new(100, AllocAlignment::default()).unwrap();
+    let buf = backend
+        .alloc(&layout)
+        .expect("MemfdShmProviderBackend: error allocating buffer");
+    let segment = buf.descriptor.segment;
+    drop(buf);
+    drop(backend);
+
+    // the segment is not handed over anymore once its provider is dropped
+    assert!(MemfdShmClient {}.attach(segment).is_err());
+}
+
+#[test]
+fn memfd_shm_provider_exchange() {
+    let backend = MemfdShmProviderBackend::builder(1024)
+        .wait()
+        .expect("Error creating MemfdShmProviderBackend!");
+    let layout = MemoryLayout::new(100, AllocAlignment::default()).unwrap();
+    let mut buf = backend
+        .alloc(&layout)
+        .expect("MemfdShmProviderBackend: error allocating buffer");
+    // SAFETY: the buffer is 100 bytes long and not shared yet.
+    unsafe { buf.data.ptr_mut().write_bytes(42, 100) };
+
+    // the segment is sent once over a link
+    let mut sent = SentSegments::default();
+    let pending = sent.take_pending(usize::MAX);
+    assert!(!pending.is_empty());
+    let sent_again: HashSet<u64> = sent.take_pending(usize::MAX).iter().map(inode).collect();
+    assert!(pending.iter().all(|fd| !sent_again.contains(&inode(fd))));
+
+    // the segment received over a link remains attachable once released by its provider
+    let mut received = ReceivedSegments::default();
+    for fd in pending {
+        received.receive(fd.try_clone().unwrap());
+    }
+    let segment = buf.descriptor.segment;
+    let chunk = buf.descriptor.chunk;
+    drop(buf);
+    drop(backend);
+    let attached = MemfdShmClient {}
+        .attach(segment)
+        .expect("Error attaching to segment");
+    let data = attached.map(chunk).expect("Error mapping chunk");
+    // SAFETY: the chunk is 100 bytes long.
+    assert_eq!(unsafe { std::slice::from_raw_parts(data, 100) }, &[42; 100]);
+
+    // the segment is not handed over anymore once the link is closed
+    drop(received);
+    assert!(MemfdShmClient {}.attach(segment).is_err());
+}
+
+fn inode(fd: &impl AsFd) -> u64 {
+    File::from(fd.as_fd().try_clone_to_owned().unwrap())
+        .metadata()
+        .unwrap()
+        .ino()
+}
+
+/// Returns a memfd segment as created by a peer, with the given seals.
+fn peer_segment(id: u32, seals: libc::c_int) -> OwnedFd {
+    let name = CString::new(format!("zenoh-shm-{id}")).unwrap();
+    // SAFETY: the name is a valid C string.
+    let fd =
+        unsafe { libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING) };
+    assert!(fd >= 0, "Error creating memfd segment");
+    // SAFETY: the file descriptor was just created and is owned by nobody else.
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    File::from(fd.try_clone().unwrap()).set_len(4096).unwrap();
+    // SAFETY: the file descriptor is valid.
+    assert_eq!(
+        unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_ADD_SEALS, seals) },
+        0
+    );
+    fd
+}
+
+#[test]
+fn memfd_shm_provider_reject_unsealed() {
+    // a segment whose size may shrink is not attachable
+    let mut received = ReceivedSegments::default();
+    let unsealed = 0xffff_0001;
+    received.receive(peer_segment(unsealed, libc::F_SEAL_GROW));
+    assert!(MemfdShmClient {}.attach(unsealed).is_err());
+
+    let sealed = 0xffff_0002;
+    received.receive(peer_segment(
+        sealed,
+        libc::F_SEAL_SHRINK | libc::F_SEAL_SEAL,
+    ));
+    assert!(MemfdShmClient {}.attach(sealed).is_ok());
+}
+
+#[test]
+fn memfd_shm_provider_no_forwarding() {
+    // the segments received from a peer are not sent to the others
+    let mut received = ReceivedSegments::default();
+    let fd = peer_segment(0xffff_0003, libc::F_SEAL_SHRINK | libc::F_SEAL_SEAL);
+    let received_inode = inode(&fd);
+    received.receive(fd);
+    let pending = SentSegments::default().take_pending(usize::MAX);
+    assert!(pending.iter().all(|fd| inode(fd) != received_inode));
+}
+
+#[test]
+fn memfd_shm_provider_allocator() {
+    let backend = MemfdShmProviderBackend::builder(BUFFER_SIZE * BUFFER_NUM)
+        .wait()
+        .expect("Error creating MemfdShmProviderBackend!");
+
+    let layout = MemoryLayout::new(BUFFER_SIZE, AllocAlignment::default()).unwrap();
+
+    // exhaust memory by allocating it all
+    let mut buffers = vec![];
+    while let Ok(buf) = backend.alloc(&layout) {
+        buffers.push(buf);
+    }
+    assert!(!buffers.is_empty());
+
+    for _ in 0..100 {
+        // there is nothing to allocate at this point
+        assert!(backend.alloc(&layout).is_err());
+
+        // free buffer
+        let to_free = buffers.pop().unwrap().descriptor;
+        backend.free(&to_free);
+
+        // allocate new one
+        let buf = backend
+            .alloc(&layout)
+            .expect("MemfdShmProviderBackend: error allocating buffer");
+        buffers.push(buf);
+    }
+
+    // free buffers
+    while let Some(buffer) = buffers.pop() {
+        backend.free(&buffer.descriptor);
+    }
+}
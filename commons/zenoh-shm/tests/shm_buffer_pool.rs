@@ -0,0 +1,109 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use zenoh_core::Wait;
+use zenoh_shm::api::{
+    buffer::zshm::ZShm,
+    protocol_implementations::posix::posix_shm_provider_backend::PosixShmProviderBackend,
+    provider::{
+        memory_layout::{MemoryLayout, TypedLayout},
+        shm_buffer_pool::ShmBufferPool,
+        types::{AllocAlignment, ZAllocError},
+    },
+};
+
+const BUFFER_NUM: usize = 4;
+const BUFFER_SIZE: usize = 1024;
+
+fn backend() -> PosixShmProviderBackend {
+    let layout = MemoryLayout::new(BUFFER_NUM * BUFFER_SIZE * 2, AllocAlignment::ALIGN_8_BYTES);
+    PosixShmProviderBackend::builder(layout.unwrap())
+        .wait()
+        .unwrap()
+}
+
+#[test]
+fn shm_buffer_pool_exhaustion() {
+    let pool = ShmBufferPool::builder(backend(), BUFFER_SIZE, BUFFER_NUM)
+        .wait()
+        .unwrap();
+    assert_eq!(pool.capacity(), BUFFER_NUM);
+    assert_eq!(pool.available(), BUFFER_NUM);
+
+    let mut buffers: Vec<ZShm> = (0..BUFFER_NUM)
+        .map(|i| {
+            let mut buffer = pool.alloc().unwrap();
+            assert_eq!(buffer.len(), BUFFER_SIZE);
+            buffer.fill(i as u8);
+            buffer.into()
+        })
+        .collect();
+    assert_eq!(pool.available(), 0);
+
+    // the pool reports backpressure while all the buffers are in use
+    assert!(matches!(pool.alloc(), Err(ZAllocError::OutOfMemory)));
+
+    // a buffer is reused only once all its holders released it
+    let held = buffers[0].clone();
+    buffers.remove(0);
+    assert!(matches!(pool.alloc(), Err(ZAllocError::OutOfMemory)));
+    drop(held);
+    let buffer = pool.alloc().unwrap();
+    assert!(buffer.iter().all(|byte| *byte == 0));
+
+    drop(buffers);
+    assert_eq!(pool.reclaim(), BUFFER_NUM - 1);
+    assert_eq!(pool.available(), BUFFER_NUM - 1);
+}
+
+#[test]
+fn shm_buffer_pool_preallocation_failure() {
+    assert!(
+        ShmBufferPool::builder(backend(), BUFFER_SIZE, BUFFER_NUM * 4)
+            .wait()
+            .is_err()
+    );
+    assert!(ShmBufferPool::builder(backend(), BUFFER_SIZE, 0)
+        .wait()
+        .is_err());
+}
+
+#[test]
+fn shm_buffer_pool_typed() {
+    let pool = ShmBufferPool::builder(backend(), TypedLayout::<u64>::new(), BUFFER_NUM)
+        .wait()
+        .unwrap();
+    let buffer = pool.alloc().unwrap().initialize(42);
+    assert_eq!(*buffer, 42);
+}
+
+#[test]
+fn shm_buffer_pool_async() {
+    let pool = ShmBufferPool::builder(backend(), BUFFER_SIZE, 1)
+        .wait()
+        .unwrap();
+    let buffer = pool.alloc().unwrap();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            drop(buffer);
+        });
+        pool.alloc_async().await.unwrap();
+        release.await.unwrap();
+    });
+}
@@ -133,6 +133,8 @@ pub enum ReasonLabel {
     Downsampling,
     LowPass,
     NoLink,
+    PayloadFilter,
+    RateLimit,
 }
 
 impl EncodeLabelValue for ReasonLabel {
@@ -143,6 +145,8 @@ impl EncodeLabelValue for ReasonLabel {
             Self::Downsampling => "downsampling",
             Self::LowPass => "low-pass",
             Self::NoLink => "no-link",
+            Self::PayloadFilter => "payload-filter",
+            Self::RateLimit => "rate-limit",
         })
     }
 }
@@ -0,0 +1,189 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use prometheus_client::{
+    collector::Collector,
+    encoding::{DescriptorEncoder, EncodeLabelSet},
+    metrics::MetricType,
+    registry::Unit,
+};
+use zenoh_protocol::core::{Timestamp, TimestampId};
+
+use crate::{
+    family::TransportMetric,
+    histogram::{Histogram, HistogramBuckets},
+};
+
+/// Buckets of the hop latency histograms, in microseconds.
+pub const HOP_LATENCY_BUCKETS: HistogramBuckets = HistogramBuckets(&[
+    10, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000,
+    1_000_000,
+]);
+
+/// The value of all the labels of the series aggregating the latencies of the series created
+/// beyond the maximum number of series. It is neither a valid zid nor a valid publication key.
+const OVERFLOW_LABEL: &str = "*";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(crate) struct HopLatencyLabels {
+    pub(crate) source: String,
+    pub(crate) router: String,
+    pub(crate) prefix: String,
+}
+
+/// Per-hop latency histograms, computed from the timestamp stacks of received messages.
+///
+/// Each hop is the interval between two consecutive timestamps of a stack. Its latency is
+/// attributed to the node which recorded the second timestamp, i.e. the router which forwarded
+/// the message, or the receiver itself for the last hop.
+///
+/// Timestamps recorded by different nodes are corrected with an estimation of the clock offset
+/// between them: the minimum delay observed in each direction is assumed to be symmetric, so
+/// the offset is half their difference. When only one direction has been observed, the offset
+/// is only corrected if it yields negative latencies.
+///
+/// Once [`max_series`](Self::set_max_series) series are created, the latencies of new series
+/// are aggregated in a series whose labels are all `*`.
+#[derive(Debug, Clone, Default)]
+pub struct HopLatencyStats(Arc<HopLatencyStatsInner>);
+
+#[derive(Debug, Default)]
+struct HopLatencyStatsInner {
+    histograms: RwLock<HashMap<HopLatencyLabels, Histogram>>,
+    min_delays: RwLock<HashMap<(TimestampId, TimestampId), i64>>,
+    /// The maximum number of series, or 0 if unbounded.
+    max_series: AtomicUsize,
+}
+
+impl HopLatencyStats {
+    /// Sets the maximum number of series, beyond which latencies are aggregated.
+    pub fn set_max_series(&self, max_series: usize) {
+        self.0.max_series.store(max_series, Ordering::Relaxed);
+    }
+
+    /// Observes the ordered timestamps recorded along the path of a message
+    /// published on a key expression starting with `prefix`.
+    pub fn observe(&self, prefix: &str, timestamps: &[Timestamp]) {
+        let Some(source) = timestamps.first().map(|ts| ts.get_id().to_string()) else {
+            return;
+        };
+        for hop in timestamps.windows(2) {
+            let (from, to) = (&hop[0], &hop[1]);
+            let delay = i64::try_from(to.get_time().as_nanos())
+                .unwrap_or(i64::MAX)
+                .saturating_sub(i64::try_from(from.get_time().as_nanos()).unwrap_or(i64::MAX));
+            let offset = if from.get_id() == to.get_id() {
+                0
+            } else {
+                self.clock_offset(*from.get_id(), *to.get_id(), delay)
+            };
+            let latency_us = delay.saturating_sub(offset).max(0) as u64 / 1_000;
+            let labels = HopLatencyLabels {
+                source: source.clone(),
+                router: to.get_id().to_string(),
+                prefix: prefix.to_string(),
+            };
+            self.histogram(&labels).observe(latency_us);
+        }
+    }
+
+    fn histogram(&self, labels: &HopLatencyLabels) -> Histogram {
+        if let Some(histogram) = self.0.histograms.read().unwrap().get(labels) {
+            return histogram.clone();
+        }
+        let mut histograms = self.0.histograms.write().unwrap();
+        let max_series = self.0.max_series.load(Ordering::Relaxed);
+        let labels = if max_series != 0
+            && histograms.len() >= max_series
+            && !histograms.contains_key(labels)
+        {
+            HopLatencyLabels {
+                source: OVERFLOW_LABEL.to_string(),
+                router: OVERFLOW_LABEL.to_string(),
+                prefix: OVERFLOW_LABEL.to_string(),
+            }
+        } else {
+            labels.clone()
+        };
+        histograms
+            .entry(labels)
+            .or_insert_with(|| Histogram::new(HOP_LATENCY_BUCKETS))
+            .clone()
+    }
+
+    /// Updates the minimum delay observed from `from` to `to`, and returns the estimated offset
+    /// of the clock of `to` relative to the clock of `from`, in nanoseconds.
+    fn clock_offset(&self, from: TimestampId, to: TimestampId, delay: i64) -> i64 {
+        let mut min_delays = self.0.min_delays.write().unwrap();
+        let min_delay = min_delays
+            .entry((from, to))
+            .and_modify(|d| *d = (*d).min(delay))
+            .or_insert(delay);
+        let min_delay = *min_delay;
+        match min_delays.get(&(to, from)) {
+            Some(reverse_min_delay) => (min_delay - reverse_min_delay) / 2,
+            None => min_delay.min(0),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let hops = (self.0.histograms.read().unwrap().iter())
+            .map(|(labels, histogram)| {
+                let (sum, count, buckets) = histogram.collect();
+                let buckets = (buckets.iter())
+                    .map(|(bound, count)| match *bound {
+                        b if b == f64::MAX => serde_json::json!({ "le": "+Inf", "count": count }),
+                        b => serde_json::json!({ "le": b as u64, "count": count }),
+                    })
+                    .collect::<Vec<_>>();
+                serde_json::json!({
+                    "source": labels.source,
+                    "router": labels.router,
+                    "prefix": labels.prefix,
+                    "count": count,
+                    "sum_us": sum as u64,
+                    "mean_us": if count > 0 { sum / count as f64 } else { 0.0 },
+                    "buckets": buckets,
+                })
+            })
+            .collect::<Vec<_>>();
+        let min_delays = self.0.min_delays.read().unwrap();
+        let clock_offsets = (min_delays.iter())
+            .filter_map(|((from, to), min_delay)| {
+                let reverse_min_delay = min_delays.get(&(*to, *from))?;
+                Some(serde_json::json!({
+                    "from": from.to_string(),
+                    "to": to.to_string(),
+                    "offset_us": (min_delay - reverse_min_delay) / 2 / 1_000,
+                }))
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({ "hops": hops, "clock_offsets": clock_offsets })
+    }
+}
+
+impl Collector for HopLatencyStats {
+    fn encode(&self, mut encoder: DescriptorEncoder) -> fmt::Result {
+        let histograms = self.0.histograms.read().unwrap();
+        if histograms.is_empty() {
+            return Ok(());
+        }
+        let unit = Unit::Other("microseconds".into());
+        let mut metric_encoder = encoder.encode_descriptor(
+            "hop_latency",
+            "Histogram of the latency added by each hop, computed from timestamp stacks",
+            Some(&unit),
+            MetricType::Histogram,
+        )?;
+        for (labels, histogram) in histograms.iter() {
+            Histogram::encode(&mut metric_encoder, labels, &histogram.collect())?;
+        }
+        Ok(())
+    }
+}
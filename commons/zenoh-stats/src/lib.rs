@@ -4,16 +4,20 @@ mod family;
 mod histogram;
 mod keys;
 mod labels;
+mod latency;
 mod link;
 mod registry;
 mod stats;
+mod traffic;
 mod transport;
 
 pub use crate::{
     keys::{StatsKeyCache, StatsKeys, StatsKeysTree},
     labels::{LocalityLabel, MessageLabel, ReasonLabel, ResourceLabel, SpaceLabel},
+    latency::HopLatencyStats,
     link::LinkStats,
     registry::StatsRegistry,
+    traffic::{KeyTrafficPrefix, KeyTrafficStats},
     transport::{DropStats, TransportStats},
     StatsDirection::*,
 };
@@ -32,7 +32,7 @@ use crate::{
         ResourceLabel, TransportLabels, TransportMessageLabels,
     },
     stats::{init_stats, StatsPath},
-    Rx, StatsDirection, StatsKeysTree, TransportStats, Tx,
+    HopLatencyStats, KeyTrafficStats, Rx, StatsDirection, StatsKeysTree, TransportStats, Tx,
 };
 
 #[derive(Debug, Clone)]
@@ -124,6 +124,8 @@ impl StatsRegistry {
                 family: network_message_payload_per_key[dir as usize].clone(),
             }));
         }
+        let hop_latency = HopLatencyStats::default();
+        registry.register_collector(Box::new(hop_latency.clone()));
         Self(Arc::new(StatsRegistryInner {
             registry: RwLock::new(registry),
             transports_opened,
@@ -136,6 +138,8 @@ impl StatsRegistry {
             network_message_dropped_payload,
             network_message_payload_per_key,
             stats_keys,
+            hop_latency,
+            key_traffic: KeyTrafficStats::default(),
         }))
     }
 
@@ -149,6 +153,14 @@ impl StatsRegistry {
         self.0.resources_declared.get_or_create(&labels).dec();
     }
 
+    pub fn hop_latency(&self) -> &HopLatencyStats {
+        &self.0.hop_latency
+    }
+
+    pub fn key_traffic(&self) -> &KeyTrafficStats {
+        &self.0.key_traffic
+    }
+
     pub fn encode_metrics(
         &self,
         writer: &mut impl Write,
@@ -258,6 +270,7 @@ impl StatsRegistry {
         for (_, family) in self.families() {
             family.remove_transport(transport);
         }
+        self.0.key_traffic.remove_transport(transport);
         self.0.transports_opened.dec();
     }
 
@@ -304,6 +317,8 @@ struct StatsRegistryInner {
         (HistogramBuckets, StatsKeysRegistry),
     >; StatsDirection::NUM],
     stats_keys: StatsKeysRegistry,
+    hop_latency: HopLatencyStats,
+    key_traffic: KeyTrafficStats,
 }
 
 pub(crate) trait TransportFamilyAny {
@@ -96,6 +96,10 @@ pub(crate) fn init_stats(json: &mut serde_json::Value, keys: &[String]) {
         downsampler_dropped_msgs,
         low_pass_dropped_bytes,
         low_pass_dropped_msgs,
+        payload_filter_dropped_bytes,
+        payload_filter_dropped_msgs,
+        rate_limit_dropped_bytes,
+        rate_limit_dropped_msgs,
         ..payload_stats,
         ..link_stats,
     );
@@ -323,6 +327,22 @@ impl StatsPath<Histogram> for NetworkMessageDroppedPayloadLabels {
                 incr_counters("rx_low_pass_dropped_msgs", count);
                 incr_counters("rx_low_pass_dropped_bytes", sum as u64);
             }
+            (Tx, ReasonLabel::PayloadFilter) => {
+                incr_counters("tx_payload_filter_dropped_msgs", count);
+                incr_counters("tx_payload_filter_dropped_bytes", sum as u64);
+            }
+            (Rx, ReasonLabel::PayloadFilter) => {
+                incr_counters("rx_payload_filter_dropped_msgs", count);
+                incr_counters("rx_payload_filter_dropped_bytes", sum as u64);
+            }
+            (Tx, ReasonLabel::RateLimit) => {
+                incr_counters("tx_rate_limit_dropped_msgs", count);
+                incr_counters("tx_rate_limit_dropped_bytes", sum as u64);
+            }
+            (Rx, ReasonLabel::RateLimit) => {
+                incr_counters("rx_rate_limit_dropped_msgs", count);
+                incr_counters("rx_rate_limit_dropped_bytes", sum as u64);
+            }
             _ => {}
         }
     }
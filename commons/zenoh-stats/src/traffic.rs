@@ -0,0 +1,260 @@
+use std::{
+    array,
+    cmp::Reverse,
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock, RwLock,
+    },
+};
+
+use zenoh_keyexpr::{
+    keyexpr,
+    keyexpr_tree::{IKeyExprTree, IKeyExprTreeMut, KeBoxTree},
+    OwnedKeyExpr,
+};
+
+use crate::{labels::TransportLabels, Rx, StatsDirection, Tx};
+
+/// The key expression prefix under which the traffic of a message is accounted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyTrafficPrefix(usize);
+
+impl KeyTrafficPrefix {
+    /// The prefix aggregating the traffic of the prefixes beyond the cardinality limit.
+    const OTHER: Self = Self(usize::MAX);
+}
+
+/// Message count and byte volume per transport and per key expression prefix, reported as
+/// the top talkers of the node.
+///
+/// Key expressions are truncated to their first chunks. The resulting prefixes are indexed in a
+/// key expression tree whose cardinality is bounded: once full, the traffic of new prefixes is
+/// aggregated in an entry with a `null` key.
+#[derive(Debug, Clone, Default)]
+pub struct KeyTrafficStats(Arc<KeyTrafficStatsInner>);
+
+#[derive(Debug, Default)]
+struct KeyTrafficStatsInner {
+    config: OnceLock<KeyTrafficConfig>,
+    prefixes: RwLock<Prefixes>,
+    transports: RwLock<HashMap<TransportLabels, TransportKeyTraffic>>,
+}
+
+#[derive(Debug)]
+struct KeyTrafficConfig {
+    prefix_chunks: usize,
+    max_keys: usize,
+}
+
+#[derive(Default)]
+struct Prefixes {
+    tree: KeBoxTree<usize>,
+    keys: Vec<OwnedKeyExpr>,
+}
+
+impl fmt::Debug for Prefixes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Prefixes")
+            .field("keys", &self.keys)
+            .finish()
+    }
+}
+
+impl KeyTrafficStats {
+    /// Enables the tracking of the traffic, aggregated by the first `prefix_chunks` chunks of
+    /// key expressions, with at most `max_keys` distinct prefixes.
+    ///
+    /// It must be called before the creation of the transport stats, and only once.
+    pub fn enable(&self, prefix_chunks: usize, max_keys: usize) {
+        let config = KeyTrafficConfig {
+            prefix_chunks,
+            max_keys,
+        };
+        let _ = self.0.config.set(config);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.config.get().is_some()
+    }
+
+    /// Returns the prefix under which the traffic on `key_expr` is accounted, or `None` if the
+    /// tracking is disabled.
+    pub fn prefix(&self, key_expr: &keyexpr) -> Option<KeyTrafficPrefix> {
+        let config = self.0.config.get()?;
+        let prefix = match key_expr
+            .as_str()
+            .match_indices('/')
+            .nth(config.prefix_chunks - 1)
+        {
+            // SAFETY: the leading chunks of a canonical key expression are a canonical key expression
+            Some((index, _)) => unsafe { keyexpr::from_str_unchecked(&key_expr[..index]) },
+            None => key_expr,
+        };
+        if let Some(id) = self.0.prefixes.read().unwrap().tree.weight_at(prefix) {
+            return Some(KeyTrafficPrefix(*id));
+        }
+        let mut prefixes = self.0.prefixes.write().unwrap();
+        if let Some(id) = prefixes.tree.weight_at(prefix) {
+            return Some(KeyTrafficPrefix(*id));
+        }
+        if prefixes.keys.len() >= config.max_keys {
+            return Some(KeyTrafficPrefix::OTHER);
+        }
+        let id = prefixes.keys.len();
+        prefixes.keys.push(prefix.into());
+        prefixes.tree.insert(prefix, id);
+        Some(KeyTrafficPrefix(id))
+    }
+
+    pub(crate) fn add_transport(&self, transport: &TransportLabels) -> Option<TransportKeyTraffic> {
+        self.0.config.get()?;
+        let mut transports = self.0.transports.write().unwrap();
+        Some(transports.entry(transport.clone()).or_default().clone())
+    }
+
+    pub(crate) fn remove_transport(&self, transport: &TransportLabels) {
+        if self.is_enabled() {
+            self.0.transports.write().unwrap().remove(transport);
+        }
+    }
+
+    /// Returns the top `n` transports by received bytes, i.e. the top publishers, each with
+    /// their top `n` key expression prefixes, and the top `n` prefixes over all the transports.
+    pub fn to_json(&self, n: usize) -> serde_json::Value {
+        let Some(config) = self.0.config.get() else {
+            return serde_json::json!({ "enabled": false });
+        };
+        let keys = self.0.prefixes.read().unwrap().keys.clone();
+        let key_json = |prefix: KeyTrafficPrefix| match keys.get(prefix.0) {
+            Some(key) => serde_json::json!(key.as_str()),
+            None => serde_json::Value::Null,
+        };
+        let mut total: HashMap<KeyTrafficPrefix, TrafficCount> = HashMap::new();
+        let mut publishers = Vec::new();
+        for (transport, traffic) in self.0.transports.read().unwrap().iter() {
+            let mut per_key = traffic.collect();
+            let mut transport_count = TrafficCount::default();
+            for (prefix, count) in per_key.iter() {
+                transport_count.add(count);
+                total.entry(*prefix).or_default().add(count);
+            }
+            per_key.sort_by_key(|(_, count)| Reverse(count.sort_key()));
+            per_key.truncate(n);
+            let mut json = serde_json::Map::new();
+            if let Some(zid) = &transport.remote_zid {
+                json.insert("zid".into(), zid.to_string().into());
+            }
+            if let Some(whatami) = &transport.remote_whatami {
+                json.insert("whatami".into(), whatami.to_string().into());
+            }
+            if let Some(group) = &transport.remote_group {
+                json.insert("group".into(), group.clone().into());
+            }
+            if let Some(cn) = &transport.remote_cn {
+                json.insert("cn".into(), cn.clone().into());
+            }
+            transport_count.merge_json(&mut json);
+            let per_key = (per_key.into_iter())
+                .map(|(prefix, count)| count.to_json(key_json(prefix)))
+                .collect::<Vec<_>>();
+            json.insert("keys".into(), per_key.into());
+            publishers.push((transport_count.sort_key(), json));
+        }
+        publishers.sort_by_key(|(sort_key, _)| Reverse(*sort_key));
+        publishers.truncate(n);
+        let mut total = total.into_iter().collect::<Vec<_>>();
+        total.sort_by_key(|(_, count)| Reverse(count.sort_key()));
+        total.truncate(n);
+        serde_json::json!({
+            "enabled": true,
+            "prefix_chunks": config.prefix_chunks,
+            "max_keys": config.max_keys,
+            "keys_tracked": keys.len(),
+            "publishers": publishers.into_iter().map(|(_, json)| json).collect::<Vec<_>>(),
+            "keys": (total.into_iter())
+                .map(|(prefix, count)| count.to_json(key_json(prefix)))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// The traffic of a transport per key expression prefix.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TransportKeyTraffic(Arc<RwLock<HashMap<KeyTrafficPrefix, Traffic>>>);
+
+impl TransportKeyTraffic {
+    pub(crate) fn observe(
+        &self,
+        direction: StatsDirection,
+        prefix: KeyTrafficPrefix,
+        payload_size: usize,
+    ) {
+        if let Some(traffic) = self.0.read().unwrap().get(&prefix) {
+            traffic.observe(direction, payload_size);
+            return;
+        }
+        (self.0.write().unwrap().entry(prefix).or_default()).observe(direction, payload_size);
+    }
+
+    fn collect(&self) -> Vec<(KeyTrafficPrefix, TrafficCount)> {
+        (self.0.read().unwrap().iter())
+            .map(|(prefix, traffic)| (*prefix, traffic.collect()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Traffic {
+    msgs: [AtomicU64; StatsDirection::NUM],
+    bytes: [AtomicU64; StatsDirection::NUM],
+}
+
+impl Traffic {
+    fn observe(&self, direction: StatsDirection, payload_size: usize) {
+        self.msgs[direction as usize].fetch_add(1, Ordering::Relaxed);
+        self.bytes[direction as usize].fetch_add(payload_size as u64, Ordering::Relaxed);
+    }
+
+    fn collect(&self) -> TrafficCount {
+        TrafficCount {
+            msgs: array::from_fn(|dir| self.msgs[dir].load(Ordering::Relaxed)),
+            bytes: array::from_fn(|dir| self.bytes[dir].load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct TrafficCount {
+    msgs: [u64; StatsDirection::NUM],
+    bytes: [u64; StatsDirection::NUM],
+}
+
+impl TrafficCount {
+    fn add(&mut self, other: &Self) {
+        for dir in [Tx, Rx] {
+            self.msgs[dir as usize] += other.msgs[dir as usize];
+            self.bytes[dir as usize] += other.bytes[dir as usize];
+        }
+    }
+
+    /// Entries are ranked by received bytes, then by sent bytes.
+    fn sort_key(&self) -> (u64, u64) {
+        (self.bytes[Rx as usize], self.bytes[Tx as usize])
+    }
+
+    fn merge_json(&self, json: &mut serde_json::Map<String, serde_json::Value>) {
+        for dir in [Rx, Tx] {
+            json.insert(format!("{dir}_msgs"), self.msgs[dir as usize].into());
+            json.insert(format!("{dir}_bytes"), self.bytes[dir as usize].into());
+        }
+    }
+
+    fn to_json(self, key: serde_json::Value) -> serde_json::Value {
+        let mut json = serde_json::Map::new();
+        json.insert("key".into(), key);
+        self.merge_json(&mut json);
+        json.into()
+    }
+}
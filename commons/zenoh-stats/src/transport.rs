@@ -12,7 +12,8 @@ use crate::{
         MessageLabel, NetworkMessageDroppedPayloadLabels, NetworkMessagePayloadLabels,
         ProtocolLabel, SpaceLabel, TransportLabels, SHM_NUM,
     },
-    LinkStats, ReasonLabel, StatsDirection, StatsKeys, StatsRegistry, Tx,
+    traffic::TransportKeyTraffic,
+    KeyTrafficPrefix, LinkStats, ReasonLabel, StatsDirection, StatsKeys, StatsRegistry, Tx,
 };
 
 #[derive(Debug, Clone)]
@@ -38,9 +39,11 @@ impl TransportStats {
             ReasonLabel::NoLink,
             None,
         );
+        let key_traffic = registry.key_traffic().add_transport(&transport);
         Self(Arc::new(TransportStatsInner {
             registry,
             transport,
+            key_traffic,
             network_message_payload: Default::default(),
             tx_no_link,
         }))
@@ -119,6 +122,17 @@ impl TransportStats {
         histogram_per_key.observe(keys, payload_size as u64);
     }
 
+    pub fn observe_key_traffic(
+        &self,
+        direction: StatsDirection,
+        prefix: KeyTrafficPrefix,
+        payload_size: usize,
+    ) {
+        if let Some(key_traffic) = &self.0.key_traffic {
+            key_traffic.observe(direction, prefix, payload_size);
+        }
+    }
+
     pub fn tx_observe_no_link(&self, msg: NetworkMessageRef) {
         self.0
             .tx_no_link
@@ -130,6 +144,7 @@ impl TransportStats {
 pub struct TransportStatsInner {
     registry: StatsRegistry,
     transport: TransportLabels,
+    key_traffic: Option<TransportKeyTraffic>,
     #[allow(clippy::type_complexity)]
     network_message_payload: [[[[[OnceLock<(Histogram, HistogramPerKey)>; SpaceLabel::NUM]; SHM_NUM];
         MessageLabel::NUM]; Priority::NUM]; StatsDirection::NUM],
@@ -11,16 +11,28 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-#[cfg(all(feature = "uring", target_os = "linux"))]
+#[cfg(all(any(feature = "uring", feature = "shared-memory"), target_os = "linux"))]
 use std::os::fd::AsRawFd;
 use std::{
     cell::UnsafeCell, collections::HashMap, fmt, fs::remove_file, os::unix::io::RawFd,
     path::PathBuf, sync::Arc, time::Duration,
 };
+#[cfg(all(feature = "shared-memory", target_os = "linux"))]
+use std::{
+    io::{self, IoSlice, IoSliceMut},
+    os::fd::{FromRawFd, OwnedFd},
+    sync::Mutex,
+};
 
 use async_trait::async_trait;
+#[cfg(all(feature = "shared-memory", target_os = "linux"))]
+use nix::sys::socket::{recvmsg, sendmsg, ControlMessage, ControlMessageOwned, MsgFlags};
+#[cfg(not(all(feature = "shared-memory", target_os = "linux")))]
+use tokio::io::AsyncReadExt;
+#[cfg(all(feature = "shared-memory", target_os = "linux"))]
+use tokio::io::Interest;
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::AsyncWriteExt,
     net::{UnixListener, UnixStream},
     sync::RwLock as AsyncRwLock,
     task::JoinHandle,
@@ -29,6 +41,8 @@ use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 #[cfg(all(feature = "uring", target_os = "linux"))]
 use zenoh_core::bail;
+#[cfg(all(feature = "shared-memory", target_os = "linux"))]
+use zenoh_core::zlock;
 use zenoh_core::{zasyncread, zasyncwrite};
 use zenoh_link_commons::{
     LinkAuthId, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait, NewLinkChannelSender,
@@ -38,6 +52,10 @@ use zenoh_protocol::{
     transport::BatchSize,
 };
 use zenoh_result::{zerror, ZResult};
+#[cfg(all(feature = "shared-memory", target_os = "linux"))]
+use zenoh_shm::api::protocol_implementations::memfd::fd_exchange::{
+    ReceivedSegments, SentSegments,
+};
 
 use super::{
     get_unix_path_as_string, UNIXSOCKSTREAM_ACCEPT_THROTTLE_TIME, UNIXSOCKSTREAM_DEFAULT_MTU,
@@ -51,8 +69,18 @@ pub struct LinkUnicastUnixSocketStream {
     src_locator: Locator,
     // The Unix domain socker destination path (random UUIDv4)
     dst_locator: Locator,
+    // The memfd segments handed over to the peer
+    #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+    sent_segments: Mutex<SentSegments>,
+    // The memfd segments handed over by the peer
+    #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+    received_segments: Mutex<ReceivedSegments>,
 }
 
+/// The maximum number of file descriptors passed in a single message (`SCM_MAX_FD`).
+#[cfg(all(feature = "shared-memory", target_os = "linux"))]
+const MAX_FDS_PER_MESSAGE: usize = 253;
+
 unsafe impl Sync for LinkUnicastUnixSocketStream {}
 
 impl LinkUnicastUnixSocketStream {
@@ -61,6 +89,10 @@ impl LinkUnicastUnixSocketStream {
             socket: UnsafeCell::new(socket),
             src_locator: Locator::new(UNIXSOCKSTREAM_LOCATOR_PREFIX, src_path, "").unwrap(),
             dst_locator: Locator::new(UNIXSOCKSTREAM_LOCATOR_PREFIX, dst_path, "").unwrap(),
+            #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+            sent_segments: Mutex::default(),
+            #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+            received_segments: Mutex::default(),
         }
     }
 
@@ -68,6 +100,102 @@ impl LinkUnicastUnixSocketStream {
     fn get_mut_socket(&self) -> &mut UnixStream {
         unsafe { &mut *self.socket.get() }
     }
+
+    /// Writes the beginning of `buffer` along with the memfd segments not handed over to the
+    /// peer yet, if any, and returns the number of bytes written.
+    #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+    async fn write_with_segments(&self, buffer: &[u8]) -> ZResult<Option<usize>> {
+        if buffer.is_empty() {
+            return Ok(None);
+        }
+        let fds = zlock!(self.sent_segments).take_pending(MAX_FDS_PER_MESSAGE);
+        if fds.is_empty() {
+            return Ok(None);
+        }
+        let fds: Vec<RawFd> = fds.iter().map(|fd| fd.as_raw_fd()).collect();
+        let socket = &*self.get_mut_socket();
+        loop {
+            socket.writable().await?;
+            // the file descriptors are attached to the first byte written
+            match socket.try_io(Interest::WRITABLE, || {
+                sendmsg::<()>(
+                    socket.as_raw_fd(),
+                    &[IoSlice::new(buffer)],
+                    &[ControlMessage::ScmRights(&fds)],
+                    MsgFlags::MSG_NOSIGNAL,
+                    None,
+                )
+                .map_err(io::Error::from)
+            }) {
+                Ok(written) => {
+                    tracing::trace!("Sent {} memfd segments on {}", fds.len(), self);
+                    return Ok(Some(written));
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => {
+                    let e = zerror!("Write error on UnixSocketStream link {}: {}", self, e);
+                    tracing::trace!("{}", e);
+                    return Err(e.into());
+                }
+            }
+        }
+    }
+
+    /// Reads into `buffer`, registering the memfd segments handed over by the peer.
+    #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+    async fn read_with_segments(&self, buffer: &mut [u8]) -> io::Result<usize> {
+        let socket = &*self.get_mut_socket();
+        loop {
+            socket.readable().await?;
+            match socket.try_io(Interest::READABLE, || {
+                let mut iov = [IoSliceMut::new(buffer)];
+                let mut cmsg = nix::cmsg_space!([RawFd; MAX_FDS_PER_MESSAGE]);
+                let msg = recvmsg::<()>(
+                    socket.as_raw_fd(),
+                    &mut iov,
+                    Some(&mut cmsg),
+                    MsgFlags::MSG_CMSG_CLOEXEC,
+                )?;
+                if msg.flags.contains(MsgFlags::MSG_CTRUNC) {
+                    tracing::warn!("Memfd segments truncated on {}", self);
+                }
+                let mut fds = vec![];
+                for cmsg in msg.cmsgs()? {
+                    if let ControlMessageOwned::ScmRights(raw) = cmsg {
+                        // SAFETY: the file descriptors received are owned by this process.
+                        fds.extend(
+                            raw.into_iter()
+                                .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) }),
+                        );
+                    }
+                }
+                Ok((msg.bytes, fds))
+            }) {
+                Ok((read, fds)) => {
+                    if !fds.is_empty() {
+                        let mut received = zlock!(self.received_segments);
+                        for fd in fds {
+                            received.receive(fd);
+                        }
+                    }
+                    return Ok(read);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+    async fn read_exact_with_segments(&self, mut buffer: &mut [u8]) -> io::Result<()> {
+        while !buffer.is_empty() {
+            match self.read_with_segments(buffer).await? {
+                0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+                read => buffer = &mut buffer[read..],
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -81,6 +209,10 @@ impl LinkUnicastTrait for LinkUnicastUnixSocketStream {
     }
 
     async fn write(&self, buffer: &[u8], _priority: Option<Priority>) -> ZResult<usize> {
+        #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+        if let Some(written) = self.write_with_segments(buffer).await? {
+            return Ok(written);
+        }
         self.get_mut_socket().write(buffer).await.map_err(|e| {
             let e = zerror!("Write error on UnixSocketStream link {}: {}", self, e);
             tracing::trace!("{}", e);
@@ -89,6 +221,11 @@ impl LinkUnicastTrait for LinkUnicastUnixSocketStream {
     }
 
     async fn write_all(&self, buffer: &[u8], _priority: Option<Priority>) -> ZResult<()> {
+        #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+        let buffer = match self.write_with_segments(buffer).await? {
+            Some(written) => &buffer[written..],
+            None => buffer,
+        };
         self.get_mut_socket().write_all(buffer).await.map_err(|e| {
             let e = zerror!("Write error on UnixSocketStream link {}: {}", self, e);
             tracing::trace!("{}", e);
@@ -97,7 +234,11 @@ impl LinkUnicastTrait for LinkUnicastUnixSocketStream {
     }
 
     async fn read(&self, buffer: &mut [u8], _priority: Option<Priority>) -> ZResult<usize> {
-        self.get_mut_socket().read(buffer).await.map_err(|e| {
+        #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+        let res = self.read_with_segments(buffer).await;
+        #[cfg(not(all(feature = "shared-memory", target_os = "linux")))]
+        let res = self.get_mut_socket().read(buffer).await;
+        res.map_err(|e| {
             let e = zerror!("Read error on UnixSocketStream link {}: {}", self, e);
             tracing::trace!("{}", e);
             e.into()
@@ -105,15 +246,19 @@ impl LinkUnicastTrait for LinkUnicastUnixSocketStream {
     }
 
     async fn read_exact(&self, buffer: &mut [u8], _priority: Option<Priority>) -> ZResult<()> {
-        self.get_mut_socket()
+        #[cfg(all(feature = "shared-memory", target_os = "linux"))]
+        let res = self.read_exact_with_segments(buffer).await;
+        #[cfg(not(all(feature = "shared-memory", target_os = "linux")))]
+        let res = self
+            .get_mut_socket()
             .read_exact(buffer)
             .await
-            .map(|_len| ())
-            .map_err(|e| {
-                let e = zerror!("Read error on UnixSocketStream link {}: {}", self, e);
-                tracing::trace!("{}", e);
-                e.into()
-            })
+            .map(|_len| ());
+        res.map_err(|e| {
+            let e = zerror!("Read error on UnixSocketStream link {}: {}", self, e);
+            tracing::trace!("{}", e);
+            e.into()
+        })
     }
 
     #[inline(always)]
@@ -32,7 +32,11 @@ use zenoh_protocol::{
 };
 use zenoh_result::{zerror, ZResult};
 #[cfg(feature = "transport_compression")]
-use {std::sync::Arc, zenoh_protocol::common::imsg};
+use {
+    super::compression::{Compressor, Decompressor},
+    std::sync::Arc,
+    zenoh_protocol::common::imsg,
+};
 
 const L_LEN: usize = (BatchSize::BITS / 8) as usize;
 const H_LEN: usize = BatchHeader::SIZE;
@@ -134,6 +138,8 @@ impl BatchHeader {
     const SIZE: usize = 1;
     #[cfg(feature = "transport_compression")]
     const COMPRESSION: u8 = 1; // 1 << 0
+    #[cfg(feature = "transport_compression")]
+    const ZSTD: u8 = 1 << 1; // if COMPRESSION==1 then the payload is compressed with zstd instead of lz4
 
     #[cfg(feature = "transport_compression")]
     const fn new(h: u8) -> Self {
@@ -151,6 +157,12 @@ impl BatchHeader {
     pub fn is_compression(&self) -> bool {
         imsg::has_flag(self.as_u8(), Self::COMPRESSION)
     }
+
+    #[cfg(feature = "transport_compression")]
+    #[inline(always)]
+    pub fn is_zstd(&self) -> bool {
+        imsg::has_flag(self.as_u8(), Self::ZSTD)
+    }
 }
 
 // WRITE BATCH
@@ -294,27 +306,36 @@ impl WBatch {
         zsplit_mut!(buffer, config)
     }
 
-    pub fn finalize(&mut self, mut buffer: Option<&mut BBuf>) -> ZResult<Finalize> {
+    /// Finalizes the batch before transmission: if a [`Compressor`] is provided, the payload is
+    /// compressed into its support buffer and [`Finalize::Buffer`] is returned when the
+    /// compressed payload is smaller. Without [`Compressor`], the batch is sent uncompressed.
+    pub fn finalize(
+        &mut self,
+        #[cfg(feature = "transport_compression")] mut compressor: Option<&mut Compressor>,
+    ) -> ZResult<Finalize> {
         #[allow(unused_mut)]
         let mut res = Finalize::Batch;
 
         #[cfg(feature = "transport_compression")]
         if let Some(h) = self.config.header() {
             if h.is_compression() {
-                let buffer = buffer
-                    .as_mut()
-                    .ok_or_else(|| zerror!("Support buffer not provided"))?;
-                res = self.compress(buffer)?;
+                res = match compressor.as_mut() {
+                    Some(compressor) => self.compress(compressor)?,
+                    None => self.uncompressed()?,
+                };
             }
         }
 
         if self.config.is_streamed {
             let buff = match res {
                 Finalize::Batch => self.buffer.as_mut_slice(),
-                Finalize::Buffer => buffer
-                    .as_mut()
+                #[cfg(feature = "transport_compression")]
+                Finalize::Buffer => compressor
                     .ok_or_else(|| zerror!("Support buffer not provided"))?
+                    .buffer
                     .as_mut_slice(),
+                #[cfg(not(feature = "transport_compression"))]
+                Finalize::Buffer => return Err(zerror!("Support buffer not provided").into()),
             };
             let (length, header, payload) = Self::split_mut(buff, &self.config);
             let len: BatchSize = (header.len() as BatchSize) + (payload.len() as BatchSize);
@@ -325,35 +346,36 @@ impl WBatch {
     }
 
     #[cfg(feature = "transport_compression")]
-    fn compress(&mut self, support: &mut BBuf) -> ZResult<Finalize> {
+    fn compress(&mut self, compressor: &mut Compressor) -> ZResult<Finalize> {
         // Write the initial bytes for the batch
-        support.clear();
-        Self::init(support, &self.config);
+        compressor.buffer.clear();
+        Self::init(&mut compressor.buffer, &self.config);
+        if compressor.config().algorithm().is_zstd() {
+            let (_l, h, _p) = Self::split_mut(compressor.buffer.as_mut_slice(), &self.config);
+            let h = h.first_mut().ok_or_else(|| zerror!("Empty BatchHeader"))?;
+            *h |= BatchHeader::ZSTD;
+        }
 
         // Compress the actual content
         let (_length, _header, payload) = Self::split(self.buffer.as_slice(), &self.config);
-        let writer = support.writer();
-        // SAFETY: assertion ensures `with_slot` precondition
-        unsafe {
-            writer.with_slot(writer.remaining(), |b| {
-                let len = lz4_flex::block::compress_into(payload, b).unwrap_or(0);
-                assert!(len <= b.len());
-                len
-            })
-        }
-        .map_err(|_| zerror!("Compression error"))?;
+        compressor.compress(payload)?;
 
         // Verify whether the resulting compressed data is smaller than the initial input
-        if support.len() < self.buffer.len() {
+        if compressor.buffer.len() < self.buffer.len() {
             Ok(Finalize::Buffer)
         } else {
-            // Keep the original uncompressed buffer and unset the compression flag from the header
-            let (_l, h, _p) = Self::split_mut(self.buffer.as_mut_slice(), &self.config);
-            let h = h.first_mut().ok_or_else(|| zerror!("Empty BatchHeader"))?;
-            *h &= !BatchHeader::COMPRESSION;
-            Ok(Finalize::Batch)
+            self.uncompressed()
         }
     }
+
+    #[cfg(feature = "transport_compression")]
+    fn uncompressed(&mut self) -> ZResult<Finalize> {
+        // Keep the original uncompressed buffer and unset the compression flag from the header
+        let (_l, h, _p) = Self::split_mut(self.buffer.as_mut_slice(), &self.config);
+        let h = h.first_mut().ok_or_else(|| zerror!("Empty BatchHeader"))?;
+        *h &= !BatchHeader::COMPRESSION;
+        Ok(Finalize::Batch)
+    }
 }
 
 pub trait Encode<Message> {
@@ -449,13 +471,25 @@ impl<TBuffer: BacktrackableReader + Buffer> RBatch<TBuffer> {
     }
 
     #[cfg(feature = "transport_compression")]
-    fn decompress<T>(&self, payload: &[u8], mut buff: impl FnMut() -> T) -> ZResult<ZSlice>
+    fn decompress<T>(
+        &self,
+        header: BatchHeader,
+        payload: &[u8],
+        mut buff: impl FnMut() -> T,
+        decompressor: Option<&mut Decompressor>,
+    ) -> ZResult<ZSlice>
     where
         T: AsMut<[u8]> + ZSliceBuffer + 'static,
     {
         let mut into = (buff)();
-        let n = lz4_flex::block::decompress_into(payload, into.as_mut())
-            .map_err(|_| zerror!("Decompression error"))?;
+        let n = if header.is_zstd() {
+            decompressor
+                .ok_or_else(|| zerror!("zstd decompressor not provided"))?
+                .decompress_zstd(payload, into.as_mut())?
+        } else {
+            lz4_flex::block::decompress_into(payload, into.as_mut())
+                .map_err(|_| zerror!("Decompression error"))?
+        };
         let zslice = ZSlice::new(Arc::new(into), 0, n)
             .map_err(|_| zerror!("Invalid decompression buffer length"))?;
         Ok(zslice)
@@ -490,6 +524,7 @@ where
     pub fn initialize_uring<C, T>(
         &mut self,
         #[allow(unused_variables)] buff: C,
+        #[cfg(feature = "transport_compression")] decompressor: Option<&mut Decompressor>,
     ) -> ZResult<Option<<RBatch<TBuffer> as DecompressUring>::Result>>
     where
         C: Fn() -> T + Copy,
@@ -502,7 +537,8 @@ where
             if let Some(header) = h {
                 if header.is_compression() {
                     let contiguous_payload = self.buffer.read_zslice(self.buffer.remaining())?;
-                    let decompressed = self.decompress(&contiguous_payload, buff)?;
+                    let decompressed =
+                        self.decompress(header, &contiguous_payload, buff, decompressor)?;
                     return Ok(Some(self.apply_decompressed(decompressed)));
                 }
             }
@@ -541,7 +577,11 @@ impl RBatch<ZSlice> {
         zsplit!(buffer, config.is_streamed, config.has_header())
     }
 
-    pub fn initialize<C, T>(&mut self, #[allow(unused_variables)] buff: C) -> ZResult<()>
+    pub fn initialize<C, T>(
+        &mut self,
+        #[allow(unused_variables)] buff: C,
+        #[cfg(feature = "transport_compression")] decompressor: Option<&mut Decompressor>,
+    ) -> ZResult<()>
     where
         C: Fn() -> T + Copy,
         T: AsMut<[u8]> + ZSliceBuffer + 'static,
@@ -558,7 +598,7 @@ impl RBatch<ZSlice> {
                 let header = BatchHeader::new(b);
 
                 if header.is_compression() {
-                    let zslice = self.decompress(p, buff)?;
+                    let zslice = self.decompress(header, p, buff, decompressor)?;
                     self.buffer = zslice;
                     return Ok(());
                 }
@@ -616,7 +656,6 @@ mod tests {
     use std::vec;
 
     use rand::Rng;
-    use zenoh_core::zcondfeat;
     use zenoh_protocol::{
         core::{CongestionControl, Priority, Reliability, WireExpr},
         network::{ext, NetworkMessage, NetworkMessageExt, Push},
@@ -627,6 +666,8 @@ mod tests {
     };
 
     use super::*;
+    #[cfg(feature = "transport_compression")]
+    use crate::common::compression::CompressionConfig;
 
     #[test]
     fn rw_batch() {
@@ -649,27 +690,46 @@ mod tests {
                 wbatch.encode(&msg_in).unwrap();
                 println!("Encoded WBatch: {wbatch:?}");
 
-                let mut buffer = zcondfeat!(
-                    "transport_compression",
-                    config.is_compression.then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(wbatch.as_slice().len()),
-                    )),
-                    None
-                );
-
-                let res = wbatch.finalize(buffer.as_mut()).unwrap();
+                #[cfg(feature = "transport_compression")]
+                let compression = match rng.gen_range(0..3) {
+                    0 => CompressionConfig::lz4(),
+                    1 => CompressionConfig::zstd(rng.gen_range(-7..=22)).unwrap(),
+                    _ => CompressionConfig::zstd_with_dictionary(
+                        rng.gen_range(-7..=22),
+                        vec![0u8; rng.gen_range(8..1_024)],
+                    )
+                    .unwrap(),
+                };
+                #[cfg(feature = "transport_compression")]
+                let mut compressor = config
+                    .is_compression
+                    .then(|| Compressor::new(compression.clone(), config.mtu));
+
+                let res = wbatch
+                    .finalize(
+                        #[cfg(feature = "transport_compression")]
+                        compressor.as_mut(),
+                    )
+                    .unwrap();
                 let bytes = match res {
                     Finalize::Batch => wbatch.as_slice(),
-                    Finalize::Buffer => buffer.as_mut().unwrap().as_slice(),
+                    #[cfg(feature = "transport_compression")]
+                    Finalize::Buffer => compressor.as_ref().unwrap().as_slice(),
+                    #[cfg(not(feature = "transport_compression"))]
+                    Finalize::Buffer => unreachable!(),
                 };
                 println!("Finalized WBatch: {bytes:02x?}");
 
+                #[cfg(feature = "transport_compression")]
+                let mut decompressor = Decompressor::new(&compression);
                 let mut rbatch = RBatch::new(config, bytes.to_vec().into_boxed_slice().into());
                 println!("Decoded RBatch: {rbatch:?}");
                 rbatch
-                    .initialize(|| {
-                        zenoh_buffers::vec::uninit(config.mtu as usize).into_boxed_slice()
-                    })
+                    .initialize(
+                        || zenoh_buffers::vec::uninit(config.mtu as usize).into_boxed_slice(),
+                        #[cfg(feature = "transport_compression")]
+                        Some(&mut decompressor),
+                    )
                     .unwrap();
                 println!("Initialized RBatch: {rbatch:?}");
                 let msg_out: TransportMessage = rbatch.decode().unwrap();
@@ -678,6 +738,31 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "transport_compression")]
+    #[test]
+    fn rw_batch_uncompressed() {
+        // Batches finalized without compressor, e.g. because of their priority, are sent as they are
+        let config = BatchConfig {
+            mtu: BatchSize::MAX,
+            is_streamed: true,
+            is_compression: true,
+        };
+        let msg_in: TransportMessage = KeepAlive.into();
+        let mut wbatch = WBatch::new(config);
+        wbatch.encode(&msg_in).unwrap();
+        assert!(matches!(wbatch.finalize(None).unwrap(), Finalize::Batch));
+
+        let mut rbatch = RBatch::new(config, wbatch.as_slice().to_vec().into_boxed_slice().into());
+        rbatch
+            .initialize(
+                || zenoh_buffers::vec::uninit(config.mtu as usize).into_boxed_slice(),
+                None,
+            )
+            .unwrap();
+        let msg_out: TransportMessage = rbatch.decode().unwrap();
+        assert_eq!(msg_in, msg_out);
+    }
+
     #[test]
     fn serialization_batch() {
         let config = BatchConfig {
@@ -0,0 +1,424 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+//! Compression of the batches sent on a link.
+//!
+//! The algorithm is negotiated during the establishment of unicast links, while multicast links
+//! always use lz4 since the algorithm is not advertised to the group. lz4 is supported by every
+//! peer, zstd can be used either alone or with a dictionary pre-shared by the peers, that is
+//! identified on the wire by its hash.
+//! The algorithm of each batch is signaled in the batch header so that receivers always know
+//! how to decompress it.
+use std::{fmt, sync::Arc};
+
+use zenoh_buffers::{
+    writer::{HasWriter, Writer},
+    BBuf,
+};
+use zenoh_config::{qos::PriorityConf, CompressionAlgorithmConf};
+use zenoh_protocol::{core::Priority, transport::BatchSize};
+use zenoh_result::{bail, zerror, Error as ZError, ZResult};
+
+/// The compression algorithm of a link.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    /// lz4, supported by every peer.
+    #[default]
+    Lz4,
+    /// zstd at the given level.
+    Zstd { level: i8 },
+    /// zstd at the given level with a pre-shared dictionary identified by its hash.
+    ZstdDictionary { level: i8, id: u32 },
+}
+
+impl CompressionAlgorithm {
+    pub const ZSTD_LEVEL_MIN: i8 = -7;
+    pub const ZSTD_LEVEL_MAX: i8 = 22;
+
+    const LZ4: u8 = 0;
+    const ZSTD: u8 = 1;
+    const ZSTD_DICTIONARY: u8 = 2;
+
+    /// Returns the algorithm supported by both sides: zstd without dictionary is used
+    /// when the dictionaries differ and lz4 when only one side uses zstd.
+    /// The lowest of the two levels is retained.
+    pub(crate) fn negotiate(self, other: Self) -> Self {
+        use CompressionAlgorithm::*;
+
+        match (self, other) {
+            (ZstdDictionary { level: a, id: x }, ZstdDictionary { level: b, id: y }) if x == y => {
+                ZstdDictionary {
+                    level: a.min(b),
+                    id: x,
+                }
+            }
+            (
+                Zstd { level: a } | ZstdDictionary { level: a, .. },
+                Zstd { level: b } | ZstdDictionary { level: b, .. },
+            ) => Zstd { level: a.min(b) },
+            _ => Lz4,
+        }
+    }
+
+    pub(crate) const fn is_zstd(&self) -> bool {
+        !matches!(self, CompressionAlgorithm::Lz4)
+    }
+}
+
+// The algorithm is encoded on a u64 as follows:
+// - byte 0: the algorithm, 0 for lz4, 1 for zstd and 2 for zstd with dictionary
+// - byte 1: the zstd level as i8
+// - bytes 2..6: the dictionary id as little-endian u32
+impl From<CompressionAlgorithm> for u64 {
+    fn from(algorithm: CompressionAlgorithm) -> Self {
+        let (a, level, id) = match algorithm {
+            CompressionAlgorithm::Lz4 => (CompressionAlgorithm::LZ4, 0, 0),
+            CompressionAlgorithm::Zstd { level } => (CompressionAlgorithm::ZSTD, level, 0),
+            CompressionAlgorithm::ZstdDictionary { level, id } => {
+                (CompressionAlgorithm::ZSTD_DICTIONARY, level, id)
+            }
+        };
+        (a as u64) | ((level as u8 as u64) << 8) | ((id as u64) << 16)
+    }
+}
+
+impl TryFrom<u64> for CompressionAlgorithm {
+    type Error = ZError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        let level = (value >> 8) as u8 as i8;
+        let id = (value >> 16) as u32;
+        let algorithm = match value as u8 {
+            Self::LZ4 => Self::Lz4,
+            Self::ZSTD => Self::Zstd { level },
+            Self::ZSTD_DICTIONARY => Self::ZstdDictionary { level, id },
+            a => bail!("Unknown compression algorithm: {a}"),
+        };
+        if algorithm.is_zstd() && !(Self::ZSTD_LEVEL_MIN..=Self::ZSTD_LEVEL_MAX).contains(&level) {
+            bail!("Invalid zstd compression level: {level}");
+        }
+        Ok(algorithm)
+    }
+}
+
+/// The compression configuration of a link.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct CompressionConfig {
+    algorithm: CompressionAlgorithm,
+    dictionary: Option<Arc<[u8]>>,
+    // Bitmask of the priorities whose batches are never compressed
+    excluded: u8,
+}
+
+impl CompressionConfig {
+    pub fn lz4() -> Self {
+        Self::default()
+    }
+
+    pub fn zstd(level: i8) -> ZResult<Self> {
+        if !(CompressionAlgorithm::ZSTD_LEVEL_MIN..=CompressionAlgorithm::ZSTD_LEVEL_MAX)
+            .contains(&level)
+        {
+            bail!(
+                "Invalid zstd compression level {level}: it must be in the [{}, {}] range",
+                CompressionAlgorithm::ZSTD_LEVEL_MIN,
+                CompressionAlgorithm::ZSTD_LEVEL_MAX
+            );
+        }
+        Ok(Self {
+            algorithm: CompressionAlgorithm::Zstd { level },
+            ..Self::default()
+        })
+    }
+
+    /// The dictionary must be the same on both sides of the link, otherwise zstd is used without it.
+    pub fn zstd_with_dictionary(level: i8, dictionary: impl Into<Arc<[u8]>>) -> ZResult<Self> {
+        let dictionary: Arc<[u8]> = dictionary.into();
+        if dictionary.is_empty() {
+            bail!("Empty zstd dictionary");
+        }
+        let mut config = Self::zstd(level)?;
+        config.algorithm = CompressionAlgorithm::ZstdDictionary {
+            level,
+            id: dictionary_id(&dictionary),
+        };
+        config.dictionary = Some(dictionary);
+        Ok(config)
+    }
+
+    /// Batches of the given priority are never compressed, e.g. because they carry already compressed payloads.
+    pub fn exclude(mut self, priority: Priority) -> Self {
+        self.excluded |= 1 << priority as u8;
+        self
+    }
+
+    pub fn algorithm(&self) -> CompressionAlgorithm {
+        self.algorithm
+    }
+
+    pub fn is_excluded(&self, priority: Priority) -> bool {
+        self.excluded & (1 << priority as u8) != 0
+    }
+
+    pub(crate) fn from_config(
+        algorithm: CompressionAlgorithmConf,
+        level: i8,
+        dictionary: Option<&str>,
+        excluded: &[PriorityConf],
+    ) -> ZResult<Self> {
+        let config = match (algorithm, dictionary) {
+            (CompressionAlgorithmConf::Lz4, None) => Self::lz4(),
+            (CompressionAlgorithmConf::Lz4, Some(_)) => {
+                bail!("A compression dictionary can only be used with the zstd algorithm")
+            }
+            (CompressionAlgorithmConf::Zstd, None) => Self::zstd(level)?,
+            (CompressionAlgorithmConf::Zstd, Some(path)) => {
+                let dictionary = std::fs::read(path)
+                    .map_err(|e| zerror!("Unable to read compression dictionary {path}: {e}"))?;
+                Self::zstd_with_dictionary(level, dictionary)?
+            }
+        };
+        Ok(excluded
+            .iter()
+            .fold(config, |c, p| c.exclude(Priority::from(*p))))
+    }
+
+    /// Returns the configuration to use on a link once the algorithm has been negotiated.
+    pub(crate) fn negotiated(&self, algorithm: CompressionAlgorithm) -> Self {
+        let dictionary = match algorithm {
+            CompressionAlgorithm::ZstdDictionary { .. } => self.dictionary.clone(),
+            _ => None,
+        };
+        Self {
+            algorithm,
+            dictionary,
+            excluded: self.excluded,
+        }
+    }
+}
+
+impl fmt::Debug for CompressionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressionConfig")
+            .field("algorithm", &self.algorithm)
+            .field("dictionary", &self.dictionary.as_ref().map(|d| d.len()))
+            .field("excluded", &format_args!("{:#010b}", self.excluded))
+            .finish()
+    }
+}
+
+// 32-bit FNV-1a hash of the dictionary
+fn dictionary_id(dictionary: &[u8]) -> u32 {
+    dictionary
+        .iter()
+        .fold(0x811c9dc5, |h, b| (h ^ *b as u32).wrapping_mul(0x01000193))
+}
+
+fn zstd_error(e: std::io::Error) -> ZError {
+    zerror!("zstd error: {e}").into()
+}
+
+/// The compression state of a link, reused across batches.
+pub struct Compressor {
+    config: CompressionConfig,
+    // The support buffer the batches are compressed into
+    pub(crate) buffer: BBuf,
+    // Lazily created at the first batch
+    zstd: Option<zstd::bulk::Compressor<'static>>,
+}
+
+impl Compressor {
+    pub fn new(config: CompressionConfig, mtu: BatchSize) -> Self {
+        let mtu = mtu as usize;
+        let capacity = if config.algorithm.is_zstd() {
+            zstd::zstd_safe::compress_bound(mtu)
+        } else {
+            lz4_flex::block::get_maximum_output_size(mtu)
+        };
+        Self {
+            config,
+            buffer: BBuf::with_capacity(capacity),
+            zstd: None,
+        }
+    }
+
+    pub fn config(&self) -> &CompressionConfig {
+        &self.config
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.buffer.as_slice()
+    }
+
+    /// Compresses the payload at the end of the support buffer.
+    pub(crate) fn compress(&mut self, payload: &[u8]) -> ZResult<()> {
+        if self.zstd.is_none() {
+            if let CompressionAlgorithm::Zstd { level }
+            | CompressionAlgorithm::ZstdDictionary { level, .. } = self.config.algorithm
+            {
+                let zstd = match self.config.dictionary.as_deref() {
+                    Some(d) => zstd::bulk::Compressor::with_dictionary(level as i32, d),
+                    None => zstd::bulk::Compressor::new(level as i32),
+                }
+                .map_err(zstd_error)?;
+                self.zstd = Some(zstd);
+            }
+        }
+
+        let zstd = &mut self.zstd;
+        let writer = self.buffer.writer();
+        // SAFETY: assertion ensures `with_slot` precondition
+        unsafe {
+            writer.with_slot(writer.remaining(), |b| {
+                let len = match zstd.as_mut() {
+                    Some(z) => z.compress_to_buffer(payload, b).unwrap_or(0),
+                    None => lz4_flex::block::compress_into(payload, b).unwrap_or(0),
+                };
+                assert!(len <= b.len());
+                len
+            })
+        }
+        .map_err(|_| zerror!("Compression error"))?;
+
+        Ok(())
+    }
+}
+
+impl Clone for Compressor {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            buffer: BBuf::with_capacity(self.buffer.capacity()),
+            zstd: None,
+        }
+    }
+}
+
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressor")
+            .field("config", &self.config)
+            .field("buffer", &self.buffer.capacity())
+            .finish()
+    }
+}
+
+/// The decompression state of a link, reused across batches.
+///
+/// lz4 batches are decompressed statelessly, a zstd context is created at the first zstd batch.
+pub struct Decompressor {
+    dictionary: Option<Arc<[u8]>>,
+    zstd: Option<zstd::bulk::Decompressor<'static>>,
+}
+
+impl Decompressor {
+    pub fn new(config: &CompressionConfig) -> Self {
+        Self {
+            dictionary: config.dictionary.clone(),
+            zstd: None,
+        }
+    }
+
+    pub(crate) fn decompress_zstd(&mut self, payload: &[u8], into: &mut [u8]) -> ZResult<usize> {
+        let zstd = match self.zstd.as_mut() {
+            Some(z) => z,
+            None => {
+                let z = match self.dictionary.as_deref() {
+                    Some(d) => zstd::bulk::Decompressor::with_dictionary(d),
+                    None => zstd::bulk::Decompressor::new(),
+                }
+                .map_err(zstd_error)?;
+                self.zstd.insert(z)
+            }
+        };
+        zstd.decompress_to_buffer(payload, into)
+            .map_err(|e| zerror!("Decompression error: {e}").into())
+    }
+}
+
+impl Clone for Decompressor {
+    fn clone(&self) -> Self {
+        Self {
+            dictionary: self.dictionary.clone(),
+            zstd: None,
+        }
+    }
+}
+
+impl fmt::Debug for Decompressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decompressor")
+            .field("dictionary", &self.dictionary.as_ref().map(|d| d.len()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compression_algorithm_codec() {
+        let algorithms = [
+            CompressionAlgorithm::Lz4,
+            CompressionAlgorithm::Zstd { level: -7 },
+            CompressionAlgorithm::Zstd { level: 22 },
+            CompressionAlgorithm::ZstdDictionary {
+                level: 3,
+                id: u32::MAX,
+            },
+        ];
+        for a in algorithms {
+            assert_eq!(CompressionAlgorithm::try_from(u64::from(a)).unwrap(), a);
+        }
+        assert!(CompressionAlgorithm::try_from(3).is_err());
+        assert!(CompressionAlgorithm::try_from(u64::from(23u8) << 8 | 1).is_err());
+    }
+
+    #[test]
+    fn compression_algorithm_negotiate() {
+        use CompressionAlgorithm::*;
+
+        let d1 = ZstdDictionary { level: 9, id: 1 };
+        let d2 = ZstdDictionary { level: 3, id: 2 };
+        assert_eq!(Lz4.negotiate(Zstd { level: 3 }), Lz4);
+        assert_eq!(Zstd { level: 3 }.negotiate(Lz4), Lz4);
+        assert_eq!(
+            Zstd { level: 3 }.negotiate(Zstd { level: 1 }),
+            Zstd { level: 1 }
+        );
+        assert_eq!(d1.negotiate(d1), d1);
+        assert_eq!(d1.negotiate(d2), Zstd { level: 3 });
+        assert_eq!(d1.negotiate(Zstd { level: 12 }), Zstd { level: 9 });
+        assert_eq!(d1.negotiate(Lz4), Lz4);
+    }
+
+    #[test]
+    fn compression_config() {
+        assert!(CompressionConfig::zstd(23).is_err());
+        assert!(CompressionConfig::zstd_with_dictionary(3, vec![]).is_err());
+
+        let dictionary = b"zenoh dictionary".to_vec();
+        let config = CompressionConfig::zstd_with_dictionary(3, dictionary.clone())
+            .unwrap()
+            .exclude(Priority::DataLow);
+        let other = CompressionConfig::zstd_with_dictionary(3, dictionary).unwrap();
+        assert_eq!(config.algorithm(), other.algorithm());
+        assert!(config.is_excluded(Priority::DataLow));
+        assert!(!config.is_excluded(Priority::Data));
+
+        let negotiated = config.negotiated(CompressionAlgorithm::Zstd { level: 3 });
+        assert!(negotiated.dictionary.is_none());
+        assert!(negotiated.is_excluded(Priority::DataLow));
+    }
+}
@@ -0,0 +1,554 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+//! Forward error correction (FEC) for datagram links.
+//!
+//! Datagrams are sent in groups of `size` data datagrams followed by one parity datagram,
+//! allowing the receiver to rebuild one lost datagram per group without any retransmission.
+//! When no datagram has been sent for [`FLUSH_TIMEOUT`], the parity of the incomplete group
+//! is sent with the number of data datagrams it actually holds as `size`, so that sparse
+//! streams are protected too.
+//! The parity payload is the XOR of the payloads of the group, each one prefixed by its
+//! length and padded with zeros to the longest one.
+//!
+//! ```text
+//!  7 6 5 4 3 2 1 0
+//! +-+-+-+-+-+-+-+-+
+//! |     group     | -- u16 little-endian
+//! |               |
+//! +---------------+
+//! |     index     | -- index == size for the parity datagram
+//! +---------------+
+//! |     size      | -- number of data datagrams in the group
+//! +---------------+
+//! ~    payload    ~
+//! +---------------+
+//! ```
+//!
+//! Data datagrams are delivered as soon as they are received in order. When a datagram is
+//! missing, the following ones of the same group are held back until the missing one is
+//! rebuilt, the group is over or [`HOLD_TIMEOUT`] has elapsed, so that batches are always
+//! delivered in order and never held back for long when the parity is lost too.
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use zenoh_buffers::ZSlice;
+use zenoh_protocol::core::{EndPoint, Metadata};
+use zenoh_result::{bail, zerror, ZResult};
+
+/// Size of the FEC header prepended to every datagram.
+pub(crate) const HEADER: usize = 4;
+/// Size of the payload length carried in the parity datagram.
+const LEN: usize = 2;
+/// Maximum wire overhead of FEC with respect to the original datagram.
+pub(crate) const OVERHEAD: usize = HEADER + LEN;
+/// Number of previous groups for which late datagrams are silently dropped.
+const LATE_GROUPS: i16 = 8;
+/// Time without any datagram sent after which the parity of an incomplete group is sent.
+pub(crate) const FLUSH_TIMEOUT: Duration = Duration::from_millis(5);
+/// Maximum time datagrams are held back waiting for a missing one to be rebuilt. It leaves
+/// room for the parity of an incomplete group, sent after [`FLUSH_TIMEOUT`], to be received.
+pub(crate) const HOLD_TIMEOUT: Duration =
+    Duration::from_millis(4 * FLUSH_TIMEOUT.as_millis() as u64);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct FecConfig {
+    /// Number of data datagrams protected by a parity datagram.
+    pub(crate) size: u8,
+}
+
+impl FecConfig {
+    pub(crate) fn from_ratio(ratio: f64) -> ZResult<Self> {
+        if !(ratio > 0.0 && ratio <= 1.0) {
+            bail!("Invalid FEC ratio {ratio}: it must be in the (0.0, 1.0] range");
+        }
+        let size = (1.0 / ratio).round().clamp(1.0, u8::MAX as f64) as u8;
+        Ok(Self { size })
+    }
+
+    pub(crate) fn from_size(size: u64) -> ZResult<Self> {
+        match u8::try_from(size) {
+            Ok(size) if size > 0 => Ok(Self { size }),
+            _ => bail!("Invalid FEC group size: {size}"),
+        }
+    }
+
+    /// Returns the FEC configuration of an endpoint. The `fec` metadata of the endpoint,
+    /// if any, overrides the given default ratio: a ratio of 0 disables FEC.
+    pub(crate) fn from_endpoint(ratio: Option<f64>, endpoint: &EndPoint) -> ZResult<Option<Self>> {
+        let ratio = match endpoint.metadata().get(Metadata::FEC) {
+            Some(value) => {
+                let ratio = value
+                    .parse::<f64>()
+                    .map_err(|e| zerror!("Invalid FEC ratio '{value}' in {endpoint}: {e}"))?;
+                (ratio != 0.0).then_some(ratio)
+            }
+            None => ratio,
+        };
+        ratio.map(Self::from_ratio).transpose()
+    }
+}
+
+fn header(group: u16, index: u8, size: u8) -> [u8; HEADER] {
+    let [g0, g1] = group.to_le_bytes();
+    [g0, g1, index, size]
+}
+
+// XOR the length-prefixed payload into the parity accumulator
+fn accumulate(parity: &mut Vec<u8>, payload: &[u8]) -> ZResult<()> {
+    let len = u16::try_from(payload.len())
+        .map_err(|_| zerror!("FEC payload too large: {} bytes", payload.len()))?;
+    if parity.len() < LEN + payload.len() {
+        parity.resize(LEN + payload.len(), 0);
+    }
+    for (p, b) in parity
+        .iter_mut()
+        .zip(len.to_le_bytes().iter().chain(payload))
+    {
+        *p ^= b;
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+pub(crate) struct FecEncoder {
+    size: u8,
+    group: u16,
+    index: u8,
+    parity: Vec<u8>,
+    data: Vec<u8>,
+    out: Vec<u8>,
+}
+
+impl FecEncoder {
+    pub(crate) fn new(config: FecConfig) -> Self {
+        Self {
+            size: config.size,
+            group: 0,
+            index: 0,
+            parity: Vec::new(),
+            data: Vec::new(),
+            out: Vec::new(),
+        }
+    }
+
+    /// Encodes a datagram. Returns the datagram to send followed, when the group is
+    /// complete, by the parity datagram to send right after it.
+    pub(crate) fn encode(&mut self, payload: &[u8]) -> ZResult<(&[u8], Option<&[u8]>)> {
+        accumulate(&mut self.parity, payload)?;
+
+        self.data.clear();
+        self.data
+            .extend_from_slice(&header(self.group, self.index, self.size));
+        self.data.extend_from_slice(payload);
+
+        self.index += 1;
+        let parity = if self.index == self.size {
+            self.close();
+            Some(self.out.as_slice())
+        } else {
+            None
+        };
+
+        Ok((self.data.as_slice(), parity))
+    }
+
+    /// Returns whether the current group holds data datagrams not protected by a parity yet.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.index > 0
+    }
+
+    /// Closes the current group if it is incomplete, returning its parity datagram to send.
+    /// To be called once no datagram has been sent for [`FLUSH_TIMEOUT`].
+    pub(crate) fn flush(&mut self) -> Option<&[u8]> {
+        if self.is_pending() {
+            self.close();
+            Some(self.out.as_slice())
+        } else {
+            None
+        }
+    }
+
+    // Writes the parity datagram of the current group, holding `index` data datagrams
+    fn close(&mut self) {
+        self.out.clear();
+        self.out
+            .extend_from_slice(&header(self.group, self.index, self.index));
+        self.out.extend_from_slice(&self.parity);
+        self.parity.clear();
+        self.index = 0;
+        self.group = self.group.wrapping_add(1);
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct FecDecoder {
+    group: Option<u16>,
+    size: u8,
+    done: bool,
+    // Index of the first data datagram neither delivered nor skipped
+    next: u8,
+    received: Vec<bool>,
+    parity: Vec<u8>,
+    // Data datagrams received after a missing one, sorted by index
+    held: Vec<(u8, ZSlice)>,
+    // When the datagrams currently held back started to be held
+    held_since: Option<Instant>,
+    ready: VecDeque<ZSlice>,
+}
+
+impl FecDecoder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the next payload ready to be delivered, if any.
+    pub(crate) fn pop(&mut self) -> Option<ZSlice> {
+        self.ready.pop_front()
+    }
+
+    /// Returns when the datagrams held back, if any, are to be delivered regardless of the
+    /// missing ones.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.held_since.map(|since| since + HOLD_TIMEOUT)
+    }
+
+    /// Gives up on the missing datagrams if the datagrams held back have been held for
+    /// [`HOLD_TIMEOUT`]. The payloads held back are then returned by [`Self::pop`].
+    pub(crate) fn expire(&mut self, now: Instant) {
+        if self.deadline().is_some_and(|deadline| deadline <= now) {
+            if let Some((last, _)) = self.held.last() {
+                self.next = last + 1;
+                self.done = self.next == self.size;
+            }
+            self.flush();
+        }
+    }
+
+    /// Decodes a datagram received at `now`. The payloads that can be delivered are then
+    /// returned by [`Self::pop`].
+    pub(crate) fn decode(&mut self, datagram: ZSlice, now: Instant) -> ZResult<()> {
+        let [g0, g1, index, size] = *datagram
+            .get(..HEADER)
+            .and_then(|h| <&[u8; HEADER]>::try_from(h).ok())
+            .ok_or_else(|| zerror!("FEC datagram too short: {} bytes", datagram.len()))?;
+        if size == 0 || index > size {
+            bail!("Invalid FEC header: index {index}, size {size}");
+        }
+        let group = u16::from_le_bytes([g0, g1]);
+        let payload = datagram
+            .subslice(HEADER..)
+            .ok_or_else(|| zerror!("Invalid FEC datagram"))?;
+
+        match self.group {
+            Some(current) if current == group => {
+                if index == size && size < self.size && !self.done {
+                    // Parity of an incomplete group, sent when the traffic stopped
+                    self.truncate(size);
+                }
+                if self.done || size != self.size {
+                    return Ok(());
+                }
+            }
+            Some(current) if (-LATE_GROUPS..0).contains(&(group.wrapping_sub(current) as i16)) => {
+                // Late datagram of a previous group
+                return Ok(());
+            }
+            _ => {
+                // The current group is over: deliver whatever has been held back
+                self.flush();
+                self.group = Some(group);
+                self.size = size;
+                self.done = false;
+                self.next = 0;
+                self.received.clear();
+                self.received.resize(size as usize, false);
+                self.parity.clear();
+            }
+        }
+
+        if index == size {
+            self.done = true;
+            let mut missing = self
+                .received
+                .iter()
+                .enumerate()
+                .filter_map(|(i, r)| (!r).then_some(i as u8));
+            // A datagram already skipped is not delivered out of order
+            if let (Some(i), None) = (missing.next(), missing.next()) {
+                if i >= self.next {
+                    if let Some(rebuilt) = self.rebuild(&payload) {
+                        let pos = self.held.partition_point(|(j, _)| *j < i);
+                        self.held.insert(pos, (i, rebuilt));
+                    }
+                }
+            }
+            self.flush();
+            return Ok(());
+        }
+
+        if self.received[index as usize] || index < self.next {
+            return Ok(());
+        }
+        self.received[index as usize] = true;
+        accumulate(&mut self.parity, &payload)?;
+
+        let pos = self.held.partition_point(|(j, _)| *j < index);
+        self.held.insert(pos, (index, payload));
+        while self
+            .received
+            .get(self.next as usize)
+            .copied()
+            .unwrap_or(false)
+        {
+            self.next += 1;
+        }
+        let n = self.held.partition_point(|(j, _)| *j < self.next);
+        self.ready.extend(self.held.drain(..n).map(|(_, p)| p));
+        if self.held.is_empty() {
+            self.held_since = None;
+        } else if self.held_since.is_none() {
+            self.held_since = Some(now);
+        }
+
+        if self.next == size {
+            self.done = true;
+        }
+
+        Ok(())
+    }
+
+    // Shrinks the current group to its first `size` data datagrams
+    fn truncate(&mut self, size: u8) {
+        self.size = size;
+        self.received.truncate(size as usize);
+        self.held.retain(|(j, _)| *j < size);
+        self.next = self.next.min(size);
+    }
+
+    fn rebuild(&self, parity: &[u8]) -> Option<ZSlice> {
+        if parity.len() < LEN || self.parity.len() > parity.len() {
+            return None;
+        }
+        let mut rebuilt = parity.to_vec();
+        for (r, p) in rebuilt.iter_mut().zip(self.parity.iter()) {
+            *r ^= p;
+        }
+        let len = u16::from_le_bytes([rebuilt[0], rebuilt[1]]) as usize;
+        if LEN + len > rebuilt.len() {
+            return None;
+        }
+        rebuilt.truncate(LEN + len);
+        rebuilt.drain(..LEN);
+        Some(rebuilt.into())
+    }
+
+    fn flush(&mut self) {
+        self.ready.extend(self.held.drain(..).map(|(_, p)| p));
+        self.held_since = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payloads(n: usize) -> Vec<Vec<u8>> {
+        (0..n).map(|i| vec![i as u8; 1 + (i * 37) % 200]).collect()
+    }
+
+    // Encodes the payloads and returns the resulting datagrams
+    fn encode(config: FecConfig, payloads: &[Vec<u8>]) -> Vec<ZSlice> {
+        let mut encoder = FecEncoder::new(config);
+        let mut datagrams = vec![];
+        for p in payloads {
+            let (data, parity) = encoder.encode(p).unwrap();
+            datagrams.push(data.to_vec().into());
+            if let Some(parity) = parity {
+                datagrams.push(parity.to_vec().into());
+            }
+        }
+        datagrams
+    }
+
+    fn decode(datagrams: impl IntoIterator<Item = ZSlice>) -> Vec<Vec<u8>> {
+        let mut decoder = FecDecoder::new();
+        let mut out = vec![];
+        for d in datagrams {
+            decoder.decode(d, Instant::now()).unwrap();
+            while let Some(p) = decoder.pop() {
+                out.push(p.as_slice().to_vec());
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn fec_config() {
+        assert_eq!(FecConfig::from_ratio(0.25).unwrap().size, 4);
+        assert_eq!(FecConfig::from_ratio(1.0).unwrap().size, 1);
+        assert_eq!(FecConfig::from_ratio(0.001).unwrap().size, u8::MAX);
+        assert!(FecConfig::from_ratio(0.0).is_err());
+        assert!(FecConfig::from_ratio(1.5).is_err());
+        assert!(FecConfig::from_size(0).is_err());
+        assert!(FecConfig::from_size(256).is_err());
+
+        let endpoint: EndPoint = "udp/127.0.0.1:7447?fec=0.5".parse().unwrap();
+        let config = FecConfig::from_endpoint(None, &endpoint).unwrap();
+        assert_eq!(config, Some(FecConfig { size: 2 }));
+        let endpoint: EndPoint = "udp/127.0.0.1:7447?fec=0".parse().unwrap();
+        assert!(FecConfig::from_endpoint(Some(0.25), &endpoint)
+            .unwrap()
+            .is_none());
+        let endpoint: EndPoint = "udp/127.0.0.1:7447".parse().unwrap();
+        let config = FecConfig::from_endpoint(Some(0.25), &endpoint).unwrap();
+        assert_eq!(config, Some(FecConfig { size: 4 }));
+    }
+
+    #[test]
+    fn fec_no_loss() {
+        let config = FecConfig { size: 4 };
+        let payloads = payloads(10);
+        let datagrams = encode(config, &payloads);
+        assert_eq!(datagrams.len(), 12);
+        assert_eq!(decode(datagrams), payloads);
+    }
+
+    #[test]
+    fn fec_single_loss() {
+        let config = FecConfig { size: 4 };
+        let payloads = payloads(16);
+        let datagrams = encode(config, &payloads);
+        // Drop one different datagram in every group, parity included
+        for lost in 0..=config.size as usize {
+            let group = config.size as usize + 1;
+            let received = datagrams
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| i % group != lost)
+                .map(|(_, d)| d.clone());
+            assert_eq!(decode(received), payloads);
+        }
+    }
+
+    #[test]
+    fn fec_double_loss() {
+        let config = FecConfig { size: 4 };
+        let payloads = payloads(8);
+        let datagrams = encode(config, &payloads);
+        // Two data datagrams lost in the first group cannot be rebuilt
+        let received = datagrams
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != 1 && *i != 2)
+            .map(|(_, d)| d.clone());
+        let mut expected = payloads.clone();
+        expected.drain(1..3);
+        assert_eq!(decode(received), expected);
+
+        // A data datagram lost with its parity is skipped once the next group starts
+        let received = datagrams
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != 1 && *i != 4)
+            .map(|(_, d)| d.clone());
+        let mut expected = payloads.clone();
+        expected.remove(1);
+        assert_eq!(decode(received), expected);
+    }
+
+    #[test]
+    fn fec_loss_then_silence() {
+        let config = FecConfig { size: 4 };
+        let payloads = payloads(4);
+        let datagrams = encode(config, &payloads);
+        let mut decoder = FecDecoder::new();
+        fn pop(decoder: &mut FecDecoder) -> Vec<Vec<u8>> {
+            std::iter::from_fn(|| decoder.pop().map(|p| p.as_slice().to_vec())).collect()
+        }
+
+        // The datagrams following a lost one are held back until the deadline
+        let now = Instant::now();
+        assert!(decoder.deadline().is_none());
+        decoder.decode(datagrams[1].clone(), now).unwrap();
+        decoder.decode(datagrams[2].clone(), now).unwrap();
+        assert!(pop(&mut decoder).is_empty());
+        let deadline = decoder.deadline().unwrap();
+        assert_eq!(deadline, now + HOLD_TIMEOUT);
+        decoder.expire(deadline - Duration::from_millis(1));
+        assert!(pop(&mut decoder).is_empty());
+        decoder.expire(deadline);
+        assert_eq!(pop(&mut decoder), payloads[1..3]);
+        assert!(decoder.deadline().is_none());
+
+        // The next datagrams are delivered right away, the lost one is not delivered late
+        decoder.decode(datagrams[3].clone(), deadline).unwrap();
+        assert_eq!(pop(&mut decoder), payloads[3..4]);
+        decoder.decode(datagrams[0].clone(), deadline).unwrap();
+        decoder.decode(datagrams[4].clone(), deadline).unwrap();
+        assert!(pop(&mut decoder).is_empty());
+    }
+
+    #[test]
+    fn fec_sparse_loss() {
+        let config = FecConfig { size: 4 };
+        let payloads = payloads(6);
+        // A sparse stream: the groups are closed by the flush timer
+        let mut encoder = FecEncoder::new(config);
+        let mut groups = vec![];
+        for chunk in [&payloads[0..1], &payloads[1..3], &payloads[3..6]] {
+            let mut group: Vec<ZSlice> = vec![];
+            for p in chunk {
+                let (data, parity) = encoder.encode(p).unwrap();
+                assert!(parity.is_none());
+                group.push(data.to_vec().into());
+            }
+            assert!(encoder.is_pending());
+            group.push(encoder.flush().unwrap().to_vec().into());
+            assert!(!encoder.is_pending());
+            assert!(encoder.flush().is_none());
+            groups.push(group);
+        }
+        // Drop one different datagram in every group, parity included
+        for lost in 0..4 {
+            let received = groups
+                .iter()
+                .flat_map(|group| {
+                    let lost = lost % group.len();
+                    group
+                        .iter()
+                        .enumerate()
+                        .filter(move |(i, _)| *i != lost)
+                        .map(|(_, d)| d.clone())
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(decode(received), payloads);
+        }
+    }
+
+    #[test]
+    fn fec_duplicates() {
+        let config = FecConfig { size: 2 };
+        let payloads = payloads(4);
+        let datagrams = encode(config, &payloads);
+        let received = datagrams.iter().flat_map(|d| [d.clone(), d.clone()]);
+        assert_eq!(decode(received), payloads);
+
+        // Late datagrams of previous groups are dropped
+        let mut received = datagrams.clone();
+        received.push(datagrams[0].clone());
+        assert_eq!(decode(received), payloads);
+    }
+}
@@ -12,7 +12,10 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 pub mod batch;
+#[cfg(feature = "transport_compression")]
+pub mod compression;
 pub(crate) mod defragmentation;
+pub(crate) mod fec;
 pub(crate) mod pipeline;
 pub(crate) mod priority;
 pub(crate) mod seq_num;
@@ -429,8 +429,10 @@ impl StageIn {
             // Serialize the message fragment
             match batch.encode((&mut reader, &mut fragment)) {
                 Ok(_) => {
-                    // Update the SN
-                    fragment.sn = tch.sn.get();
+                    // Update the SN for the next fragment, if any
+                    if reader.can_read() {
+                        fragment.sn = tch.sn.get();
+                    }
                     fragment.ext_first = None;
                     // Move the serialization batch into the OUT pipeline
                     self.s_out.move_batch(batch);
@@ -1310,6 +1312,55 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn tx_pipeline_fragmentation_sn() -> ZResult<()> {
+        let tct = TransportPriorityTx::make(Bits::from(TransportSn::MAX))?;
+        let (producer, mut consumer) =
+            TransmissionPipeline::make(CONFIG_NOT_STREAMED, &[tct], false);
+
+        // Send a fragmented message followed by a small one
+        let t_s = task::spawn_blocking(move || {
+            for payload_size in [2 * BatchSize::MAX as usize, 8] {
+                let message = NetworkMessage::from(Push {
+                    wire_expr: "test".into(),
+                    ext_qos: ext::QoSType::new(Priority::Control, CongestionControl::Block, false),
+                    ..Push::from(vec![0_u8; payload_size])
+                });
+                assert!(producer.push_network_message(message.as_ref()).unwrap());
+            }
+            producer
+        });
+
+        let mut fragment_sns: Vec<TransportSn> = vec![];
+        let mut frame_sn = None;
+        while frame_sn.is_none() {
+            let (batch, priority) = timeout(TIMEOUT, consumer.pull()).await?.unwrap();
+            let mut reader = batch.as_slice().reader();
+            let codec = Zenoh080::new();
+            loop {
+                let res: Result<TransportMessage, DidntRead> = codec.read(&mut reader);
+                let Ok(msg) = res else {
+                    break;
+                };
+                match msg.body {
+                    TransportBody::Fragment(Fragment { sn, .. }) => fragment_sns.push(sn),
+                    TransportBody::Frame(Frame { sn, .. }) => frame_sn = Some(sn),
+                    _ => {}
+                }
+            }
+            consumer.refill(batch, priority);
+        }
+        let _producer = timeout(TIMEOUT, t_s).await??;
+
+        // The last fragment doesn't consume the sequence number of the next message
+        assert!(fragment_sns.len() > 1);
+        let expected: Vec<TransportSn> = (fragment_sns[0]..).take(fragment_sns.len() + 1).collect();
+        fragment_sns.extend(frame_sn);
+        assert_eq!(fragment_sns, expected);
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn tx_pipeline_blocking() -> ZResult<()> {
         fn schedule(queue: TransmissionPipelineProducer, counter: Arc<AtomicUsize>, id: usize) {
@@ -23,7 +23,7 @@ use zenoh_protocol::{
 use zenoh_result::{bail, ZResult};
 
 use crate::{
-    common::{batch::BatchConfig, seq_num},
+    common::{batch::BatchConfig, fec::FecConfig, seq_num},
     multicast::{
         link::{TransportLinkMulticast, TransportLinkMulticastConfig},
         transport::TransportMulticastInner,
@@ -70,6 +70,11 @@ pub(crate) async fn open_link(
             is_compression: manager.config.multicast.is_compression,
             ..Default::default()
         },
+        fec: FecConfig::from_endpoint(manager.config.multicast.fec, &locator.to_endpoint())?,
+        #[cfg(feature = "test")]
+        datagram_loss: manager.config.multicast.datagram_loss,
+        #[cfg(feature = "transport_compression")]
+        compression: manager.config.multicast.compression.clone(),
     };
     let link = TransportLinkMulticast::new(link, config);
 
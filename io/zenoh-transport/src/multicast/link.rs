@@ -12,6 +12,7 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 use std::{
+    collections::HashMap,
     convert::TryInto,
     fmt,
     sync::Arc,
@@ -19,7 +20,7 @@ use std::{
 };
 
 use tokio::task::JoinHandle;
-use zenoh_buffers::{BBuf, ZSlice, ZSliceBuffer};
+use zenoh_buffers::{ZSlice, ZSliceBuffer};
 use zenoh_core::{zcondfeat, zlock};
 use zenoh_link::{LinkMulticast, Locator};
 use zenoh_protocol::{
@@ -31,24 +32,46 @@ use zenoh_protocol::{
 use zenoh_result::{zerror, ZResult};
 use zenoh_sync::{RecyclingObject, RecyclingObjectPool, Signal};
 
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::{CompressionConfig, Compressor, Decompressor};
 use crate::{
     common::{
         batch::{BatchConfig, Encode, Finalize, RBatch, WBatch},
+        fec::{self, FecConfig, FecDecoder, FecEncoder},
         pipeline::{
             PipelineConsumer, TransmissionPipeline, TransmissionPipelineConf,
             TransmissionPipelineConsumer, TransmissionPipelineProducer,
         },
         priority::TransportPriorityTx,
     },
-    multicast::transport::TransportMulticastInner,
+    multicast::{
+        reliability::{Nack, RetransmissionWindow},
+        transport::TransportMulticastInner,
+        TransportMulticastReliabilityConfig,
+    },
 };
 
+const NACK_QUEUE_SIZE: usize = 64;
+
 /****************************/
 /* TRANSPORT MULTICAST LINK */
 /****************************/
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub(crate) struct TransportLinkMulticastConfig {
     pub(crate) batch: BatchConfig,
+    pub(crate) fec: Option<FecConfig>,
+    #[cfg(feature = "test")]
+    pub(crate) datagram_loss: Option<usize>,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) compression: CompressionConfig,
+}
+
+impl TransportLinkMulticastConfig {
+    /// Size of the buffers required to receive a batch, FEC overhead included.
+    pub(crate) fn batch_buffer_size(&self) -> usize {
+        let overhead = if self.fec.is_some() { fec::OVERHEAD } else { 0 };
+        self.batch.mtu as usize + overhead
+    }
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -61,28 +84,42 @@ impl TransportLinkMulticast {
     pub(crate) fn new(link: LinkMulticast, mut config: TransportLinkMulticastConfig) -> Self {
         config.batch.mtu = link.get_mtu().min(config.batch.mtu);
         config.batch.is_streamed = false;
+        if config.fec.is_some() {
+            // The FEC overhead must fit in the link MTU
+            config.batch.mtu = config
+                .batch
+                .mtu
+                .min(link.get_mtu().saturating_sub(fec::OVERHEAD as BatchSize));
+        }
         Self { link, config }
     }
 
     pub(crate) fn tx(&self) -> TransportLinkMulticastTx {
         TransportLinkMulticastTx {
             inner: self.clone(),
-            buffer: zcondfeat!(
-                "transport_compression",
-                self.config
-                    .batch
-                    .is_compression
-                    .then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(self.config.batch.mtu as usize),
-                    )),
-                None
-            ),
+            #[cfg(feature = "transport_compression")]
+            compressor: self
+                .config
+                .batch
+                .is_compression
+                .then(|| Compressor::new(self.config.compression.clone(), self.config.batch.mtu)),
+            fec: self.config.fec.map(FecEncoder::new),
+            #[cfg(feature = "test")]
+            datagram_loss: self.config.datagram_loss.map(DatagramLoss::new),
         }
     }
 
     pub(crate) fn rx(&self) -> TransportLinkMulticastRx {
         TransportLinkMulticastRx {
             inner: self.clone(),
+            fec: self.config.fec.map(|_| HashMap::new()),
+            pending: None,
+            #[cfg(feature = "transport_compression")]
+            decompressor: self
+                .config
+                .batch
+                .is_compression
+                .then(|| Decompressor::new(&self.config.compression)),
         }
     }
 
@@ -128,28 +165,109 @@ impl fmt::Debug for TransportLinkMulticast {
 
 pub(crate) struct TransportLinkMulticastTx {
     pub(crate) inner: TransportLinkMulticast,
-    pub(crate) buffer: Option<BBuf>,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) compressor: Option<Compressor>,
+    pub(crate) fec: Option<FecEncoder>,
+    #[cfg(feature = "test")]
+    pub(crate) datagram_loss: Option<DatagramLoss>,
 }
 
 impl TransportLinkMulticastTx {
-    pub(crate) async fn send_batch(&mut self, batch: &mut WBatch) -> ZResult<()> {
-        const ERR: &str = "Write error on link: ";
-
+    /// Finalizes the batch and returns the bytes to be written on the link.
+    /// The priority of the batch is used to decide whether to compress it.
+    #[cfg_attr(
+        not(feature = "transport_compression"),
+        allow(clippy::needless_lifetimes)
+    )]
+    pub(crate) fn finalize<'a>(
+        batch: &'a mut WBatch,
+        #[cfg(feature = "transport_compression")] compressor: &'a mut Option<Compressor>,
+        #[cfg(feature = "transport_compression")] priority: Priority,
+    ) -> ZResult<&'a [u8]> {
         let res = batch
-            .finalize(self.buffer.as_mut())
-            .map_err(|_| zerror!("{ERR}{self}"))?;
+            .finalize(
+                #[cfg(feature = "transport_compression")]
+                compressor
+                    .as_mut()
+                    .filter(|c| !c.config().is_excluded(priority)),
+            )
+            .map_err(|_| zerror!("Invalid batch finalization"))?;
 
         let bytes = match res {
             Finalize::Batch => batch.as_slice(),
-            Finalize::Buffer => self
-                .buffer
-                .as_ref()
-                .ok_or_else(|| zerror!("Invalid buffer finalization"))?
-                .as_slice(),
+            Finalize::Buffer => zcondfeat!(
+                "transport_compression",
+                compressor.as_ref().map(|c| c.as_slice()),
+                None
+            )
+            .ok_or_else(|| zerror!("Invalid buffer finalization"))?,
         };
 
+        Ok(bytes)
+    }
+
+    /// Writes the bytes on the link, followed by the parity datagram of the FEC group if any.
+    pub(crate) async fn write_all(
+        link: &LinkMulticast,
+        fec: Option<&mut FecEncoder>,
+        #[cfg(feature = "test")] mut datagram_loss: Option<&mut DatagramLoss>,
+        bytes: &[u8],
+    ) -> ZResult<()> {
+        let (data, parity) = match fec {
+            Some(fec) => fec.encode(bytes)?,
+            None => (bytes, None),
+        };
+        for datagram in std::iter::once(data).chain(parity) {
+            #[cfg(feature = "test")]
+            if datagram_loss.as_mut().is_some_and(|loss| loss.drop_next()) {
+                continue;
+            }
+            link.write_all(datagram).await?;
+        }
+        Ok(())
+    }
+
+    /// Sends the parity datagram of the incomplete FEC group, if any.
+    pub(crate) async fn flush_fec(&mut self) -> ZResult<()> {
+        if let Some(parity) = self.fec.as_mut().and_then(|fec| fec.flush()) {
+            #[cfg(feature = "test")]
+            if self
+                .datagram_loss
+                .as_mut()
+                .is_some_and(|loss| loss.drop_next())
+            {
+                return Ok(());
+            }
+            self.inner.link.write_all(parity).await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn send_batch(
+        &mut self,
+        batch: &mut WBatch,
+        #[cfg(feature = "transport_compression")] priority: Priority,
+    ) -> ZResult<()> {
+        const ERR: &str = "Write error on link: ";
+
+        let bytes = Self::finalize(
+            batch,
+            #[cfg(feature = "transport_compression")]
+            &mut self.compressor,
+            #[cfg(feature = "transport_compression")]
+            priority,
+        )
+        .map_err(|e| zerror!("{ERR}{}: {e}", self.inner))?;
+
         // Send the message on the link
-        self.inner.link.write_all(bytes).await?;
+        Self::write_all(
+            &self.inner.link,
+            self.fec.as_mut(),
+            #[cfg(feature = "test")]
+            self.datagram_loss.as_mut(),
+            bytes,
+        )
+        .await?;
 
         Ok(())
     }
@@ -161,11 +279,38 @@ impl TransportLinkMulticastTx {
         let mut batch = WBatch::new(self.inner.config.batch);
         batch.encode(msg).map_err(|_| zerror!("{ERR}{self}"))?;
         let len = batch.len() as usize;
-        self.send_batch(&mut batch).await?;
+        self.send_batch(
+            &mut batch,
+            #[cfg(feature = "transport_compression")]
+            Priority::Control,
+        )
+        .await?;
         Ok(len)
     }
 }
 
+/// Drops one datagram out of every `every` datagrams written on a link, so as to test the
+/// recovery of lost datagrams.
+#[cfg(feature = "test")]
+#[derive(Debug)]
+pub(crate) struct DatagramLoss {
+    every: usize,
+    written: usize,
+}
+
+#[cfg(feature = "test")]
+impl DatagramLoss {
+    fn new(every: usize) -> Self {
+        Self { every, written: 0 }
+    }
+
+    // Returns whether the next datagram is to be dropped
+    fn drop_next(&mut self) -> bool {
+        self.written += 1;
+        self.written % self.every == 0
+    }
+}
+
 impl fmt::Display for TransportLinkMulticastTx {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.inner)
@@ -179,7 +324,7 @@ impl fmt::Debug for TransportLinkMulticastTx {
             .field("config", &self.inner.config);
         #[cfg(feature = "transport_compression")]
         {
-            s.field("buffer", &self.buffer.as_ref().map(|b| b.capacity()));
+            s.field("compressor", &self.compressor);
         }
         s.finish()
     }
@@ -187,22 +332,88 @@ impl fmt::Debug for TransportLinkMulticastTx {
 
 pub(crate) struct TransportLinkMulticastRx {
     pub(crate) inner: TransportLinkMulticast,
+    // One FEC decoder per sender
+    pub(crate) fec: Option<HashMap<Locator, FecDecoder>>,
+    // The sender whose FEC decoder has payloads ready to be delivered
+    pending: Option<Locator>,
+    #[cfg(feature = "transport_compression")]
+    decompressor: Option<Decompressor>,
 }
 
 impl TransportLinkMulticastRx {
-    pub async fn recv_batch<C, T>(&self, buff: C) -> ZResult<(RBatch<ZSlice>, Locator)>
+    pub async fn recv_batch<C, T>(&mut self, buff: C) -> ZResult<(RBatch<ZSlice>, Locator)>
     where
         C: Fn() -> T + Copy,
         T: AsMut<[u8]> + ZSliceBuffer + 'static,
     {
         const ERR: &str = "Read error from link: ";
 
-        let mut into = (buff)();
-        let (n, locator) = self.inner.link.read(into.as_mut()).await?;
-        let buffer = ZSlice::new(Arc::new(into), 0, n).map_err(|_| zerror!("Error"))?;
+        let (buffer, locator) = loop {
+            if let Some(locator) = self.pending.take() {
+                let payload = self
+                    .fec
+                    .as_mut()
+                    .and_then(|fec| fec.get_mut(&locator))
+                    .and_then(|fec| fec.pop());
+                if let Some(payload) = payload {
+                    self.pending = Some(locator.clone());
+                    break (payload, locator);
+                }
+            }
+
+            // The sender whose datagrams held back by FEC are due first, if any
+            let deadline = self.fec.as_ref().and_then(|fec| {
+                fec.iter()
+                    .filter_map(|(l, fec)| Some((fec.deadline()?, l)))
+                    .min_by_key(|(deadline, _)| *deadline)
+                    .map(|(deadline, l)| (deadline, l.clone()))
+            });
+
+            let mut into = (buff)();
+            let (n, locator) = match deadline {
+                Some((deadline, expired)) => {
+                    let read = tokio::select! {
+                        res = self.inner.link.read(into.as_mut()) => Some(res?),
+                        _ = tokio::time::sleep_until(deadline.into()) => None,
+                    };
+                    match read {
+                        Some(read) => read,
+                        None => {
+                            if let Some(fec) =
+                                self.fec.as_mut().and_then(|fec| fec.get_mut(&expired))
+                            {
+                                fec.expire(Instant::now());
+                            }
+                            self.pending = Some(expired);
+                            continue;
+                        }
+                    }
+                }
+                None => self.inner.link.read(into.as_mut()).await?,
+            };
+            let buffer = ZSlice::new(Arc::new(into), 0, n).map_err(|_| zerror!("Error"))?;
+            match self.fec.as_mut() {
+                Some(fec) => {
+                    let locator = locator.into_owned();
+                    let decoder = fec.entry(locator.clone()).or_default();
+                    if let Err(e) = decoder.decode(buffer, Instant::now()) {
+                        // A malformed datagram must not tear down the link: drop it
+                        tracing::debug!("{ERR}{self}. Dropping datagram: {e}.");
+                    }
+                    self.pending = Some(locator);
+                }
+                None => break (buffer, locator.into_owned()),
+            }
+        };
         let mut batch = RBatch::new(self.inner.config.batch, buffer);
-        batch.initialize(buff).map_err(|_| zerror!("{ERR}{self}"))?;
-        Ok((batch, locator.into_owned()))
+        batch
+            .initialize(
+                buff,
+                #[cfg(feature = "transport_compression")]
+                self.decompressor.as_mut(),
+            )
+            .map_err(|_| zerror!("{ERR}{self}"))?;
+        Ok((batch, locator))
     }
 
     // pub async fn recv(&mut self) -> ZResult<(TransportMessage, Locator)> {
@@ -243,6 +454,7 @@ pub(super) struct TransportLinkMulticastConfigUniversal {
     pub(super) join_interval: Duration,
     pub(super) sn_resolution: Bits,
     pub(super) batch_size: BatchSize,
+    pub(super) reliability: Option<TransportMulticastReliabilityConfig>,
 }
 
 // TODO(yuyuan): Introduce TaskTracker or JoinSet and retire handle_tx, handle_rx, and signal_rx.
@@ -252,6 +464,8 @@ pub(super) struct TransportLinkMulticastUniversal {
     pub(super) link: TransportLinkMulticast,
     // The transmission pipeline
     pub(super) pipeline: Option<TransmissionPipelineProducer>,
+    // The NACKs addressed to this node, to be served by the TX task
+    pub(super) nacks: Option<flume::Sender<Nack>>,
     // The transport this link is associated to
     transport: TransportMulticastInner,
     // The signals to stop TX/RX tasks
@@ -269,6 +483,7 @@ impl TransportLinkMulticastUniversal {
             transport,
             link,
             pipeline: None,
+            nacks: None,
             handle_tx: None,
             signal_rx: Signal::new(),
             handle_rx: None,
@@ -323,6 +538,23 @@ impl TransportLinkMulticastUniversal {
             let (producer, consumer) = TransmissionPipeline::make(tpc, &priority_tx, false);
             self.pipeline = Some(producer);
 
+            // The retransmission window, only if reliable batches are to be kept
+            let retransmission = config
+                .reliability
+                .as_ref()
+                .filter(|r| r.retransmission_window > 0)
+                .map(|r| {
+                    // NACKs exceeding the capacity are dropped, receivers will retry them
+                    let (sender, receiver) = flume::bounded(NACK_QUEUE_SIZE);
+                    self.nacks = Some(sender);
+                    let window = RetransmissionWindow::new(
+                        r,
+                        priority_tx.len(),
+                        config.sn_resolution.mask() as TransportSn,
+                    );
+                    (window, receiver)
+                });
+
             // Spawn the TX task
             let c_link = self.link.clone();
             let c_transport = self.transport.clone();
@@ -333,6 +565,7 @@ impl TransportLinkMulticastUniversal {
                     c_link.tx(),
                     config,
                     initial_sns,
+                    retransmission,
                     #[cfg(feature = "stats")]
                     c_transport.link_stats.clone(),
                 )
@@ -416,6 +649,7 @@ async fn tx_task(
     mut link: TransportLinkMulticastTx,
     config: TransportLinkMulticastConfigUniversal,
     mut last_sns: Vec<PrioritySn>,
+    mut retransmission: Option<(RetransmissionWindow, flume::Receiver<Nack>)>,
     #[cfg(feature = "stats")] stats: zenoh_stats::LinkStats,
 ) -> ZResult<()> {
     async fn join(last_join: Instant, join_interval: Duration) {
@@ -427,14 +661,54 @@ async fn tx_task(
         }
     }
 
+    async fn next_nack(
+        retransmission: &Option<(RetransmissionWindow, flume::Receiver<Nack>)>,
+    ) -> Nack {
+        match retransmission.as_ref().map(|(_, r)| r.recv_async()) {
+            Some(recv) => match recv.await {
+                Ok(nack) => nack,
+                Err(_) => std::future::pending().await,
+            },
+            None => std::future::pending().await,
+        }
+    }
+
+    let mask = config.sn_resolution.mask() as TransportSn;
+
     let mut last_join = Instant::now().checked_sub(config.join_interval).unwrap();
     loop {
         tokio::select! {
             res = pipeline.pull() => {
                 match res {
                     Some((mut batch, priority)) => {
-                        // Send the buffer on the link
-                        link.send_batch(&mut batch).await?;
+                        match (retransmission.as_mut(), batch.codec.latest_sn.reliable) {
+                            (Some((window, _)), Some(last)) => {
+                                // Keep a copy of the reliable batch for retransmission
+                                let first = (1 + last_sns[priority as usize].reliable) & mask;
+                                let bytes: Box<[u8]> =
+                                    TransportLinkMulticastTx::finalize(
+                                        &mut batch,
+                                        #[cfg(feature = "transport_compression")]
+                                        &mut link.compressor,
+                                        #[cfg(feature = "transport_compression")]
+                                        priority,
+                                    )?.into();
+                                TransportLinkMulticastTx::write_all(
+                                    &link.inner.link,
+                                    link.fec.as_mut(),
+                                    #[cfg(feature = "test")]
+                                    link.datagram_loss.as_mut(),
+                                    &bytes,
+                                ).await?;
+                                window.push(priority as usize, first, last, bytes);
+                            }
+                            // Send the buffer on the link
+                            _ => link.send_batch(
+                                &mut batch,
+                                #[cfg(feature = "transport_compression")]
+                                priority,
+                            ).await?,
+                        }
                         // Keep track of next SNs
                         if let Some(sn) = batch.codec.latest_sn.reliable {
                             last_sns[priority as usize].reliable = sn;
@@ -453,8 +727,14 @@ async fn tx_task(
                     None => {
                         // Drain the transmission pipeline and write remaining bytes on the wire
                         let mut batches = pipeline.drain();
-                        for (mut b, _) in batches.drain(..) {
-                            tokio::time::timeout(config.join_interval, link.send_batch(&mut b))
+                        #[allow(unused_variables)] // Used when transport_compression feature is enabled
+                        for (mut b, priority) in batches.drain(..) {
+                            let send = link.send_batch(
+                                &mut b,
+                                #[cfg(feature = "transport_compression")]
+                                priority,
+                            );
+                            tokio::time::timeout(config.join_interval, send)
                                 .await
                                 .map_err(|_| {
                                     zerror!(
@@ -476,6 +756,34 @@ async fn tx_task(
                 }
             }
 
+            _ = tokio::time::sleep(fec::FLUSH_TIMEOUT),
+                if link.fec.as_ref().is_some_and(FecEncoder::is_pending) => {
+                // No batch has been sent for a while: protect the last ones with a parity
+                link.flush_fec().await?;
+            }
+
+            nack = next_nack(&retransmission) => {
+                let Some((window, _)) = retransmission.as_mut() else {
+                    continue;
+                };
+                let priority = if last_sns.len() == Priority::NUM {
+                    nack.priority as usize
+                } else {
+                    0
+                };
+                for bytes in window.retransmit(&nack, priority, Instant::now()) {
+                    TransportLinkMulticastTx::write_all(
+                        &link.inner.link,
+                        link.fec.as_mut(),
+                        #[cfg(feature = "test")]
+                        link.datagram_loss.as_mut(),
+                        bytes,
+                    ).await?;
+                    #[cfg(feature = "stats")]
+                    stats.inc_bytes(zenoh_stats::Tx, bytes.len() as u64);
+                }
+            }
+
             _ = join(last_join, config.join_interval) => {
                 let next_sns = last_sns
                     .iter()
@@ -485,6 +793,14 @@ async fn tx_task(
                             & config.sn_resolution.mask() as TransportSn,
                     })
                     .collect::<Vec<PrioritySn>>();
+                // Advertise the oldest reliable sn that can still be retransmitted
+                let ext_nack = retransmission.as_ref().map(|(window, _)| {
+                    let mut oldest = [0; Priority::NUM];
+                    for (i, (o, n)) in oldest.iter_mut().zip(next_sns.iter()).enumerate() {
+                        *o = window.oldest(i).unwrap_or(n.reliable);
+                    }
+                    Box::new(oldest)
+                });
                 let (next_sn, ext_qos) = if next_sns.len() == Priority::NUM {
                     let tmp: [PrioritySn; Priority::NUM] = next_sns.try_into().unwrap();
                     (PrioritySn::DEFAULT, Some(Box::new(tmp)))
@@ -501,6 +817,7 @@ async fn tx_task(
                     next_sn,
                     ext_qos,
                     ext_shm: None,
+                    ext_nack,
                     ext_patch: PatchType::CURRENT
                 }
                 .into();
@@ -544,8 +861,25 @@ async fn rx_task(
         Ok((rbatch, locator))
     }
 
+    async fn repair(interval: Option<&mut tokio::time::Interval>) {
+        match interval {
+            Some(interval) => {
+                interval.tick().await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    // The periodic check of the reliable SNs for gaps
+    let mut repair_interval = transport
+        .manager
+        .config
+        .multicast
+        .reliability
+        .map(|r| tokio::time::interval(r.nack_interval));
+
     // The pool of buffers
-    let mtu = link.inner.config.batch.mtu as usize;
+    let mtu = link.inner.config.batch_buffer_size();
     let mut n = rx_buffer_size / mtu;
     if n == 0 {
         tracing::debug!("RX configured buffer of {rx_buffer_size} bytes is too small for {link} that has an MTU of {mtu} bytes. Defaulting to {mtu} bytes for RX buffer.");
@@ -556,6 +890,7 @@ async fn rx_task(
     loop {
         tokio::select! {
             _ = signal.wait() => break,
+            _ = repair(repair_interval.as_mut()) => transport.repair_peers()?,
             res = read(&mut link, &pool) => {
                 let (batch, locator) = res?;
 
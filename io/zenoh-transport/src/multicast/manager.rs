@@ -25,8 +25,13 @@ use zenoh_protocol::{
 };
 use zenoh_result::{bail, zerror, ZResult};
 
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::{CompressionAlgorithm, CompressionConfig};
 use crate::{
-    multicast::{transport::TransportMulticastInner, TransportMulticast},
+    common::fec::FecConfig,
+    multicast::{
+        transport::TransportMulticastInner, TransportMulticast, TransportMulticastReliabilityConfig,
+    },
     TransportManager,
 };
 
@@ -39,6 +44,12 @@ pub struct TransportManagerConfigMulticast {
     pub is_qos: bool,
     #[cfg(feature = "transport_compression")]
     pub is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    pub compression: CompressionConfig,
+    pub reliability: Option<TransportMulticastReliabilityConfig>,
+    pub fec: Option<f64>,
+    #[cfg(feature = "test")]
+    pub datagram_loss: Option<usize>,
 }
 
 pub struct TransportManagerBuilderMulticast {
@@ -49,6 +60,12 @@ pub struct TransportManagerBuilderMulticast {
     is_qos: bool,
     #[cfg(feature = "transport_compression")]
     is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    compression: CompressionConfig,
+    reliability: Option<TransportMulticastReliabilityConfig>,
+    fec: Option<f64>,
+    #[cfg(feature = "test")]
+    datagram_loss: Option<usize>,
 }
 
 impl fmt::Debug for TransportManagerBuilderMulticast {
@@ -61,7 +78,13 @@ impl fmt::Debug for TransportManagerBuilderMulticast {
             .field("max_sessions", &self.max_sessions)
             .field("is_qos", &self.is_qos);
         #[cfg(feature = "transport_compression")]
-        debug.field("is_compression", &self.is_compression);
+        debug
+            .field("is_compression", &self.is_compression)
+            .field("compression", &self.compression);
+        debug.field("reliability", &self.reliability);
+        debug.field("fec", &self.fec);
+        #[cfg(feature = "test")]
+        debug.field("datagram_loss", &self.datagram_loss);
         debug.finish()
     }
 }
@@ -128,6 +151,34 @@ impl TransportManagerBuilderMulticast {
         self
     }
 
+    /// Sets the compression algorithm and excluded priorities used when compression is enabled.
+    /// Only lz4 is supported, since the algorithm is not advertised to the other nodes of the group.
+    #[cfg(feature = "transport_compression")]
+    pub fn compression_config(mut self, compression: CompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn reliability(mut self, reliability: Option<TransportMulticastReliabilityConfig>) -> Self {
+        self.reliability = reliability;
+        self
+    }
+
+    /// Sets the ratio of parity batches over data batches used by forward error correction,
+    /// `None` disables it.
+    pub fn fec(mut self, ratio: Option<f64>) -> Self {
+        self.fec = ratio;
+        self
+    }
+
+    /// Drops one datagram out of every `every` datagrams written on the links, `None` disables it.
+    /// This allows to test the recovery of lost datagrams.
+    #[cfg(feature = "test")]
+    pub fn datagram_loss(mut self, every: Option<usize>) -> Self {
+        self.datagram_loss = every;
+        self
+    }
+
     pub fn from_config(mut self, config: &Config) -> ZResult<TransportManagerBuilderMulticast> {
         self = self.lease(Duration::from_millis(
             *config.transport().link().tx().lease(),
@@ -138,11 +189,42 @@ impl TransportManagerBuilderMulticast {
         ));
         self = self.max_sessions(config.transport().multicast().max_sessions().unwrap());
         self = self.qos(*config.transport().multicast().qos().enabled());
+        let reliability = config.transport().multicast().reliability();
+        self = self.reliability((*reliability.enabled()).then(|| reliability.into()));
+        let fec = config.transport().multicast().fec();
+        self = self.fec(fec.enabled().then_some(*fec.ratio()));
+        #[cfg(feature = "transport_compression")]
+        {
+            let compression = config.transport().multicast().compression();
+            self = self.compression(*compression.enabled());
+            // Only lz4 is accepted on multicast, the zstd level and dictionary are unused
+            self = self.compression_config(CompressionConfig::from_config(
+                *compression.algorithm(),
+                0,
+                None,
+                compression.excluded_priorities(),
+            )?);
+        }
 
         Ok(self)
     }
 
     pub fn build(self) -> ZResult<TransportManagerParamsMulticast> {
+        if let Some(ratio) = self.fec {
+            FecConfig::from_ratio(ratio)?;
+        }
+        #[cfg(feature = "transport_compression")]
+        if self.compression.algorithm() != CompressionAlgorithm::Lz4 {
+            bail!(
+                "Invalid multicast compression algorithm {:?}: only lz4 is supported",
+                self.compression.algorithm()
+            );
+        }
+        #[cfg(feature = "test")]
+        if self.datagram_loss == Some(0) {
+            bail!("Invalid datagram loss: it must be strictly positive");
+        }
+
         let config = TransportManagerConfigMulticast {
             lease: self.lease,
             keep_alive: self.keep_alive,
@@ -151,6 +233,12 @@ impl TransportManagerBuilderMulticast {
             is_qos: self.is_qos,
             #[cfg(feature = "transport_compression")]
             is_compression: self.is_compression,
+            #[cfg(feature = "transport_compression")]
+            compression: self.compression,
+            reliability: self.reliability,
+            fec: self.fec,
+            #[cfg(feature = "test")]
+            datagram_loss: self.datagram_loss,
         };
 
         let state = TransportManagerStateMulticast {
@@ -178,6 +266,12 @@ impl Default for TransportManagerBuilderMulticast {
             is_qos: false,
             #[cfg(feature = "transport_compression")]
             is_compression: *compression.enabled(),
+            #[cfg(feature = "transport_compression")]
+            compression: CompressionConfig::default(),
+            reliability: None,
+            fec: None,
+            #[cfg(feature = "test")]
+            datagram_loss: None,
         };
         tmb.from_config(&Config::default()).unwrap()
     }
@@ -13,6 +13,7 @@
 pub(crate) mod establishment;
 pub(crate) mod link;
 pub(crate) mod manager;
+pub(crate) mod reliability;
 pub(crate) mod rx;
 pub(crate) mod transport;
 pub(crate) mod tx;
@@ -26,6 +27,7 @@ pub use manager::{
     TransportManagerBuilderMulticast, TransportManagerConfigMulticast,
     TransportManagerParamsMulticast,
 };
+pub use reliability::TransportMulticastReliabilityConfig;
 use transport::TransportMulticastInner;
 use zenoh_core::{zcondfeat, zread};
 use zenoh_link::Link;
@@ -0,0 +1,474 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+//! NACK-based reliability for multicast transports.
+//!
+//! Receivers detect gaps in the reliable sequence numbers of each peer and priority,
+//! buffer the out-of-order messages and request the missing ones with a NACK sent to
+//! the group. Senders keep a bounded window of the reliable batches they have sent per
+//! priority and retransmit the ones covered by a NACK addressed to them.
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use zenoh_buffers::{reader::HasReader, writer::HasWriter, ZBuf};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_config::ReliabilityMulticastConf;
+use zenoh_protocol::{
+    common::ZExtBody,
+    core::{Priority, ZenohIdProto},
+    network::NetworkMessage,
+    transport::{
+        oam::{self, id::OAM_NACK},
+        Fragment, Oam, TransportSn,
+    },
+};
+
+/// Parameters of the NACK-based reliability of multicast transports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransportMulticastReliabilityConfig {
+    /// Number of reliable batches kept per priority for retransmission.
+    pub retransmission_window: usize,
+    /// Minimum interval between two NACKs (or two retransmissions) for the same gap.
+    pub nack_interval: Duration,
+    /// Time after which a gap that has not been repaired is skipped.
+    pub gap_timeout: Duration,
+    /// Maximum number of reliable messages per priority buffered while waiting for a gap to be repaired.
+    pub max_pending: usize,
+    /// Number of past reliable sequence numbers per priority to request from a newly discovered peer.
+    pub history: usize,
+}
+
+impl From<&ReliabilityMulticastConf> for TransportMulticastReliabilityConfig {
+    fn from(conf: &ReliabilityMulticastConf) -> Self {
+        Self {
+            retransmission_window: *conf.retransmission_window(),
+            nack_interval: Duration::from_millis(*conf.nack_interval()),
+            gap_timeout: Duration::from_millis(*conf.gap_timeout()),
+            max_pending: *conf.max_pending(),
+            history: *conf.history(),
+        }
+    }
+}
+
+/// Signed distance from `from` to `to` in a sequence number space of resolution `mask`.
+pub(super) fn sn_offset(from: TransportSn, to: TransportSn, mask: TransportSn) -> i64 {
+    let gap = to.wrapping_sub(from) & mask;
+    if gap > mask >> 1 {
+        gap as i64 - (mask as i64 + 1)
+    } else {
+        gap as i64
+    }
+}
+
+/*************************************/
+/*               NACK                */
+/*************************************/
+/// Request for the retransmission of the reliable sequence numbers `first..=last`
+/// sent by `zid` on `priority`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Nack {
+    pub(crate) zid: ZenohIdProto,
+    pub(crate) priority: Priority,
+    pub(crate) first: TransportSn,
+    pub(crate) last: TransportSn,
+}
+
+impl Nack {
+    pub(crate) fn to_oam(self) -> Oam {
+        let codec = Zenoh080::new();
+        let mut zbuf = ZBuf::empty();
+        let mut writer = zbuf.writer();
+        // Writing on a ZBuf never fails
+        let _ = codec.write(&mut writer, &self.zid);
+        let _ = codec.write(&mut writer, self.first);
+        let _ = codec.write(&mut writer, self.last);
+        Oam {
+            id: OAM_NACK,
+            body: ZExtBody::ZBuf(zbuf),
+            ext_qos: oam::ext::QoSType::new(self.priority),
+        }
+    }
+
+    pub(crate) fn from_oam(oam: &Oam) -> Option<Self> {
+        if oam.id != OAM_NACK {
+            return None;
+        }
+        let ZExtBody::ZBuf(zbuf) = &oam.body else {
+            return None;
+        };
+        let codec = Zenoh080::new();
+        let mut reader = zbuf.reader();
+        let zid: ZenohIdProto = codec.read(&mut reader).ok()?;
+        let first: TransportSn = codec.read(&mut reader).ok()?;
+        let last: TransportSn = codec.read(&mut reader).ok()?;
+        Some(Self {
+            zid,
+            priority: oam.ext_qos.priority(),
+            first,
+            last,
+        })
+    }
+}
+
+/*************************************/
+/*                TX                 */
+/*************************************/
+struct SentBatch {
+    first: TransportSn,
+    last: TransportSn,
+    bytes: Box<[u8]>,
+    resent: Option<Instant>,
+}
+
+/// The reliable batches recently sent on each priority, kept for retransmission.
+pub(super) struct RetransmissionWindow {
+    capacity: usize,
+    interval: Duration,
+    mask: TransportSn,
+    batches: Box<[VecDeque<SentBatch>]>,
+}
+
+impl RetransmissionWindow {
+    pub(super) fn new(
+        config: &TransportMulticastReliabilityConfig,
+        priorities: usize,
+        mask: TransportSn,
+    ) -> Self {
+        Self {
+            capacity: config.retransmission_window,
+            interval: config.nack_interval,
+            mask,
+            batches: (0..priorities).map(|_| VecDeque::new()).collect(),
+        }
+    }
+
+    /// Keeps a sent batch carrying the reliable sequence numbers `first..=last`.
+    pub(super) fn push(
+        &mut self,
+        priority: usize,
+        first: TransportSn,
+        last: TransportSn,
+        bytes: Box<[u8]>,
+    ) {
+        let queue = &mut self.batches[priority];
+        if queue.len() >= self.capacity {
+            queue.pop_front();
+        }
+        queue.push_back(SentBatch {
+            first,
+            last,
+            bytes,
+            resent: None,
+        });
+    }
+
+    /// The oldest reliable sequence number that can still be retransmitted on `priority`.
+    pub(super) fn oldest(&self, priority: usize) -> Option<TransportSn> {
+        self.batches
+            .get(priority)
+            .and_then(|q| q.front())
+            .map(|b| b.first)
+    }
+
+    /// The batches to retransmit for `nack`. Batches already retransmitted less than a NACK
+    /// interval ago are skipped, since they are likely to answer the same loss reported by
+    /// several receivers.
+    pub(super) fn retransmit(&mut self, nack: &Nack, priority: usize, now: Instant) -> Vec<&[u8]> {
+        let Some(queue) = self.batches.get_mut(priority) else {
+            return vec![];
+        };
+        let Some(base) = queue.front().map(|b| b.first) else {
+            return vec![];
+        };
+        let (mask, interval) = (self.mask, self.interval);
+        let first = sn_offset(base, nack.first, mask);
+        let last = sn_offset(base, nack.last, mask);
+        queue
+            .iter_mut()
+            .filter(|b| {
+                sn_offset(base, b.first, mask) <= last && first <= sn_offset(base, b.last, mask)
+            })
+            .filter(|b| b.resent.map_or(true, |t| now.duration_since(t) >= interval))
+            .map(|b| {
+                b.resent = Some(now);
+                &*b.bytes
+            })
+            .collect()
+    }
+}
+
+/*************************************/
+/*                RX                 */
+/*************************************/
+/// A reliable message received ahead of a gap.
+pub(super) enum Pending {
+    Frame(Vec<NetworkMessage>),
+    Fragment(Fragment),
+}
+
+struct Gap {
+    expected: TransportSn,
+    since: Instant,
+    last_nack: Option<Instant>,
+}
+
+/// What to do about a gap in the reliable sequence numbers.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Repair {
+    /// Nothing to do for now.
+    Wait,
+    /// Request the retransmission of `first..=last`.
+    Nack {
+        first: TransportSn,
+        last: TransportSn,
+    },
+    /// Give up on the gap and resume delivery from `sn`.
+    Skip { sn: TransportSn },
+}
+
+/// The reliable messages of a peer priority waiting for a gap to be repaired.
+pub(super) struct ReliableRx {
+    config: TransportMulticastReliabilityConfig,
+    mask: TransportSn,
+    pending: HashMap<TransportSn, Pending>,
+    highest: Option<TransportSn>,
+    tail: Option<TransportSn>,
+    gap: Option<Gap>,
+}
+
+impl ReliableRx {
+    pub(super) fn new(config: TransportMulticastReliabilityConfig, mask: TransportSn) -> Self {
+        Self {
+            config,
+            mask,
+            pending: HashMap::new(),
+            highest: None,
+            tail: None,
+            gap: None,
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Buffers a message received ahead of the expected sequence number.
+    /// Returns `false` if the message was already buffered.
+    pub(super) fn insert(&mut self, sn: TransportSn, msg: Pending) -> bool {
+        if self.pending.contains_key(&sn) {
+            return false;
+        }
+        self.pending.insert(sn, msg);
+        if self
+            .highest
+            .map_or(true, |h| sn_offset(h, sn, self.mask) > 0)
+        {
+            self.highest = Some(sn);
+        }
+        true
+    }
+
+    /// Takes the buffered message with sequence number `sn`, if any.
+    pub(super) fn take(&mut self, sn: TransportSn) -> Option<Pending> {
+        let msg = self.pending.remove(&sn);
+        if self.pending.is_empty() {
+            self.highest = None;
+        }
+        msg
+    }
+
+    /// Decides how to repair the gap starting at `expected`. `tail` is the next sequence number
+    /// announced by the peer, if any, so that losses at the end of a burst are detected too.
+    pub(super) fn repair(
+        &mut self,
+        expected: TransportSn,
+        tail: Option<TransportSn>,
+        now: Instant,
+    ) -> Repair {
+        let mask = self.mask;
+        if tail.is_some() {
+            self.tail = tail;
+        }
+        let tail = self.tail;
+        // The first sequence number known to be available after the gap
+        let nearest = self
+            .pending
+            .keys()
+            .copied()
+            .min_by_key(|sn| sn_offset(expected, *sn, mask));
+        let Some(resume) = nearest.or(tail.filter(|t| sn_offset(expected, *t, mask) > 0)) else {
+            self.gap = None;
+            return Repair::Wait;
+        };
+        // The last sequence number known to be missing
+        let frontier = [self.highest, tail]
+            .into_iter()
+            .flatten()
+            .max_by_key(|sn| sn_offset(expected, *sn, mask))
+            .unwrap_or(resume);
+        let last = frontier.wrapping_sub(1) & mask;
+
+        let gap = match self.gap.as_mut() {
+            Some(gap) if gap.expected == expected => gap,
+            _ => self.gap.insert(Gap {
+                expected,
+                since: now,
+                last_nack: None,
+            }),
+        };
+        if self.pending.len() > self.config.max_pending
+            || now.duration_since(gap.since) >= self.config.gap_timeout
+        {
+            self.gap = None;
+            return Repair::Skip { sn: resume };
+        }
+        if gap
+            .last_nack
+            .map_or(true, |t| now.duration_since(t) >= self.config.nack_interval)
+        {
+            gap.last_nack = Some(now);
+            return Repair::Nack {
+                first: expected,
+                last,
+            };
+        }
+        Repair::Wait
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zenoh_protocol::core::Bits;
+
+    use super::*;
+    use crate::common::seq_num::get_mask;
+
+    fn config() -> TransportMulticastReliabilityConfig {
+        TransportMulticastReliabilityConfig {
+            retransmission_window: 4,
+            nack_interval: Duration::from_millis(20),
+            gap_timeout: Duration::from_millis(1000),
+            max_pending: 8,
+            history: 0,
+        }
+    }
+
+    #[test]
+    fn nack_oam() {
+        let nack = Nack {
+            zid: ZenohIdProto::rand(),
+            priority: Priority::DataHigh,
+            first: 7,
+            last: 42,
+        };
+        assert_eq!(Nack::from_oam(&nack.to_oam()), Some(nack));
+    }
+
+    #[test]
+    fn sn_offset_wraps() {
+        let mask = get_mask(Bits::U8);
+        assert_eq!(sn_offset(10, 12, mask), 2);
+        assert_eq!(sn_offset(12, 10, mask), -2);
+        assert_eq!(sn_offset(mask, 1, mask), 2);
+        assert_eq!(sn_offset(1, mask, mask), -2);
+    }
+
+    #[test]
+    fn window_retransmit() {
+        let mask = get_mask(Bits::U8);
+        let mut window = RetransmissionWindow::new(&config(), 1, mask);
+        // Batches [124..=125], [126..=127], [0..=1], [2..=3], [4..=5] wrapping around the resolution
+        for first in [124, 126, 0, 2, 4] {
+            window.push(0, first, first + 1, vec![first as u8].into());
+        }
+        // The oldest batch has been evicted
+        assert_eq!(window.oldest(0), Some(126));
+
+        let zid = ZenohIdProto::rand();
+        let now = Instant::now();
+        let nack = Nack {
+            zid,
+            priority: Priority::DEFAULT,
+            first: 127,
+            last: 2,
+        };
+        assert_eq!(
+            window.retransmit(&nack, 0, now),
+            vec![&[126][..], &[0][..], &[2][..]]
+        );
+        // Retransmissions are not repeated within a NACK interval
+        let nack = Nack { first: 1, ..nack };
+        assert!(window.retransmit(&nack, 0, now).is_empty());
+        let later = now + Duration::from_millis(20);
+        assert_eq!(window.retransmit(&nack, 0, later), vec![&[0][..], &[2][..]]);
+        // Evicted sequence numbers can not be retransmitted
+        let nack = Nack {
+            first: 120,
+            last: 125,
+            ..nack
+        };
+        assert!(window.retransmit(&nack, 0, later).is_empty());
+    }
+
+    #[test]
+    fn rx_repair() {
+        let mask = get_mask(Bits::U8);
+        let mut rx = ReliableRx::new(config(), mask);
+        let t0 = Instant::now();
+        let at = |ms| t0 + Duration::from_millis(ms);
+
+        // No gap
+        assert_eq!(rx.repair(5, Some(5), at(0)), Repair::Wait);
+        // A loss at the end of a burst is detected from the announced next sn
+        assert_eq!(
+            rx.repair(5, Some(7), at(0)),
+            Repair::Nack { first: 5, last: 6 }
+        );
+        // NACKs are rate limited
+        assert_eq!(rx.repair(5, None, at(10)), Repair::Wait);
+        // The announced next sn is remembered
+        assert_eq!(
+            rx.repair(5, None, at(20)),
+            Repair::Nack { first: 5, last: 6 }
+        );
+
+        assert!(rx.insert(8, Pending::Frame(vec![])));
+        assert!(!rx.insert(8, Pending::Frame(vec![])));
+        assert!(rx.insert(10, Pending::Frame(vec![])));
+        assert_eq!(
+            rx.repair(5, None, at(40)),
+            Repair::Nack { first: 5, last: 9 }
+        );
+        // The gap is skipped up to the nearest buffered message once expired
+        assert_eq!(rx.repair(5, None, at(1000)), Repair::Skip { sn: 8 });
+
+        assert!(rx.take(8).is_some());
+        assert!(rx.take(9).is_none());
+        assert!(!rx.is_empty());
+        assert!(rx.take(10).is_some());
+        assert!(rx.is_empty());
+    }
+
+    #[test]
+    fn rx_repair_overflow() {
+        let mask = get_mask(Bits::U8);
+        let mut rx = ReliableRx::new(config(), mask);
+        let now = Instant::now();
+        for sn in 10..=18 {
+            rx.insert(sn, Pending::Frame(vec![]));
+        }
+        assert_eq!(rx.repair(5, None, now), Repair::Skip { sn: 10 });
+    }
+}
@@ -11,7 +11,7 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::sync::MutexGuard;
+use std::{sync::MutexGuard, time::Instant};
 
 use zenoh_buffers::ZSlice;
 use zenoh_codec::transport::frame::FrameReader;
@@ -20,12 +20,16 @@ use zenoh_protocol::{
     core::{Locator, Priority, Reliability},
     network::NetworkMessageMut,
     transport::{
-        BatchSize, Close, Fragment, Join, KeepAlive, TransportBody, TransportMessage, TransportSn,
+        oam::id::OAM_NACK, BatchSize, Close, Fragment, Join, KeepAlive, Oam, TransportBody,
+        TransportMessage, TransportSn,
     },
 };
 use zenoh_result::{bail, zerror, ZResult};
 
-use super::transport::{TransportMulticastInner, TransportMulticastPeer};
+use super::{
+    reliability::{Nack, Pending, ReliableRx, Repair},
+    transport::{TransportMulticastInner, TransportMulticastPeer},
+};
 use crate::common::{
     batch::{Decode, RBatch},
     priority::TransportChannelRx,
@@ -55,7 +59,7 @@ impl TransportMulticastInner {
                 if let Err(e) = crate::common::shm::interop::map_zmsg_to_shmbuf(
                     msg.as_mut(),
                     &shm_context.shm_reader,
-                    &RxHandoffChannel::Disabled, // Multicast transport is unreliable, so it don't support SHM handoff
+                    &RxHandoffChannel::Disabled, // SHM handoff is negotiated point-to-point, so multicast doesn't support it
                 ) {
                     tracing::debug!("Error receiving SHM buffer: {e}");
                     return Ok(());
@@ -86,6 +90,21 @@ impl TransportMulticastInner {
             bail!("{}", e);
         }
 
+        // The next SNs announced by the peer reveal the losses at the end of a burst
+        if peer.reliable_rx.is_some() {
+            match join.ext_qos.as_ref() {
+                Some(sns) => {
+                    for (i, sn) in sns.iter().enumerate() {
+                        let priority = Priority::try_from(i as u8)?;
+                        self.repair_reliable(peer, priority, Some(sn.reliable))?;
+                    }
+                }
+                None => {
+                    self.repair_reliable(peer, Priority::DEFAULT, Some(join.next_sn.reliable))?
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -174,6 +193,22 @@ impl TransportMulticastInner {
             Reliability::BestEffort => zlock!(c.best_effort),
         };
 
+        if let (Reliability::Reliable, Some(reliable_rx)) =
+            (frame.reliability, peer.reliable_rx.as_ref())
+        {
+            let mut rx = zlock!(reliable_rx[peer.priority_index(priority)]);
+            if frame.sn != guard.sn.next() {
+                let sn = frame.sn;
+                let msg = Pending::Frame(frame.collect());
+                return self.buffer_reliable(sn, msg, priority, &mut guard, &mut rx, peer);
+            }
+            let _ = guard.sn.set(frame.sn);
+            for mut msg in frame {
+                self.trigger_callback(msg.as_mut(), peer)?;
+            }
+            return self.deliver_reliable(priority, &mut guard, &mut rx, peer);
+        }
+
         if !self.verify_sn("Frame", frame.sn, &mut guard)? {
             // Drop invalid message and continue
             return Ok(());
@@ -186,17 +221,7 @@ impl TransportMulticastInner {
     }
 
     fn handle_fragment(&self, fragment: Fragment, peer: &TransportMulticastPeer) -> ZResult<()> {
-        let Fragment {
-            reliability,
-            more,
-            sn,
-            ext_qos,
-            ext_first,
-            ext_drop,
-            payload,
-        } = fragment;
-
-        let priority = ext_qos.priority();
+        let priority = fragment.ext_qos.priority();
         let c = if self.is_qos() {
             &peer.priority_rx[priority as usize]
         } else if priority == Priority::DEFAULT {
@@ -210,15 +235,48 @@ impl TransportMulticastInner {
             );
         };
 
-        let mut guard = match reliability {
+        let mut guard = match fragment.reliability {
             Reliability::Reliable => zlock!(c.reliable),
             Reliability::BestEffort => zlock!(c.best_effort),
         };
 
-        if !self.verify_sn("Fragment", sn, &mut guard)? {
+        if let (Reliability::Reliable, Some(reliable_rx)) =
+            (fragment.reliability, peer.reliable_rx.as_ref())
+        {
+            let mut rx = zlock!(reliable_rx[peer.priority_index(priority)]);
+            if fragment.sn != guard.sn.next() {
+                let sn = fragment.sn;
+                let msg = Pending::Fragment(fragment);
+                return self.buffer_reliable(sn, msg, priority, &mut guard, &mut rx, peer);
+            }
+            let _ = guard.sn.set(fragment.sn);
+            self.defragment(fragment, &mut guard, peer)?;
+            return self.deliver_reliable(priority, &mut guard, &mut rx, peer);
+        }
+
+        if !self.verify_sn("Fragment", fragment.sn, &mut guard)? {
             // Drop invalid message and continue
             return Ok(());
         }
+        self.defragment(fragment, &mut guard, peer)
+    }
+
+    fn defragment(
+        &self,
+        fragment: Fragment,
+        guard: &mut MutexGuard<'_, TransportChannelRx>,
+        peer: &TransportMulticastPeer,
+    ) -> ZResult<()> {
+        let Fragment {
+            more,
+            sn,
+            ext_qos,
+            ext_first,
+            ext_drop,
+            payload,
+            ..
+        } = fragment;
+
         if peer.patch.has_fragmentation_markers() {
             if ext_first.is_some() {
                 guard.defrag.clear();
@@ -251,14 +309,159 @@ impl TransportMulticastInner {
                     "Transport: {}. Peer: {}. Priority: {:?}. Defragmentation error.",
                     self.manager.config.zid,
                     peer.zid,
-                    priority
+                    ext_qos.priority()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Buffers a reliable message received ahead of the expected SN and requests the missing ones.
+    fn buffer_reliable(
+        &self,
+        sn: TransportSn,
+        msg: Pending,
+        priority: Priority,
+        guard: &mut MutexGuard<'_, TransportChannelRx>,
+        rx: &mut MutexGuard<'_, ReliableRx>,
+        peer: &TransportMulticastPeer,
+    ) -> ZResult<()> {
+        if !guard.sn.precedes(sn)? || !rx.insert(sn, msg) {
+            tracing::trace!(
+                "Transport: {}. Peer: {}. Priority: {:?}. Duplicate reliable SN dropped: {}.",
+                self.manager.config.zid,
+                peer.zid,
+                priority,
+                sn
+            );
+            return Ok(());
+        }
+        self.repair(priority, guard, rx, peer, None)
+    }
+
+    /// Delivers the buffered reliable messages that are now in order.
+    fn deliver_reliable(
+        &self,
+        priority: Priority,
+        guard: &mut MutexGuard<'_, TransportChannelRx>,
+        rx: &mut MutexGuard<'_, ReliableRx>,
+        peer: &TransportMulticastPeer,
+    ) -> ZResult<()> {
+        while let Some(msg) = rx.take(guard.sn.next()) {
+            let sn = guard.sn.next();
+            let _ = guard.sn.set(sn);
+            match msg {
+                Pending::Frame(msgs) => {
+                    for mut msg in msgs {
+                        self.trigger_callback(msg.as_mut(), peer)?;
+                    }
+                }
+                Pending::Fragment(fragment) => self.defragment(fragment, guard, peer)?,
+            }
+        }
+        if rx.is_empty() {
+            return Ok(());
+        }
+        self.repair(priority, guard, rx, peer, None)
+    }
+
+    fn repair(
+        &self,
+        priority: Priority,
+        guard: &mut MutexGuard<'_, TransportChannelRx>,
+        rx: &mut MutexGuard<'_, ReliableRx>,
+        peer: &TransportMulticastPeer,
+        tail: Option<TransportSn>,
+    ) -> ZResult<()> {
+        let expected = guard.sn.next();
+        match rx.repair(expected, tail, Instant::now()) {
+            Repair::Wait => Ok(()),
+            Repair::Nack { first, last } => {
+                let nack = Nack {
+                    zid: peer.zid,
+                    priority,
+                    first,
+                    last,
+                };
+                tracing::trace!(
+                    "Transport: {}. Peer: {}. Sending {:?}.",
+                    self.manager.config.zid,
+                    peer.zid,
+                    nack
                 );
+                let pipeline = zread!(self.link).as_ref().and_then(|l| l.pipeline.clone());
+                if let Some(pipeline) = pipeline {
+                    pipeline
+                        .push_transport_message(TransportBody::OAM(nack.to_oam()).into(), priority);
+                }
+                Ok(())
+            }
+            Repair::Skip { sn } => {
+                tracing::debug!(
+                    "Transport: {}. Peer: {}. Priority: {:?}. Reliable SNs {}..{} could not be repaired and are skipped.",
+                    self.manager.config.zid,
+                    peer.zid,
+                    priority,
+                    expected,
+                    sn
+                );
+                let resolution = guard.sn.resolution();
+                let _ = guard.sn.set(sn.wrapping_sub(1) & resolution);
+                guard.defrag.clear();
+                self.deliver_reliable(priority, guard, rx, peer)
             }
         }
+    }
 
+    /// Checks the reliable SNs of `priority` for gaps, `tail` being the next SN announced by the peer.
+    pub(super) fn repair_reliable(
+        &self,
+        peer: &TransportMulticastPeer,
+        priority: Priority,
+        tail: Option<TransportSn>,
+    ) -> ZResult<()> {
+        let Some(reliable_rx) = peer.reliable_rx.as_ref() else {
+            return Ok(());
+        };
+        let index = peer.priority_index(priority);
+        let mut guard = zlock!(peer.priority_rx[index].reliable);
+        let mut rx = zlock!(reliable_rx[index]);
+        self.repair(priority, &mut guard, &mut rx, peer, tail)
+    }
+
+    /// Checks the reliable SNs of all the peers for gaps, so that NACKs are retried
+    /// and expired gaps are skipped even when no further message is received.
+    pub(super) fn repair_peers(&self) -> ZResult<()> {
+        for peer in zread!(self.peers).values() {
+            if peer.reliable_rx.is_none() {
+                continue;
+            }
+            for i in 0..peer.priority_rx.len() {
+                let priority = if peer.is_qos() {
+                    Priority::try_from(i as u8)?
+                } else {
+                    Priority::DEFAULT
+                };
+                self.repair_reliable(peer, priority, None)?;
+            }
+        }
         Ok(())
     }
 
+    fn handle_nack(&self, oam: &Oam) {
+        let Some(nack) = Nack::from_oam(oam) else {
+            return;
+        };
+        if nack.zid != self.manager.config.zid {
+            return;
+        }
+        let nacks = zread!(self.link).as_ref().and_then(|l| l.nacks.clone());
+        if let Some(nacks) = nacks {
+            let _ = nacks.try_send(nack);
+        }
+    }
+
     fn verify_sn(
         &self,
         message_type: &str,
@@ -326,6 +529,7 @@ impl TransportMulticastInner {
                         }
                         TransportBody::Join(join) => self.handle_join_from_peer(join, peer)?,
                         TransportBody::KeepAlive(KeepAlive { .. }) => {}
+                        TransportBody::OAM(oam) if oam.id == OAM_NACK => self.handle_nack(&oam),
                         TransportBody::Close(Close { reason, .. }) => {
                             drop(r_guard);
                             self.del_peer(&locator, reason)?;
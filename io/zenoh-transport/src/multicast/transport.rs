@@ -16,7 +16,7 @@ use std::{
     collections::HashMap,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc, RwLock,
+        Arc, Mutex, RwLock,
     },
     time::Duration,
 };
@@ -26,7 +26,9 @@ use zenoh_core::{zcondfeat, zread, zwrite};
 use zenoh_link::{Link, Locator};
 use zenoh_protocol::{
     core::{Bits, Field, Priority, Resolution, WhatAmI, ZenohIdProto},
-    transport::{batch_size, close, join::ext::PatchType, Close, Join, TransportMessage},
+    transport::{
+        batch_size, close, join::ext::PatchType, Close, Join, TransportMessage, TransportSn,
+    },
 };
 use zenoh_result::{bail, ZResult};
 use zenoh_task::TaskController;
@@ -34,6 +36,7 @@ use zenoh_task::TaskController;
 use super::{
     common::priority::{TransportPriorityRx, TransportPriorityTx},
     link::{TransportLinkMulticastConfigUniversal, TransportLinkMulticastUniversal},
+    reliability::{sn_offset, ReliableRx},
 };
 #[cfg(feature = "shared-memory")]
 use crate::common::shm::shm_context::MulticastTransportShmContext;
@@ -58,6 +61,8 @@ pub(super) struct TransportMulticastPeer {
     pub(super) is_active: Arc<AtomicBool>,
     token: CancellationToken,
     pub(super) priority_rx: Box<[TransportPriorityRx]>,
+    // The reliable messages waiting for a gap to be repaired, if the peer retransmits upon NACK
+    pub(super) reliable_rx: Option<Arc<[Mutex<ReliableRx>]>>,
     pub(super) handler: Arc<dyn TransportPeerEventHandler>,
     pub(super) patch: PatchType,
     #[cfg(feature = "stats")]
@@ -72,6 +77,14 @@ impl TransportMulticastPeer {
     pub(super) fn is_qos(&self) -> bool {
         self.priority_rx.len() == Priority::NUM
     }
+
+    pub(super) fn priority_index(&self, priority: Priority) -> usize {
+        if self.is_qos() {
+            priority as usize
+        } else {
+            0
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -256,6 +269,7 @@ impl TransportMulticastInner {
                     join_interval: self.manager.config.multicast.join_interval,
                     sn_resolution: self.manager.config.resolution.get(Field::FrameSN),
                     batch_size,
+                    reliability: self.manager.config.multicast.reliability,
                 };
                 l.start_tx(config, self.priority_tx.clone());
                 Ok(())
@@ -359,16 +373,40 @@ impl TransportMulticastInner {
         }
         .into_boxed_slice();
 
+        // NACK-based reliability is used only if the peer retransmits upon NACK
+        let sn_resolution = join.resolution.get(Field::FrameSN);
+        let mask = sn_resolution.mask() as TransportSn;
+        let reliability = self
+            .manager
+            .config
+            .multicast
+            .reliability
+            .filter(|_| join.ext_nack.is_some());
+
         let mut priority_rx = Vec::with_capacity(next_sns.len());
-        for sn in next_sns.iter() {
-            let tprx = TransportPriorityRx::make(
-                join.resolution.get(Field::FrameSN),
-                self.manager.config.defrag_buff_size,
-            )?;
-            tprx.sync(*sn)?;
+        for (i, sn) in next_sns.iter().enumerate() {
+            let tprx =
+                TransportPriorityRx::make(sn_resolution, self.manager.config.defrag_buff_size)?;
+            let mut sn = *sn;
+            // Start from the requested history, as far as the peer can still retransmit it
+            if let (Some(r), Some(oldest)) = (reliability.as_ref(), join.ext_nack.as_ref()) {
+                let history = (r.history as i64).min(mask as i64 >> 1);
+                if sn_offset(oldest[i], sn.reliable, mask) <= history {
+                    sn.reliable = oldest[i];
+                } else {
+                    sn.reliable = sn.reliable.wrapping_sub(history as TransportSn) & mask;
+                }
+            }
+            tprx.sync(sn)?;
             priority_rx.push(tprx);
         }
         let priority_rx = priority_rx.into_boxed_slice();
+        let reliable_rx = reliability.map(|r| {
+            next_sns
+                .iter()
+                .map(|_| Mutex::new(ReliableRx::new(r, mask)))
+                .collect::<Arc<[_]>>()
+        });
 
         tracing::debug!(
                 "New transport joined on {}: zid {}, whatami {}, resolution {:?}, locator {}, is_qos {}, is_shm {}, initial sn: {:?}",
@@ -421,6 +459,7 @@ impl TransportMulticastInner {
             is_active,
             token,
             priority_rx,
+            reliable_rx,
             handler,
             patch: min(PatchType::CURRENT, join.ext_patch),
             #[cfg(feature = "stats")]
@@ -428,6 +467,17 @@ impl TransportMulticastInner {
                 .stats
                 .peer_link_stats(peer.zid, peer.whatami, &self.link_stats),
         };
+        // Request the history, if any
+        if peer.reliable_rx.is_some() {
+            for (i, sn) in next_sns.iter().enumerate() {
+                let priority = if peer.is_qos() {
+                    Priority::try_from(i as u8)?
+                } else {
+                    Priority::DEFAULT
+                };
+                self.repair_reliable(&peer, priority, Some(sn.reliable))?;
+            }
+        }
         zwrite!(self.peers).insert(locator.clone(), peer);
 
         Ok(())
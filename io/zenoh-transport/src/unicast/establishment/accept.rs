@@ -34,6 +34,8 @@ use zenoh_result::ZResult;
 
 #[cfg(feature = "auth_usrpwd")]
 use super::ext::auth::UsrPwdId;
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::CompressionConfig;
 #[cfg(feature = "shared-memory")]
 use crate::common::shm::interop::LinkShmHandoffConfig;
 use crate::{
@@ -64,17 +66,16 @@ struct StateTransport {
     ext_region_name: ext::region_name::StateAccept,
 }
 
-#[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
 struct StateLink {
     #[cfg(feature = "transport_auth")]
     ext_auth: ext::auth::StateAccept,
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::StateAccept,
+    ext_fec: ext::fec::StateAccept,
 }
 
 struct State {
     transport: StateTransport,
-    #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
     link: StateLink,
 }
 
@@ -140,6 +141,7 @@ struct AcceptLink<'a> {
     ext_lowlatency: ext::lowlatency::LowLatencyFsm<'a>,
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::CompressionFsm<'a>,
+    ext_fec: ext::fec::FecFsm<'a>,
     ext_patch: ext::patch::PatchFsm<'a>,
     ext_remote_bound: Option<RemoteBoundCallback>,
     ext_region_name: ext::region_name::RegionNameFsm,
@@ -263,7 +265,10 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
         // Extension Compression
         #[cfg(feature = "transport_compression")]
         self.ext_compression
-            .recv_init_syn((&mut state.link.ext_compression, init_syn.ext_compression))
+            .recv_init_syn((
+                &mut state.link.ext_compression,
+                (init_syn.ext_compression, init_syn.ext_compression_algorithm),
+            ))
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
@@ -282,6 +287,12 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Fec
+        self.ext_fec
+            .recv_init_syn((&mut state.link.ext_fec, init_syn.ext_fec))
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         let output = RecvInitSynOut {
             other_zid: init_syn.zid,
             other_whatami: init_syn.whatami,
@@ -343,15 +354,22 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
         // Extension Compression
-        let ext_compression = zcondfeat!(
+        let (ext_compression, ext_compression_algorithm) = zcondfeat!(
             "transport_compression",
             self.ext_compression
                 .send_init_ack(&state.link.ext_compression)
                 .await
                 .map_err(|e| (e, Some(close::reason::GENERIC)))?,
-            None
+            (None, None)
         );
 
+        // Extension Fec
+        let ext_fec = self
+            .ext_fec
+            .send_init_ack(&state.link.ext_fec)
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         // Extension Patch
         let ext_patch = self
             .ext_patch
@@ -389,6 +407,7 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
                 ext_compression: state.link.ext_compression,
                 ext_patch: state.transport.ext_patch,
                 ext_region_name: state.transport.ext_region_name,
+                ext_fec: state.link.ext_fec,
             };
 
             let mut encrypted = vec![];
@@ -425,6 +444,8 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
             ext_compression,
             ext_patch,
             ext_region_name,
+            ext_fec,
+            ext_compression_algorithm,
         }
         .into();
 
@@ -525,12 +546,12 @@ impl<'a, 'b: 'a> AcceptFsm for &'a mut AcceptLink<'b> {
                 ext_patch: cookie.ext_patch,
                 ext_region_name: cookie.ext_region_name,
             },
-            #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
             link: StateLink {
                 #[cfg(feature = "transport_auth")]
                 ext_auth: cookie.ext_auth,
                 #[cfg(feature = "transport_compression")]
                 ext_compression: cookie.ext_compression,
+                ext_fec: cookie.ext_fec,
             },
         };
 
@@ -731,6 +752,9 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         },
         priorities: None,
         reliability: None,
+        fec: None,
+        #[cfg(feature = "transport_compression")]
+        compression: CompressionConfig::default(),
     };
     let mut link_unicast = TransportLinkUnicast::new(
         link.clone(),
@@ -756,6 +780,7 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         ext_lowlatency: ext::lowlatency::LowLatencyFsm::new(),
         #[cfg(feature = "transport_compression")]
         ext_compression: ext::compression::CompressionFsm::new(),
+        ext_fec: ext::fec::FecFsm::new(),
         ext_patch: ext::patch::PatchFsm::new(),
         ext_remote_bound: manager.config.bound_callback.clone(),
         ext_region_name: ext::region_name::RegionNameFsm::new(manager.config.region_name.clone()),
@@ -804,14 +829,15 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
                     ext_patch: ext::patch::StateAccept::new(),
                     ext_region_name: ext::region_name::StateAccept::new(),
                 },
-                #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
                 link: StateLink {
                     #[cfg(feature = "transport_auth")]
                     ext_auth: manager.state.unicast.authenticator.accept(&mut *prng),
                     #[cfg(feature = "transport_compression")]
                     ext_compression: ext::compression::StateAccept::new(
                         manager.config.unicast.is_compression,
+                        manager.config.unicast.compression.algorithm(),
                     ),
+                    ext_fec: ext::fec::StateAccept::new(manager.config.unicast.fec, &endpoint)?,
                 },
             }
         };
@@ -877,6 +903,14 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         region_name: state.transport.ext_region_name.other_region_name(),
     };
 
+    // FEC is not supported by the LowLatency transport
+    let fec = state.link.ext_fec.fec().filter(|_| !config.is_lowlatency);
+    #[cfg(feature = "transport_compression")]
+    let compression = manager
+        .config
+        .unicast
+        .compression
+        .negotiated(state.link.ext_compression.algorithm());
     let a_config = TransportLinkUnicastConfig {
         direction,
         batch: BatchConfig {
@@ -887,6 +921,9 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
         },
         priorities: state.transport.ext_qos.priorities(),
         reliability: state.transport.ext_qos.reliability(),
+        fec,
+        #[cfg(feature = "transport_compression")]
+        compression: compression.clone(),
     };
     let a_link = link_unicast.reconfigure(
         a_config,
@@ -915,6 +952,9 @@ pub(crate) async fn accept_link(link: LinkUnicast, manager: &TransportManager) -
                 priorities: state.transport.ext_qos.priorities(),
                 // Do not apply reliability override to MixedReliability associated links
                 reliability: None,
+                fec,
+                #[cfg(feature = "transport_compression")]
+                compression: compression.clone(),
             };
             let link = TransportLinkUnicast::new(
                 LinkUnicast::from(best_effort),
@@ -46,6 +46,7 @@ pub(crate) struct Cookie {
     pub(crate) ext_compression: ext::compression::StateAccept,
     pub(crate) ext_patch: ext::patch::StateAccept,
     pub(crate) ext_region_name: ext::region_name::StateAccept,
+    pub(crate) ext_fec: ext::fec::StateAccept,
 }
 
 impl<W> WCodec<&Cookie, &mut W> for Zenoh080
@@ -74,6 +75,7 @@ where
         self.write(&mut *writer, &x.ext_compression)?;
         self.write(&mut *writer, &x.ext_patch)?;
         self.write(&mut *writer, &x.ext_region_name)?;
+        self.write(&mut *writer, &x.ext_fec)?;
 
         Ok(())
     }
@@ -106,6 +108,7 @@ where
         let ext_compression: ext::compression::StateAccept = self.read(&mut *reader)?;
         let ext_patch: ext::patch::StateAccept = self.read(&mut *reader)?;
         let ext_region_name: ext::region_name::StateAccept = self.read(&mut *reader)?;
+        let ext_fec: ext::fec::StateAccept = self.read(&mut *reader)?;
 
         let cookie = Cookie {
             zid,
@@ -125,6 +128,7 @@ where
             ext_compression,
             ext_patch,
             ext_region_name,
+            ext_fec,
         };
 
         Ok(cookie)
@@ -198,6 +202,7 @@ impl Cookie {
             ext_compression: ext::compression::StateAccept::rand(),
             ext_patch: ext::patch::StateAccept::rand(),
             ext_region_name: ext::region_name::StateAccept::rand(),
+            ext_fec: ext::fec::StateAccept::rand(),
         }
     }
 }
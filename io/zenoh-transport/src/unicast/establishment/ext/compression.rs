@@ -20,9 +20,12 @@ use zenoh_buffers::{
 };
 use zenoh_codec::{RCodec, WCodec, Zenoh080};
 use zenoh_protocol::transport::{init, open};
-use zenoh_result::Error as ZError;
+use zenoh_result::{zerror, Error as ZError};
 
-use crate::unicast::establishment::{AcceptFsm, OpenFsm};
+use crate::{
+    common::compression::CompressionAlgorithm,
+    unicast::establishment::{AcceptFsm, OpenFsm},
+};
 
 // Extension Fsm
 pub(crate) struct CompressionFsm<'a> {
@@ -41,16 +44,34 @@ impl CompressionFsm<'_> {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) struct StateOpen {
     is_compression: bool,
+    algorithm: CompressionAlgorithm,
 }
 
 impl StateOpen {
-    pub(crate) const fn new(is_compression: bool) -> Self {
-        Self { is_compression }
+    pub(crate) const fn new(is_compression: bool, algorithm: CompressionAlgorithm) -> Self {
+        Self {
+            is_compression,
+            algorithm,
+        }
     }
 
     pub(crate) const fn is_compression(&self) -> bool {
         self.is_compression
     }
+
+    pub(crate) const fn algorithm(&self) -> CompressionAlgorithm {
+        self.algorithm
+    }
+}
+
+// The CompressionAlgorithm extension is only sent when lz4 is not used, keeping the
+// InitSyn/InitAck unchanged for peers that only support lz4.
+fn algorithm_ext(
+    is_compression: bool,
+    algorithm: CompressionAlgorithm,
+) -> Option<init::ext::CompressionAlgorithm> {
+    (is_compression && algorithm != CompressionAlgorithm::Lz4)
+        .then(|| init::ext::CompressionAlgorithm::new(algorithm.into()))
 }
 
 #[async_trait]
@@ -58,25 +79,48 @@ impl<'a> OpenFsm for &'a CompressionFsm<'a> {
     type Error = ZError;
 
     type SendInitSynIn = &'a StateOpen;
-    type SendInitSynOut = Option<init::ext::Compression>;
+    type SendInitSynOut = (
+        Option<init::ext::Compression>,
+        Option<init::ext::CompressionAlgorithm>,
+    );
     async fn send_init_syn(
         self,
         state: Self::SendInitSynIn,
     ) -> Result<Self::SendInitSynOut, Self::Error> {
-        let output = state
+        let compression = state
             .is_compression
             .then_some(init::ext::Compression::new());
-        Ok(output)
+        let algorithm = algorithm_ext(state.is_compression, state.algorithm);
+        Ok((compression, algorithm))
     }
 
-    type RecvInitAckIn = (&'a mut StateOpen, Option<init::ext::Compression>);
+    type RecvInitAckIn = (
+        &'a mut StateOpen,
+        (
+            Option<init::ext::Compression>,
+            Option<init::ext::CompressionAlgorithm>,
+        ),
+    );
     type RecvInitAckOut = ();
     async fn recv_init_ack(
         self,
         input: Self::RecvInitAckIn,
     ) -> Result<Self::RecvInitAckOut, Self::Error> {
-        let (state, other_ext) = input;
-        state.is_compression &= other_ext.is_some();
+        let (state, (other_compression, other_algorithm)) = input;
+        state.is_compression &= other_compression.is_some();
+        if !state.is_compression {
+            return Ok(());
+        }
+
+        // The acceptor selects the algorithm, verify it is one we support
+        let algorithm = match other_algorithm {
+            Some(ext) => CompressionAlgorithm::try_from(ext.value)?,
+            None => CompressionAlgorithm::Lz4,
+        };
+        if state.algorithm.negotiate(algorithm) != algorithm {
+            return Err(zerror!("Unsupported compression algorithm: {algorithm:?}").into());
+        }
+        state.algorithm = algorithm;
         Ok(())
     }
 
@@ -105,22 +149,44 @@ impl<'a> OpenFsm for &'a CompressionFsm<'a> {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) struct StateAccept {
     is_compression: bool,
+    algorithm: CompressionAlgorithm,
 }
 
 impl StateAccept {
-    pub(crate) const fn new(is_compression: bool) -> Self {
-        Self { is_compression }
+    pub(crate) const fn new(is_compression: bool, algorithm: CompressionAlgorithm) -> Self {
+        Self {
+            is_compression,
+            algorithm,
+        }
     }
 
     pub(crate) const fn is_compression(&self) -> bool {
         self.is_compression
     }
 
+    pub(crate) const fn algorithm(&self) -> CompressionAlgorithm {
+        self.algorithm
+    }
+
     #[cfg(test)]
     pub(crate) fn rand() -> Self {
         use rand::Rng;
         let mut rng = rand::thread_rng();
-        Self::new(rng.gen_bool(0.5))
+        let algorithm = match rng.gen_range(0..3) {
+            0 => CompressionAlgorithm::Lz4,
+            1 => CompressionAlgorithm::Zstd {
+                level: rng.gen_range(
+                    CompressionAlgorithm::ZSTD_LEVEL_MIN..=CompressionAlgorithm::ZSTD_LEVEL_MAX,
+                ),
+            },
+            _ => CompressionAlgorithm::ZstdDictionary {
+                level: rng.gen_range(
+                    CompressionAlgorithm::ZSTD_LEVEL_MIN..=CompressionAlgorithm::ZSTD_LEVEL_MAX,
+                ),
+                id: rng.gen(),
+            },
+        };
+        Self::new(rng.gen_bool(0.5), algorithm)
     }
 }
 
@@ -134,6 +200,7 @@ where
     fn write(self, writer: &mut W, x: &StateAccept) -> Self::Output {
         let is_compression = u8::from(x.is_compression);
         self.write(&mut *writer, is_compression)?;
+        self.write(&mut *writer, u64::from(x.algorithm))?;
         Ok(())
     }
 }
@@ -147,7 +214,12 @@ where
     fn read(self, reader: &mut R) -> Result<StateAccept, Self::Error> {
         let is_compression: u8 = self.read(&mut *reader)?;
         let is_compression = is_compression == 1;
-        Ok(StateAccept { is_compression })
+        let algorithm: u64 = self.read(&mut *reader)?;
+        let algorithm = CompressionAlgorithm::try_from(algorithm).map_err(|_| DidntRead)?;
+        Ok(StateAccept {
+            is_compression,
+            algorithm,
+        })
     }
 }
 
@@ -155,27 +227,46 @@ where
 impl<'a> AcceptFsm for &'a CompressionFsm<'a> {
     type Error = ZError;
 
-    type RecvInitSynIn = (&'a mut StateAccept, Option<init::ext::Compression>);
+    type RecvInitSynIn = (
+        &'a mut StateAccept,
+        (
+            Option<init::ext::Compression>,
+            Option<init::ext::CompressionAlgorithm>,
+        ),
+    );
     type RecvInitSynOut = ();
     async fn recv_init_syn(
         self,
         input: Self::RecvInitSynIn,
     ) -> Result<Self::RecvInitSynOut, Self::Error> {
-        let (state, other_ext) = input;
-        state.is_compression &= other_ext.is_some();
+        let (state, (other_compression, other_algorithm)) = input;
+        state.is_compression &= other_compression.is_some();
+        if !state.is_compression {
+            return Ok(());
+        }
+
+        // Fallback on lz4 for peers not advertising any algorithm or an unknown one
+        let algorithm = other_algorithm
+            .and_then(|ext| CompressionAlgorithm::try_from(ext.value).ok())
+            .unwrap_or_default();
+        state.algorithm = state.algorithm.negotiate(algorithm);
         Ok(())
     }
 
     type SendInitAckIn = &'a StateAccept;
-    type SendInitAckOut = Option<init::ext::Compression>;
+    type SendInitAckOut = (
+        Option<init::ext::Compression>,
+        Option<init::ext::CompressionAlgorithm>,
+    );
     async fn send_init_ack(
         self,
         state: Self::SendInitAckIn,
     ) -> Result<Self::SendInitAckOut, Self::Error> {
-        let output = state
+        let compression = state
             .is_compression
             .then_some(init::ext::Compression::new());
-        Ok(output)
+        let algorithm = algorithm_ext(state.is_compression, state.algorithm);
+        Ok((compression, algorithm))
     }
 
     type RecvOpenSynIn = (&'a mut StateAccept, Option<open::ext::Compression>);
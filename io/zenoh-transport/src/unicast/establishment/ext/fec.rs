@@ -0,0 +1,216 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+use zenoh_buffers::{
+    reader::{DidntRead, Reader},
+    writer::{DidntWrite, Writer},
+};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_protocol::{core::EndPoint, transport::init};
+use zenoh_result::{Error as ZError, ZResult};
+
+use crate::{
+    common::fec::FecConfig,
+    unicast::establishment::{AcceptFsm, OpenFsm},
+};
+
+// Extension Fsm
+pub(crate) struct FecFsm<'a> {
+    _a: PhantomData<&'a ()>,
+}
+
+impl FecFsm<'_> {
+    pub(crate) const fn new() -> Self {
+        Self { _a: PhantomData }
+    }
+}
+
+/*************************************/
+/*              OPEN                 */
+/*************************************/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct StateOpen {
+    fec: Option<FecConfig>,
+}
+
+impl StateOpen {
+    pub(crate) fn new(ratio: Option<f64>, endpoint: &EndPoint) -> ZResult<Self> {
+        let fec = FecConfig::from_endpoint(ratio, endpoint)?;
+        Ok(Self { fec })
+    }
+
+    pub(crate) const fn fec(&self) -> Option<FecConfig> {
+        self.fec
+    }
+}
+
+#[async_trait]
+impl<'a> OpenFsm for &'a FecFsm<'a> {
+    type Error = ZError;
+
+    type SendInitSynIn = &'a StateOpen;
+    type SendInitSynOut = Option<init::ext::Fec>;
+    async fn send_init_syn(
+        self,
+        state: Self::SendInitSynIn,
+    ) -> Result<Self::SendInitSynOut, Self::Error> {
+        let output = state.fec.map(|fec| init::ext::Fec::new(fec.size as u64));
+        Ok(output)
+    }
+
+    type RecvInitAckIn = (&'a mut StateOpen, Option<init::ext::Fec>);
+    type RecvInitAckOut = ();
+    async fn recv_init_ack(
+        self,
+        input: Self::RecvInitAckIn,
+    ) -> Result<Self::RecvInitAckOut, Self::Error> {
+        let (state, other_ext) = input;
+        // The acceptor replies with the group size to be used by both sides
+        state.fec = match (state.fec, other_ext) {
+            (Some(_), Some(other)) => Some(FecConfig::from_size(other.value)?),
+            _ => None,
+        };
+        Ok(())
+    }
+
+    type SendOpenSynIn = &'a StateOpen;
+    type SendOpenSynOut = ();
+    async fn send_open_syn(
+        self,
+        _state: Self::SendOpenSynIn,
+    ) -> Result<Self::SendOpenSynOut, Self::Error> {
+        Ok(())
+    }
+
+    type RecvOpenAckIn = (&'a mut StateOpen, ());
+    type RecvOpenAckOut = ();
+    async fn recv_open_ack(
+        self,
+        _state: Self::RecvOpenAckIn,
+    ) -> Result<Self::RecvOpenAckOut, Self::Error> {
+        Ok(())
+    }
+}
+
+/*************************************/
+/*            ACCEPT                 */
+/*************************************/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct StateAccept {
+    fec: Option<FecConfig>,
+}
+
+impl StateAccept {
+    pub(crate) fn new(ratio: Option<f64>, endpoint: &EndPoint) -> ZResult<Self> {
+        let fec = FecConfig::from_endpoint(ratio, endpoint)?;
+        Ok(Self { fec })
+    }
+
+    pub(crate) const fn fec(&self) -> Option<FecConfig> {
+        self.fec
+    }
+
+    #[cfg(test)]
+    pub(crate) fn rand() -> Self {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let fec = rng.gen_bool(0.5).then(|| FecConfig {
+            size: rng.gen_range(1..=u8::MAX),
+        });
+        Self { fec }
+    }
+}
+
+// Codec
+impl<W> WCodec<&StateAccept, &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: &StateAccept) -> Self::Output {
+        // A group size of 0 means that FEC is disabled
+        let size = x.fec.map_or(0, |fec| fec.size);
+        self.write(&mut *writer, size)?;
+        Ok(())
+    }
+}
+
+impl<R> RCodec<StateAccept, &mut R> for Zenoh080
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<StateAccept, Self::Error> {
+        let size: u8 = self.read(&mut *reader)?;
+        let fec = (size != 0).then_some(FecConfig { size });
+        Ok(StateAccept { fec })
+    }
+}
+
+#[async_trait]
+impl<'a> AcceptFsm for &'a FecFsm<'a> {
+    type Error = ZError;
+
+    type RecvInitSynIn = (&'a mut StateAccept, Option<init::ext::Fec>);
+    type RecvInitSynOut = ();
+    async fn recv_init_syn(
+        self,
+        input: Self::RecvInitSynIn,
+    ) -> Result<Self::RecvInitSynOut, Self::Error> {
+        let (state, other_ext) = input;
+        // Use the smallest group size, i.e. the highest ratio, of the two sides
+        state.fec = match (state.fec, other_ext) {
+            (Some(mine), Some(other)) => {
+                let other = FecConfig::from_size(other.value)?;
+                Some(FecConfig {
+                    size: mine.size.min(other.size),
+                })
+            }
+            _ => None,
+        };
+        Ok(())
+    }
+
+    type SendInitAckIn = &'a StateAccept;
+    type SendInitAckOut = Option<init::ext::Fec>;
+    async fn send_init_ack(
+        self,
+        state: Self::SendInitAckIn,
+    ) -> Result<Self::SendInitAckOut, Self::Error> {
+        let output = state.fec.map(|fec| init::ext::Fec::new(fec.size as u64));
+        Ok(output)
+    }
+
+    type RecvOpenSynIn = (&'a mut StateAccept, ());
+    type RecvOpenSynOut = ();
+    async fn recv_open_syn(
+        self,
+        _state: Self::RecvOpenSynIn,
+    ) -> Result<Self::RecvOpenSynOut, Self::Error> {
+        Ok(())
+    }
+
+    type SendOpenAckIn = &'a StateAccept;
+    type SendOpenAckOut = ();
+    async fn send_open_ack(
+        self,
+        _state: Self::SendOpenAckIn,
+    ) -> Result<Self::SendOpenAckOut, Self::Error> {
+        Ok(())
+    }
+}
@@ -15,6 +15,7 @@
 pub mod auth;
 #[cfg(feature = "transport_compression")]
 pub(crate) mod compression;
+pub(crate) mod fec;
 pub(crate) mod lowlatency;
 #[cfg(feature = "transport_multilink")]
 pub(crate) mod multilink;
@@ -28,6 +28,8 @@ use zenoh_protocol::{
 };
 use zenoh_result::ZResult;
 
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::CompressionConfig;
 #[cfg(feature = "shared-memory")]
 use crate::common::shm::interop::LinkShmHandoffConfig;
 #[cfg(feature = "auth_usrpwd")]
@@ -60,17 +62,16 @@ struct StateTransport {
     ext_region_name: ext::region_name::StateOpen,
 }
 
-#[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
 struct StateLink {
     #[cfg(feature = "transport_auth")]
     ext_auth: ext::auth::StateOpen,
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::StateOpen,
+    ext_fec: ext::fec::StateOpen,
 }
 
 struct State {
     transport: StateTransport,
-    #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
     link: StateLink,
 }
 
@@ -120,6 +121,7 @@ struct OpenLink<'a> {
     ext_lowlatency: ext::lowlatency::LowLatencyFsm<'a>,
     #[cfg(feature = "transport_compression")]
     ext_compression: ext::compression::CompressionFsm<'a>,
+    ext_fec: ext::fec::FecFsm<'a>,
     ext_patch: ext::patch::PatchFsm<'a>,
     ext_region_name: ext::region_name::RegionNameFsm,
     // TODO(regions): move this into `ext::region::RegionFsm` (?)
@@ -183,13 +185,13 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
         // Extension Compression
-        let ext_compression = zcondfeat!(
+        let (ext_compression, ext_compression_algorithm) = zcondfeat!(
             "transport_compression",
             self.ext_compression
                 .send_init_syn(&state.link.ext_compression)
                 .await
                 .map_err(|e| (e, Some(close::reason::GENERIC)))?,
-            None
+            (None, None)
         );
 
         // Extension Patch
@@ -206,6 +208,13 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Fec
+        let ext_fec = self
+            .ext_fec
+            .send_init_syn(&state.link.ext_fec)
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         let msg: TransportMessage = InitSyn {
             version: input.mine_version,
             whatami: input.mine_whatami,
@@ -222,6 +231,8 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             ext_compression,
             ext_patch,
             ext_region_name,
+            ext_fec,
+            ext_compression_algorithm,
         }
         .into();
 
@@ -357,7 +368,10 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
         // Extension Compression
         #[cfg(feature = "transport_compression")]
         self.ext_compression
-            .recv_init_ack((&mut state.link.ext_compression, init_ack.ext_compression))
+            .recv_init_ack((
+                &mut state.link.ext_compression,
+                (init_ack.ext_compression, init_ack.ext_compression_algorithm),
+            ))
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
@@ -376,6 +390,12 @@ impl<'a, 'b: 'a> OpenFsm for &'a mut OpenLink<'b> {
             .await
             .map_err(|e| (e, Some(close::reason::GENERIC)))?;
 
+        // Extension Fec
+        self.ext_fec
+            .recv_init_ack((&mut state.link.ext_fec, init_ack.ext_fec))
+            .await
+            .map_err(|e| (e, Some(close::reason::GENERIC)))?;
+
         let output = RecvInitAckOut {
             other_zid: init_ack.zid,
             other_whatami: init_ack.whatami,
@@ -619,6 +639,9 @@ pub(crate) async fn open_link(
         },
         priorities: None,
         reliability: None,
+        fec: None,
+        #[cfg(feature = "transport_compression")]
+        compression: CompressionConfig::default(),
     };
     let mut link_unicast = TransportLinkUnicast::new(
         link.clone(),
@@ -641,6 +664,7 @@ pub(crate) async fn open_link(
         ext_lowlatency: ext::lowlatency::LowLatencyFsm::new(),
         #[cfg(feature = "transport_compression")]
         ext_compression: ext::compression::CompressionFsm::new(),
+        ext_fec: ext::fec::FecFsm::new(),
         ext_patch: ext::patch::PatchFsm::new(),
         ext_remote_bound: manager.config.bound_callback.clone(),
         ext_region_name: ext::region_name::RegionNameFsm::new(manager.config.region_name.clone()),
@@ -678,14 +702,15 @@ pub(crate) async fn open_link(
                 ext_patch: ext::patch::StateOpen::new(),
                 ext_region_name: ext::region_name::StateOpen::new(),
             },
-            #[cfg(any(feature = "transport_auth", feature = "transport_compression"))]
             link: StateLink {
                 #[cfg(feature = "transport_auth")]
                 ext_auth: manager.state.unicast.authenticator.open(&mut *prng),
                 #[cfg(feature = "transport_compression")]
                 ext_compression: ext::compression::StateOpen::new(
                     manager.config.unicast.is_compression,
+                    manager.config.unicast.compression.algorithm(),
                 ),
+                ext_fec: ext::fec::StateOpen::new(manager.config.unicast.fec, &endpoint)?,
             },
         }
     };
@@ -771,6 +796,14 @@ pub(crate) async fn open_link(
         region_name: state.transport.ext_region_name.other_region_name(),
     };
 
+    // FEC is not supported by the LowLatency transport
+    let fec = state.link.ext_fec.fec().filter(|_| !config.is_lowlatency);
+    #[cfg(feature = "transport_compression")]
+    let compression = manager
+        .config
+        .unicast
+        .compression
+        .negotiated(state.link.ext_compression.algorithm());
     let o_config = TransportLinkUnicastConfig {
         direction,
         batch: BatchConfig {
@@ -781,6 +814,9 @@ pub(crate) async fn open_link(
         },
         priorities: state.transport.ext_qos.priorities(),
         reliability: state.transport.ext_qos.reliability(),
+        fec,
+        #[cfg(feature = "transport_compression")]
+        compression: compression.clone(),
     };
     let o_link = link_unicast.reconfigure(
         o_config,
@@ -809,6 +845,9 @@ pub(crate) async fn open_link(
                 priorities: state.transport.ext_qos.priorities(),
                 // Do not apply reliability override to MixedReliability associated links
                 reliability: None,
+                fec,
+                #[cfg(feature = "transport_compression")]
+                compression: compression.clone(),
             };
             let link = TransportLinkUnicast::new(
                 LinkUnicast::from(best_effort),
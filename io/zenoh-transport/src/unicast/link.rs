@@ -11,9 +11,9 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{fmt, sync::Arc};
+use std::{fmt, sync::Arc, time::Instant};
 
-use zenoh_buffers::{BBuf, ZSlice, ZSliceBuffer};
+use zenoh_buffers::{ZSlice, ZSliceBuffer};
 use zenoh_core::zcondfeat;
 use zenoh_link::{Link, LinkUnicast};
 use zenoh_protocol::{
@@ -22,7 +22,12 @@ use zenoh_protocol::{
 };
 use zenoh_result::{zerror, ZResult};
 
-use crate::common::batch::{BatchConfig, Decode, Encode, Finalize, RBatch, WBatch};
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::{CompressionConfig, Compressor, Decompressor};
+use crate::common::{
+    batch::{BatchConfig, Decode, Encode, Finalize, RBatch, WBatch},
+    fec::{self, FecConfig, FecDecoder, FecEncoder},
+};
 #[cfg(feature = "shared-memory")]
 use crate::{
     common::shm::interop::LinkShmHandoffConfig,
@@ -42,6 +47,17 @@ pub(crate) struct TransportLinkUnicastConfig {
     pub(crate) batch: BatchConfig,
     pub(crate) priorities: Option<PriorityRange>,
     pub(crate) reliability: Option<Reliability>,
+    pub(crate) fec: Option<FecConfig>,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) compression: CompressionConfig,
+}
+
+impl TransportLinkUnicastConfig {
+    /// Size of the buffers required to receive a batch, FEC overhead included.
+    pub(crate) fn batch_buffer_size(&self) -> usize {
+        let overhead = if self.fec.is_some() { fec::OVERHEAD } else { 0 };
+        self.batch.mtu as usize + overhead
+    }
 }
 
 #[cfg(feature = "shared-memory")]
@@ -112,6 +128,17 @@ impl TransportLinkUnicast {
         #[cfg(feature = "shared-memory")] shm_handoff: TransportLinkShmHandoff,
     ) -> Self {
         config.batch.mtu = link.get_mtu().min(config.batch.mtu);
+        if config.fec.is_some() {
+            // FEC only applies to datagram links and its overhead must fit in the link MTU
+            if link.is_streamed() {
+                config.fec = None;
+            } else {
+                config.batch.mtu = config
+                    .batch
+                    .mtu
+                    .min(link.get_mtu().saturating_sub(fec::OVERHEAD as BatchSize));
+            }
+        }
         Self {
             link,
             config,
@@ -131,16 +158,13 @@ impl TransportLinkUnicast {
     pub(crate) fn tx(&self) -> TransportLinkUnicastTx {
         TransportLinkUnicastTx {
             inner: self.clone(),
-            buffer: zcondfeat!(
-                "transport_compression",
-                self.config
-                    .batch
-                    .is_compression
-                    .then_some(BBuf::with_capacity(
-                        lz4_flex::block::get_maximum_output_size(self.config.batch.mtu as usize),
-                    )),
-                None
-            ),
+            #[cfg(feature = "transport_compression")]
+            compressor: self
+                .config
+                .batch
+                .is_compression
+                .then(|| Compressor::new(self.config.compression.clone(), self.config.batch.mtu)),
+            fec: self.config.fec.map(FecEncoder::new),
         }
     }
 
@@ -148,6 +172,13 @@ impl TransportLinkUnicast {
         TransportLinkUnicastRx {
             link: self.link.clone(),
             config: self.config.clone(),
+            fec: self.config.fec.map(|_| FecDecoder::new()),
+            #[cfg(feature = "transport_compression")]
+            decompressor: self
+                .config
+                .batch
+                .is_compression
+                .then(|| Decompressor::new(&self.config.compression)),
             #[cfg(feature = "shared-memory")]
             shm: self.shm_handoff.rx.clone(),
         }
@@ -202,40 +233,78 @@ impl PartialEq<Link> for TransportLinkUnicast {
 #[derive(Clone)]
 pub(crate) struct TransportLinkUnicastTx {
     pub(crate) inner: TransportLinkUnicast,
-    pub(crate) buffer: Option<BBuf>,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) compressor: Option<Compressor>,
+    pub(crate) fec: Option<FecEncoder>,
 }
 
 impl TransportLinkUnicastTx {
+    /// Sends the batch on the link. The priority of the batch is used to decide whether to compress it,
+    /// the one of the link, if any, to select the stream to write on.
     pub(crate) async fn send_batch(
         &mut self,
         batch: &mut WBatch,
+        #[cfg(feature = "transport_compression")] batch_priority: Priority,
         priority: Option<Priority>,
     ) -> ZResult<()> {
         const ERR: &str = "Write error on link: ";
 
         // tracing::trace!("WBatch: {:?}", batch);
 
+        #[cfg(feature = "transport_compression")]
+        let compressor = self
+            .compressor
+            .as_mut()
+            .filter(|c| !c.config().is_excluded(batch_priority));
         let res = batch
-            .finalize(self.buffer.as_mut())
+            .finalize(
+                #[cfg(feature = "transport_compression")]
+                compressor,
+            )
             .map_err(|_| zerror!("{ERR}{self}"))?;
 
         let bytes = match res {
             Finalize::Batch => batch.as_slice(),
-            Finalize::Buffer => self
-                .buffer
-                .as_ref()
-                .ok_or_else(|| zerror!("Invalid buffer finalization"))?
-                .as_slice(),
+            Finalize::Buffer => zcondfeat!(
+                "transport_compression",
+                self.compressor.as_ref().map(|c| c.as_slice()),
+                None
+            )
+            .ok_or_else(|| zerror!("Invalid buffer finalization"))?,
         };
 
         // tracing::trace!("WBytes: {:02x?}", bytes);
 
         // Send the message on the link
-        self.inner.link.write_all(bytes, priority).await?;
+        match self.fec.as_mut() {
+            Some(fec) => {
+                let (data, parity) = fec.encode(bytes).map_err(|e| zerror!("{ERR}{e}"))?;
+                self.inner.link.write_all(data, priority).await?;
+                if let Some(parity) = parity {
+                    self.inner.link.write_all(parity, priority).await?;
+                }
+            }
+            None => {
+                self.inner.link.write_all(bytes, priority).await?;
+            }
+        }
 
         Ok(())
     }
 
+    /// Sends the parity datagram of the incomplete FEC group, if any.
+    pub(crate) async fn flush_fec(&mut self, priority: Option<Priority>) -> ZResult<()> {
+        if let Some(parity) = self.fec.as_mut().and_then(|fec| fec.flush()) {
+            self.inner.link.write_all(parity, priority).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether datagrams sent on the link are waiting for their FEC parity.
+    pub(crate) fn is_fec_pending(&self) -> bool {
+        self.fec.as_ref().is_some_and(FecEncoder::is_pending)
+    }
+
     pub(crate) async fn send(
         &mut self,
         msg: &TransportMessage,
@@ -247,7 +316,13 @@ impl TransportLinkUnicastTx {
         let mut batch = WBatch::new(self.inner.config.batch);
         batch.encode(msg).map_err(|_| zerror!("{ERR}{self}"))?;
         let len = batch.len() as usize;
-        self.send_batch(&mut batch, priority).await?;
+        self.send_batch(
+            &mut batch,
+            #[cfg(feature = "transport_compression")]
+            priority.unwrap_or(Priority::Control),
+            priority,
+        )
+        .await?;
         Ok(len)
     }
 }
@@ -260,11 +335,14 @@ impl fmt::Display for TransportLinkUnicastTx {
 
 impl fmt::Debug for TransportLinkUnicastTx {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("TransportLinkUnicastRx")
-            .field("link", &self.inner.link)
-            .field("config", &self.inner.config)
-            .field("buffer", &self.buffer.as_ref().map(|b| b.capacity()))
-            .finish()
+        let mut s = f.debug_struct("TransportLinkUnicastRx");
+        s.field("link", &self.inner.link)
+            .field("config", &self.inner.config);
+        #[cfg(feature = "transport_compression")]
+        {
+            s.field("compressor", &self.compressor);
+        }
+        s.finish()
     }
 }
 
@@ -272,6 +350,9 @@ impl fmt::Debug for TransportLinkUnicastTx {
 pub(crate) struct TransportLinkUnicastRx {
     pub(crate) link: LinkUnicast,
     pub(crate) config: TransportLinkUnicastConfig,
+    pub(crate) fec: Option<FecDecoder>,
+    #[cfg(feature = "transport_compression")]
+    pub(crate) decompressor: Option<Decompressor>,
     #[cfg(feature = "shared-memory")]
     pub(crate) shm: Arc<RxHandoffChannel>,
 }
@@ -288,32 +369,66 @@ impl TransportLinkUnicastRx {
     {
         const ERR: &str = "Read error from link: ";
 
-        let mut into = (buff)();
-        let end = if self.link.is_streamed() {
-            // Read and decode the message length
-            let mut len = BatchSize::MIN.to_le_bytes();
-            self.link.read_exact(&mut len, priority).await?;
-            let l = BatchSize::from_le_bytes(len) as usize;
-
-            // Read the bytes
-            let slice = into
-                .as_mut()
-                .get_mut(len.len()..len.len() + l)
-                .ok_or_else(|| zerror!("{ERR}{self}. Invalid batch length or buffer size."))?;
-            self.link.read_exact(slice, priority).await?;
-            len.len() + l
-        } else {
-            // Read the bytes
-            self.link.read(into.as_mut(), priority).await?
-        };
-
-        // tracing::trace!("RBytes: {:02x?}", &into.as_slice()[0..end]);
+        let buffer = loop {
+            if let Some(payload) = self.fec.as_mut().and_then(|fec| fec.pop()) {
+                break payload;
+            }
 
-        let buffer = ZSlice::new(Arc::new(into), 0, end)
-            .map_err(|_| zerror!("{ERR}{self}. ZSlice index(es) out of bounds"))?;
+            let mut into = (buff)();
+            let end = if self.link.is_streamed() {
+                // Read and decode the message length
+                let mut len = BatchSize::MIN.to_le_bytes();
+                self.link.read_exact(&mut len, priority).await?;
+                let l = BatchSize::from_le_bytes(len) as usize;
+
+                // Read the bytes
+                let slice = into
+                    .as_mut()
+                    .get_mut(len.len()..len.len() + l)
+                    .ok_or_else(|| zerror!("{ERR}{self}. Invalid batch length or buffer size."))?;
+                self.link.read_exact(slice, priority).await?;
+                len.len() + l
+            } else if let Some(deadline) = self.fec.as_ref().and_then(|fec| fec.deadline()) {
+                // Read the bytes, unless the datagrams held back by FEC are due
+                let read = tokio::select! {
+                    res = self.link.read(into.as_mut(), priority) => Some(res?),
+                    _ = tokio::time::sleep_until(deadline.into()) => None,
+                };
+                match read {
+                    Some(end) => end,
+                    None => {
+                        if let Some(fec) = self.fec.as_mut() {
+                            fec.expire(Instant::now());
+                        }
+                        continue;
+                    }
+                }
+            } else {
+                // Read the bytes
+                self.link.read(into.as_mut(), priority).await?
+            };
+
+            // tracing::trace!("RBytes: {:02x?}", &into.as_slice()[0..end]);
+
+            let buffer = ZSlice::new(Arc::new(into), 0, end)
+                .map_err(|_| zerror!("{ERR}{self}. ZSlice index(es) out of bounds"))?;
+            match self.fec.as_mut() {
+                Some(fec) => {
+                    if let Err(e) = fec.decode(buffer, Instant::now()) {
+                        // A malformed datagram must not tear down the link: drop it
+                        tracing::debug!("{ERR}{self}. Dropping datagram: {e}.");
+                    }
+                }
+                None => break buffer,
+            }
+        };
         let mut batch = RBatch::new(self.config.batch, buffer);
         batch
-            .initialize(buff)
+            .initialize(
+                buff,
+                #[cfg(feature = "transport_compression")]
+                self.decompressor.as_mut(),
+            )
             .map_err(|e| zerror!("{ERR}{self}. {e}."))?;
 
         // tracing::trace!("RBatch: {:?}", batch);
@@ -322,7 +437,7 @@ impl TransportLinkUnicastRx {
     }
 
     pub async fn recv(&mut self) -> ZResult<TransportMessage> {
-        let mtu = self.config.batch.mtu as usize;
+        let mtu = self.config.batch_buffer_size();
         let mut batch = self
             .recv_batch(|| zenoh_buffers::vec::uninit(mtu).into_boxed_slice(), None)
             .await?;
@@ -363,6 +478,9 @@ impl MaybeOpenAck {
 
     pub(crate) async fn send_open_ack(mut self) -> ZResult<()> {
         if let Some(msg) = self.open_ack {
+            // Same as compression below, the OpenAck is not supposed to be FEC-encoded.
+            // This link is used only once, so there is no need to re-enable FEC afterwards.
+            self.link.fec = None;
             zcondfeat!(
                 "transport_compression",
                 {
@@ -24,7 +24,7 @@ use std::{
 use tokio::sync::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
 #[cfg(feature = "transport_compression")]
 use zenoh_config::CompressionUnicastConf;
-use zenoh_config::{Config, LinkTxConf, QoSUnicastConf, TransportUnicastConf};
+use zenoh_config::{Config, FecUnicastConf, LinkTxConf, QoSUnicastConf, TransportUnicastConf};
 use zenoh_core::{zasynclock, zcondfeat};
 use zenoh_crypto::PseudoRng;
 use zenoh_link::*;
@@ -35,11 +35,14 @@ use zenoh_protocol::{
 use zenoh_result::{bail, zerror, ZResult};
 
 use super::{link::LinkUnicastWithOpenAck, transport_unicast_inner::InitTransportResult};
+#[cfg(feature = "transport_compression")]
+use crate::common::compression::CompressionConfig;
 #[cfg(feature = "transport_auth")]
 use crate::unicast::establishment::ext::auth::Auth;
 #[cfg(feature = "transport_multilink")]
 use crate::unicast::establishment::ext::multilink::MultiLink;
 use crate::{
+    common::fec::FecConfig,
     unicast::{
         lowlatency::transport::TransportUnicastLowlatency,
         transport_unicast_inner::{InitTransportError, TransportUnicastTrait},
@@ -66,6 +69,9 @@ pub struct TransportManagerConfigUnicast {
     pub max_links: usize,
     #[cfg(feature = "transport_compression")]
     pub is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    pub compression: CompressionConfig,
+    pub fec: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -163,6 +169,9 @@ pub struct TransportManagerBuilderUnicast {
     pub(super) is_lowlatency: bool,
     #[cfg(feature = "transport_compression")]
     pub(super) is_compression: bool,
+    #[cfg(feature = "transport_compression")]
+    pub(super) compression: CompressionConfig,
+    pub(super) fec: Option<f64>,
 }
 
 impl fmt::Debug for TransportManagerBuilderUnicast {
@@ -182,7 +191,10 @@ impl fmt::Debug for TransportManagerBuilderUnicast {
         debug.field("authenticator", &"..");
         debug.field("is_lowlatency", &self.is_lowlatency);
         #[cfg(feature = "transport_compression")]
-        debug.field("is_compression", &self.is_compression);
+        debug
+            .field("is_compression", &self.is_compression)
+            .field("compression", &self.compression);
+        debug.field("fec", &self.fec);
         debug.finish()
     }
 }
@@ -246,6 +258,21 @@ impl TransportManagerBuilderUnicast {
         self
     }
 
+    /// Sets the compression algorithm, dictionary and excluded priorities used when compression is enabled.
+    /// The algorithm actually used on a link is negotiated with the other side.
+    #[cfg(feature = "transport_compression")]
+    pub fn compression_config(mut self, compression: CompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the ratio of parity batches over data batches used by forward error correction
+    /// on datagram links, `None` disables it.
+    pub fn fec(mut self, ratio: Option<f64>) -> Self {
+        self.fec = ratio;
+        self
+    }
+
     pub async fn from_config(mut self, config: &Config) -> ZResult<TransportManagerBuilderUnicast> {
         self = self.lease(Duration::from_millis(
             *config.transport().link().tx().lease(),
@@ -272,8 +299,17 @@ impl TransportManagerBuilderUnicast {
         }
         #[cfg(feature = "transport_compression")]
         {
-            self = self.compression(*config.transport().unicast().compression().enabled());
+            let compression = config.transport().unicast().compression();
+            self = self.compression(*compression.enabled());
+            self = self.compression_config(CompressionConfig::from_config(
+                *compression.algorithm(),
+                *compression.level(),
+                compression.dictionary().as_deref(),
+                compression.excluded_priorities(),
+            )?);
         }
+        let fec = config.transport().unicast().fec();
+        self = self.fec(fec.enabled().then_some(*fec.ratio()));
 
         Ok(self)
     }
@@ -285,6 +321,9 @@ impl TransportManagerBuilderUnicast {
         if self.is_qos && self.is_lowlatency {
             bail!("'qos' and 'lowlatency' options are incompatible");
         }
+        if let Some(ratio) = self.fec {
+            FecConfig::from_ratio(ratio)?;
+        }
 
         let config = TransportManagerConfigUnicast {
             lease: self.lease,
@@ -299,6 +338,9 @@ impl TransportManagerBuilderUnicast {
             is_lowlatency: self.is_lowlatency,
             #[cfg(feature = "transport_compression")]
             is_compression: self.is_compression,
+            #[cfg(feature = "transport_compression")]
+            compression: self.compression,
+            fec: self.fec,
         };
 
         let state = TransportManagerStateUnicast {
@@ -324,6 +366,7 @@ impl Default for TransportManagerBuilderUnicast {
         let qos = QoSUnicastConf::default();
         #[cfg(feature = "transport_compression")]
         let compression = CompressionUnicastConf::default();
+        let fec = FecUnicastConf::default();
 
         Self {
             lease: Duration::from_millis(*link_tx.lease()),
@@ -340,6 +383,9 @@ impl Default for TransportManagerBuilderUnicast {
             is_lowlatency: *transport.lowlatency(),
             #[cfg(feature = "transport_compression")]
             is_compression: *compression.enabled(),
+            #[cfg(feature = "transport_compression")]
+            compression: CompressionConfig::default(),
+            fec: fec.enabled().then_some(*fec.ratio()),
         }
     }
 }
@@ -82,6 +82,7 @@ use super::transport::TransportUnicastUniversal;
 use crate::{
     common::{
         batch::{BatchConfig, RBatch},
+        fec,
         pipeline::{
             PipelineConsumer, TransmissionPipeline, TransmissionPipelineConf,
             TransmissionPipelineConsumer, TransmissionPipelineProducer,
@@ -332,7 +333,13 @@ async fn write_loop(
                         break
                     };
                     debug_assert!(write_priority.is_none() || write_priority == Some(priority));
-                    link.send_batch(&mut batch, write_priority).await?;
+                    link.send_batch(
+                        &mut batch,
+                        #[cfg(feature = "transport_compression")]
+                        priority,
+                        write_priority,
+                    )
+                    .await?;
                     // inform the latest message tracker that a message has been sent
                     keep_alive_tracker.reset();
 
@@ -345,6 +352,10 @@ async fn write_loop(
                     // Reinsert the batch into the queue
                     pipeline.refill(batch, priority);
                 },
+                _ = tokio::time::sleep(fec::FLUSH_TIMEOUT), if link.is_fec_pending() => {
+                    // No batch has been sent for a while: protect the last ones with a parity
+                    link.flush_fec(write_priority).await?;
+                },
                 _ = keep_alive_tracker.wait_if(write_priority.unwrap_or(Priority::Control) == Priority::Control) => {
                     // A timeout occurred, no control/data messages have been sent during
                     // the keep_alive period, we need to send a KeepAlive message
@@ -369,10 +380,16 @@ async fn write_loop(
 
     // Drain the transmission pipeline and write remaining bytes on the wire
     let mut batches = pipeline.drain();
-    for (mut b, _) in batches.drain(..) {
+    #[allow(unused_variables)] // Used when transport_compression feature is enabled
+    for (mut b, priority) in batches.drain(..) {
         tokio::time::timeout(
             keep_alive_tracker.timeout(),
-            link.send_batch(&mut b, write_priority),
+            link.send_batch(
+                &mut b,
+                #[cfg(feature = "transport_compression")]
+                priority,
+                write_priority,
+            ),
         )
         .await
         .map_err(|_| {
@@ -411,7 +428,10 @@ async fn rx_task(
             target_arch = "powerpc64"
         )
     ))]
-    if transport.manager.state.uring.is_some() && link.link.get_fd().is_ok() {
+    if transport.manager.state.uring.is_some()
+        && link.link.get_fd().is_ok()
+        && link.config.fec.is_none()
+    {
         return rx_task_uring(
             link,
             transport.clone(),
@@ -447,7 +467,7 @@ async fn rx_task_non_uring(
     #[cfg(feature = "stats")] stats: zenoh_stats::LinkStats,
 ) -> ZResult<()> {
     // The pool of buffers
-    let mtu = link.config.batch.mtu as usize;
+    let mtu = link.config.batch_buffer_size();
     let mut n = rx_buffer_size / mtu;
     if n == 0 {
         tracing::debug!("RX configured buffer of {rx_buffer_size} bytes is too small for {link} that has an MTU of {mtu} bytes. Defaulting to {mtu} bytes for RX buffer.");
@@ -626,7 +646,7 @@ async fn rx_task_uring(
     cancellation_token: CancellationToken,
 ) -> ZResult<()> {
     // The pool of buffers
-    let mtu = link.config.batch.mtu as usize;
+    let mtu = link.config.batch_buffer_size();
     let mut n = rx_buffer_size / mtu;
     if n == 0 {
         tracing::debug!("RX configured buffer of {rx_buffer_size} bytes is too small for {link} that has an MTU of {mtu} bytes. Defaulting to {mtu} bytes for RX buffer.");
@@ -638,6 +658,8 @@ async fn rx_task_uring(
     let c_link = link.clone();
 
     let batch_config = link.config.batch;
+    #[cfg(feature = "transport_compression")]
+    let mut decompressor = link.decompressor.clone();
 
     let r = transport
         .manager
@@ -678,9 +700,11 @@ async fn rx_task_uring(
                     match data.defragment()? {
                         DefragmentationState::Single(slice) => {
                             let mut batch = RBatch::new(batch_config, slice);
-                            batch.initialize_uring(|| {
-                                pool.try_take().unwrap_or_else(|| pool.alloc())
-                            })?;
+                            batch.initialize_uring(
+                                || pool.try_take().unwrap_or_else(|| pool.alloc()),
+                                #[cfg(feature = "transport_compression")]
+                                decompressor.as_mut(),
+                            )?;
                             read_batch(
                                 &transport,
                                 &c_link,
@@ -691,9 +715,11 @@ async fn rx_task_uring(
                         }
                         DefragmentationState::Fragmented(buf) => {
                             let mut batch = RBatch::new(batch_config, buf.reader());
-                            match batch.initialize_uring(|| {
-                                pool.try_take().unwrap_or_else(|| pool.alloc())
-                            })? {
+                            match batch.initialize_uring(
+                                || pool.try_take().unwrap_or_else(|| pool.alloc()),
+                                #[cfg(feature = "transport_compression")]
+                                decompressor.as_mut(),
+                            )? {
                                 Some(decompressed_batch) => read_batch(
                                     &transport,
                                     &c_link,
@@ -722,7 +748,11 @@ async fn rx_task_uring(
                     lt.reset();
                     let slice: ZSlice = data.into();
                     let mut batch = RBatch::new(batch_config, slice);
-                    batch.initialize_uring(|| pool.try_take().unwrap_or_else(|| pool.alloc()))?;
+                    batch.initialize_uring(
+                        || pool.try_take().unwrap_or_else(|| pool.alloc()),
+                        #[cfg(feature = "transport_compression")]
+                        decompressor.as_mut(),
+                    )?;
 
                     read_batch(
                         &transport,
@@ -39,6 +39,7 @@ mod tests {
     use zenoh_result::ZResult;
     use zenoh_test::get_free_udp_port;
     use zenoh_transport::{
+        common::compression::CompressionConfig,
         multicast::{TransportManagerBuilderMulticast, TransportMulticast},
         unicast::TransportUnicast,
         TransportEventHandler, TransportManager, TransportMulticastEventHandler, TransportPeer,
@@ -305,6 +306,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transport_multicast_compression_lz4_only() {
+        // The algorithm is not advertised to the group, which may not support zstd
+        let builder = TransportManagerBuilderMulticast::default()
+            .compression(true)
+            .compression_config(CompressionConfig::zstd(3).unwrap());
+        assert!(builder.build().is_err());
+        let builder = TransportManagerBuilderMulticast::default()
+            .compression(true)
+            .compression_config(CompressionConfig::lz4());
+        assert!(builder.build().is_ok());
+
+        let mut config = zenoh_config::Config::default();
+        assert!(config
+            .insert_json5("transport/multicast/compression/algorithm", r#""zstd""#)
+            .is_err());
+        assert!(config
+            .insert_json5("transport/multicast/compression/dictionary", r#""dict""#)
+            .is_err());
+    }
+
     #[cfg(feature = "transport_udp")]
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn transport_multicast_compression_udp_only() {
@@ -35,6 +35,7 @@ mod tests {
     use zenoh_result::ZResult;
     use zenoh_test::{get_free_tcp_port, get_free_udp_port};
     use zenoh_transport::{
+        common::compression::CompressionConfig,
         multicast::TransportMulticast,
         unicast::{test_helpers::make_transport_manager_builder, TransportUnicast},
         TransportEventHandler, TransportManager, TransportMulticastEventHandler, TransportPeer,
@@ -156,6 +157,7 @@ mod tests {
         client_endpoints: &[EndPoint],
         server_endpoints: &[EndPoint],
         lowlatency_transport: bool,
+        compression: CompressionConfig,
     ) -> (
         TransportManager,
         Arc<SHRouter>,
@@ -173,7 +175,8 @@ mod tests {
             server_endpoints.len(),
             lowlatency_transport,
         )
-        .compression(true);
+        .compression(true)
+        .compression_config(compression.clone());
         let router_manager = TransportManager::builder()
             .zid(router_id)
             .whatami(WhatAmI::Router)
@@ -193,7 +196,8 @@ mod tests {
             client_endpoints.len(),
             lowlatency_transport,
         )
-        .compression(true);
+        .compression(true)
+        .compression_config(compression);
         let client_manager = TransportManager::builder()
             .whatami(WhatAmI::Client)
             .zid(client_id)
@@ -309,6 +313,7 @@ mod tests {
         channel: Channel,
         msg_size: usize,
         lowlatency_transport: bool,
+        compression: CompressionConfig,
     ) {
         println!(
             "\n>>> Running test for:  {client_endpoints:?}, {server_endpoints:?}, {channel:?}, {msg_size}"
@@ -316,7 +321,13 @@ mod tests {
 
         #[allow(unused_variables)] // Used when stats feature is enabled
         let (router_manager, router_handler, client_manager, client_transport) =
-            open_transport_unicast(client_endpoints, server_endpoints, lowlatency_transport).await;
+            open_transport_unicast(
+                client_endpoints,
+                server_endpoints,
+                lowlatency_transport,
+                compression,
+            )
+            .await;
 
         test_transport(
             router_handler.clone(),
@@ -341,6 +352,7 @@ mod tests {
         channel: &[Channel],
         msg_size: &[usize],
         lowlatency_transport: bool,
+        compression: CompressionConfig,
     ) {
         for ch in channel.iter() {
             for ms in msg_size.iter() {
@@ -350,6 +362,7 @@ mod tests {
                     *ch,
                     *ms,
                     lowlatency_transport,
+                    compression.clone(),
                 )
                 .await;
             }
@@ -362,7 +375,15 @@ mod tests {
         channel: &[Channel],
         msg_size: &[usize],
     ) {
-        run_internal(client_endpoints, server_endpoints, channel, msg_size, false).await;
+        run_internal(
+            client_endpoints,
+            server_endpoints,
+            channel,
+            msg_size,
+            false,
+            CompressionConfig::default(),
+        )
+        .await;
     }
 
     async fn run_with_lowlatency_transport(
@@ -375,7 +396,15 @@ mod tests {
             println!("LowLatency transport doesn't support more than one link, so this test would produce MAX_LINKS error!");
             panic!();
         }
-        run_internal(client_endpoints, server_endpoints, channel, msg_size, true).await;
+        run_internal(
+            client_endpoints,
+            server_endpoints,
+            channel,
+            msg_size,
+            true,
+            CompressionConfig::default(),
+        )
+        .await;
     }
 
     #[cfg(feature = "transport_tcp")]
@@ -483,4 +512,33 @@ mod tests {
         // Run
         run_with_lowlatency_transport(&endpoints, &endpoints, &channel, &MSG_SIZE_NOFRAG).await;
     }
+
+    #[cfg(feature = "transport_tcp")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transport_unicast_compression_zstd_tcp_only() {
+        zenoh_util::init_log_from_env_or("error");
+
+        // Define the locator
+        let endpoints: Vec<EndPoint> = vec![format!("tcp/127.0.0.1:{}", get_free_tcp_port())
+            .parse()
+            .unwrap()];
+        // Define the reliability and congestion control
+        let channel = [Channel {
+            priority: Priority::DEFAULT,
+            reliability: Reliability::Reliable,
+        }];
+        // Define the compression configurations
+        let dictionary: Vec<u8> = (0..1_024).map(|i| (i % 251) as u8).collect();
+        let compression = [
+            CompressionConfig::zstd(3).unwrap(),
+            CompressionConfig::zstd_with_dictionary(3, dictionary).unwrap(),
+            CompressionConfig::zstd(3)
+                .unwrap()
+                .exclude(Priority::DEFAULT),
+        ];
+        // Run
+        for c in compression {
+            run_internal(&endpoints, &endpoints, &channel, &MSG_SIZE_ALL, false, c).await;
+        }
+    }
 }
@@ -618,6 +618,59 @@ mod tests {
         run(&endpoint, true).await;
     }
 
+    #[cfg(all(feature = "transport_unixsock-stream", target_os = "linux"))]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transport_unixsock_stream_memfd_segments() {
+        use zenoh_link::LinkManagerBuilderUnicast;
+        use zenoh_shm::api::{
+            client::shm_client::ShmClient,
+            protocol_implementations::memfd::{
+                memfd_shm_client::MemfdShmClient,
+                memfd_shm_provider_backend::MemfdShmProviderBackend,
+            },
+            provider::{
+                memory_layout::MemoryLayout, shm_provider_backend::ShmProviderBackend,
+                types::AllocAlignment,
+            },
+        };
+
+        zenoh_util::init_log_from_env_or("error");
+        let f1 = "zenoh-test-unix-socket-memfd.sock";
+        let _ = std::fs::remove_file(f1);
+        let endpoint: EndPoint = format!("unixsock-stream/{f1}").parse().unwrap();
+
+        let backend = MemfdShmProviderBackend::builder(MSG_SIZE).wait().unwrap();
+        let layout = MemoryLayout::new(MSG_SIZE, AllocAlignment::default()).unwrap();
+        let buf = backend.alloc(&layout).unwrap();
+        let segment = buf.descriptor.segment;
+
+        let (sender, receiver) = flume::unbounded();
+        let manager = LinkManagerBuilderUnicast::make(sender, &endpoint).unwrap();
+        ztimeout!(manager.new_listener(endpoint.clone())).unwrap();
+        let link = ztimeout!(manager.new_link(endpoint.clone())).unwrap();
+        let peer = ztimeout!(receiver.recv_async()).unwrap();
+
+        // the segment is handed over along with the data
+        ztimeout!(link.write_all(&[STALLED_QUEUE_SHM_MARKER; 4], None)).unwrap();
+        let mut data = [0; 4];
+        ztimeout!(peer.read_exact(&mut data, None)).unwrap();
+        assert_eq!(data, [STALLED_QUEUE_SHM_MARKER; 4]);
+
+        // the segment remains attachable while the link it was received over is open
+        drop(buf);
+        drop(backend);
+        assert!(MemfdShmClient {}.attach(segment).is_ok());
+
+        ztimeout!(link.close()).unwrap();
+        ztimeout!(peer.close()).unwrap();
+        drop(link);
+        drop(peer);
+        ztimeout!(manager.del_listener(&endpoint)).unwrap();
+        assert!(MemfdShmClient {}.attach(segment).is_err());
+        let _ = std::fs::remove_file(f1);
+        let _ = std::fs::remove_file(format!("{f1}.lock"));
+    }
+
     #[cfg(feature = "transport_unixpipe")]
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn transport_unixpipe_shm() {
@@ -29,6 +29,12 @@ use zenoh_util::{
     LibSearchDirs,
 };
 
+/// The default maximum time-to-live of the values of a storage: 1 year.
+pub const DEFAULT_MAX_TTL: Duration = Duration::from_secs(365 * 24 * 3600);
+/// The upper limit of the maximum time-to-live of the values of a storage: 10 years, so that the
+/// expiry of a value remains far within the range of the timestamps.
+pub const MAX_TTL_LIMIT: Duration = Duration::from_secs(10 * 365 * 24 * 3600);
+
 #[derive(JsonSchema, Debug, Clone, AsMut, AsRef)]
 pub struct PluginConfig {
     #[schemars(skip)]
@@ -68,6 +74,11 @@ pub struct StorageConfig {
     pub volume_id: String,
     pub volume_cfg: JsonValue,
     pub garbage_collection_config: GarbageCollectionConfig,
+    // The default time-to-live of the values, after which they are deleted. It can be overridden
+    // per sample with a `ttl` in its attachment
+    pub ttl: Option<Duration>,
+    // The maximum time-to-live of the values, to which the time-to-live of the samples is clamped
+    pub max_ttl: Duration,
     // Note: ReplicaConfig is optional. Alignment will be performed only if it is a replica
     pub replication: Option<ReplicaConfig>,
 }
@@ -79,6 +90,19 @@ pub struct ReplicaConfig {
     pub hot: u64,
     pub warm: u64,
     pub propagation_delay: Duration,
+    pub filters: Vec<ReplicationFilter>,
+}
+// A filter restricting the key expressions a replica aligns with the other replicas
+#[derive(JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct ReplicationFilter {
+    pub key_expr: OwnedKeyExpr,
+    pub kind: ReplicationFilterKind,
+    pub priority: u32,
+}
+#[derive(JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReplicationFilterKind {
+    Include,
+    Exclude,
 }
 
 impl StructVersion for VolumeConfig {
@@ -149,6 +173,16 @@ impl Default for ReplicaConfig {
             //
             // ⚠️ THIS VALUE SHOULD BE THE SAME FOR ALL REPLICAS.
             propagation_delay: Duration::from_millis(250),
+            // The filters restricting the key expressions that are aligned with the other
+            // replicas. A key expression is aligned according to the matching filter with the
+            // highest `priority`, an `exclude` filter winning over an `include` filter of same
+            // priority. A key expression matching no filter is aligned only if no `include`
+            // filter is configured.
+            //
+            // Replicas with different filters align the key expressions they both replicate.
+            //
+            // By default, there is no filter: the whole key expression of the storage is aligned.
+            filters: Vec::new(),
         }
     }
 }
@@ -425,6 +459,10 @@ impl StorageConfig {
                 _ => unreachable!(),
             },
         );
+        if let Some(ttl) = &self.ttl {
+            result.insert("ttl".into(), ttl.as_secs_f64().into());
+        }
+        result.insert("max_ttl".into(), self.max_ttl.as_secs_f64().into());
         Value::Object(result)
     }
     fn try_from<V: AsObject>(plugin_name: &str, storage_name: &str, config: &V) -> ZResult<Self> {
@@ -561,6 +599,45 @@ impl StorageConfig {
             }
             None => GarbageCollectionConfig::default(),
         };
+        let ttl = match config.get("ttl") {
+            Some(ttl) => match ttl
+                .to_string()
+                .parse::<f64>()
+                .map(Duration::try_from_secs_f64)
+            {
+                Ok(Ok(ttl)) if !ttl.is_zero() => Some(ttl),
+                _ => bail!(
+                    "Invalid value for field `ttl` of storage `{}`. Only positive numbers of \
+                     seconds are accepted.",
+                    storage_name
+                ),
+            },
+            None => None,
+        };
+        let max_ttl = match config.get("max_ttl") {
+            Some(max_ttl) => match max_ttl
+                .to_string()
+                .parse::<f64>()
+                .map(Duration::try_from_secs_f64)
+            {
+                Ok(Ok(max_ttl)) if !max_ttl.is_zero() && max_ttl <= MAX_TTL_LIMIT => max_ttl,
+                _ => bail!(
+                    "Invalid value for field `max_ttl` of storage `{}`. Only positive numbers of \
+                     seconds up to {} are accepted.",
+                    storage_name,
+                    MAX_TTL_LIMIT.as_secs()
+                ),
+            },
+            None => DEFAULT_MAX_TTL,
+        };
+        if ttl.is_some_and(|ttl| ttl > max_ttl) {
+            bail!(
+                "Invalid value for field `ttl` of storage `{}`. It must not exceed `max_ttl` ({} \
+                 seconds).",
+                storage_name,
+                max_ttl.as_secs_f64()
+            );
+        }
         let replication = match config.get("replication") {
             Some(s) => {
                 let mut replication = ReplicaConfig::default();
@@ -634,6 +711,19 @@ impl StorageConfig {
                         )
                     }
                 }
+                if let Some(filters) = s.get("filters") {
+                    let Some(filters) = filters.as_array() else {
+                        bail!(
+                            "Invalid type for field `filters` in `replica_config` of storage \
+                             `{}`. Expecting an array of filters.",
+                            plugin_name
+                        )
+                    };
+                    replication.filters = filters
+                        .iter()
+                        .map(|filter| ReplicationFilter::try_from(plugin_name, filter))
+                        .collect::<ZResult<_>>()?;
+                }
                 Some(replication)
             }
             None => None,
@@ -646,10 +736,64 @@ impl StorageConfig {
             volume_id,
             volume_cfg: volume_cfg.into(),
             garbage_collection_config,
+            ttl,
+            max_ttl,
             replication,
         })
     }
 }
+impl ReplicationFilter {
+    pub fn try_from<S: AsRef<str>>(plugin_name: S, filter: &Value) -> ZResult<Self> {
+        let plugin_name = plugin_name.as_ref();
+        let key_expr = |kind: &str| -> ZResult<Option<OwnedKeyExpr>> {
+            match filter.get(kind) {
+                Some(Value::String(key_expr)) => match keyexpr::new(key_expr.as_str()) {
+                    Ok(key_expr) => Ok(Some(key_expr.to_owned())),
+                    Err(e) => bail!(
+                        "Invalid key expression `{}` in `filters` of `replica_config` of \
+                         storage `{}`: {}",
+                        key_expr,
+                        plugin_name,
+                        e
+                    ),
+                },
+                Some(_) => bail!(
+                    "Invalid type for field `{}` in `filters` of `replica_config` of storage \
+                     `{}`. Expecting a key expression.",
+                    kind,
+                    plugin_name
+                ),
+                None => Ok(None),
+            }
+        };
+        let (key_expr, kind) = match (key_expr("include")?, key_expr("exclude")?) {
+            (Some(key_expr), None) => (key_expr, ReplicationFilterKind::Include),
+            (None, Some(key_expr)) => (key_expr, ReplicationFilterKind::Exclude),
+            _ => bail!(
+                "Invalid filter {} in `replica_config` of storage `{}`. Expecting exactly one of \
+                 `include` or `exclude`.",
+                filter,
+                plugin_name
+            ),
+        };
+        let priority = match filter.get("priority") {
+            Some(priority) => match priority.to_string().parse::<u32>() {
+                Ok(priority) => priority,
+                Err(_) => bail!(
+                    "Invalid type for field `priority` in `filters` of `replica_config` of \
+                     storage `{}`. Only integer values are accepted.",
+                    plugin_name
+                ),
+            },
+            None => 0,
+        };
+        Ok(ReplicationFilter {
+            key_expr,
+            kind,
+            priority,
+        })
+    }
+}
 impl PartialEq for VolumeConfig {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name && self.paths == other.paths && self.rest == other.rest
@@ -17,7 +17,7 @@ use std::time::Duration;
 use serde_json::json;
 
 use super::StorageConfig;
-use crate::config::ReplicaConfig;
+use crate::config::{ReplicaConfig, ReplicationFilter, ReplicationFilterKind};
 
 #[test]
 fn test_replica_config() {
@@ -72,7 +72,53 @@ Actual message: {err}",
             sub_intervals: 4,
             hot: 6,
             warm: 60,
-            propagation_delay: Duration::from_millis(250)
+            propagation_delay: Duration::from_millis(250),
+            filters: Vec::new(),
         })
     );
+
+    let filters_config = json!({
+        "key_expr": "fleet/**",
+        "volume": "memory",
+        "replication": {
+            "filters": [
+                { "include": "fleet/*/summary/**", "priority": 1 },
+                { "exclude": "fleet/**" },
+            ],
+        }
+    });
+    let storage_config =
+        StorageConfig::try_from("test-plugin", "test-storage", &filters_config).unwrap();
+    assert_eq!(
+        storage_config.replication.unwrap().filters,
+        vec![
+            ReplicationFilter {
+                key_expr: "fleet/*/summary/**".try_into().unwrap(),
+                kind: ReplicationFilterKind::Include,
+                priority: 1,
+            },
+            ReplicationFilter {
+                key_expr: "fleet/**".try_into().unwrap(),
+                kind: ReplicationFilterKind::Exclude,
+                priority: 0,
+            },
+        ]
+    );
+
+    for invalid_filter in [
+        json!({ "include": "fleet/**", "exclude": "fleet/a/**" }),
+        json!({ "priority": 1 }),
+        json!({ "include": "fleet/**", "priority": -1 }),
+        json!({ "exclude": "fleet/**/" }),
+    ] {
+        let invalid_filters_config = json!({
+            "key_expr": "fleet/**",
+            "volume": "memory",
+            "replication": { "filters": [invalid_filter] }
+        });
+        assert!(
+            StorageConfig::try_from("test-plugin", "test-storage", &invalid_filters_config)
+                .is_err()
+        );
+    }
 }
@@ -239,6 +239,40 @@ pub trait Storage: Send + Sync {
         timestamp: Timestamp,
     ) -> ZResult<StorageInsertionResult>;
 
+    /// Function called instead of [`Storage::put`] for the data having a time-to-live, which
+    /// expires at `expiry`.
+    ///
+    /// As long as it runs, the storage manager deletes the expired data, calling
+    /// [`Storage::delete`] with `expiry` as timestamp. Backends able to expire data natively
+    /// should override this function, so that the data also expires while the storage manager is
+    /// not running, e.g. across the restarts of a durable storage. The default implementation
+    /// calls [`Storage::put`].
+    async fn put_with_expiry(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+        expiry: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        let _ = expiry;
+        self.put(key, payload, encoding, timestamp).await
+    }
+
+    /// Returns the expiry of the data stored with [`Storage::put_with_expiry`] which is still
+    /// present, so that the storage manager deletes it once expired, e.g. after a restart of a
+    /// durable storage. The default implementation returns no expiry.
+    async fn get_all_expiries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the keys deleted from the storage which it still keeps track of, with the
+    /// timestamp of their deletion, so that the storage manager does not forget these deletions,
+    /// e.g. after a restart of a durable storage. The default implementation returns no deletion.
+    async fn get_all_tombstones(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        Ok(Vec::new())
+    }
+
     /// Function called for each incoming delete request to this storage.
     /// A key can be `None` if it matches the `strip_prefix` exactly.
     /// In order to avoid data loss, the storage must delete the entry corresponding to the `None` key
@@ -263,4 +297,17 @@ pub trait Storage: Send + Sync {
     /// The latest Timestamp corresponding to each key is either the timestamp of the delete or put whichever is the latest.
     /// Remember to fetch the entry corresponding to the `None` key
     async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>>;
+
+    /// Returns the keys having values in the history of the storage, including the keys deleted
+    /// since, so that the time-travel queries with wildcards return their values. Only called on
+    /// the storages whose volume keeps all the history ([`History::All`]). The default
+    /// implementation returns the keys of [`Storage::get_all_entries`].
+    async fn get_all_history_keys(&self) -> ZResult<Vec<Option<OwnedKeyExpr>>> {
+        Ok(self
+            .get_all_entries()
+            .await?
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect())
+    }
 }
@@ -0,0 +1,387 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The authentication of the clients, and the access control of their requests.
+//!
+//! A client is identified by the username of its HTTP basic credentials or of its bearer token,
+//! and by the common name of its certificate when connected through mTLS. The access control
+//! rules allow or deny the operations of a client on some key expressions, depending on these
+//! identifiers, as the `access_control` configuration of Zenoh does for the Zenoh sessions.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts, StatusCode},
+    response::{AppendHeaders, IntoResponse, Response},
+};
+use base64::Engine;
+use subtle::ConstantTimeEq;
+use zenoh::{
+    internal::{bail, zerror},
+    key_expr::{keyexpr, OwnedKeyExpr},
+    Result as ZResult,
+};
+
+use crate::{
+    config::{AccessControlConfig, AclMessage, Config, Permission},
+    tls::TlsPeer,
+    AppState,
+};
+
+const REALM: &str = "zenoh";
+
+/// The credentials accepted from the clients, and the access control of their requests.
+pub(crate) struct Auth {
+    users: Option<HashMap<String, String>>,
+    tokens: Option<HashMap<String, String>>,
+    client_certificates: bool,
+    access_control: Option<AccessControl>,
+}
+
+impl Auth {
+    pub(crate) fn new(conf: &Config) -> ZResult<Self> {
+        let users = conf
+            .auth
+            .basic
+            .as_ref()
+            .map(|basic| load_dictionary(&basic.dictionary_file))
+            .transpose()?;
+        let tokens = conf.auth.bearer.as_ref().map(|bearer| {
+            bearer
+                .tokens
+                .iter()
+                .map(|token| (token.token.clone(), token.username.clone()))
+                .collect()
+        });
+        let client_certificates = conf
+            .auth
+            .tls
+            .as_ref()
+            .is_some_and(|tls| tls.root_ca_certificate.is_some());
+        let access_control = conf
+            .access_control
+            .as_ref()
+            .map(AccessControl::new)
+            .transpose()?;
+        Ok(Auth {
+            users,
+            tokens,
+            client_certificates,
+            access_control,
+        })
+    }
+
+    /// Whether the clients must authenticate.
+    fn is_required(&self) -> bool {
+        self.users.is_some() || self.tokens.is_some() || self.client_certificates
+    }
+
+    /// Returns the identity of the client sending a request, or the response rejecting it if its
+    /// credentials are invalid, or missing while required.
+    fn authenticate(&self, parts: &Parts) -> Result<Identity, AuthError> {
+        let mut identity = Identity {
+            username: None,
+            cert_common_name: parts
+                .extensions
+                .get::<ConnectInfo<TlsPeer>>()
+                .and_then(|ConnectInfo(peer)| peer.cert_common_name.clone()),
+        };
+        if let Some(authorization) = parts.headers.get(header::AUTHORIZATION) {
+            let authorization = authorization
+                .to_str()
+                .map_err(|_| self.unauthorized("Invalid Authorization header"))?;
+            identity.username = Some(match authorization.split_once(' ') {
+                Some((scheme, credentials)) if scheme.eq_ignore_ascii_case("basic") => {
+                    self.check_basic(credentials.trim())?
+                }
+                Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") => {
+                    self.check_bearer(token.trim())?
+                }
+                _ => return Err(self.unauthorized("Unsupported authentication scheme")),
+            });
+        }
+        if self.is_required() && identity.is_anonymous() {
+            return Err(self.unauthorized("Authentication required"));
+        }
+        Ok(identity)
+    }
+
+    fn check_basic(&self, credentials: &str) -> Result<String, AuthError> {
+        let Some(users) = &self.users else {
+            return Err(self.unauthorized("Basic authentication is not enabled"));
+        };
+        let credentials = base64::engine::general_purpose::STANDARD
+            .decode(credentials)
+            .ok()
+            .and_then(|credentials| String::from_utf8(credentials).ok())
+            .ok_or_else(|| self.unauthorized("Invalid basic credentials"))?;
+        match credentials.split_once(':') {
+            Some((user, password))
+                if users
+                    .get(user)
+                    .is_some_and(|p| bool::from(p.as_bytes().ct_eq(password.as_bytes()))) =>
+            {
+                Ok(user.to_string())
+            }
+            _ => Err(self.unauthorized("Invalid username or password")),
+        }
+    }
+
+    fn check_bearer(&self, token: &str) -> Result<String, AuthError> {
+        let Some(tokens) = &self.tokens else {
+            return Err(self.unauthorized("Bearer authentication is not enabled"));
+        };
+        // All the tokens are compared in constant time, so that the time to reject a token does not
+        // tell how close it is to a valid one
+        let mut username = None;
+        for (candidate, user) in tokens {
+            if bool::from(candidate.as_bytes().ct_eq(token.as_bytes())) {
+                username = Some(user);
+            }
+        }
+        username
+            .cloned()
+            .ok_or_else(|| self.unauthorized("Invalid bearer token"))
+    }
+
+    fn unauthorized(&self, message: &'static str) -> AuthError {
+        let mut challenges = Vec::new();
+        if self.users.is_some() {
+            challenges.push("Basic");
+        }
+        if self.tokens.is_some() {
+            challenges.push("Bearer");
+        }
+        AuthError::Unauthorized {
+            message,
+            challenges,
+        }
+    }
+
+    /// Returns whether the client with the provided identity is allowed to perform the operation
+    /// `message` on `key_expr`.
+    pub(crate) fn is_allowed(
+        &self,
+        identity: &Identity,
+        message: AclMessage,
+        key_expr: &keyexpr,
+    ) -> bool {
+        let allowed = self.access_control.as_ref().map_or(true, |access_control| {
+            access_control.is_allowed(identity, message, key_expr)
+        });
+        if !allowed {
+            tracing::debug!("{identity} is not allowed to {message:?} on {key_expr}");
+        }
+        allowed
+    }
+
+    /// Checks that the client is allowed to perform the operation `message` on `key_expr`.
+    pub(crate) fn authorize(
+        &self,
+        identity: &Identity,
+        message: AclMessage,
+        key_expr: &keyexpr,
+    ) -> Result<(), AuthError> {
+        if self.is_allowed(identity, message, key_expr) {
+            Ok(())
+        } else {
+            Err(AuthError::Forbidden(format!(
+                "Not allowed to {message:?} on {key_expr}"
+            )))
+        }
+    }
+}
+
+/// The rejection of a request by the authentication or the access control.
+pub(crate) enum AuthError {
+    /// The credentials are invalid, or missing while required. The `challenges` are the schemes
+    /// of the authentication methods enabled.
+    Unauthorized {
+        message: &'static str,
+        challenges: Vec<&'static str>,
+    },
+    /// The client is not allowed to perform the operation.
+    Forbidden(String),
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        match self {
+            AuthError::Unauthorized {
+                message,
+                challenges,
+            } => {
+                let challenges = challenges
+                    .into_iter()
+                    .map(|scheme| {
+                        (
+                            header::WWW_AUTHENTICATE,
+                            format!("{scheme} realm=\"{REALM}\""),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                (StatusCode::UNAUTHORIZED, AppendHeaders(challenges), message).into_response()
+            }
+            AuthError::Forbidden(message) => (StatusCode::FORBIDDEN, message).into_response(),
+        }
+    }
+}
+
+/// Loads a credentials file, with one `<username>:<password>` per line.
+fn load_dictionary(file: &str) -> ZResult<HashMap<String, String>> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| zerror!("Invalid basic authentication dictionary file {file}: {e}"))?;
+    let mut users = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match line.split_once(':') {
+            Some((user, password)) if !user.trim().is_empty() && !password.trim().is_empty() => {
+                users.insert(user.trim().to_string(), password.trim().to_string());
+            }
+            _ => bail!(
+                "Invalid user-password dictionary file: each line must be <username>:<password>"
+            ),
+        }
+    }
+    Ok(users)
+}
+
+/// The identity of an authenticated client.
+#[derive(Clone, Debug)]
+pub(crate) struct Identity {
+    username: Option<String>,
+    cert_common_name: Option<String>,
+}
+
+impl Identity {
+    fn is_anonymous(&self) -> bool {
+        self.username.is_none() && self.cert_common_name.is_none()
+    }
+}
+
+impl std::fmt::Display for Identity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.username, &self.cert_common_name) {
+            (Some(username), _) => write!(f, "User '{username}'"),
+            (None, Some(common_name)) => write!(f, "Certificate '{common_name}'"),
+            (None, None) => write!(f, "Anonymous client"),
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for Identity {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        state.auth.authenticate(parts)
+    }
+}
+
+struct AccessControl {
+    default_permission: Permission,
+    rules: Vec<Rule>,
+}
+
+struct Rule {
+    permission: Permission,
+    messages: Vec<AclMessage>,
+    key_exprs: Vec<OwnedKeyExpr>,
+    usernames: Option<Vec<String>>,
+    cert_common_names: Option<Vec<String>>,
+}
+
+impl AccessControl {
+    fn new(conf: &AccessControlConfig) -> ZResult<Self> {
+        let rules = conf
+            .rules
+            .iter()
+            .map(|rule| {
+                let key_exprs = rule
+                    .key_exprs
+                    .iter()
+                    .map(|key_expr| OwnedKeyExpr::autocanonize(key_expr.clone()))
+                    .collect::<ZResult<Vec<_>>>()
+                    .map_err(|e| {
+                        zerror!(
+                            "Invalid key expression in access control rule {}: {e}",
+                            rule.id.as_deref().unwrap_or_default()
+                        )
+                    })?;
+                Ok(Rule {
+                    permission: rule.permission,
+                    messages: rule.messages.clone(),
+                    key_exprs,
+                    usernames: rule.usernames.clone(),
+                    cert_common_names: rule.cert_common_names.clone(),
+                })
+            })
+            .collect::<ZResult<Vec<_>>>()?;
+        Ok(AccessControl {
+            default_permission: conf.default_permission,
+            rules,
+        })
+    }
+
+    /// Returns whether the client is allowed to perform the operation `message` on `key_expr`:
+    /// denied if a `deny` rule applies to a key expression intersecting `key_expr`, otherwise
+    /// allowed if an `allow` rule applies to a key expression including `key_expr`, otherwise
+    /// given the default permission.
+    fn is_allowed(&self, identity: &Identity, message: AclMessage, key_expr: &keyexpr) -> bool {
+        let applying = |permission| {
+            self.rules.iter().filter(move |rule| {
+                rule.permission == permission
+                    && rule.messages.contains(&message)
+                    && rule.applies_to(identity)
+            })
+        };
+        if applying(Permission::Deny).any(|rule| {
+            rule.key_exprs
+                .iter()
+                .any(|rule_key_expr| rule_key_expr.intersects(key_expr))
+        }) {
+            return false;
+        }
+        if applying(Permission::Allow).any(|rule| {
+            rule.key_exprs
+                .iter()
+                .any(|rule_key_expr| rule_key_expr.includes(key_expr))
+        }) {
+            return true;
+        }
+        self.default_permission == Permission::Allow
+    }
+}
+
+impl Rule {
+    fn applies_to(&self, identity: &Identity) -> bool {
+        if self.usernames.is_none() && self.cert_common_names.is_none() {
+            return true;
+        }
+        let matches = |names: &Option<Vec<String>>, name: &Option<String>| {
+            names
+                .as_ref()
+                .zip(name.as_ref())
+                .is_some_and(|(names, name)| names.contains(name))
+        };
+        matches(&self.usernames, &identity.username)
+            || matches(&self.cert_common_names, &identity.cert_common_name)
+    }
+}
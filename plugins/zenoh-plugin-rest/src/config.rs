@@ -12,15 +12,30 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 use std::{
+    collections::HashMap,
     fmt,
     net::{Ipv6Addr, SocketAddr},
 };
 
-use schemars::JsonSchema;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::{de, de::Visitor, Deserialize, Deserializer};
+pub use zenoh_config::{AclMessage, Permission};
 
 pub const DEFAULT_WORK_THREAD_NUM: usize = 2;
 pub const DEFAULT_MAX_BLOCK_THREAD_NUM: usize = 50;
+pub const DEFAULT_SSE_CACHE_LINGER: u64 = 60;
+pub const DEFAULT_WEBHOOK_TIMEOUT: u64 = 10000;
+
+/// The operations subject to the access control of the plugin, named as in the `access_control`
+/// configuration of Zenoh: `put` for the PUT and PATCH requests, `delete` for the DELETE requests,
+/// `query` for the GET and POST requests and `declare_subscriber` for the subscriptions through
+/// Server-Sent Events. The WebSocket operations are controlled likewise.
+pub const SUPPORTED_ACL_MESSAGES: [AclMessage; 4] = [
+    AclMessage::Put,
+    AclMessage::Delete,
+    AclMessage::Query,
+    AclMessage::DeclareSubscriber,
+];
 
 #[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug)]
 #[serde(deny_unknown_fields)]
@@ -31,6 +46,15 @@ pub struct Config {
     pub work_thread_num: usize,
     #[serde(default = "default_max_block_thread_num")]
     pub max_block_thread_num: usize,
+    #[serde(default)]
+    pub sse: SseConfig,
+    #[serde(default)]
+    pub cors: CorsConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    pub access_control: Option<AccessControlConfig>,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
     #[serde(default, deserialize_with = "deserialize_path")]
     __path__: Option<Vec<String>>,
     __required__: Option<bool>,
@@ -38,6 +62,147 @@ pub struct Config {
     __plugin__: Option<String>,
 }
 
+/// The configuration of the subscriptions through Server-Sent Events.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct SseConfig {
+    /// The number of samples cached for each subscribed key expression, replayed to the clients
+    /// resuming their subscription. The cache is disabled if 0.
+    pub cache_size: usize,
+    /// The duration, in seconds, during which the cache of a key expression is kept after its
+    /// last client disconnected.
+    pub cache_linger: u64,
+    /// Whether the samples missed by a resuming client, and not found in the cache, are queried
+    /// from the storages and the publication caches. They are only queried for the clients
+    /// allowed to query the subscribed key expression.
+    pub resume_query: bool,
+}
+
+impl Default for SseConfig {
+    fn default() -> Self {
+        SseConfig {
+            cache_size: 0,
+            cache_linger: DEFAULT_SSE_CACHE_LINGER,
+            resume_query: true,
+        }
+    }
+}
+
+/// The configuration of the Cross-Origin Resource Sharing.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CorsConfig {
+    /// The origins allowed to send cross-origin requests and to open WebSockets, e.g.
+    /// `https://example.com`. Any origin is allowed if not set.
+    pub allowed_origins: Option<Vec<String>>,
+}
+
+/// The configuration of the authentication of the clients.
+///
+/// The clients must authenticate as soon as one of the methods is configured, except when `tls`
+/// is configured without `root_ca_certificate`, which only enables HTTPS.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    /// The HTTP basic authentication.
+    pub basic: Option<BasicAuthConfig>,
+    /// The authentication with bearer tokens.
+    pub bearer: Option<BearerAuthConfig>,
+    /// The TLS listener, with the authentication of the clients by their certificate if
+    /// `root_ca_certificate` is set.
+    pub tls: Option<TlsConfig>,
+}
+
+/// The configuration of the HTTP basic authentication.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BasicAuthConfig {
+    /// The file of the users credentials, with one `<username>:<password>` per line, in the
+    /// format of `transport/auth/usrpwd/dictionary_file`.
+    pub dictionary_file: String,
+}
+
+/// The configuration of the authentication with bearer tokens.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BearerAuthConfig {
+    pub tokens: Vec<BearerToken>,
+}
+
+/// A bearer token, and the username of the clients presenting it.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BearerToken {
+    /// The secret token, not exposed on the adminspace.
+    #[serde(skip_serializing)]
+    pub token: String,
+    pub username: String,
+}
+
+/// The configuration of the TLS listener.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    /// The file of the certificate chain of the server, in PEM format.
+    pub server_certificate: String,
+    /// The file of the private key of the server, in PEM format.
+    pub server_private_key: String,
+    /// The file of the certificates of the authorities trusted to sign the certificates of the
+    /// clients, in PEM format. The clients authenticate with their certificate, whose common name
+    /// identifies them, if set.
+    pub root_ca_certificate: Option<String>,
+}
+
+/// The configuration of the access control of the authenticated clients.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AccessControlConfig {
+    /// The permission of the requests no rule applies to.
+    #[schemars(schema_with = "permission_schema")]
+    pub default_permission: Permission,
+    #[serde(default)]
+    pub rules: Vec<AccessControlRule>,
+}
+
+/// A rule allowing or denying some operations on some key expressions, to some clients.
+///
+/// A rule applies to the clients authenticated with one of its `usernames` or
+/// `cert_common_names`, or to all the clients if neither is set. The `deny` rules take precedence
+/// over the `allow` rules.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AccessControlRule {
+    pub id: Option<String>,
+    #[schemars(schema_with = "permission_schema")]
+    pub permission: Permission,
+    #[serde(deserialize_with = "deserialize_acl_messages")]
+    #[schemars(schema_with = "acl_messages_schema")]
+    pub messages: Vec<AclMessage>,
+    pub key_exprs: Vec<String>,
+    pub usernames: Option<Vec<String>>,
+    pub cert_common_names: Option<Vec<String>>,
+}
+
+/// A queryable answering the queries on `key_expr` by forwarding them to an HTTP service.
+#[derive(JsonSchema, Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// The key expression of the queryable.
+    pub key_expr: String,
+    /// The base URL of the service: a query on `a/b?p=1` is forwarded to `<url>/a/b?p=1`.
+    pub url: String,
+    /// Whether the queryable is complete for `key_expr`.
+    #[serde(default)]
+    pub complete: bool,
+    /// The timeout, in milliseconds, of the requests to the service.
+    #[serde(default = "default_webhook_timeout")]
+    pub timeout: u64,
+    /// The headers added to the requests to the service, e.g. its credentials. They are not
+    /// exposed on the adminspace.
+    #[serde(default, skip_serializing)]
+    pub headers: HashMap<String, String>,
+}
+
 impl From<&Config> for serde_json::Value {
     fn from(c: &Config) -> Self {
         serde_json::to_value(c).unwrap()
@@ -51,6 +216,35 @@ where
     deserializer.deserialize_any(HttpPortVisitor)
 }
 
+fn deserialize_acl_messages<'de, D>(deserializer: D) -> Result<Vec<AclMessage>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let messages = Vec::<AclMessage>::deserialize(deserializer)?;
+    if let Some(message) = messages
+        .iter()
+        .find(|m| !SUPPORTED_ACL_MESSAGES.contains(m))
+    {
+        return Err(de::Error::custom(format!(
+            "unsupported access control message {}, expected one of {}",
+            serde_json::json!(message),
+            serde_json::json!(SUPPORTED_ACL_MESSAGES)
+        )));
+    }
+    Ok(messages)
+}
+
+fn permission_schema(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({"type": "string", "enum": [Permission::Allow, Permission::Deny]})
+}
+
+fn acl_messages_schema(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "type": "array",
+        "items": {"type": "string", "enum": SUPPORTED_ACL_MESSAGES}
+    })
+}
+
 fn default_work_thread_num() -> usize {
     DEFAULT_WORK_THREAD_NUM
 }
@@ -59,6 +253,10 @@ fn default_max_block_thread_num() -> usize {
     DEFAULT_MAX_BLOCK_THREAD_NUM
 }
 
+fn default_webhook_timeout() -> u64 {
+    DEFAULT_WEBHOOK_TIMEOUT
+}
+
 struct HttpPortVisitor;
 
 impl Visitor<'_> for HttpPortVisitor {
@@ -161,7 +359,7 @@ impl<'de> serde::de::Visitor<'de> for PathVisitor {
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{AclMessage, Config, Permission, SseConfig, DEFAULT_WEBHOOK_TIMEOUT};
 
     #[test]
     fn test_path_field() {
@@ -236,4 +434,113 @@ mod tests {
         assert_eq!(__path__, None);
         assert_eq!(__required__, None);
     }
+
+    #[test]
+    fn test_sse_field() {
+        let config = serde_json::from_str::<Config>(r#"{"http_port": 8080}"#).unwrap();
+        assert_eq!(config.sse, SseConfig::default());
+
+        let config = serde_json::from_str::<Config>(
+            r#"{"http_port": 8080, "sse": {"cache_size": 100, "resume_query": false}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.sse,
+            SseConfig {
+                cache_size: 100,
+                cache_linger: 60,
+                resume_query: false,
+            }
+        );
+
+        let config =
+            serde_json::from_str::<Config>(r#"{"http_port": 8080, "sse": {"cache": 100}}"#);
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn test_auth_fields() {
+        let config = serde_json::from_str::<Config>(r#"{"http_port": 8080}"#).unwrap();
+        assert_eq!(config.cors.allowed_origins, None);
+        assert_eq!(config.auth, Default::default());
+        assert_eq!(config.access_control, None);
+
+        let config = serde_json::from_str::<Config>(
+            r#"{
+                "http_port": 8080,
+                "cors": {"allowed_origins": ["https://example.com"]},
+                "auth": {
+                    "basic": {"dictionary_file": "credentials.txt"},
+                    "bearer": {"tokens": [{"token": "secret", "username": "alice"}]}
+                },
+                "access_control": {
+                    "default_permission": "deny",
+                    "rules": [{
+                        "permission": "allow",
+                        "messages": ["query", "declare_subscriber"],
+                        "key_exprs": ["demo/**"],
+                        "usernames": ["alice"]
+                    }]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.cors.allowed_origins,
+            Some(vec!["https://example.com".to_string()])
+        );
+        assert_eq!(
+            config.auth.basic.unwrap().dictionary_file,
+            "credentials.txt"
+        );
+        assert_eq!(config.auth.bearer.unwrap().tokens[0].username, "alice");
+        let access_control = config.access_control.unwrap();
+        assert_eq!(access_control.default_permission, Permission::Deny);
+        assert_eq!(
+            access_control.rules[0].messages,
+            vec![AclMessage::Query, AclMessage::DeclareSubscriber]
+        );
+
+        let config = serde_json::from_str::<Config>(
+            r#"{"http_port": 8080, "access_control": {"default_permission": "deny", "rules": [{"permission": "allow", "messages": ["reply"], "key_exprs": ["**"]}]}}"#,
+        );
+        assert!(config
+            .unwrap_err()
+            .to_string()
+            .starts_with(r#"unsupported access control message "reply""#));
+    }
+
+    #[test]
+    fn test_webhooks_field() {
+        let config = serde_json::from_str::<Config>(r#"{"http_port": 8080}"#).unwrap();
+        assert!(config.webhooks.is_empty());
+
+        let config = serde_json::from_str::<Config>(
+            r#"{
+                "http_port": 8080,
+                "webhooks": [
+                    {"key_expr": "demo/**", "url": "http://localhost:9000/zenoh"},
+                    {
+                        "key_expr": "sensors/*",
+                        "url": "http://sensors:8000",
+                        "complete": true,
+                        "timeout": 500,
+                        "headers": {"Authorization": "Bearer secret"}
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.webhooks.len(), 2);
+        assert!(!config.webhooks[0].complete);
+        assert_eq!(config.webhooks[0].timeout, DEFAULT_WEBHOOK_TIMEOUT);
+        assert!(config.webhooks[1].complete);
+        assert_eq!(config.webhooks[1].timeout, 500);
+        assert_eq!(config.webhooks[1].headers["Authorization"], "Bearer secret");
+
+        let config = serde_json::from_str::<Config>(
+            r#"{"http_port": 8080, "webhooks": [{"key_expr": "demo/**"}]}"#,
+        );
+        assert!(config.is_err());
+    }
 }
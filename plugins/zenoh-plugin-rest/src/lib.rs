@@ -21,28 +21,29 @@ use std::{
     convert::{Infallible, TryFrom},
     fmt::Write,
     future::Future,
-    net::SocketAddr,
-    pin::Pin,
     str::FromStr,
-    sync::atomic::{AtomicUsize, Ordering},
-    task::{Context, Poll},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 
 use axum::{
     extract::{FromRequest, FromRequestParts, Path, Request, State},
-    http::{header, request::Parts, HeaderValue, Method, StatusCode, Uri},
-    response::{sse::Event, Html, IntoResponse, Response, Sse},
+    http::{header, request::Parts, HeaderName, HeaderValue, Method, StatusCode, Uri},
+    response::{Html, IntoResponse, Response},
     routing::get,
     Json, Router,
 };
 use base64::Engine;
-use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use futures::{FutureExt, StreamExt, TryFutureExt};
 use mime::Mime;
 use serde::Serialize;
 use tokio::{net::TcpListener, task::JoinHandle, time::timeout};
+use tokio_rustls::TlsAcceptor;
 use tower_http::{
-    cors::{Any, CorsLayer},
+    cors::{AllowOrigin, Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
     trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
 };
@@ -63,13 +64,24 @@ use zenoh::{
 };
 use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin, PluginControl};
 
+mod auth;
+use auth::{Auth, Identity};
+
 mod config;
+use config::AclMessage;
 pub use config::Config;
-use zenoh::{
-    handlers::{fifo::RecvStream, FifoChannelHandler},
-    pubsub::Subscriber,
-    time::Timestamp,
-};
+use zenoh::time::Timestamp;
+
+mod sse;
+use sse::{LastEventId, SseCaches};
+
+mod tls;
+use tls::{TlsListener, TlsPeer};
+
+mod webhook;
+use webhook::Webhooks;
+
+mod ws;
 
 const GIT_VERSION: &str = git_version::git_version!(prefix = "v", cargo_prefix = "v");
 lazy_static::lazy_static! {
@@ -173,6 +185,24 @@ impl JSONSample {
             _ => serde_json::Value::String(base64_encode(&payload.to_bytes())),
         }
     }
+
+    /// The reverse of [`JSONSample::payload_to_json`]: JSON values are serialized for the JSON
+    /// encodings, strings are taken as is for the string encodings and base64 decoded otherwise.
+    fn json_to_payload(value: &serde_json::Value, encoding: &Encoding) -> ZResult<ZBytes> {
+        match (encoding, value) {
+            (_, serde_json::Value::Null) => Ok(ZBytes::new()),
+            (&Encoding::APPLICATION_JSON | &Encoding::TEXT_JSON | &Encoding::TEXT_JSON5, _) => {
+                Ok(serde_json::to_vec(value)?.into())
+            }
+            (&Encoding::TEXT_PLAIN | &Encoding::ZENOH_STRING, serde_json::Value::String(s)) => {
+                Ok(s.clone().into())
+            }
+            (_, serde_json::Value::String(s)) => {
+                Ok(base64::engine::general_purpose::STANDARD.decode(s)?.into())
+            }
+            _ => bail!("Expected a base64 string as value for encoding {encoding}"),
+        }
+    }
 }
 
 impl From<&Sample> for JSONSample {
@@ -221,7 +251,11 @@ impl Plugin for RestPlugin {
         WORKER_THREAD_NUM.store(conf.work_thread_num, Ordering::SeqCst);
         MAX_BLOCK_THREAD_NUM.store(conf.max_block_thread_num, Ordering::SeqCst);
 
-        let task = run(runtime.clone(), conf.addr);
+        // The credentials, certificates and webhooks are loaded beforehand to report their errors
+        let auth = Auth::new(&conf)?;
+        let tls = tls_acceptor(&conf)?;
+        let webhooks = Webhooks::new(&conf.webhooks)?;
+        let task = serve(runtime.clone(), conf.clone(), auth, tls, webhooks);
         let task =
             blockon_runtime(async { timeout(Duration::from_millis(1), spawn_runtime(task)).await });
 
@@ -284,8 +318,9 @@ fn with_extended_string<R, F: FnMut(&mut String) -> R>(
     result
 }
 
-fn app(session: Session) -> Router {
-    Router::new()
+fn app(session: Session, conf: &Config, auth: Auth) -> Router {
+    let router = Router::new()
+        .route(ws::WEBSOCKET_PATH, get(ws::websocket))
         .route(
             "/{*key_expr}",
             get(subscribe_or_query)
@@ -294,7 +329,12 @@ fn app(session: Session) -> Router {
                 .patch(publish)
                 .delete(publish),
         )
-        .with_state(AppState { session })
+        .with_state(AppState {
+            session,
+            sse: Arc::new(SseCaches::new(conf.sse.clone())),
+            auth: Arc::new(auth),
+            allowed_origins: conf.cors.allowed_origins.clone().map(Arc::new),
+        })
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(DefaultMakeSpan::new().include_headers(true))
@@ -304,56 +344,56 @@ fn app(session: Session) -> Router {
                         .level(Level::TRACE)
                         .include_headers(true),
                 ),
-        )
-        .layer(
-            CorsLayer::new()
-                .allow_methods([
-                    Method::GET,
-                    Method::POST,
-                    Method::PUT,
-                    Method::PATCH,
-                    Method::DELETE,
-                ])
-                .allow_origin(Any)
-                .allow_credentials(false),
-        )
-        .layer(SetResponseHeaderLayer::overriding(
-            header::ACCESS_CONTROL_ALLOW_ORIGIN,
-            HeaderValue::from_static("*"),
-        ))
+        );
+    let cors = CorsLayer::new().allow_methods([
+        Method::GET,
+        Method::POST,
+        Method::PUT,
+        Method::PATCH,
+        Method::DELETE,
+    ]);
+    match &conf.cors.allowed_origins {
+        Some(origins) => router.layer(
+            cors.allow_origin(AllowOrigin::list(origins.iter().filter_map(|origin| {
+                match HeaderValue::from_str(origin) {
+                    Ok(origin) => Some(origin),
+                    Err(_) => {
+                        tracing::warn!("Ignoring invalid CORS origin: {origin}");
+                        None
+                    }
+                }
+            })))
+            .allow_headers([
+                header::AUTHORIZATION,
+                header::CONTENT_TYPE,
+                HeaderName::from_static("last-event-id"),
+            ]),
+        ),
+        None => router
+            .layer(cors.allow_origin(Any).allow_credentials(false))
+            .layer(SetResponseHeaderLayer::overriding(
+                header::ACCESS_CONTROL_ALLOW_ORIGIN,
+                HeaderValue::from_static("*"),
+            )),
+    }
 }
 
 #[derive(Clone)]
 struct AppState {
     session: Session,
+    sse: Arc<SseCaches>,
+    auth: Arc<Auth>,
+    // The origins allowed to open a WebSocket, as CORS does not apply to WebSocket upgrades
+    allowed_origins: Option<Arc<Vec<String>>>,
 }
 
-async fn subscribe(state: AppState, key_expr: KeyExpr<'static>) -> Response {
-    let subscriber = match state.session.declare_subscriber(&key_expr).await {
-        Ok(sub) => sub,
-        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
-    };
-    struct SubscriberStream {
-        stream: RecvStream<'static, Sample>,
-        _subscriber: Subscriber<FifoChannelHandler<Sample>>,
-    }
-    impl Stream for SubscriberStream {
-        type Item = Sample;
-
-        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-            self.stream.poll_next_unpin(cx)
-        }
+/// Returns the consolidation of a query: all the replies of time-travel queries are expected.
+fn consolidation(parameters: &Parameters) -> QueryConsolidation {
+    if parameters.time_range().is_some() {
+        QueryConsolidation::from(zenoh::query::ConsolidationMode::None)
+    } else {
+        QueryConsolidation::from(zenoh::query::ConsolidationMode::Latest)
     }
-    let stream = SubscriberStream {
-        stream: subscriber.handler().clone().into_stream(),
-        _subscriber: subscriber,
-    };
-    Sse::new(stream.map(|sample| {
-        Event::default()
-            .event(sample.kind().to_string())
-            .json_data(JSONSample::from(&sample))
-    }))
-    .into_response()
 }
 
 async fn query(
@@ -365,15 +405,10 @@ async fn query(
     body: ZBytes,
 ) -> Response {
     let parameters = Parameters::from(uri.query().unwrap_or_default());
-    let consolidation = if parameters.time_range().is_some() {
-        QueryConsolidation::from(zenoh::query::ConsolidationMode::None)
-    } else {
-        QueryConsolidation::from(zenoh::query::ConsolidationMode::Latest)
-    };
     let mut query = state
         .session
         .get(Selector::borrowed(&key_expr, &parameters))
-        .consolidation(consolidation)
+        .consolidation(consolidation(&parameters))
         .with(flume::unbounded());
     if !body.is_empty() {
         query = query.payload(body).encoding(encoding);
@@ -420,27 +455,46 @@ async fn query(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn subscribe_or_query(
     State(state): State<AppState>,
+    identity: Identity,
     accept: Accept,
     KeyExprPath(key_expr): KeyExprPath,
     EncodingHeader(encoding): EncodingHeader,
+    last_event_id: LastEventId,
     uri: Uri,
     ZBytesBody(body): ZBytesBody,
 ) -> Response {
+    let message = match accept {
+        Accept::EventStream => AclMessage::DeclareSubscriber,
+        _ => AclMessage::Query,
+    };
+    if let Err(e) = state.auth.authorize(&identity, message, &key_expr) {
+        return e.into_response();
+    }
     match accept {
-        Accept::EventStream => subscribe(state, key_expr).await,
+        Accept::EventStream => sse::subscribe(state, &identity, key_expr, last_event_id).await,
         accept => query(state, accept, key_expr, encoding, uri, body).await,
     }
 }
 
 async fn publish(
     State(state): State<AppState>,
+    identity: Identity,
     method: Method,
     KeyExprPath(key_expr): KeyExprPath,
     EncodingHeader(encoding): EncodingHeader,
     ZBytesBody(bytes): ZBytesBody,
 ) -> Response {
+    let message = if method == Method::DELETE {
+        AclMessage::Delete
+    } else {
+        AclMessage::Put
+    };
+    if let Err(e) = state.auth.authorize(&identity, message, &key_expr) {
+        return e.into_response();
+    }
     // @TODO: Define the right congestion control value
     let res = if method == Method::DELETE {
         state.session.delete(key_expr).await
@@ -453,7 +507,31 @@ async fn publish(
     }
 }
 
-pub async fn run(runtime: DynamicRuntime, addr: SocketAddr) -> ZResult<()> {
+pub async fn run(runtime: DynamicRuntime, conf: Config) -> ZResult<()> {
+    let auth = Auth::new(&conf)?;
+    let tls = tls_acceptor(&conf)?;
+    let webhooks = Webhooks::new(&conf.webhooks)?;
+    serve(runtime, conf, auth, tls, webhooks).await
+}
+
+/// Returns the acceptor of the TLS connections, if configured. A certificate is required from the
+/// clients if they authenticate with it and can't use other credentials.
+fn tls_acceptor(conf: &Config) -> ZResult<Option<TlsAcceptor>> {
+    let optional_client_certificate = conf.auth.basic.is_some() || conf.auth.bearer.is_some();
+    conf.auth
+        .tls
+        .as_ref()
+        .map(|tls| tls::acceptor(tls, optional_client_certificate))
+        .transpose()
+}
+
+async fn serve(
+    runtime: DynamicRuntime,
+    conf: Config,
+    auth: Auth,
+    tls: Option<TlsAcceptor>,
+    webhooks: Webhooks,
+) -> ZResult<()> {
     // Try to initiate login.
     // Required in case of dynamic lib, otherwise no logs.
     // But cannot be done twice in case of static link.
@@ -461,9 +539,23 @@ pub async fn run(runtime: DynamicRuntime, addr: SocketAddr) -> ZResult<()> {
 
     match tokio::try_join!(
         zenoh::session::init(runtime),
-        TcpListener::bind(addr).map_err(Into::into)
+        TcpListener::bind(conf.addr).map_err(Into::into)
     ) {
-        Ok((session, listener)) => axum::serve(listener, app(session)).await?,
+        Ok((session, listener)) => {
+            let _queryables = webhooks.declare(&session).await?;
+            let app = app(session, &conf, auth);
+            match tls {
+                Some(tls) => {
+                    let listener = TlsListener::new(listener, tls);
+                    axum::serve(
+                        listener,
+                        app.into_make_service_with_connect_info::<TlsPeer>(),
+                    )
+                    .await?
+                }
+                None => axum::serve(listener, app).await?,
+            }
+        }
         Err(err) => {
             tracing::error!("Unable to start http server for REST: {:?}", err);
             return Err(err);
@@ -472,6 +564,16 @@ pub async fn run(runtime: DynamicRuntime, addr: SocketAddr) -> ZResult<()> {
     Ok(())
 }
 
+/// Replaces the `@/local` prefix of a key expression by the admin space of the session.
+fn resolve_local(key_expr: String, session: &Session) -> String {
+    match key_expr.strip_prefix("@/local") {
+        Some(suffix) if suffix.is_empty() || suffix.starts_with('/') => {
+            format!("@/{zid}{suffix}", zid = session.zid())
+        }
+        _ => key_expr,
+    }
+}
+
 struct KeyExprPath(KeyExpr<'static>);
 
 impl FromRequestParts<AppState> for KeyExprPath {
@@ -481,15 +583,10 @@ impl FromRequestParts<AppState> for KeyExprPath {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
-        let Path(mut key_expr): Path<String> = Path::from_request_parts(parts, state)
+        let Path(key_expr): Path<String> = Path::from_request_parts(parts, state)
             .await
             .map_err(IntoResponse::into_response)?;
-        if let Some(suffix) = key_expr.strip_prefix("@/local") {
-            if suffix.is_empty() || suffix.starts_with('/') {
-                key_expr = format!("@/{zid}{suffix}", zid = state.session.zid());
-            }
-        }
-        match KeyExpr::try_from(key_expr) {
+        match KeyExpr::try_from(resolve_local(key_expr, &state.session)) {
             Ok(key_expr) => Ok(KeyExprPath(key_expr)),
             Err(err) => Err((StatusCode::BAD_REQUEST, err.to_string()).into_response()),
         }
@@ -563,19 +660,35 @@ impl FromRequest<AppState> for ZBytesBody {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::{path::PathBuf, sync::Arc, time::Duration};
 
     use axum::{
         body::{Body, Bytes},
-        http::{header, Method, Request},
+        http::{header, Method, Request, StatusCode},
+        Router,
     };
-    use futures::{FutureExt, StreamExt};
-    use tokio::time::timeout;
+    use futures::{FutureExt, SinkExt, Stream, StreamExt};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        time::timeout,
+    };
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
     use tower::ServiceExt;
-    use zenoh::{bytes::Encoding, sample::SampleKind, Session, Wait};
+    use zenoh::{
+        bytes::Encoding, internal::plugins::RunningPluginTrait, key_expr::KeyExpr,
+        sample::SampleKind, time::Timestamp, Session, Wait,
+    };
     use zenoh_test::TestSessions;
 
-    use crate::app;
+    use crate::{tls, Auth, Config, RunningPlugin, TlsListener, TlsPeer, Webhooks};
+
+    fn app(session: Session, conf: &Config) -> Router {
+        crate::app(session, conf, Auth::new(conf).unwrap())
+    }
+
+    fn config(sse: serde_json::Value) -> Config {
+        serde_json::from_value(serde_json::json!({ "http_port": 8080, "sse": sse })).unwrap()
+    }
 
     async fn setup() -> (TestSessions, Session, Session) {
         let mut test_sessions = TestSessions::new();
@@ -590,7 +703,7 @@ mod tests {
         let subscriber = sub_session.declare_subscriber("test/**").await.unwrap();
         tokio::time::sleep(Duration::from_secs(1)).await;
         for method in [Method::PUT, Method::PATCH] {
-            let response = app(pub_session.clone())
+            let response = app(pub_session.clone(), &config(serde_json::json!({})))
                 .oneshot(
                     Request::builder()
                         .method(method)
@@ -609,7 +722,7 @@ mod tests {
             assert_eq!(sample.payload().try_to_string().unwrap(), "payload");
             assert_eq!(sample.encoding(), &Encoding::TEXT_PLAIN);
         }
-        let response = app(pub_session.clone())
+        let response = app(pub_session.clone(), &config(serde_json::json!({})))
             .oneshot(
                 Request::builder()
                     .method(Method::DELETE)
@@ -631,7 +744,7 @@ mod tests {
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn subscribe() {
         let (mut test_sessions, pub_session, sub_session) = setup().await;
-        let response = app(sub_session.clone())
+        let response = app(sub_session.clone(), &config(serde_json::json!({})))
             .oneshot(
                 Request::builder()
                     .method(Method::GET)
@@ -709,7 +822,7 @@ mod tests {
             ("", "text/html; charset=utf-8", check_html),
             ("?_raw=true", "text/plain", check_raw),
         ] {
-            let response = app(get_session.clone())
+            let response = app(get_session.clone(), &config(serde_json::json!({})))
                 .oneshot(
                     Request::builder()
                         .method(Method::GET)
@@ -738,4 +851,741 @@ mod tests {
 
         test_sessions.close().await;
     }
+
+    /// Returns the id and the data of a Server-Sent Event.
+    fn parse_event(event: &[u8]) -> (Option<String>, serde_json::Value) {
+        let (mut id, mut data) = (None, serde_json::Value::Null);
+        for line in std::str::from_utf8(event).unwrap().lines() {
+            if let Some(value) = line.strip_prefix("id: ") {
+                id = Some(value.to_string());
+            } else if let Some(value) = line.strip_prefix("data: ") {
+                data = serde_json::from_str(value).unwrap();
+            }
+        }
+        (id, data)
+    }
+
+    fn sse_request(uri: &str, last_event_id: Option<&str>) -> Request<Body> {
+        let mut request = Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .header(header::ACCEPT, "text/event-stream");
+        if let Some(last_event_id) = last_event_id {
+            request = request.header("Last-Event-ID", last_event_id);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn subscribe_resume_from_cache() {
+        let (mut test_sessions, pub_session, sub_session) = setup().await;
+        let app = app(
+            sub_session.clone(),
+            &config(serde_json::json!({ "cache_size": 10 })),
+        );
+        let put = |i: usize| {
+            pub_session
+                .put("test/resume", format!("payload {i}"))
+                .encoding(Encoding::TEXT_PLAIN)
+                .timestamp(pub_session.new_timestamp())
+        };
+
+        let response = app
+            .clone()
+            .oneshot(sse_request("/test/resume", None))
+            .await
+            .unwrap();
+        let mut stream = response.into_body().into_data_stream();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        put(0).await.unwrap();
+        let event = timeout(Duration::from_secs(1), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let (id, data) = parse_event(&event);
+        let id = id.unwrap();
+        assert_eq!(data["value"], "payload 0");
+        assert_eq!(data["timestamp"], id);
+
+        // Samples published while the client is disconnected are kept in the cache
+        drop(stream);
+        for i in 1..3 {
+            put(i).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let response = app
+            .clone()
+            .oneshot(sse_request("/test/resume", Some(&id)))
+            .await
+            .unwrap();
+        let mut stream = response.into_body().into_data_stream();
+        for i in 1..4 {
+            if i == 3 {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                put(i).await.unwrap();
+            }
+            let event = timeout(Duration::from_secs(1), stream.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert_eq!(parse_event(&event).1["value"], format!("payload {i}"));
+        }
+
+        test_sessions.close().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn subscribe_resume_from_query() {
+        let (mut test_sessions, sub_session, queryable_session) = setup().await;
+        let timestamps = (0..3)
+            .map(|_| queryable_session.new_timestamp())
+            .collect::<Vec<Timestamp>>();
+        let _queryable = queryable_session
+            .declare_queryable("test/**")
+            .callback({
+                let timestamps = timestamps.clone();
+                move |q| {
+                    for (i, timestamp) in timestamps.iter().enumerate() {
+                        q.reply("test/resume", format!("payload {i}"))
+                            .encoding(Encoding::TEXT_PLAIN)
+                            .timestamp(*timestamp)
+                            .wait()
+                            .unwrap();
+                    }
+                }
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let response = app(sub_session.clone(), &config(serde_json::json!({})))
+            .oneshot(sse_request(
+                "/test/resume",
+                Some(&timestamps[0].to_string()),
+            ))
+            .await
+            .unwrap();
+        let mut stream = response.into_body().into_data_stream();
+        for (i, timestamp) in timestamps.iter().enumerate().skip(1) {
+            let event = timeout(Duration::from_secs(1), stream.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            let (id, data) = parse_event(&event);
+            assert_eq!(id, Some(timestamp.to_string()));
+            assert_eq!(data["value"], format!("payload {i}"));
+        }
+
+        // The missed samples are not queried for a client not allowed to query
+        let conf: Config = serde_json::from_value(serde_json::json!({
+            "http_port": 8080,
+            "access_control": {
+                "default_permission": "deny",
+                "rules": [{
+                    "permission": "allow",
+                    "messages": ["declare_subscriber"],
+                    "key_exprs": ["test/**"],
+                }],
+            },
+        }))
+        .unwrap();
+        let response = app(sub_session.clone(), &conf)
+            .oneshot(sse_request(
+                "/test/resume",
+                Some(&timestamps[0].to_string()),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut stream = response.into_body().into_data_stream();
+        assert!(timeout(Duration::from_secs(1), stream.next())
+            .await
+            .is_err());
+
+        test_sessions.close().await;
+    }
+
+    #[test]
+    fn adminspace_hides_secrets() {
+        let plugin = RunningPlugin(
+            serde_json::from_value(serde_json::json!({
+                "http_port": 8080,
+                "auth": {"bearer": {"tokens": [{"token": "secret-token", "username": "alice"}]}},
+                "webhooks": [{
+                    "key_expr": "demo/**",
+                    "url": "http://localhost:9000",
+                    "headers": {"Authorization": "Bearer secret-header"},
+                }],
+            }))
+            .unwrap(),
+        );
+        let status_key = "@/0123456789abcdef/router/plugins/rest";
+        let key_expr = KeyExpr::new(format!("{status_key}/**")).unwrap();
+        let responses = plugin.adminspace_getter(&key_expr, status_key).unwrap();
+        let port = responses
+            .iter()
+            .find(|r| r.key == format!("{status_key}/port"))
+            .map(|r| serde_json::Value::from(&r.value))
+            .unwrap();
+        assert_eq!(port["auth"]["bearer"]["tokens"][0]["username"], "alice");
+        assert!(!port.to_string().contains("secret-token"));
+        assert_eq!(port["webhooks"][0]["key_expr"], "demo/**");
+        assert!(!port.to_string().contains("secret-header"));
+    }
+
+    /// Returns a WebSocket request, encoded in JSON or CBOR.
+    fn ws_request(request: &serde_json::Value, cbor: bool) -> WsMessage {
+        if cbor {
+            let mut bytes = Vec::new();
+            ciborium::into_writer(request, &mut bytes).unwrap();
+            WsMessage::Binary(bytes)
+        } else {
+            WsMessage::Text(request.to_string())
+        }
+    }
+
+    /// Returns the next answer received on a WebSocket, decoded from JSON or CBOR.
+    async fn ws_answer<S>(ws_rx: &mut S) -> serde_json::Value
+    where
+        S: Stream<Item = Result<WsMessage, tokio_tungstenite::tungstenite::Error>> + Unpin,
+    {
+        match timeout(Duration::from_secs(5), ws_rx.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap()
+        {
+            WsMessage::Text(text) => serde_json::from_str(&text).unwrap(),
+            WsMessage::Binary(bytes) => ciborium::from_reader(&bytes[..]).unwrap(),
+            message => panic!("Unexpected message {message:?}"),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn websocket() {
+        let (mut test_sessions, ws_session, session) = setup().await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = app(ws_session.clone(), &config(serde_json::json!({})));
+        let server = tokio::spawn(async move { axum::serve(listener, app).await });
+        let (ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/@ws"))
+            .await
+            .unwrap();
+        let (mut ws_tx, mut ws_rx) = ws.split();
+        for (request, cbor) in [
+            (
+                serde_json::json!({ "op": "subscribe", "id": 1, "key_expr": "test/ws/**" }),
+                false,
+            ),
+            (
+                serde_json::json!({ "op": "delete", "id": 2, "key_expr": "test/pub" }),
+                false,
+            ),
+            (serde_json::json!({ "op": "unsubscribe", "id": 3 }), true),
+            (serde_json::json!({ "op": "publish" }), false),
+        ] {
+            ws_tx.send(ws_request(&request, cbor)).await.unwrap();
+        }
+        assert_eq!(
+            ws_answer(&mut ws_rx).await,
+            serde_json::json!({ "op": "ok", "id": 1 })
+        );
+        assert_eq!(
+            ws_answer(&mut ws_rx).await,
+            serde_json::json!({ "op": "ok", "id": 2 })
+        );
+        let answer = ws_answer(&mut ws_rx).await;
+        assert_eq!((&answer["op"], &answer["id"]), (&"error".into(), &3.into()));
+        assert!(answer["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Unknown subscriber 3"));
+        assert_eq!(ws_answer(&mut ws_rx).await["op"], "error");
+
+        session
+            .put("test/ws/a", "hello")
+            .encoding(Encoding::TEXT_PLAIN)
+            .await
+            .unwrap();
+        assert_eq!(
+            ws_answer(&mut ws_rx).await,
+            serde_json::json!({
+                "op": "sample",
+                "id": 1,
+                "kind": "PUT",
+                "sample": {
+                    "key": "test/ws/a",
+                    "value": "hello",
+                    "encoding": "text/plain",
+                    "timestamp": null,
+                },
+            })
+        );
+
+        let subscriber = session.declare_subscriber("test/pub").await.unwrap();
+        let _queryable = session
+            .declare_queryable("test/query")
+            .callback(|q| {
+                q.reply(q.key_expr(), "reply")
+                    .encoding(Encoding::TEXT_PLAIN)
+                    .wait()
+                    .unwrap()
+            })
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        for request in [
+            serde_json::json!({ "op": "put", "key_expr": "test/pub", "value": { "a": 1 } }),
+            serde_json::json!({ "op": "get", "id": 4, "selector": "test/query?p=1" }),
+        ] {
+            ws_tx.send(ws_request(&request, true)).await.unwrap();
+        }
+        let sample = timeout(Duration::from_secs(5), subscriber.recv_async())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sample.encoding(), &Encoding::APPLICATION_JSON);
+        assert_eq!(sample.payload().try_to_string().unwrap(), r#"{"a":1}"#);
+        assert_eq!(
+            ws_answer(&mut ws_rx).await,
+            serde_json::json!({
+                "op": "reply",
+                "id": 4,
+                "sample": {
+                    "key": "test/query",
+                    "value": "reply",
+                    "encoding": "text/plain",
+                    "timestamp": null,
+                },
+            })
+        );
+        assert_eq!(
+            ws_answer(&mut ws_rx).await,
+            serde_json::json!({ "op": "done", "id": 4 })
+        );
+
+        server.abort();
+        test_sessions.close().await;
+    }
+
+    /// Returns a directory for the files of a test.
+    fn test_dir(test: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("zenoh-plugin-rest-{test}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn auth_request(method: Method, uri: &str, authorization: Option<&str>) -> Request<Body> {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn authentication_and_access_control() {
+        let (mut test_sessions, session, _) = setup().await;
+        let dictionary_file = test_dir("auth").join("credentials.txt");
+        std::fs::write(&dictionary_file, "alice:secret\n\nbob : password\n").unwrap();
+        let conf: Config = serde_json::from_value(serde_json::json!({
+            "http_port": 8080,
+            "auth": {
+                "basic": { "dictionary_file": dictionary_file },
+                "bearer": { "tokens": [{ "token": "t0k3n", "username": "bob" }] },
+            },
+            "access_control": {
+                "default_permission": "deny",
+                "rules": [
+                    {
+                        "permission": "allow",
+                        "messages": ["put", "query"],
+                        "key_exprs": ["test/auth/**"],
+                    },
+                    {
+                        "permission": "deny",
+                        "messages": ["put"],
+                        "key_exprs": ["test/auth/alice/**"],
+                        "usernames": ["bob"],
+                    },
+                ],
+            },
+        }))
+        .unwrap();
+        let app = app(session.clone(), &conf);
+
+        // "YWxpY2U6c2VjcmV0" is "alice:secret", "Ym9iOnNlY3JldA==" is "bob:secret"
+        let alice = Some("Basic YWxpY2U6c2VjcmV0");
+        let bob = Some("Bearer t0k3n");
+        for (method, uri, authorization, status) in [
+            (
+                Method::PUT,
+                "/test/auth/alice/a",
+                None,
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                Method::PUT,
+                "/test/auth/alice/a",
+                Some("Basic Ym9iOnNlY3JldA=="),
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                Method::PUT,
+                "/test/auth/alice/a",
+                Some("Bearer unknown"),
+                StatusCode::UNAUTHORIZED,
+            ),
+            (Method::PUT, "/test/auth/alice/a", alice, StatusCode::OK),
+            (Method::GET, "/test/auth/**", alice, StatusCode::OK),
+            (
+                Method::DELETE,
+                "/test/auth/alice/a",
+                alice,
+                StatusCode::FORBIDDEN,
+            ),
+            (Method::PUT, "/test/other", alice, StatusCode::FORBIDDEN),
+            (
+                Method::PUT,
+                "/test/auth/alice/a",
+                bob,
+                StatusCode::FORBIDDEN,
+            ),
+            (Method::PUT, "/test/auth/bob/a", bob, StatusCode::OK),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(auth_request(method.clone(), uri, authorization))
+                .await
+                .unwrap();
+            assert_eq!(
+                response.status(),
+                status,
+                "{method} {uri} {authorization:?}"
+            );
+            if status == StatusCode::UNAUTHORIZED {
+                let challenges = response
+                    .headers()
+                    .get_all(header::WWW_AUTHENTICATE)
+                    .iter()
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    challenges,
+                    ["Basic realm=\"zenoh\"", "Bearer realm=\"zenoh\""]
+                );
+            }
+        }
+
+        test_sessions.close().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn cors_allowed_origins() {
+        let (mut test_sessions, session, _) = setup().await;
+        let conf: Config = serde_json::from_value(serde_json::json!({
+            "http_port": 8080,
+            "cors": { "allowed_origins": ["https://example.com"] },
+        }))
+        .unwrap();
+        let app = app(session.clone(), &conf);
+        for (origin, allowed) in [("https://example.com", true), ("https://other.com", false)] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(Method::OPTIONS)
+                        .uri("/test/cors")
+                        .header(header::ORIGIN, origin)
+                        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "PUT")
+                        .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let allowed_origin = response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN);
+            if allowed {
+                assert_eq!(allowed_origin.unwrap(), origin);
+            } else {
+                assert!(allowed_origin.is_none());
+            }
+
+            // WebSocket upgrades are not covered by CORS: their origin is checked by the plugin
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(Method::GET)
+                        .uri(crate::ws::WEBSOCKET_PATH)
+                        .header(header::ORIGIN, origin)
+                        .header(header::CONNECTION, "upgrade")
+                        .header(header::UPGRADE, "websocket")
+                        .header(header::SEC_WEBSOCKET_VERSION, "13")
+                        .header(header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ==")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                response.status() == StatusCode::FORBIDDEN,
+                !allowed,
+                "WebSocket upgrade from {origin}: {}",
+                response.status()
+            );
+        }
+
+        test_sessions.close().await;
+    }
+
+    /// Sends a PUT request through a TLS connection, returning the status line of the response.
+    async fn tls_put(
+        addr: std::net::SocketAddr,
+        client_config: rustls::ClientConfig,
+        key_expr: &str,
+    ) -> std::io::Result<String> {
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+        let stream = tokio::net::TcpStream::connect(addr).await?;
+        let mut stream = connector
+            .connect("localhost".try_into().unwrap(), stream)
+            .await?;
+        stream
+            .write_all(
+                format!(
+                    "PUT /{key_expr} HTTP/1.1\r\nHost: localhost\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                )
+                .as_bytes(),
+            )
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response.lines().next().unwrap_or_default().to_string())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn mutual_tls() {
+        use rcgen::{
+            BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, Issuer,
+            KeyPair,
+        };
+        use rustls::{
+            crypto::ring::default_provider,
+            pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer},
+            RootCertStore,
+        };
+
+        let (mut test_sessions, session, _) = setup().await;
+        let dir = test_dir("mtls");
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+        let issuer = Issuer::new(ca_params, ca_key);
+        let certificate = |name: &str, purpose| {
+            let key = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::new(vec![name.to_string()]).unwrap();
+            params.distinguished_name.push(DnType::CommonName, name);
+            params.extended_key_usages = vec![purpose];
+            (params.signed_by(&key, &issuer).unwrap(), key)
+        };
+        let (server, server_key) = certificate("localhost", ExtendedKeyUsagePurpose::ServerAuth);
+        let (client, client_key) = certificate("client", ExtendedKeyUsagePurpose::ClientAuth);
+        for (file, pem) in [
+            ("ca.pem", ca.pem()),
+            ("server.pem", server.pem()),
+            ("server_key.pem", server_key.serialize_pem()),
+        ] {
+            std::fs::write(dir.join(file), pem).unwrap();
+        }
+
+        let conf: Config = serde_json::from_value(serde_json::json!({
+            "http_port": 8080,
+            "auth": {
+                "tls": {
+                    "server_certificate": dir.join("server.pem"),
+                    "server_private_key": dir.join("server_key.pem"),
+                    "root_ca_certificate": dir.join("ca.pem"),
+                },
+            },
+            "access_control": {
+                "default_permission": "deny",
+                "rules": [{
+                    "permission": "allow",
+                    "messages": ["put"],
+                    "key_exprs": ["test/tls/**"],
+                    "cert_common_names": ["client"],
+                }],
+            },
+        }))
+        .unwrap();
+        let acceptor = tls::acceptor(conf.auth.tls.as_ref().unwrap(), false).unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = app(session.clone(), &conf);
+        let server = tokio::spawn(async move {
+            axum::serve(
+                TlsListener::new(listener, acceptor),
+                app.into_make_service_with_connect_info::<TlsPeer>(),
+            )
+            .await
+        });
+
+        let mut roots = RootCertStore::empty();
+        roots.add(ca.der().clone()).unwrap();
+        let client_config = |with_certificate: bool| {
+            let builder = rustls::ClientConfig::builder_with_provider(Arc::new(default_provider()))
+                .with_safe_default_protocol_versions()
+                .unwrap()
+                .with_root_certificates(roots.clone());
+            if with_certificate {
+                builder
+                    .with_client_auth_cert(
+                        vec![client.der().clone()],
+                        PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(client_key.serialize_der())),
+                    )
+                    .unwrap()
+            } else {
+                builder.with_no_client_auth()
+            }
+        };
+
+        assert_eq!(
+            tls_put(addr, client_config(true), "test/tls/a")
+                .await
+                .unwrap(),
+            "HTTP/1.1 200 OK"
+        );
+        assert_eq!(
+            tls_put(addr, client_config(true), "test/other")
+                .await
+                .unwrap(),
+            "HTTP/1.1 403 Forbidden"
+        );
+        // The client certificate is required
+        assert!(!tls_put(addr, client_config(false), "test/tls/a")
+            .await
+            .is_ok_and(|status| status.starts_with("HTTP/1.1")));
+
+        server.abort();
+        test_sessions.close().await;
+    }
+
+    /// The HTTP service of the webhook test, answering depending on the last chunk of the key
+    /// expression.
+    async fn webhook_service(
+        axum::extract::Path(path): axum::extract::Path<String>,
+        uri: axum::http::Uri,
+        headers: axum::http::HeaderMap,
+        body: Bytes,
+    ) -> axum::response::Response {
+        use axum::response::IntoResponse;
+
+        assert_eq!(headers["zenoh-key-expr"], path);
+        assert_eq!(headers["x-service-token"], "secret");
+        match path.rsplit('/').next().unwrap() {
+            "ok" => (
+                [(header::CONTENT_TYPE, "text/plain")],
+                format!("parameters {}", uri.query().unwrap_or_default()),
+            )
+                .into_response(),
+            "echo" => (
+                [(header::CONTENT_TYPE, headers[header::CONTENT_TYPE].clone())],
+                body,
+            )
+                .into_response(),
+            "**" => ([("zenoh-key-expr", "test/webhook/a")], "wildcard").into_response(),
+            "missing" => StatusCode::NOT_FOUND.into_response(),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "failure").into_response(),
+        }
+    }
+
+    /// Returns the replies to a query, as `(key, payload, encoding)` or the error payload.
+    async fn webhook_get(
+        session: &Session,
+        selector: &str,
+        payload: Option<&str>,
+    ) -> Vec<Result<(String, String, Encoding), String>> {
+        let mut get = session.get(selector);
+        if let Some(payload) = payload {
+            get = get
+                .payload(payload.to_string())
+                .encoding(Encoding::APPLICATION_JSON);
+        }
+        get.await
+            .unwrap()
+            .into_iter()
+            .map(|reply| match reply.into_result() {
+                Ok(sample) => Ok((
+                    sample.key_expr().to_string(),
+                    sample.payload().try_to_string().unwrap().into_owned(),
+                    sample.encoding().clone(),
+                )),
+                Err(err) => Err(err.payload().try_to_string().unwrap().into_owned()),
+            })
+            .collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn webhook_queryables() {
+        let (mut test_sessions, webhook_session, session) = setup().await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let service = Router::new().route(
+            "/svc/{*path}",
+            axum::routing::get(webhook_service).post(webhook_service),
+        );
+        let server = tokio::spawn(async move { axum::serve(listener, service).await });
+
+        let conf: Config = serde_json::from_value(serde_json::json!({
+            "http_port": 8080,
+            "webhooks": [{
+                "key_expr": "test/webhook/**",
+                "url": format!("http://{addr}/svc/"),
+                "headers": { "X-Service-Token": "secret" },
+            }],
+        }))
+        .unwrap();
+        let webhooks = Webhooks::new(&conf.webhooks).unwrap();
+        let _queryables = webhooks.declare(&webhook_session).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let get = |selector, payload| webhook_get(&session, selector, payload);
+        assert_eq!(
+            get("test/webhook/ok?a=1;b=2", None).await,
+            [Ok((
+                "test/webhook/ok".to_string(),
+                "parameters a=1;b=2".to_string(),
+                Encoding::TEXT_PLAIN
+            ))]
+        );
+        assert_eq!(
+            get("test/webhook/echo", Some(r#"{"a":1}"#)).await,
+            [Ok((
+                "test/webhook/echo".to_string(),
+                r#"{"a":1}"#.to_string(),
+                Encoding::APPLICATION_JSON
+            ))]
+        );
+        assert_eq!(
+            get("test/webhook/**", None).await,
+            [Ok((
+                "test/webhook/a".to_string(),
+                "wildcard".to_string(),
+                Encoding::from("text/plain; charset=utf-8")
+            ))]
+        );
+        assert_eq!(get("test/webhook/missing", None).await, []);
+        assert_eq!(
+            get("test/webhook/fail", None).await,
+            [Err("failure".to_string())]
+        );
+
+        server.abort();
+        test_sessions.close().await;
+    }
 }
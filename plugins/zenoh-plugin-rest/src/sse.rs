@@ -0,0 +1,362 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The subscriptions through Server-Sent Events.
+//!
+//! The id of the events is the timestamp of their sample, such that a client reconnecting with
+//! the `Last-Event-ID` header gets the samples it missed: from the cache of the key expression if
+//! it still holds the last event received by the client, otherwise by querying the storages and
+//! the publication caches for the samples published since.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::Infallible,
+    pin::Pin,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+    response::{sse::Event, IntoResponse, Response, Sse},
+};
+use futures::{Stream, StreamExt};
+use zenoh::{
+    handlers::{fifo::RecvStream, FifoChannelHandler},
+    key_expr::{KeyExpr, OwnedKeyExpr},
+    pubsub::Subscriber,
+    query::{
+        ConsolidationMode, Parameters, Selector, TimeBound, TimeExpr, TimeRange, ZenohParameters,
+    },
+    sample::Sample,
+    session::Session,
+    time::Timestamp,
+    KE_ADV_PREFIX, KE_PUB, KE_STARSTAR,
+};
+
+use crate::{
+    auth::Identity,
+    config::{AclMessage, SseConfig},
+    spawn_runtime, AppState, JSONSample,
+};
+
+const LAST_EVENT_ID: &str = "Last-Event-ID";
+
+/// The caches of the samples received on the key expressions subscribed through Server-Sent
+/// Events.
+///
+/// The cache of a key expression is created by its first subscription, and is kept for
+/// `cache_linger` seconds after its last subscription ended, so that the clients reconnecting
+/// meanwhile can resume their subscription from it.
+pub(crate) struct SseCaches {
+    config: SseConfig,
+    caches: Mutex<HashMap<OwnedKeyExpr, CacheEntry>>,
+}
+
+struct CacheEntry {
+    cache: Arc<SampleCache>,
+    subscriptions: usize,
+    released_at: Instant,
+}
+
+/// The last `cache_size` samples received on a key expression.
+struct SampleCache {
+    samples: Arc<Mutex<VecDeque<Sample>>>,
+    _subscriber: Subscriber<()>,
+}
+
+impl SampleCache {
+    /// Returns the samples received after the one with the provided timestamp, or `None` if this
+    /// sample is no longer (or not yet) in the cache.
+    fn samples_after(&self, last: &Timestamp) -> Option<Vec<Sample>> {
+        let samples = self.samples.lock().unwrap();
+        let position = samples
+            .iter()
+            .position(|sample| sample.timestamp() == Some(last))?;
+        Some(
+            samples
+                .iter()
+                .skip(position + 1)
+                .filter(|sample| sample.timestamp().is_some())
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+/// Registers a subscription on the cache of a key expression, for as long as it is alive.
+struct CacheSubscription {
+    caches: Arc<SseCaches>,
+    key_expr: OwnedKeyExpr,
+    cache: Arc<SampleCache>,
+}
+
+impl Drop for CacheSubscription {
+    fn drop(&mut self) {
+        let mut caches = self.caches.caches.lock().unwrap();
+        let Some(entry) = caches.get_mut(&self.key_expr) else {
+            return;
+        };
+        entry.subscriptions -= 1;
+        entry.released_at = Instant::now();
+        if entry.subscriptions > 0 {
+            return;
+        }
+
+        let caches = self.caches.clone();
+        let key_expr = self.key_expr.clone();
+        let linger = Duration::from_secs(caches.config.cache_linger);
+        spawn_runtime(async move {
+            tokio::time::sleep(linger).await;
+            let mut caches = caches.caches.lock().unwrap();
+            if caches.get(&key_expr).is_some_and(|entry| {
+                entry.subscriptions == 0 && entry.released_at.elapsed() >= linger
+            }) {
+                tracing::debug!("Dropping the SSE cache of {key_expr}");
+                caches.remove(&key_expr);
+            }
+        });
+    }
+}
+
+impl SseCaches {
+    pub(crate) fn new(config: SseConfig) -> Self {
+        SseCaches {
+            config,
+            caches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cache of the provided key expression, creating it if needed, or `None` if the
+    /// cache is disabled.
+    async fn subscribe(
+        self: &Arc<Self>,
+        session: &Session,
+        key_expr: &KeyExpr<'static>,
+    ) -> zenoh::Result<Option<CacheSubscription>> {
+        if self.config.cache_size == 0 {
+            return Ok(None);
+        }
+        let key_expr = OwnedKeyExpr::from(key_expr.clone());
+        if let Some(cache) = self.register(&key_expr, None) {
+            return Ok(Some(cache));
+        }
+
+        let samples = Arc::new(Mutex::new(VecDeque::new()));
+        let subscriber = session
+            .declare_subscriber(&key_expr)
+            .callback({
+                let samples = samples.clone();
+                let cache_size = self.config.cache_size;
+                move |sample| {
+                    let mut samples = samples.lock().unwrap();
+                    if samples.len() == cache_size {
+                        samples.pop_front();
+                    }
+                    samples.push_back(sample);
+                }
+            })
+            .await?;
+        let cache = Arc::new(SampleCache {
+            samples,
+            _subscriber: subscriber,
+        });
+        Ok(self.register(&key_expr, Some(cache)))
+    }
+
+    /// Registers a subscription on the cache of `key_expr`, inserting the provided `cache` if
+    /// there is none.
+    fn register(
+        self: &Arc<Self>,
+        key_expr: &OwnedKeyExpr,
+        cache: Option<Arc<SampleCache>>,
+    ) -> Option<CacheSubscription> {
+        let mut caches = self.caches.lock().unwrap();
+        let entry = match cache {
+            Some(cache) => caches.entry(key_expr.clone()).or_insert(CacheEntry {
+                cache,
+                subscriptions: 0,
+                released_at: Instant::now(),
+            }),
+            None => caches.get_mut(key_expr)?,
+        };
+        entry.subscriptions += 1;
+        Some(CacheSubscription {
+            caches: self.clone(),
+            key_expr: key_expr.clone(),
+            cache: entry.cache.clone(),
+        })
+    }
+}
+
+/// The `Last-Event-ID` header sent by a client resuming its subscription.
+pub(crate) struct LastEventId(Option<Timestamp>);
+
+impl FromRequestParts<AppState> for LastEventId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let last_event_id = parts.headers.get(LAST_EVENT_ID).and_then(|id| {
+            let timestamp = id.to_str().ok().and_then(|id| Timestamp::from_str(id).ok());
+            if timestamp.is_none() {
+                tracing::warn!("Ignoring invalid {LAST_EVENT_ID} header: {id:?}");
+            }
+            timestamp
+        });
+        Ok(LastEventId(last_event_id))
+    }
+}
+
+/// The samples missed by a resuming client, followed by the samples received by its subscriber.
+struct SubscriptionStream {
+    missed: std::vec::IntoIter<Sample>,
+    replayed: HashSet<Timestamp>,
+    stream: RecvStream<'static, Sample>,
+    _subscriber: Subscriber<FifoChannelHandler<Sample>>,
+    _cache: Option<CacheSubscription>,
+}
+
+impl Stream for SubscriptionStream {
+    type Item = Sample;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(sample) = self.missed.next() {
+            return Poll::Ready(Some(sample));
+        }
+        loop {
+            match self.stream.poll_next_unpin(cx) {
+                // The samples received while the missed ones were retrieved are replayed already
+                Poll::Ready(Some(sample))
+                    if sample
+                        .timestamp()
+                        .is_some_and(|timestamp| self.replayed.contains(timestamp)) => {}
+                poll => return poll,
+            }
+        }
+    }
+}
+
+pub(crate) async fn subscribe(
+    state: AppState,
+    identity: &Identity,
+    key_expr: KeyExpr<'static>,
+    LastEventId(last_event_id): LastEventId,
+) -> Response {
+    let subscriber = match state.session.declare_subscriber(&key_expr).await {
+        Ok(sub) => sub,
+        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    };
+    let cache = match state.sse.subscribe(&state.session, &key_expr).await {
+        Ok(cache) => cache,
+        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    };
+
+    let mut missed = Vec::new();
+    if let Some(last_event_id) = &last_event_id {
+        match cache
+            .as_ref()
+            .and_then(|cache| cache.cache.samples_after(last_event_id))
+        {
+            Some(samples) => missed = samples,
+            // Querying the missed samples requires the permission to query
+            None if state.sse.config.resume_query
+                && state
+                    .auth
+                    .is_allowed(identity, AclMessage::Query, &key_expr) =>
+            {
+                missed = query_missed(&state.session, &key_expr, last_event_id).await
+            }
+            None => tracing::debug!(
+                "Samples published on {key_expr} since {last_event_id} are not available"
+            ),
+        }
+        tracing::debug!(
+            "Resuming subscription on {key_expr} from {last_event_id}: {} samples missed",
+            missed.len()
+        );
+    }
+
+    let stream = SubscriptionStream {
+        replayed: missed
+            .iter()
+            .filter_map(Sample::timestamp)
+            .copied()
+            .collect(),
+        missed: missed.into_iter(),
+        stream: subscriber.handler().clone().into_stream(),
+        _subscriber: subscriber,
+        _cache: cache,
+    };
+    Sse::new(stream.map(|sample| {
+        let event = Event::default().event(sample.kind().to_string());
+        let event = match sample.timestamp() {
+            Some(timestamp) => event.id(timestamp.to_string()),
+            None => event,
+        };
+        event.json_data(JSONSample::from(&sample))
+    }))
+    .into_response()
+}
+
+/// Queries the samples published on `key_expr` after `last`, to the storages and to the caches of
+/// the advanced publishers, sorted by timestamp.
+async fn query_missed(
+    session: &Session,
+    key_expr: &KeyExpr<'static>,
+    last: &Timestamp,
+) -> Vec<Sample> {
+    let mut parameters = Parameters::empty();
+    parameters.set_time_range(TimeRange {
+        start: TimeBound::Inclusive(TimeExpr::Fixed(last.get_time().to_system_time())),
+        end: TimeBound::Unbounded,
+    });
+    let advanced_publishers = key_expr / KE_ADV_PREFIX / KE_PUB / KE_STARSTAR;
+
+    let mut missed = Vec::new();
+    for key_expr in [key_expr, &advanced_publishers] {
+        let replies = match session
+            .get(Selector::borrowed(key_expr, &parameters))
+            .consolidation(ConsolidationMode::None)
+            .with(flume::unbounded())
+            .await
+        {
+            Ok(replies) => replies,
+            Err(e) => {
+                tracing::warn!("Failed to query the samples missed on {key_expr}: {e}");
+                continue;
+            }
+        };
+        while let Ok(reply) = replies.recv_async().await {
+            match reply.into_result() {
+                Ok(sample) if sample.timestamp().is_some_and(|timestamp| timestamp > last) => {
+                    missed.push(sample)
+                }
+                Ok(_) => {}
+                Err(err) => tracing::debug!(
+                    "Error reply querying the samples missed on {key_expr}: {}",
+                    err.payload().try_to_string().unwrap_or_default()
+                ),
+            }
+        }
+    }
+    missed.sort_by(|a, b| a.timestamp().cmp(&b.timestamp()));
+    missed.dedup_by(|a, b| a.timestamp() == b.timestamp());
+    missed
+}
@@ -0,0 +1,217 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The TLS listener, through which the clients may authenticate with their certificate (mTLS).
+
+use std::{fs::File, io::BufReader, net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{
+    extract::connect_info::Connected,
+    serve::{IncomingStream, Listener},
+};
+use rustls::{
+    crypto::ring::default_provider,
+    pki_types::{CertificateDer, PrivateKeyDer},
+    server::WebPkiClientVerifier,
+    RootCertStore, ServerConfig,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, Semaphore},
+    task::JoinHandle,
+};
+use tokio_rustls::{server::TlsStream, TlsAcceptor};
+use x509_parser::prelude::{FromDer, X509Certificate};
+use zenoh::{internal::zerror, Result as ZResult};
+
+use crate::{config::TlsConfig, spawn_runtime};
+
+/// The maximum duration of a TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The number of established connections waiting to be served.
+const ACCEPT_QUEUE_SIZE: usize = 64;
+
+/// The maximum number of concurrent TLS handshakes. Beyond, the incoming connections wait in the
+/// backlog of the TCP listener.
+const MAX_CONCURRENT_HANDSHAKES: usize = 128;
+
+/// Returns the acceptor of the TLS connections, requiring a certificate from the clients if a
+/// `root_ca_certificate` is configured, unless `optional_client_certificate`.
+pub(crate) fn acceptor(
+    conf: &TlsConfig,
+    optional_client_certificate: bool,
+) -> ZResult<TlsAcceptor> {
+    let provider = Arc::new(default_provider());
+    let certificates = load_certificates(&conf.server_certificate)?;
+    let private_key = load_private_key(&conf.server_private_key)?;
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+    let builder = match &conf.root_ca_certificate {
+        Some(root_ca_certificate) => {
+            let mut roots = RootCertStore::empty();
+            roots.add_parsable_certificates(load_certificates(root_ca_certificate)?);
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = if optional_client_certificate {
+                verifier.allow_unauthenticated()
+            } else {
+                verifier
+            };
+            builder.with_client_cert_verifier(verifier.build()?)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let server_config = builder.with_single_cert(certificates, private_key)?;
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+fn load_certificates(file: &str) -> ZResult<Vec<CertificateDer<'static>>> {
+    let mut pem = BufReader::new(
+        File::open(file).map_err(|e| zerror!("Invalid TLS certificate file {file}: {e}"))?,
+    );
+    let certificates = rustls_pemfile::certs(&mut pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| zerror!("Invalid TLS certificate file {file}: {e}"))?;
+    if certificates.is_empty() {
+        return Err(zerror!("No certificate found in TLS certificate file {file}").into());
+    }
+    Ok(certificates)
+}
+
+fn load_private_key(file: &str) -> ZResult<PrivateKeyDer<'static>> {
+    let mut pem = BufReader::new(
+        File::open(file).map_err(|e| zerror!("Invalid TLS private key file {file}: {e}"))?,
+    );
+    rustls_pemfile::private_key(&mut pem)
+        .map_err(|e| zerror!("Invalid TLS private key file {file}: {e}"))?
+        .ok_or_else(|| zerror!("No private key found in TLS private key file {file}").into())
+}
+
+/// A client connected through TLS, identified by the common name of its certificate if any.
+#[derive(Clone, Debug)]
+pub(crate) struct TlsPeer {
+    pub(crate) cert_common_name: Option<String>,
+}
+
+impl Connected<IncomingStream<'_, TlsListener>> for TlsPeer {
+    fn connect_info(stream: IncomingStream<'_, TlsListener>) -> Self {
+        stream.remote_addr().clone()
+    }
+}
+
+/// A listener accepting TLS connections.
+///
+/// The handshakes are performed concurrently, so that a slow client doesn't delay the others, up
+/// to [`MAX_CONCURRENT_HANDSHAKES`].
+pub(crate) struct TlsListener {
+    connections: mpsc::Receiver<(TlsStream<TcpStream>, TlsPeer)>,
+    accept_task: JoinHandle<()>,
+}
+
+impl TlsListener {
+    pub(crate) fn new(listener: TcpListener, acceptor: TlsAcceptor) -> Self {
+        let (tx, connections) = mpsc::channel(ACCEPT_QUEUE_SIZE);
+        let handshakes = Arc::new(Semaphore::new(MAX_CONCURRENT_HANDSHAKES));
+        let accept_task = spawn_runtime(async move {
+            loop {
+                // The semaphore is never closed
+                let Ok(permit) = handshakes.clone().acquire_owned().await else {
+                    return;
+                };
+                let (stream, addr) = match listener.accept().await {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        tracing::warn!("Failed to accept a TLS connection: {e}");
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        continue;
+                    }
+                };
+                let acceptor = acceptor.clone();
+                let tx = tx.clone();
+                spawn_runtime(async move {
+                    let connection = handshake(&acceptor, stream, addr).await;
+                    drop(permit);
+                    if let Some(connection) = connection {
+                        let _ = tx.send(connection).await;
+                    }
+                });
+            }
+        });
+        TlsListener {
+            connections,
+            accept_task,
+        }
+    }
+}
+
+impl Drop for TlsListener {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+impl Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = TlsPeer;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.connections.recv().await {
+            Some(connection) => connection,
+            // The accept task is only stopped with the listener
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        Ok(TlsPeer {
+            cert_common_name: None,
+        })
+    }
+}
+
+async fn handshake(
+    acceptor: &TlsAcceptor,
+    stream: TcpStream,
+    addr: SocketAddr,
+) -> Option<(TlsStream<TcpStream>, TlsPeer)> {
+    let stream = match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(e)) => {
+            tracing::debug!("TLS handshake with {addr} failed: {e}");
+            return None;
+        }
+        Err(_) => {
+            tracing::debug!("TLS handshake with {addr} timed out");
+            return None;
+        }
+    };
+    let peer = TlsPeer {
+        cert_common_name: cert_common_name(&stream),
+    };
+    Some((stream, peer))
+}
+
+/// Returns the common name of the certificate of the client, if any.
+fn cert_common_name(stream: &TlsStream<TcpStream>) -> Option<String> {
+    let certificate = stream.get_ref().1.peer_certificates()?.first()?;
+    let (_, certificate) = X509Certificate::from_der(certificate.as_ref()).ok()?;
+    let common_name = certificate
+        .subject()
+        .iter_common_name()
+        .next()?
+        .as_str()
+        .ok()?
+        .to_string();
+    Some(common_name)
+}
@@ -0,0 +1,252 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The webhook queryables, which answer the queries by forwarding them to HTTP services.
+//!
+//! A query is forwarded as a `GET` request, or a `POST` request with the payload of the query as
+//! body, to the URL of the webhook followed by the key expression and the parameters of the
+//! query. The response of the service is turned into:
+//! - a reply with its body, and its `Content-Type` as encoding, if its status is a success, on the
+//!   key expression given by its `Zenoh-Key-Expr` header, or otherwise the one of the query;
+//! - no reply if its status is `204 No Content` or `404 Not Found`;
+//! - an error reply with its body otherwise.
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    body::Bytes,
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri},
+};
+use http_body_util::{BodyExt, Full};
+use hyper_util::{
+    client::legacy::{connect::HttpConnector, Client},
+    rt::TokioExecutor,
+};
+use zenoh::{
+    bytes::Encoding,
+    internal::{bail, zerror},
+    key_expr::{KeyExpr, OwnedKeyExpr},
+    query::{Query, Queryable},
+    session::Session,
+    Result as ZResult,
+};
+
+use crate::{config::WebhookConfig, spawn_runtime};
+
+/// The header giving the key expression of the query in the requests, and the key expression of
+/// the reply in the responses.
+const KEY_EXPR_HEADER: &str = "zenoh-key-expr";
+
+type HttpClient = Client<HttpConnector, Full<Bytes>>;
+
+/// The webhook queryables of the plugin.
+pub(crate) struct Webhooks {
+    client: HttpClient,
+    webhooks: Vec<Arc<Webhook>>,
+}
+
+struct Webhook {
+    key_expr: OwnedKeyExpr,
+    url: url::Url,
+    complete: bool,
+    timeout: Duration,
+    headers: HeaderMap,
+}
+
+impl Webhooks {
+    pub(crate) fn new(conf: &[WebhookConfig]) -> ZResult<Self> {
+        let webhooks = conf
+            .iter()
+            .map(|conf| Webhook::new(conf).map(Arc::new))
+            .collect::<ZResult<Vec<_>>>()?;
+        Ok(Webhooks {
+            client: Client::builder(TokioExecutor::new()).build_http(),
+            webhooks,
+        })
+    }
+
+    /// Declares the queryables of the webhooks, which are undeclared when dropped.
+    pub(crate) async fn declare(&self, session: &Session) -> ZResult<Vec<Queryable<()>>> {
+        let mut queryables = Vec::with_capacity(self.webhooks.len());
+        for webhook in &self.webhooks {
+            let queryable = session
+                .declare_queryable(&webhook.key_expr)
+                .complete(webhook.complete)
+                .callback({
+                    let webhook = webhook.clone();
+                    let client = self.client.clone();
+                    move |query| {
+                        let webhook = webhook.clone();
+                        let client = client.clone();
+                        spawn_runtime(async move { webhook.answer(&client, query).await });
+                    }
+                })
+                .await?;
+            tracing::debug!(
+                "Forwarding the queries on {} to {}",
+                webhook.key_expr,
+                webhook.url
+            );
+            queryables.push(queryable);
+        }
+        Ok(queryables)
+    }
+}
+
+impl Webhook {
+    fn new(conf: &WebhookConfig) -> ZResult<Self> {
+        let key_expr = OwnedKeyExpr::autocanonize(conf.key_expr.clone())
+            .map_err(|e| zerror!("Invalid webhook key expression {}: {e}", conf.key_expr))?;
+        let url = url::Url::parse(&conf.url)
+            .map_err(|e| zerror!("Invalid webhook URL {}: {e}", conf.url))?;
+        if url.scheme() != "http" || url.cannot_be_a_base() {
+            bail!(
+                "Invalid webhook URL {}: only http URLs are supported",
+                conf.url
+            );
+        }
+        let headers = conf
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    HeaderName::try_from(name.as_str())
+                        .map_err(|e| zerror!("Invalid webhook header name {name}: {e}"))?,
+                    HeaderValue::try_from(value.as_str())
+                        .map_err(|e| zerror!("Invalid webhook header value for {name}: {e}"))?,
+                ))
+            })
+            .collect::<ZResult<HeaderMap>>()?;
+        Ok(Webhook {
+            key_expr,
+            url,
+            complete: conf.complete,
+            timeout: Duration::from_millis(conf.timeout),
+            headers,
+        })
+    }
+
+    /// Returns the URL a query is forwarded to.
+    fn uri(&self, query: &Query) -> ZResult<Uri> {
+        let mut url = self.url.clone();
+        url.path_segments_mut()
+            .map_err(|_| zerror!("Invalid webhook URL {}", self.url))?
+            .pop_if_empty()
+            .extend(query.key_expr().as_str().split('/'));
+        if !query.parameters().is_empty() {
+            url.set_query(Some(query.parameters().as_str()));
+        }
+        Ok(url.as_str().parse()?)
+    }
+
+    /// Forwards a query to the service, and returns the status, the headers and the body of its
+    /// response.
+    async fn request(
+        &self,
+        client: &HttpClient,
+        query: &Query,
+    ) -> ZResult<(StatusCode, HeaderMap, Bytes)> {
+        let mut request = hyper::Request::builder()
+            .uri(self.uri(query)?)
+            .header(KEY_EXPR_HEADER, query.key_expr().as_str());
+        if let Some(headers) = request.headers_mut() {
+            headers.extend(self.headers.clone());
+        }
+        let request = match query.payload() {
+            Some(payload) => {
+                if let Some(encoding) = query.encoding() {
+                    request = request.header(header::CONTENT_TYPE, encoding.to_string());
+                }
+                request
+                    .method(Method::POST)
+                    .body(Full::new(Bytes::from(payload.to_bytes().into_owned())))?
+            }
+            None => request.method(Method::GET).body(Full::default())?,
+        };
+        let response = tokio::time::timeout(self.timeout, async {
+            let response = client.request(request).await?;
+            let (parts, body) = response.into_parts();
+            let body = body.collect().await?.to_bytes();
+            ZResult::Ok((parts.status, parts.headers, body))
+        })
+        .await
+        .map_err(|_| zerror!("Timeout after {:?}", self.timeout))??;
+        Ok(response)
+    }
+
+    async fn answer(&self, client: &HttpClient, query: Query) {
+        let (status, headers, body) = match self.request(client, &query).await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::warn!("Webhook request for query {} failed: {e}", query.selector());
+                reply_err(
+                    &query,
+                    format!("Webhook request failed: {e}"),
+                    Encoding::TEXT_PLAIN,
+                )
+                .await;
+                return;
+            }
+        };
+        let encoding = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map_or(Encoding::default(), Encoding::from);
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => {}
+            status if status.is_success() => {
+                let key_expr = match headers.get(KEY_EXPR_HEADER) {
+                    Some(key_expr) => match key_expr
+                        .to_str()
+                        .map_err(|e| zerror!("{e}").into())
+                        .and_then(KeyExpr::try_from)
+                    {
+                        Ok(key_expr) => key_expr,
+                        Err(e) => {
+                            let message = format!("Invalid {KEY_EXPR_HEADER} header: {e}");
+                            reply_err(&query, message, Encoding::TEXT_PLAIN).await;
+                            return;
+                        }
+                    },
+                    None => query.key_expr().clone(),
+                };
+                if let Err(e) = query
+                    .reply(&key_expr, body.to_vec())
+                    .encoding(encoding)
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to reply to query {} on {key_expr}: {e}",
+                        query.selector()
+                    );
+                    let message = format!("Invalid reply on {key_expr}: {e}");
+                    reply_err(&query, message, Encoding::TEXT_PLAIN).await;
+                }
+            }
+            status => {
+                tracing::debug!(
+                    "Webhook answered query {} with status {status}",
+                    query.selector()
+                );
+                reply_err(&query, body.to_vec(), encoding).await;
+            }
+        }
+    }
+}
+
+async fn reply_err(query: &Query, payload: impl Into<zenoh::bytes::ZBytes>, encoding: Encoding) {
+    if let Err(e) = query.reply_err(payload).encoding(encoding).await {
+        tracing::warn!("Failed to reply to query {}: {e}", query.selector());
+    }
+}
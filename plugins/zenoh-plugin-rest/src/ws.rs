@@ -0,0 +1,414 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! The WebSocket endpoint, through which a single connection can declare several subscribers,
+//! publish and query.
+//!
+//! The messages are JSON objects in text frames, or their CBOR equivalent in binary frames, with
+//! an `op` field giving the operation. The messages sent by the plugin in response to a request
+//! use the format of the request, and carry its `id`:
+//! - `{"op": "subscribe", "id": 1, "key_expr": "demo/**"}`: answered by `ok`, and then by a
+//!   `sample` message for each sample received;
+//! - `{"op": "unsubscribe", "id": 1}`: undeclares the subscriber of id 1, answered by `ok`;
+//! - `{"op": "put", "key_expr": "demo/a", "value": "hello", "encoding": "text/plain"}` and
+//!   `{"op": "delete", "key_expr": "demo/a"}`: answered by `ok` if they have an `id`;
+//! - `{"op": "get", "id": 2, "selector": "demo/**", "timeout": 1000}`: answered by a `reply`, or
+//!   `reply_error`, message for each reply, and then by `done`.
+//!
+//! A failed request is answered by `{"op": "error", "id": 1, "message": "..."}`, as are the
+//! requests the client isn't allowed to perform by the access control.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use axum::{
+    extract::{
+        ws::{rejection::WebSocketUpgradeRejection, Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::{sync::mpsc, task::JoinHandle};
+use zenoh::{
+    bytes::Encoding,
+    internal::zerror,
+    key_expr::KeyExpr,
+    query::{Parameters, Selector},
+    session::Session,
+    Result as ZResult,
+};
+
+use crate::{
+    config::AclMessage, consolidation, resolve_local, spawn_runtime, AppState, Auth, Identity,
+    JSONSample,
+};
+
+/// The path of the WebSocket endpoint.
+pub(crate) const WEBSOCKET_PATH: &str = "/@ws";
+
+/// The number of messages waiting to be sent on a connection before the subscribers and queries
+/// wait for the client.
+const SEND_QUEUE_SIZE: usize = 256;
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Request {
+    Subscribe {
+        id: u64,
+        key_expr: String,
+    },
+    Unsubscribe {
+        id: u64,
+    },
+    Put {
+        id: Option<u64>,
+        key_expr: String,
+        #[serde(default)]
+        value: serde_json::Value,
+        encoding: Option<String>,
+    },
+    Delete {
+        id: Option<u64>,
+        key_expr: String,
+    },
+    Get {
+        id: u64,
+        selector: String,
+        value: Option<serde_json::Value>,
+        encoding: Option<String>,
+        timeout: Option<u64>,
+    },
+}
+
+impl Request {
+    fn id(&self) -> Option<u64> {
+        match self {
+            Request::Subscribe { id, .. }
+            | Request::Unsubscribe { id }
+            | Request::Get { id, .. } => Some(*id),
+            Request::Put { id, .. } | Request::Delete { id, .. } => *id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Answer {
+    Ok {
+        id: u64,
+    },
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<u64>,
+        message: String,
+    },
+    Sample {
+        id: u64,
+        kind: String,
+        sample: JSONSample,
+    },
+    Reply {
+        id: u64,
+        sample: JSONSample,
+    },
+    ReplyError {
+        id: u64,
+        error: JSONSample,
+    },
+    Done {
+        id: u64,
+    },
+}
+
+/// The format of the messages: JSON in text frames, CBOR in binary frames.
+#[derive(Clone, Copy, Debug)]
+enum Format {
+    Json,
+    Cbor,
+}
+
+impl Format {
+    fn decode(message: Message) -> Option<(Format, ZResult<Request>)> {
+        match message {
+            Message::Text(text) => Some((
+                Format::Json,
+                serde_json::from_str(text.as_str()).map_err(Into::into),
+            )),
+            Message::Binary(bytes) => Some((
+                Format::Cbor,
+                ciborium::from_reader(bytes.as_ref()).map_err(|e| zerror!("{e}").into()),
+            )),
+            _ => None,
+        }
+    }
+
+    fn encode(self, answer: &Answer) -> ZResult<Message> {
+        match self {
+            Format::Json => Ok(Message::Text(serde_json::to_string(answer)?.into())),
+            Format::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(answer, &mut bytes).map_err(|e| zerror!("{e}"))?;
+                Ok(Message::Binary(bytes.into()))
+            }
+        }
+    }
+}
+
+pub(crate) async fn websocket(
+    State(state): State<AppState>,
+    identity: Identity,
+    headers: HeaderMap,
+    ws: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Response {
+    // Browsers do not apply CORS to WebSocket upgrades: without this check any web page could
+    // open a WebSocket with the credentials of the user (cross-site WebSocket hijacking).
+    // Clients other than browsers do not send an origin.
+    if let (Some(allowed_origins), Some(origin)) =
+        (&state.allowed_origins, headers.get(header::ORIGIN))
+    {
+        if !allowed_origins
+            .iter()
+            .any(|allowed| allowed.as_bytes() == origin.as_bytes())
+        {
+            tracing::debug!("Rejecting WebSocket upgrade from origin {origin:?}");
+            return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+        }
+    }
+    match ws {
+        Ok(ws) => ws.on_upgrade(move |socket| serve(state.session, state.auth, identity, socket)),
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+async fn serve(session: Session, auth: Arc<Auth>, identity: Identity, socket: WebSocket) {
+    let (mut sink, mut stream) = socket.split();
+    let (tx, mut rx) = mpsc::channel::<(Format, Answer)>(SEND_QUEUE_SIZE);
+    let sender = spawn_runtime(async move {
+        while let Some((format, answer)) = rx.recv().await {
+            let message = match format.encode(&answer) {
+                Ok(message) => message,
+                Err(e) => {
+                    tracing::warn!("Failed to encode WebSocket message: {e}");
+                    continue;
+                }
+            };
+            if sink.send(message).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    let mut connection = Connection {
+        session,
+        auth,
+        identity,
+        tx,
+        subscribers: HashMap::new(),
+    };
+    while let Some(Ok(message)) = stream.next().await {
+        match Format::decode(message) {
+            Some((format, Ok(request))) => {
+                let id = request.id();
+                if let Err(e) = connection.process(format, request).await {
+                    connection
+                        .answer(
+                            format,
+                            Answer::Error {
+                                id,
+                                message: e.to_string(),
+                            },
+                        )
+                        .await;
+                }
+            }
+            Some((format, Err(e))) => {
+                connection
+                    .answer(
+                        format,
+                        Answer::Error {
+                            id: None,
+                            message: format!("Invalid request: {e}"),
+                        },
+                    )
+                    .await
+            }
+            None => {}
+        }
+    }
+
+    drop(connection);
+    sender.abort();
+}
+
+/// The state of a WebSocket connection.
+struct Connection {
+    session: Session,
+    auth: Arc<Auth>,
+    identity: Identity,
+    tx: mpsc::Sender<(Format, Answer)>,
+    subscribers: HashMap<u64, JoinHandle<()>>,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        for subscriber in self.subscribers.values() {
+            subscriber.abort();
+        }
+    }
+}
+
+impl Connection {
+    async fn answer(&self, format: Format, answer: Answer) {
+        // The connection is closing if the answer can't be sent
+        let _ = self.tx.send((format, answer)).await;
+    }
+
+    /// Returns the key expression of a request, if the client is allowed to perform the
+    /// operation `message` on it.
+    fn authorized_key_expr(
+        &self,
+        key_expr: String,
+        message: AclMessage,
+    ) -> ZResult<KeyExpr<'static>> {
+        let key_expr = KeyExpr::try_from(resolve_local(key_expr, &self.session))?;
+        if !self.auth.is_allowed(&self.identity, message, &key_expr) {
+            return Err(zerror!("Not allowed to {message:?} on {key_expr}").into());
+        }
+        Ok(key_expr)
+    }
+
+    async fn process(&mut self, format: Format, request: Request) -> ZResult<()> {
+        match request {
+            Request::Subscribe { id, key_expr } => {
+                if self.subscribers.contains_key(&id) {
+                    return Err(zerror!("Subscriber {id} already declared").into());
+                }
+                let key_expr = self.authorized_key_expr(key_expr, AclMessage::DeclareSubscriber)?;
+                let subscriber = self.session.declare_subscriber(key_expr).await?;
+                let tx = self.tx.clone();
+                let subscriber = spawn_runtime(async move {
+                    while let Ok(sample) = subscriber.recv_async().await {
+                        let answer = Answer::Sample {
+                            id,
+                            kind: sample.kind().to_string(),
+                            sample: JSONSample::from(&sample),
+                        };
+                        if tx.send((format, answer)).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+                self.subscribers.insert(id, subscriber);
+                self.answer(format, Answer::Ok { id }).await;
+            }
+            Request::Unsubscribe { id } => {
+                let Some(subscriber) = self.subscribers.remove(&id) else {
+                    return Err(zerror!("Unknown subscriber {id}").into());
+                };
+                subscriber.abort();
+                self.answer(format, Answer::Ok { id }).await;
+            }
+            Request::Put {
+                id,
+                key_expr,
+                value,
+                encoding,
+            } => {
+                let key_expr = self.authorized_key_expr(key_expr, AclMessage::Put)?;
+                let encoding = encoding_of(&value, encoding);
+                let payload = JSONSample::json_to_payload(&value, &encoding)?;
+                self.session
+                    .put(key_expr, payload)
+                    .encoding(encoding)
+                    .await?;
+                if let Some(id) = id {
+                    self.answer(format, Answer::Ok { id }).await;
+                }
+            }
+            Request::Delete { id, key_expr } => {
+                let key_expr = self.authorized_key_expr(key_expr, AclMessage::Delete)?;
+                self.session.delete(key_expr).await?;
+                if let Some(id) = id {
+                    self.answer(format, Answer::Ok { id }).await;
+                }
+            }
+            Request::Get {
+                id,
+                selector,
+                value,
+                encoding,
+                timeout,
+            } => {
+                let (key_expr, parameters) = match selector.split_once('?') {
+                    Some((key_expr, parameters)) => (key_expr.to_string(), parameters),
+                    None => (selector, ""),
+                };
+                let key_expr = self.authorized_key_expr(key_expr, AclMessage::Query)?;
+                let parameters = Parameters::from(parameters);
+                let mut query = self
+                    .session
+                    .get(Selector::borrowed(&key_expr, &parameters))
+                    .consolidation(consolidation(&parameters))
+                    .with(flume::unbounded());
+                if let Some(value) = value {
+                    let encoding = encoding_of(&value, encoding);
+                    query = query
+                        .payload(JSONSample::json_to_payload(&value, &encoding)?)
+                        .encoding(encoding);
+                }
+                if let Some(timeout) = timeout {
+                    query = query.timeout(Duration::from_millis(timeout));
+                }
+                let replies = query.await?;
+                let tx = self.tx.clone();
+                spawn_runtime(async move {
+                    while let Ok(reply) = replies.recv_async().await {
+                        let answer = match reply.result() {
+                            Ok(sample) => Answer::Reply {
+                                id,
+                                sample: JSONSample::from(sample),
+                            },
+                            Err(err) => Answer::ReplyError {
+                                id,
+                                error: JSONSample::new(
+                                    "ERROR",
+                                    err.payload(),
+                                    err.encoding(),
+                                    None,
+                                ),
+                            },
+                        };
+                        if tx.send((format, answer)).await.is_err() {
+                            return;
+                        }
+                    }
+                    let _ = tx.send((format, Answer::Done { id })).await;
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the encoding of a value: the one provided, or by default `text/plain` for the strings
+/// and `application/json` for the other values.
+fn encoding_of(value: &serde_json::Value, encoding: Option<String>) -> Encoding {
+    match encoding {
+        Some(encoding) => Encoding::from(encoding),
+        None if value.is_string() => Encoding::TEXT_PLAIN,
+        None => Encoding::APPLICATION_JSON,
+    }
+}
@@ -0,0 +1,631 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! A durable backend, persisting the values and their timestamps in a local append-only log.
+//!
+//! Each storage owns a directory containing a single log file. Every put or delete appends a
+//! record to the log, which is flushed (and synced to disk if `sync` is enabled) before the
+//! operation is acknowledged. The log is written from the blocking threads of the async runtime.
+//! The latest value of each key is also kept in memory to serve the queries. When the log
+//! contains more obsolete records than live ones, it is compacted: the live entries and the
+//! tombstones of the deleted keys are written to a new log file which atomically replaces the
+//! previous one. Tombstones older than the garbage collection lifespan of the storage are
+//! discarded by the compaction.
+//!
+//! On startup, the log is replayed to restore the values and their timestamps, so that the
+//! replication log of the storage is rebuilt as it was. A truncated or corrupted tail, e.g. due
+//! to a power loss while writing, is discarded.
+//!
+//! The expiry of the values having a time-to-live is persisted with them, so that they expire
+//! natively, including while the storage is not running.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, zenoh_home, zlock},
+    key_expr::OwnedKeyExpr,
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::{
+    config::{StorageConfig, VolumeConfig},
+    *,
+};
+use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin};
+use zenoh_util::ffi::JsonValue;
+
+use crate::FILE_BACKEND_NAME;
+
+/// The volume property setting the directory containing the storages. If relative, it is
+/// relative to `${ZENOH_HOME}`.
+const PROP_VOLUME_ROOT: &str = "root";
+/// The storage property setting the directory of the storage, relative to the volume root.
+/// Defaults to the storage name.
+const PROP_STORAGE_DIR: &str = "dir";
+/// The storage property enabling the sync of the log to disk after each write.
+const PROP_STORAGE_SYNC: &str = "sync";
+
+const DEFAULT_ROOT_DIR: &str = "zenoh_backend_file";
+const LOG_FILE: &str = "data.log";
+const COMPACTION_FILE: &str = "data.log.compaction";
+
+/// The minimum number of obsolete records in the log before a compaction is triggered.
+const COMPACTION_MIN_OBSOLETE: usize = 1024;
+
+/// The header of a log record: the length of its body and the XXH3 hash of the body.
+const RECORD_HEADER_LEN: usize = 4 + 8;
+
+pub struct FileBackend {
+    config: VolumeConfig,
+    root: PathBuf,
+}
+
+impl Plugin for FileBackend {
+    type StartArgs = VolumeConfig;
+    type Instance = VolumeInstance;
+
+    const DEFAULT_NAME: &'static str = FILE_BACKEND_NAME;
+    const PLUGIN_VERSION: &'static str = plugin_version!();
+    const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
+
+    fn start(_: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+        let root = match args.rest.into_serde_map().get(PROP_VOLUME_ROOT) {
+            Some(serde_json::Value::String(root)) => zenoh_home().join(root),
+            Some(_) => bail!(
+                "`{PROP_VOLUME_ROOT}` property of volume `{}` must be a string",
+                args.name
+            ),
+            None => zenoh_home().join(DEFAULT_ROOT_DIR),
+        };
+        tracing::debug!("File backend root directory: {}", root.display());
+        Ok(Box::new(FileBackend {
+            config: args.clone(),
+            root,
+        }))
+    }
+}
+
+#[async_trait]
+impl Volume for FileBackend {
+    fn get_admin_status(&self) -> JsonValue {
+        self.config.to_json_value().into()
+    }
+
+    fn get_capability(&self) -> Capability {
+        Capability {
+            persistence: Persistence::Durable,
+            history: History::Latest,
+        }
+    }
+
+    async fn create_storage(&self, properties: StorageConfig) -> ZResult<Box<dyn Storage>> {
+        tracing::debug!("Create File Storage with configuration: {:?}", properties);
+        let volume_cfg = serde_json::Value::from(&properties.volume_cfg);
+        let dir = match volume_cfg.get(PROP_STORAGE_DIR) {
+            Some(serde_json::Value::String(dir)) => dir.clone(),
+            Some(_) => bail!(
+                "`{PROP_STORAGE_DIR}` property of storage `{}` must be a string",
+                properties.name
+            ),
+            None => properties.name.clone(),
+        };
+        if !Path::new(&dir)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            bail!(
+                "`{PROP_STORAGE_DIR}` property of storage `{}` must be a relative path without \
+                 `..`, found `{dir}`",
+                properties.name
+            );
+        }
+        let sync = match volume_cfg.get(PROP_STORAGE_SYNC) {
+            Some(serde_json::Value::Bool(sync)) => *sync,
+            Some(_) => bail!(
+                "`{PROP_STORAGE_SYNC}` property of storage `{}` must be a boolean",
+                properties.name
+            ),
+            None => true,
+        };
+        let dir = self.root.join(dir);
+        let storage =
+            tokio::task::spawn_blocking(move || FileStorage::open(properties, dir, sync)).await??;
+        Ok(Box::new(storage))
+    }
+}
+
+impl Drop for FileBackend {
+    fn drop(&mut self) {
+        tracing::trace!("FileBackend::drop()");
+    }
+}
+
+/// A record of the log.
+#[derive(Debug, Serialize, Deserialize)]
+enum Record {
+    Put {
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+        encoding: String,
+        payload: Vec<u8>,
+    },
+    Delete {
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    },
+    PutWithExpiry {
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+        encoding: String,
+        payload: Vec<u8>,
+        expiry: Timestamp,
+    },
+}
+
+impl Record {
+    fn put(key: Option<OwnedKeyExpr>, data: &StoredData, expiry: Option<Timestamp>) -> Self {
+        let timestamp = data.timestamp;
+        let encoding = data.encoding.to_string();
+        let payload = data.payload.to_bytes().into_owned();
+        match expiry {
+            Some(expiry) => Self::PutWithExpiry {
+                key,
+                timestamp,
+                encoding,
+                payload,
+                expiry,
+            },
+            None => Self::Put {
+                key,
+                timestamp,
+                encoding,
+                payload,
+            },
+        }
+    }
+
+    /// Appends the record, preceded by its header, to `writer`.
+    fn write(&self, writer: &mut impl Write) -> ZResult<()> {
+        let body = bincode::serialize(self)?;
+        let Ok(len) = u32::try_from(body.len()) else {
+            bail!("Record of {} bytes is too large", body.len());
+        };
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        header[..4].copy_from_slice(&len.to_le_bytes());
+        header[4..].copy_from_slice(&xxhash_rust::xxh3::xxh3_64(&body).to_le_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(&body)?;
+        Ok(())
+    }
+
+    /// Reads the next record from `reader`. Returns `None` at the end of the log, or if the
+    /// remaining bytes are not a valid record.
+    fn read(reader: &mut impl Read) -> io::Result<Option<(Self, usize)>> {
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        if !read_exact_or_eof(reader, &mut header)? {
+            return Ok(None);
+        }
+        let len = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+        let hash = u64::from_le_bytes(header[4..].try_into().unwrap());
+        let mut body = Vec::new();
+        if reader.take(len as u64).read_to_end(&mut body)? != len
+            || xxhash_rust::xxh3::xxh3_64(&body) != hash
+        {
+            return Ok(None);
+        }
+        Ok(bincode::deserialize(&body)
+            .ok()
+            .map(|record| (record, RECORD_HEADER_LEN + len)))
+    }
+}
+
+/// Fills `buf` from `reader`. Returns `false` if the reader is at EOF or if `buf` could not be
+/// entirely filled.
+fn read_exact_or_eof(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => return Ok(false),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+/// The log file of a storage.
+struct Log {
+    dir: PathBuf,
+    file: File,
+    sync: bool,
+}
+
+impl Log {
+    fn append(&mut self, buf: &[u8]) -> ZResult<()> {
+        self.file.write_all(buf)?;
+        if self.sync {
+            self.file.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the log with the given records.
+    fn rewrite(&mut self, records: impl IntoIterator<Item = Record>) -> ZResult<()> {
+        let compaction_path = self.dir.join(COMPACTION_FILE);
+        let mut writer = BufWriter::new(File::create(&compaction_path)?);
+        for record in records {
+            record.write(&mut writer)?;
+        }
+        writer
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+        let path = self.dir.join(LOG_FILE);
+        fs::rename(&compaction_path, &path)?;
+        // Persist the rename; directories can't be opened on every platform, hence best effort
+        if let Ok(dir) = File::open(&self.dir) {
+            let _ = dir.sync_all();
+        }
+        self.file = OpenOptions::new().append(true).open(&path)?;
+        Ok(())
+    }
+}
+
+impl Drop for Log {
+    fn drop(&mut self) {
+        if let Err(e) = self.file.sync_all() {
+            tracing::warn!("Failed to sync log of {}: {e}", self.dir.display());
+        }
+    }
+}
+
+/// A live entry of the storage, to be written by a compaction.
+type LiveEntry = (Option<OwnedKeyExpr>, StoredData, Option<Timestamp>);
+
+struct FileStorage {
+    config: StorageConfig,
+    log: Arc<Mutex<Log>>,
+    /// The number of records in the log, including the obsolete ones.
+    records: usize,
+    map: HashMap<Option<OwnedKeyExpr>, StoredData>,
+    /// The expiry of the values having a time-to-live.
+    expiries: HashMap<Option<OwnedKeyExpr>, Timestamp>,
+    /// The timestamp of the last delete of the deleted keys.
+    tombstones: HashMap<Option<OwnedKeyExpr>, Timestamp>,
+}
+
+impl FileStorage {
+    fn open(config: StorageConfig, dir: PathBuf, sync: bool) -> ZResult<FileStorage> {
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(LOG_FILE);
+        let mut map = HashMap::new();
+        let mut expiries = HashMap::new();
+        let mut tombstones = HashMap::new();
+        let mut records = 0;
+        let mut valid_len = 0;
+        if let Ok(file) = File::open(&path) {
+            let file_len = file.metadata()?.len();
+            let mut reader = BufReader::new(file);
+            while let Some((record, len)) = Record::read(&mut reader)? {
+                match record {
+                    Record::Put {
+                        key,
+                        timestamp,
+                        encoding,
+                        payload,
+                    } => {
+                        let data = StoredData {
+                            payload: ZBytes::from(payload),
+                            encoding: Encoding::from(encoding),
+                            timestamp,
+                        };
+                        expiries.remove(&key);
+                        tombstones.remove(&key);
+                        map.insert(key, data);
+                    }
+                    Record::PutWithExpiry {
+                        key,
+                        timestamp,
+                        encoding,
+                        payload,
+                        expiry,
+                    } => {
+                        let data = StoredData {
+                            payload: ZBytes::from(payload),
+                            encoding: Encoding::from(encoding),
+                            timestamp,
+                        };
+                        expiries.insert(key.clone(), expiry);
+                        tombstones.remove(&key);
+                        map.insert(key, data);
+                    }
+                    Record::Delete { key, timestamp } => {
+                        expiries.remove(&key);
+                        map.remove(&key);
+                        tombstones.insert(key, timestamp);
+                    }
+                }
+                records += 1;
+                valid_len += len as u64;
+            }
+            if valid_len < file_len {
+                tracing::warn!(
+                    "Storage '{}': discarding {} bytes of truncated or corrupted records at the \
+                     end of {}",
+                    config.name,
+                    file_len - valid_len,
+                    path.display()
+                );
+            }
+        }
+        // The values expired while the storage was not running are discarded by the compaction
+        expiries.retain(|key, expiry| {
+            let expired = is_expired(expiry);
+            if expired {
+                map.remove(key);
+            }
+            !expired
+        });
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.set_len(valid_len)?;
+        tracing::debug!(
+            "Storage '{}': restored {} entries and {} tombstones from {} records of {}",
+            config.name,
+            map.len(),
+            tombstones.len(),
+            records,
+            path.display()
+        );
+        let mut storage = FileStorage {
+            config,
+            log: Arc::new(Mutex::new(Log { dir, file, sync })),
+            records,
+            map,
+            expiries,
+            tombstones,
+        };
+        storage.drop_old_tombstones();
+        if storage.records > storage.live_records() {
+            let (entries, tombstones) = storage.compaction_snapshot();
+            zlock!(storage.log).rewrite(compaction_records(entries, tombstones))?;
+            storage.records = storage.live_records();
+        }
+        Ok(storage)
+    }
+
+    /// The number of records of the log which are not obsolete.
+    fn live_records(&self) -> usize {
+        self.map.len() + self.tombstones.len()
+    }
+
+    /// Discards the tombstones older than the garbage collection lifespan of the storage.
+    fn drop_old_tombstones(&mut self) {
+        let lifespan = self.config.garbage_collection_config.lifespan;
+        let Some(oldest) = SystemTime::now().checked_sub(lifespan) else {
+            return;
+        };
+        self.tombstones
+            .retain(|_, timestamp| timestamp.get_time().to_system_time() >= oldest);
+    }
+
+    fn compaction_snapshot(&self) -> (Vec<LiveEntry>, Vec<(Option<OwnedKeyExpr>, Timestamp)>) {
+        let entries = self
+            .map
+            .iter()
+            .map(|(key, data)| (key.clone(), data.clone(), self.expiries.get(key).copied()))
+            .collect();
+        let tombstones = self
+            .tombstones
+            .iter()
+            .map(|(key, timestamp)| (key.clone(), *timestamp))
+            .collect();
+        (entries, tombstones)
+    }
+
+    async fn append(&mut self, record: Record) -> ZResult<()> {
+        let mut buf = Vec::new();
+        record.write(&mut buf)?;
+        let log = self.log.clone();
+        tokio::task::spawn_blocking(move || zlock!(log).append(&buf)).await??;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Compacts the log if it contains enough obsolete records.
+    async fn maybe_compact(&mut self) -> ZResult<()> {
+        let obsolete = self.records - self.live_records();
+        if obsolete >= COMPACTION_MIN_OBSOLETE && obsolete > self.live_records() {
+            self.compact().await?;
+        }
+        Ok(())
+    }
+
+    /// Rewrites the log with only the live entries and the recent tombstones.
+    async fn compact(&mut self) -> ZResult<()> {
+        self.drop_old_tombstones();
+        tracing::debug!(
+            "Storage '{}': compacting {} records into {}",
+            self.config.name,
+            self.records,
+            self.live_records()
+        );
+        let (entries, tombstones) = self.compaction_snapshot();
+        let log = self.log.clone();
+        tokio::task::spawn_blocking(move || {
+            zlock!(log).rewrite(compaction_records(entries, tombstones))
+        })
+        .await??;
+        self.records = self.live_records();
+        Ok(())
+    }
+
+    async fn put_data(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        data: StoredData,
+        expiry: Option<Timestamp>,
+    ) -> ZResult<StorageInsertionResult> {
+        // A put older than the deletion of its key, e.g. pushed by a replica or arriving late
+        // after a restart, must not bring the key back
+        if self
+            .tombstones
+            .get(&key)
+            .is_some_and(|deleted| data.timestamp <= *deleted)
+        {
+            return Ok(StorageInsertionResult::Outdated);
+        }
+        self.append(Record::put(key.clone(), &data, expiry)).await?;
+        match expiry {
+            Some(expiry) => self.expiries.insert(key.clone(), expiry),
+            None => self.expiries.remove(&key),
+        };
+        self.tombstones.remove(&key);
+        let result = match self.map.insert(key, data) {
+            Some(_) => StorageInsertionResult::Replaced,
+            None => StorageInsertionResult::Inserted,
+        };
+        self.maybe_compact().await?;
+        Ok(result)
+    }
+
+    fn is_live(&self, key: &Option<OwnedKeyExpr>) -> bool {
+        !self.expiries.get(key).is_some_and(is_expired)
+    }
+}
+
+/// Returns the records of a compacted log.
+fn compaction_records(
+    entries: Vec<LiveEntry>,
+    tombstones: Vec<(Option<OwnedKeyExpr>, Timestamp)>,
+) -> impl Iterator<Item = Record> {
+    entries
+        .into_iter()
+        .map(|(key, data, expiry)| Record::put(key, &data, expiry))
+        .chain(
+            tombstones
+                .into_iter()
+                .map(|(key, timestamp)| Record::Delete { key, timestamp }),
+        )
+}
+
+fn is_expired(expiry: &Timestamp) -> bool {
+    expiry.get_time().to_system_time() <= SystemTime::now()
+}
+
+#[async_trait]
+impl Storage for FileStorage {
+    fn get_admin_status(&self) -> JsonValue {
+        self.config.to_json_value().into()
+    }
+
+    async fn put(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("put for {:?}", key);
+        let data = StoredData {
+            payload,
+            encoding,
+            timestamp,
+        };
+        self.put_data(key, data, None).await
+    }
+
+    async fn put_with_expiry(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        payload: ZBytes,
+        encoding: Encoding,
+        timestamp: Timestamp,
+        expiry: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("put for {:?} expiring at {}", key, expiry);
+        let data = StoredData {
+            payload,
+            encoding,
+            timestamp,
+        };
+        self.put_data(key, data, Some(expiry)).await
+    }
+
+    async fn delete(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        timestamp: Timestamp,
+    ) -> ZResult<StorageInsertionResult> {
+        tracing::trace!("delete for {:?}", key);
+        // Deletes of absent keys are logged too, as their tombstone must outlive a restart
+        self.append(Record::Delete {
+            key: key.clone(),
+            timestamp,
+        })
+        .await?;
+        self.map.remove(&key);
+        self.expiries.remove(&key);
+        self.tombstones.insert(key, timestamp);
+        self.maybe_compact().await?;
+        Ok(StorageInsertionResult::Deleted)
+    }
+
+    async fn get(
+        &mut self,
+        key: Option<OwnedKeyExpr>,
+        _parameters: &str,
+    ) -> ZResult<Vec<StoredData>> {
+        tracing::trace!("get for {:?}", key);
+        match self.map.get(&key).filter(|_| self.is_live(&key)) {
+            Some(v) => Ok(vec![v.clone()]),
+            None => Err(format!("Key {key:?} is not present").into()),
+        }
+    }
+
+    async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        Ok(self
+            .map
+            .iter()
+            .filter(|(k, _)| self.is_live(k))
+            .map(|(k, v)| (k.clone(), v.timestamp))
+            .collect())
+    }
+
+    async fn get_all_tombstones(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        Ok(self
+            .tombstones
+            .iter()
+            .map(|(k, timestamp)| (k.clone(), *timestamp))
+            .collect())
+    }
+
+    async fn get_all_expiries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
+        Ok(self
+            .expiries
+            .iter()
+            .map(|(k, expiry)| (k.clone(), *expiry))
+            .collect())
+    }
+}
@@ -22,10 +22,12 @@
 use std::{
     collections::HashMap,
     convert::TryFrom,
+    path::PathBuf,
     str::FromStr,
     sync::{Arc, Mutex},
 };
 
+use file_backend::FileBackend;
 use memory_backend::MemoryBackend;
 use storages_mgt::StorageMessage;
 use tokio::sync::broadcast::Sender;
@@ -34,7 +36,7 @@ use zenoh::{
         bail,
         plugins::{Response, RunningPlugin, RunningPluginTrait, ZenohPlugin},
         runtime::DynamicRuntime,
-        zlock, LibLoader,
+        zenoh_home, zlock, LibLoader,
     },
     key_expr::{keyexpr, KeyExpr, OwnedKeyExpr},
     session::Session,
@@ -48,6 +50,7 @@ use zenoh_plugin_trait::{
     plugin_long_version, plugin_version, Plugin, PluginControl, PluginReport, PluginStatusRec,
 };
 
+mod file_backend;
 mod memory_backend;
 mod replication;
 mod storages_mgt;
@@ -102,6 +105,7 @@ struct StorageRuntimeInner {
     session: Arc<Session>,
     storages: HashMap<String, HashMap<String, Sender<StorageMessage>>>,
     plugins_manager: PluginsManager,
+    snapshots_dir: PathBuf,
 }
 impl StorageRuntimeInner {
     fn status_key(&self) -> String {
@@ -122,12 +126,21 @@ impl StorageRuntimeInner {
             backend_search_dirs,
             volumes,
             storages,
+            rest,
             ..
         } = config;
+        let snapshots_dir = match rest.get(SNAPSHOTS_DIR_PROPERTY) {
+            Some(serde_json::Value::String(dir)) => zenoh_home().join(dir),
+            Some(_) => {
+                bail!("`{SNAPSHOTS_DIR_PROPERTY}` field of {name}'s configuration must be a string")
+            }
+            None => zenoh_home().join(DEFAULT_SNAPSHOTS_DIR),
+        };
         let lib_loader = LibLoader::new(backend_search_dirs);
 
         let mut plugins_manager = PluginsManager::dynamic(lib_loader.clone(), BACKEND_LIB_PREFIX);
         plugins_manager.declare_static_plugin::<MemoryBackend, &str>(MEMORY_BACKEND_NAME, true);
+        plugins_manager.declare_static_plugin::<FileBackend, &str>(FILE_BACKEND_NAME, true);
 
         let session = Arc::new(zenoh::session::init(runtime.clone()).wait()?);
 
@@ -156,26 +169,34 @@ impl StorageRuntimeInner {
             session,
             storages: Default::default(),
             plugins_manager,
+            snapshots_dir,
         };
-        new_self
-            .spawn_volume(&VolumeConfig {
+        // The memory volume is always spawned, with its configuration if declared
+        let memory_volume = volumes
+            .iter()
+            .find(|volume| volume.name() == MEMORY_BACKEND_NAME)
+            .cloned()
+            .unwrap_or_else(|| VolumeConfig {
                 name: MEMORY_BACKEND_NAME.into(),
                 backend: None,
                 paths: None,
                 required: false,
                 rest: Default::default(),
-            })
-            .map_or_else(
-                |e| {
-                    tracing::error!(
-                        "Cannot spawn static volume '{}': {}",
-                        MEMORY_BACKEND_NAME,
-                        e
-                    )
-                },
-                |_| (),
-            );
-        for volume in &volumes {
+            });
+        new_self.spawn_volume(&memory_volume).map_or_else(
+            |e| {
+                tracing::error!(
+                    "Cannot spawn static volume '{}': {}",
+                    MEMORY_BACKEND_NAME,
+                    e
+                )
+            },
+            |_| (),
+        );
+        for volume in volumes
+            .iter()
+            .filter(|volume| volume.name() != MEMORY_BACKEND_NAME)
+        {
             new_self.spawn_volume(volume).map_or_else(
                 |e| tracing::error!("Cannot spawn volume '{}': {}", volume.name(), e),
                 |_| (),
@@ -286,6 +307,7 @@ impl StorageRuntimeInner {
                 storage.clone(),
                 backend.instance(),
                 self.session.clone(),
+                self.snapshots_dir.clone(),
             ))
         })?;
         self.storages
@@ -397,6 +419,11 @@ impl RunningPluginTrait for StorageRuntime {
 
 const BACKEND_LIB_PREFIX: &str = "zenoh_backend_";
 const MEMORY_BACKEND_NAME: &str = "memory";
+const FILE_BACKEND_NAME: &str = "file";
+/// The field of the configuration setting the directory of the storage snapshots. If relative, it
+/// is relative to `${ZENOH_HOME}`.
+const SNAPSHOTS_DIR_PROPERTY: &str = "snapshots_dir";
+const DEFAULT_SNAPSHOTS_DIR: &str = "zenoh_storage_snapshots";
 
 fn with_extended_string<R, F: FnMut(&mut String) -> R>(
     prefix: &mut String,
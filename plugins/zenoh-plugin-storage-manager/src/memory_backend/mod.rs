@@ -11,18 +11,24 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+    time::SystemTime,
+};
 
 use async_trait::async_trait;
 use tokio::sync::RwLock;
 use zenoh::{
     bytes::{Encoding, ZBytes},
+    internal::bail,
     key_expr::OwnedKeyExpr,
+    query::{Parameters, TimeRange, ZenohParameters},
     time::Timestamp,
     Result as ZResult,
 };
 use zenoh_backend_traits::{
-    config::{StorageConfig, VolumeConfig},
+    config::{GarbageCollectionConfig, StorageConfig, VolumeConfig},
     *,
 };
 use zenoh_plugin_trait::{plugin_long_version, plugin_version, Plugin};
@@ -30,8 +36,12 @@ use zenoh_util::ffi::JsonValue;
 
 use crate::MEMORY_BACKEND_NAME;
 
+/// The volume property selecting the history kept by the storages: "latest" (default) or "all".
+const PROP_VOLUME_HISTORY: &str = "history";
+
 pub struct MemoryBackend {
     config: VolumeConfig,
+    history: History,
 }
 
 impl Plugin for MemoryBackend {
@@ -43,8 +53,19 @@ impl Plugin for MemoryBackend {
     const PLUGIN_LONG_VERSION: &'static str = plugin_long_version!();
 
     fn start(_: &str, args: &VolumeConfig) -> ZResult<VolumeInstance> {
+        let history = match args.rest.into_serde_map().get(PROP_VOLUME_HISTORY) {
+            None => History::Latest,
+            Some(serde_json::Value::String(s)) if s == "latest" => History::Latest,
+            Some(serde_json::Value::String(s)) if s == "all" => History::All,
+            Some(v) => bail!(
+                "`{PROP_VOLUME_HISTORY}` property of volume `{}` must be \"latest\" or \"all\", \
+                 found {v}",
+                args.name
+            ),
+        };
         Ok(Box::new(MemoryBackend {
             config: args.clone(),
+            history,
         }))
     }
 }
@@ -58,13 +79,15 @@ impl Volume for MemoryBackend {
     fn get_capability(&self) -> Capability {
         Capability {
             persistence: Persistence::Volatile,
-            history: History::Latest,
+            history: self.history.clone(),
         }
     }
 
     async fn create_storage(&self, properties: StorageConfig) -> ZResult<Box<dyn Storage>> {
         tracing::debug!("Create Memory Storage with configuration: {:?}", properties);
-        Ok(Box::new(MemoryStorage::new(properties).await?))
+        Ok(Box::new(
+            MemoryStorage::new(properties, self.history.clone()).await?,
+        ))
     }
 }
 
@@ -75,18 +98,95 @@ impl Drop for MemoryBackend {
     }
 }
 
+/// A version of the value of a key.
+#[derive(Clone)]
+enum Version {
+    Put(StoredData),
+    Delete(Timestamp),
+}
+
+impl Version {
+    fn timestamp(&self) -> &Timestamp {
+        match self {
+            Version::Put(data) => &data.timestamp,
+            Version::Delete(timestamp) => timestamp,
+        }
+    }
+}
+
+/// The versions of the value of a key, ordered by timestamp.
+///
+/// With `History::Latest`, a key only has its latest value. With `History::All`, deletions are
+/// kept as versions as well, so that the values of a key before its deletion can still be
+/// queried. Versions older than the garbage collection lifespan are discarded, except the latest
+/// value of a key.
+type Versions = Vec<Version>;
+
 struct MemoryStorage {
     config: StorageConfig,
-    map: Arc<RwLock<HashMap<Option<OwnedKeyExpr>, StoredData>>>,
+    history: History,
+    map: Arc<RwLock<HashMap<Option<OwnedKeyExpr>, Versions>>>,
 }
 
 impl MemoryStorage {
-    async fn new(properties: StorageConfig) -> ZResult<MemoryStorage> {
+    async fn new(properties: StorageConfig, history: History) -> ZResult<MemoryStorage> {
+        let map = Arc::new(RwLock::new(HashMap::new()));
+        if history == History::All {
+            tokio::task::spawn(Self::collect_garbage(
+                Arc::downgrade(&map),
+                properties.garbage_collection_config.clone(),
+            ));
+        }
         Ok(MemoryStorage {
             config: properties,
-            map: Arc::new(RwLock::new(HashMap::new())),
+            history,
+            map,
         })
     }
+
+    /// Inserts `version` in `versions`, replacing the version with the same timestamp if any.
+    fn insert_version(versions: &mut Versions, version: Version) {
+        match versions.binary_search_by(|v| v.timestamp().cmp(version.timestamp())) {
+            Ok(i) => versions[i] = version,
+            Err(i) => versions.insert(i, version),
+        }
+    }
+
+    /// Discards the versions older than the garbage collection lifespan, except the latest value.
+    /// Returns `false` if no version is left.
+    fn retain_versions(versions: &mut Versions, time_limit: SystemTime) -> bool {
+        let Some(latest) = versions.len().checked_sub(1) else {
+            return false;
+        };
+        let mut index = 0;
+        versions.retain(|v| {
+            index += 1;
+            (index - 1 == latest && matches!(v, Version::Put(_)))
+                || v.timestamp().get_time().to_system_time() >= time_limit
+        });
+        !versions.is_empty()
+    }
+
+    /// Applies the retention of the versions to all the keys, once per garbage collection period,
+    /// until the storage is dropped.
+    async fn collect_garbage(
+        map: Weak<RwLock<HashMap<Option<OwnedKeyExpr>, Versions>>>,
+        gc_config: GarbageCollectionConfig,
+    ) {
+        loop {
+            tokio::time::sleep(gc_config.period).await;
+            let Some(map) = map.upgrade() else {
+                return;
+            };
+            // the whole history is kept if the lifespan goes back before the epoch
+            let Some(time_limit) = SystemTime::now().checked_sub(gc_config.lifespan) else {
+                continue;
+            };
+            map.write()
+                .await
+                .retain(|_, versions| Self::retain_versions(versions, time_limit));
+        }
+    }
 }
 
 #[async_trait]
@@ -103,22 +203,28 @@ impl Storage for MemoryStorage {
         timestamp: Timestamp,
     ) -> ZResult<StorageInsertionResult> {
         tracing::trace!("put for {:?}", key);
+        let version = Version::Put(StoredData {
+            payload,
+            encoding,
+            timestamp,
+        });
         let mut map = self.map.write().await;
         match map.entry(key) {
             std::collections::hash_map::Entry::Occupied(mut e) => {
-                e.insert(StoredData {
-                    payload,
-                    encoding,
-                    timestamp,
-                });
-                return Ok(StorageInsertionResult::Replaced);
+                let result = match e.get().last() {
+                    Some(Version::Put(_)) => StorageInsertionResult::Replaced,
+                    _ => StorageInsertionResult::Inserted,
+                };
+                match self.history {
+                    History::Latest => {
+                        e.insert(vec![version]);
+                    }
+                    History::All => Self::insert_version(e.get_mut(), version),
+                }
+                return Ok(result);
             }
             std::collections::hash_map::Entry::Vacant(e) => {
-                e.insert(StoredData {
-                    payload,
-                    encoding,
-                    timestamp,
-                });
+                e.insert(vec![version]);
                 return Ok(StorageInsertionResult::Inserted);
             }
         }
@@ -127,34 +233,84 @@ impl Storage for MemoryStorage {
     async fn delete(
         &mut self,
         key: Option<OwnedKeyExpr>,
-        _timestamp: Timestamp,
+        timestamp: Timestamp,
     ) -> ZResult<StorageInsertionResult> {
         tracing::trace!("delete for {:?}", key);
-        self.map.write().await.remove_entry(&key);
+        let mut map = self.map.write().await;
+        match self.history {
+            History::Latest => {
+                map.remove_entry(&key);
+            }
+            History::All => {
+                if let Some(versions) = map.get_mut(&key) {
+                    Self::insert_version(versions, Version::Delete(timestamp));
+                }
+            }
+        }
         return Ok(StorageInsertionResult::Deleted);
     }
 
     async fn get(
         &mut self,
         key: Option<OwnedKeyExpr>,
-        _parameters: &str,
+        parameters: &str,
     ) -> ZResult<Vec<StoredData>> {
         tracing::trace!("get for {:?}", key);
-        // @TODO: use parameters???
-        match self.map.read().await.get(&key) {
-            Some(v) => Ok(vec![v.clone()]),
-            None => Err(format!("Key {key:?} is not present").into()),
+        // Storages keeping only the latest values ignore the time range
+        let time_range = match Parameters::from(parameters).time_range() {
+            Some(Ok(time_range)) if self.history == History::All => Some(time_range.resolve()),
+            Some(Err(e)) if self.history == History::All => bail!("Invalid time range: {e}"),
+            _ => None,
+        };
+        let map = self.map.read().await;
+        let versions = map.get(&key).map(Vec::as_slice).unwrap_or_default();
+        let result = match time_range {
+            // With a time range, all the values in this range are returned
+            Some(time_range) => in_time_range(versions, &time_range),
+            // Otherwise, only the latest value is returned, unless the key has been deleted
+            None => match versions.last() {
+                Some(Version::Put(data)) => vec![data.clone()],
+                _ => Vec::new(),
+            },
+        };
+        if result.is_empty() && time_range.is_none() {
+            return Err(format!("Key {key:?} is not present").into());
         }
+        Ok(result)
     }
 
+    /// Returns the keys of the storage that currently have a value, along with its timestamp.
+    ///
+    /// With `History::All`, the keys whose latest version is a deletion are not returned: they are
+    /// returned by [`Storage::get_all_history_keys`] as long as they have versions retained.
     async fn get_all_entries(&self) -> ZResult<Vec<(Option<OwnedKeyExpr>, Timestamp)>> {
         let map = self.map.read().await;
         let mut result = Vec::with_capacity(map.len());
-        for (k, v) in map.iter() {
-            result.push((k.clone(), v.timestamp));
+        for (k, versions) in map.iter() {
+            if let Some(Version::Put(data)) = versions.last() {
+                result.push((k.clone(), data.timestamp));
+            }
         }
         Ok(result)
     }
+
+    async fn get_all_history_keys(&self) -> ZResult<Vec<Option<OwnedKeyExpr>>> {
+        Ok(self.map.read().await.keys().cloned().collect())
+    }
+}
+
+fn in_time_range(versions: &[Version], time_range: &TimeRange<SystemTime>) -> Vec<StoredData> {
+    versions
+        .iter()
+        .filter_map(|v| match v {
+            Version::Put(data)
+                if time_range.contains(data.timestamp.get_time().to_system_time()) =>
+            {
+                Some(data.clone())
+            }
+            _ => None,
+        })
+        .collect()
 }
 
 impl Drop for MemoryStorage {
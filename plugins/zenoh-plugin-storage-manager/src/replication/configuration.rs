@@ -23,6 +23,8 @@ use zenoh_backend_traits::config::ReplicaConfig;
 use super::{
     classification::{IntervalIdx, SubIntervalIdx},
     digest::Fingerprint,
+    filters::Filters,
+    log::EventMetadata,
 };
 
 /// The [Configuration] is, mostly, a thin wrapper around the [ReplicaConfig].
@@ -33,6 +35,13 @@ use super::{
 /// a Replica active on "replication/**" to receive and process the Digests emitted by a Replica
 /// active on "replication/a/*".
 ///
+/// The [Filters] are, on purpose, not part of the fingerprint: Replicas with different filters
+/// still exchange their Digest, aligning the key expressions they both replicate. The fingerprint
+/// is hence unchanged with the versions that do not support filters, which still decode the Digest
+/// of Replicas without filters (see [Digest::encode]).
+///
+/// [Digest::encode]: super::digest::Digest::encode
+///
 /// Using the newtype pattern allows us to add methods to compute the time classification of
 /// events.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -40,6 +49,7 @@ pub(crate) struct Configuration {
     storage_key_expr: OwnedKeyExpr,
     prefix: Option<OwnedKeyExpr>,
     replica_config: ReplicaConfig,
+    filters: Filters,
     fingerprint: Fingerprint,
 }
 
@@ -74,6 +84,7 @@ impl Configuration {
         Self {
             storage_key_expr,
             prefix,
+            filters: Filters::from(replica_config.filters.as_slice()),
             replica_config,
             fingerprint: Fingerprint::from(hasher.digest()),
         }
@@ -88,9 +99,20 @@ impl Configuration {
         self.prefix.as_ref()
     }
 
+    /// Returns the [Filters] restricting the key expressions that are replicated.
+    pub fn filters(&self) -> &Filters {
+        &self.filters
+    }
+
+    /// Returns true if the key expression of the provided [EventMetadata] is replicated.
+    pub fn replicates(&self, event: &EventMetadata) -> bool {
+        self.filters.replicates_event(self.prefix(), event)
+    }
+
     /// Returns the [Fingerprint] of the `Configuration`.
     ///
-    /// The fingerprint is the hash of all its fields, using the `xxhash_rust` crate.
+    /// The fingerprint is the hash of all its fields except the [Filters], using the
+    /// `xxhash_rust` crate.
     pub fn fingerprint(&self) -> Fingerprint {
         self.fingerprint
     }
@@ -36,9 +36,12 @@ use zenoh::{
 };
 
 use self::aligner_reply::AlignmentReply;
-use super::{digest::Digest, log::LogLatest, Action, Event, LogLatestKey};
+use super::{digest::Digest, log::LogLatest, Action, Event, LogLatestKey, ReplicationStatus};
 use crate::{
-    replication::core::aligner_query::AlignmentQuery,
+    replication::{
+        core::aligner_query::{AlignmentQuery, AlignmentRequest},
+        filters::Filters,
+    },
     storages_mgt::{LatestUpdates, StorageService},
 };
 
@@ -54,6 +57,7 @@ pub(crate) struct Replication {
     pub(crate) storage_key_expr: OwnedKeyExpr,
     pub(crate) latest_updates: Arc<RwLock<LatestUpdates>>,
     pub(crate) storage_service: Arc<StorageService>,
+    pub(crate) status: Arc<ReplicationStatus>,
 }
 
 impl Replication {
@@ -102,7 +106,7 @@ impl Replication {
         tokio::time::sleep(Duration::from_millis(delay)).await;
 
         if let Err(e) = self
-            .spawn_query_replica_aligner(ke_all_replicas, AlignmentQuery::Discovery)
+            .spawn_query_replica_aligner(ke_all_replicas, AlignmentQuery::Discovery, None)
             .await
         {
             tracing::error!("Initial alignment failed with: {e:?}");
@@ -224,7 +228,7 @@ impl Replication {
                     };
                 }
 
-                if let Err(e) = bincode::serialize_into(&mut serialization_buffer, &digest) {
+                if let Err(e) = digest.encode(&mut serialization_buffer) {
                     tracing::warn!("Failed to serialise the replication Digest: {e:?}");
                     continue;
                 }
@@ -250,7 +254,10 @@ impl Replication {
                     )
                     .await
                 {
-                    Ok(_) => tracing::trace!("Published Digest: {digest:?}"),
+                    Ok(_) => {
+                        tracing::trace!("Published Digest: {digest:?}");
+                        replication.status.digest_published();
+                    }
                     Err(e) => tracing::error!("Failed to publish the replication Digest: {e:?}"),
                 }
 
@@ -350,55 +357,125 @@ impl Replication {
                     // Async block such that we can `instrument` it in an asynchronous compatible
                     // manner using the `span` we created just above.
                     async {
-                        let other_digest =
-                            match bincode::deserialize::<Digest>(&sample.payload().to_bytes()) {
-                                Ok(other_digest) => other_digest,
+                        let other_digest = match Digest::decode(&sample.payload().to_bytes()) {
+                            Ok(other_digest) => other_digest,
+                            Err(e) => {
+                                tracing::warn!(
+                                    "Failed to deserialize Payload as Digest: {e:?}. Skipping."
+                                );
+                                return;
+                            }
+                        };
+
+                        tracing::debug!("Replication digest received");
+                        replication
+                            .status
+                            .digest_received(source_zid.as_str(), &other_digest.filters);
+
+                        // The Digest of a Replica with different filters cannot be compared with
+                        // ours: we request its Digest restricted to the key expressions we both
+                        // replicate.
+                        let (alignment_query, replica_filters) = if other_digest.filters
+                            != *configuration.filters()
+                        {
+                            (AlignmentQuery::Digest, Some(other_digest.filters))
+                        } else {
+                            let digest = match replication.replication_log.read().await.digest() {
+                                Ok(digest) => digest,
                                 Err(e) => {
-                                    tracing::warn!(
-                                        "Failed to deserialize Payload as Digest: {e:?}. Skipping."
+                                    tracing::error!(
+                                        "Fatal error, failed to compute local Digest: \
+                                                 {e:?}"
                                     );
                                     return;
                                 }
                             };
 
-                        tracing::debug!("Replication digest received");
+                            let digest_diff = digest.diff(other_digest);
+                            replication
+                                .status
+                                .digests_compared(source_zid.as_str(), digest_diff.as_ref());
+                            match digest_diff {
+                                Some(digest_diff) => {
+                                    tracing::debug!(
+                                        "Potential misalignment detected: {digest_diff:?}"
+                                    );
+                                    (AlignmentQuery::Diff(digest_diff), None)
+                                }
+                                None => return,
+                            }
+                        };
 
-                        let digest = match replication.replication_log.read().await.digest() {
-                            Ok(digest) => digest,
+                        let replica_aligner_ke = match keformat!(
+                            aligner_key_expr_formatter::formatter(),
+                            hash_configuration = *configuration.fingerprint(),
+                            zid = source_zid,
+                        ) {
+                            Ok(key) => key,
                             Err(e) => {
-                                tracing::error!(
-                                    "Fatal error, failed to compute local Digest: {e:?}"
+                                tracing::warn!(
+                                    "Failed to generate a key expression to contact aligner: \
+                                     {e:?}"
                                 );
                                 return;
                             }
                         };
 
-                        if let Some(digest_diff) = digest.diff(other_digest) {
-                            tracing::debug!("Potential misalignment detected: {digest_diff:?}");
+                        replication.spawn_query_replica_aligner(
+                            replica_aligner_ke,
+                            alignment_query,
+                            replica_filters,
+                        );
+                    }
+                    .instrument(span)
+                    .await;
+                }
+            }
+        })
+    }
 
-                            let replica_aligner_ke = match keformat!(
-                                aligner_key_expr_formatter::formatter(),
-                                hash_configuration = *configuration.fingerprint(),
-                                zid = source_zid,
-                            ) {
-                                Ok(key) => key,
-                                Err(e) => {
-                                    tracing::warn!(
-                                        "Failed to generate a key expression to contact aligner: \
-                                         {e:?}"
-                                    );
-                                    return;
-                                }
-                            };
+    /// Spawns a task that handles the alignments requested through the admin space.
+    ///
+    /// Upon request, the Digest of all the Replicas from which a Digest was received is queried,
+    /// without waiting for their next publication. If it differs from the local Digest, an
+    /// alignment is started.
+    pub(crate) fn spawn_alignment_requests_handler(&self) -> JoinHandle<()> {
+        let replication = self.clone();
+
+        tokio::task::spawn(async move {
+            let configuration = replication
+                .replication_log
+                .read()
+                .await
+                .configuration
+                .clone();
 
-                            replication.spawn_query_replica_aligner(
-                                replica_aligner_ke,
-                                AlignmentQuery::Diff(digest_diff),
+            loop {
+                replication.status.alignment_requested().await;
+                tracing::debug!("Alignment requested");
+
+                for (replica_zid, replica_filters) in replication.status.replicas() {
+                    let replica_aligner_ke = match keformat!(
+                        aligner_key_expr_formatter::formatter(),
+                        hash_configuration = *configuration.fingerprint(),
+                        zid = replica_zid,
+                    ) {
+                        Ok(key) => key,
+                        Err(e) => {
+                            tracing::warn!(
+                                "Failed to generate a key expression to contact aligner: {e:?}"
                             );
+                            continue;
                         }
-                    }
-                    .instrument(span)
-                    .await;
+                    };
+
+                    let replica_filters =
+                        (replica_filters != *configuration.filters()).then_some(replica_filters);
+                    replication.spawn_query_replica_aligner(
+                        replica_aligner_ke,
+                        AlignmentQuery::Digest,
+                        replica_filters,
+                    );
                 }
             }
         })
@@ -479,20 +556,36 @@ impl Replication {
     /// information), consequently spawning a new task.
     ///
     /// This process is stateless and all the required information are carried in the query / reply.
+    ///
+    /// If the filters of the remote Replica differ from ours, they must be provided as
+    /// `replica_filters`: the replies are then processed considering only the key expressions
+    /// replicated by both Replicas.
     pub(crate) fn spawn_query_replica_aligner(
         &self,
         replica_aligner_ke: OwnedKeyExpr,
         alignment_query: AlignmentQuery,
+        replica_filters: Option<Filters>,
     ) -> JoinHandle<()> {
         let replication = self.clone();
         tokio::task::spawn(async move {
-            let attachment = match bincode::serialize(&alignment_query) {
+            let alignment_request = AlignmentRequest {
+                query: alignment_query,
+                filters: replication
+                    .replication_log
+                    .read()
+                    .await
+                    .configuration()
+                    .filters()
+                    .clone(),
+            };
+            let attachment = match alignment_request.encode() {
                 Ok(attachment) => attachment,
                 Err(e) => {
-                    tracing::error!("Failed to serialize AlignmentQuery: {e:?}");
+                    tracing::error!("Failed to serialize AlignmentRequest: {e:?}");
                     return;
                 }
             };
+            let alignment_query = alignment_request.query;
 
             // NOTE: We need to put the Consolidation to `None` as otherwise if multiple replies are
             //       sent, they will be "consolidated" and only one of them will make it through.
@@ -559,6 +652,7 @@ impl Replication {
                                 replica_aligner_ke.clone(),
                                 alignment_reply,
                                 sample,
+                                replica_filters.as_ref(),
                             )
                             .await;
 
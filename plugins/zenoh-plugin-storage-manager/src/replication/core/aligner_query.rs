@@ -26,6 +26,7 @@ use crate::replication::{
     classification::{IntervalIdx, SubIntervalIdx},
     core::Replication,
     digest::DigestDiff,
+    filters::Filters,
     log::{Action, EventMetadata},
 };
 
@@ -48,6 +49,10 @@ use crate::replication::{
 /// The `Discovery` and `All` variants are used to perform the initial alignment. After receiving a
 /// `Discovery` Query, a Replica will reply with its Zenoh ID. The Replica that replied first will
 /// then receive an `All` Query to transfer all its content.
+///
+/// The `Digest` variant is used when the filters of the Replicas differ: their Digests cannot be
+/// compared directly and a Replica requests the Digest restricted to the key expressions they both
+/// replicate.
 #[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub(crate) enum AlignmentQuery {
     /// Ask Replica for their Zenoh ID to perform an initial alignment.
@@ -62,6 +67,48 @@ pub(crate) enum AlignmentQuery {
     SubIntervals(HashMap<IntervalIdx, HashSet<SubIntervalIdx>>),
     /// Request the Payload associated with the provided EventMetadata.
     Events(Vec<EventMetadata>),
+    /// Request the Digest restricted to the key expressions replicated by both Replicas.
+    Digest,
+}
+
+/// The `AlignmentRequest` is the attachment of a Query sent to the Aligner of a Replica: the
+/// [AlignmentQuery] along with the [Filters] of the Replica that sent it.
+///
+/// If the [Filters] differ from its own, the Aligner restricts its replies to the key expressions
+/// replicated by both Replicas.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct AlignmentRequest {
+    pub(crate) query: AlignmentQuery,
+    pub(crate) filters: Filters,
+}
+
+impl AlignmentRequest {
+    /// Serializes the `AlignmentRequest`.
+    ///
+    /// As for the [Digest], the [Filters] follow the [AlignmentQuery] and only if some are
+    /// configured: without filters, the attachment is the [AlignmentQuery] sent by the versions
+    /// that do not support them.
+    ///
+    /// [Digest]: crate::replication::digest::Digest
+    pub(crate) fn encode(&self) -> bincode::Result<Vec<u8>> {
+        let mut buffer = bincode::serialize(&self.query)?;
+        if !self.filters.is_empty() {
+            bincode::serialize_into(&mut buffer, &self.filters)?;
+        }
+        Ok(buffer)
+    }
+
+    /// Deserializes an `AlignmentRequest` serialized with [AlignmentRequest::encode], or the
+    /// [AlignmentQuery] sent by a version that does not support [Filters].
+    pub(crate) fn decode(mut bytes: &[u8]) -> bincode::Result<Self> {
+        let query = bincode::deserialize_from(&mut bytes)?;
+        let filters = if bytes.is_empty() {
+            Filters::default()
+        } else {
+            bincode::deserialize(bytes)?
+        };
+        Ok(Self { query, filters })
+    }
 }
 
 impl Replication {
@@ -79,17 +126,28 @@ impl Replication {
             }
         };
 
-        let alignment_query = match bincode::deserialize::<AlignmentQuery>(&attachment.to_bytes()) {
+        let AlignmentRequest {
+            query: alignment_query,
+            filters,
+        } = match AlignmentRequest::decode(&attachment.to_bytes()) {
             Ok(alignment) => alignment,
             Err(e) => {
                 tracing::error!(
-                    "Failed to deserialize `attachment` of received Query into AlignmentQuery: \
+                    "Failed to deserialize `attachment` of received Query into AlignmentRequest: \
                      {e:?}"
                 );
                 return;
             }
         };
 
+        let replica_filters =
+            if filters != *self.replication_log.read().await.configuration().filters() {
+                Some(filters)
+            } else {
+                None
+            };
+        let replica_filters = replica_filters.as_ref();
+
         match alignment_query {
             AlignmentQuery::Discovery => {
                 tracing::trace!("Processing `AlignmentQuery::Discovery`");
@@ -114,19 +172,21 @@ impl Replication {
 
                 for interval_idx in idx_intervals {
                     let mut events_to_retrieve = Vec::default();
-                    if let Some(interval) = self
-                        .replication_log
-                        .read()
-                        .await
-                        .intervals
-                        .get(&interval_idx)
                     {
-                        interval.sub_intervals().for_each(|(_, sub_interval)| {
-                            events_to_retrieve.extend(sub_interval.events().map(Into::into));
-                        });
+                        let log = self.replication_log.read().await;
+                        if let Some(interval) = log.intervals.get(&interval_idx) {
+                            interval.sub_intervals().for_each(|(_, sub_interval)| {
+                                events_to_retrieve.extend(
+                                    sub_interval
+                                        .events()
+                                        .filter(|event| log.replicates(event, replica_filters))
+                                        .map(Into::into),
+                                );
+                            });
+                        }
                     }
 
-                    // NOTE: As we took the lock in the `if let` block, it is released here,
+                    // NOTE: As we took the lock in the block above, it is released here,
                     // diminishing contention.
                     for event_to_retrieve in events_to_retrieve {
                         self.reply_event_retrieval(&query, event_to_retrieve).await;
@@ -136,38 +196,72 @@ impl Replication {
             AlignmentQuery::Diff(digest_diff) => {
                 tracing::trace!("Processing `AlignmentQuery::Diff`");
                 if digest_diff.cold_eras_differ {
-                    self.reply_cold_era(&query).await;
+                    self.reply_cold_era(&query, replica_filters).await;
                 }
 
                 if !digest_diff.warm_eras_differences.is_empty() {
-                    self.reply_sub_intervals(&query, digest_diff.warm_eras_differences)
-                        .await;
+                    self.reply_sub_intervals(
+                        &query,
+                        digest_diff.warm_eras_differences,
+                        replica_filters,
+                    )
+                    .await;
                 }
 
                 if !digest_diff.hot_eras_differences.is_empty() {
-                    self.reply_events_metadata(&query, digest_diff.hot_eras_differences)
-                        .await;
+                    self.reply_events_metadata(
+                        &query,
+                        digest_diff.hot_eras_differences,
+                        replica_filters,
+                    )
+                    .await;
                 }
             }
             AlignmentQuery::Intervals(different_intervals) => {
                 tracing::trace!("Processing `AlignmentQuery::Intervals`");
                 if !different_intervals.is_empty() {
-                    self.reply_sub_intervals(&query, different_intervals).await;
+                    self.reply_sub_intervals(&query, different_intervals, replica_filters)
+                        .await;
                 }
             }
             AlignmentQuery::SubIntervals(different_sub_intervals) => {
                 tracing::trace!("Processing `AlignmentQuery::SubIntervals`");
                 if !different_sub_intervals.is_empty() {
-                    self.reply_events_metadata(&query, different_sub_intervals)
+                    self.reply_events_metadata(&query, different_sub_intervals, replica_filters)
                         .await;
                 }
             }
             AlignmentQuery::Events(events_to_retrieve) => {
                 tracing::trace!("Processing `AlignmentQuery::Events`");
+                let events_to_retrieve = {
+                    let log = self.replication_log.read().await;
+                    events_to_retrieve
+                        .into_iter()
+                        .filter(|event| log.replicates(event, replica_filters))
+                        .collect::<Vec<_>>()
+                };
                 for event_to_retrieve in events_to_retrieve {
                     self.reply_event_retrieval(&query, event_to_retrieve).await;
                 }
             }
+            AlignmentQuery::Digest => {
+                tracing::trace!("Processing `AlignmentQuery::Digest`");
+                let digest = {
+                    let log = self.replication_log.read().await;
+                    match replica_filters {
+                        Some(replica_filters) => log.digest_for(replica_filters),
+                        None => log.digest(),
+                    }
+                };
+                match digest {
+                    Ok(digest) => {
+                        reply_to_query(&query, AlignmentReply::Digest(digest), None).await;
+                    }
+                    Err(e) => {
+                        tracing::error!("Fatal error, failed to compute local Digest: {e:?}");
+                    }
+                }
+            }
         }
     }
 
@@ -184,7 +278,7 @@ impl Replication {
     /// We believe this is not important: the Replication Log does not separate the Intervals based
     /// on their Era so performing this comparison will still be relevant — even if an Interval is
     /// in the Cold Era on one end and in the Warm Era in the other.
-    pub(crate) async fn reply_cold_era(&self, query: &Query) {
+    pub(crate) async fn reply_cold_era(&self, query: &Query, replica_filters: Option<&Filters>) {
         let log = self.replication_log.read().await;
         let configuration = log.configuration();
         let last_elapsed_interval = match configuration.last_elapsed_interval() {
@@ -202,7 +296,7 @@ impl Replication {
             log.intervals
                 .iter()
                 .filter(|(&idx, _)| idx < warm_era_lower_bound)
-                .map(|(idx, interval)| (*idx, interval.fingerprint()))
+                .map(|(idx, interval)| (*idx, log.interval_fingerprint(interval, replica_filters)))
                 .collect::<HashMap<_, _>>()
         });
 
@@ -210,13 +304,15 @@ impl Replication {
     }
 
     /// Replies to the [Query] with a structure containing, for each Interval index present in the
-    /// `different_intervals`, all the [SubInterval]s [Fingerprint].
+    /// `different_intervals`, all the [SubInterval]s [Fingerprint] — restricted, if they are
+    /// provided, to the key expressions replicated with the `replica_filters`.
     ///
     /// The Replica will use this structure to assess which [SubInterval]s differ.
     pub(crate) async fn reply_sub_intervals(
         &self,
         query: &Query,
         different_intervals: HashSet<IntervalIdx>,
+        replica_filters: Option<&Filters>,
     ) {
         let mut sub_intervals_fingerprints = HashMap::with_capacity(different_intervals.len());
 
@@ -224,8 +320,10 @@ impl Replication {
             let log = self.replication_log.read().await;
             different_intervals.iter().for_each(|interval_idx| {
                 if let Some(interval) = log.intervals.get(interval_idx) {
-                    sub_intervals_fingerprints
-                        .insert(*interval_idx, interval.sub_intervals_fingerprints());
+                    sub_intervals_fingerprints.insert(
+                        *interval_idx,
+                        log.sub_intervals_fingerprints(interval, replica_filters),
+                    );
                 }
             });
         }
@@ -235,7 +333,8 @@ impl Replication {
     }
 
     /// Replies to the [Query] with all the [EventMetadata] of the [Event]s present in the
-    /// [SubInterval]s listed in `different_sub_intervals`.
+    /// [SubInterval]s listed in `different_sub_intervals` — restricted, if they are provided, to
+    /// the key expressions replicated with the `replica_filters`.
     ///
     /// The Replica will use this structure to assess which [Event] (and its associated payload) are
     /// missing in its Replication Log and connected Storage.
@@ -243,6 +342,7 @@ impl Replication {
         &self,
         query: &Query,
         different_sub_intervals: HashMap<IntervalIdx, HashSet<SubIntervalIdx>>,
+        replica_filters: Option<&Filters>,
     ) {
         let mut events = Vec::default();
         {
@@ -253,7 +353,12 @@ impl Replication {
                     if let Some(interval) = log.intervals.get(interval_idx) {
                         sub_intervals.iter().for_each(|sub_interval_idx| {
                             if let Some(sub_interval) = interval.sub_interval_at(sub_interval_idx) {
-                                events.extend(sub_interval.events().map(Into::into));
+                                events.extend(
+                                    sub_interval
+                                        .events()
+                                        .filter(|event| log.replicates(event, replica_filters))
+                                        .map(Into::into),
+                                );
                             }
                         });
                     }
@@ -331,18 +436,26 @@ impl Replication {
             }
         };
 
-        reply_to_query(query, AlignmentReply::Retrieval(event_to_retrieve), value).await;
+        if reply_to_query(query, AlignmentReply::Retrieval(event_to_retrieve), value).await {
+            self.status.event_pushed();
+        }
     }
 }
 
 /// Replies to a Query, adding the [AlignmentReply] as an attachment and, if provided, the payload
 /// with the corresponding [zenoh::bytes::Encoding].
-async fn reply_to_query(query: &Query, reply: AlignmentReply, value: Option<(ZBytes, Encoding)>) {
+///
+/// Returns `true` if the reply was sent.
+async fn reply_to_query(
+    query: &Query,
+    reply: AlignmentReply,
+    value: Option<(ZBytes, Encoding)>,
+) -> bool {
     let attachment = match bincode::serialize(&reply) {
         Ok(attachment) => attachment,
         Err(e) => {
             tracing::error!("Failed to serialize AlignmentReply: {e:?}");
-            return;
+            return false;
         }
     };
 
@@ -359,5 +472,8 @@ async fn reply_to_query(query: &Query, reply: AlignmentReply, value: Option<(ZBy
 
     if let Err(e) = reply_fut.await {
         tracing::error!("Failed to reply to Query: {e:?}");
+        return false;
     }
+
+    true
 }
@@ -29,7 +29,8 @@ use crate::{
     replication::{
         classification::{EventRemoval, IntervalIdx, SubIntervalIdx},
         core::{aligner_key_expr_formatter, aligner_query::AlignmentQuery, Replication},
-        digest::Fingerprint,
+        digest::{Digest, Fingerprint},
+        filters::Filters,
         log::{Action, EventMetadata},
         Event, LogLatest,
     },
@@ -55,6 +56,7 @@ pub(crate) enum AlignmentReply {
     SubIntervals(HashMap<IntervalIdx, HashMap<SubIntervalIdx, Fingerprint>>),
     EventsMetadata(Vec<EventMetadata>),
     Retrieval(EventMetadata),
+    Digest(Digest),
 }
 
 impl Replication {
@@ -95,12 +97,21 @@ impl Replication {
     ///
     ///   ⚠️ Note that only one [Event] is sent per reply but multiple replies are sent to the same
     ///   Query (by setting `Consolidation::None`).
+    ///
+    ///
+    /// - Digest: the remote Replica, which has different filters, sent its [Digest] restricted to
+    ///   the key expressions both Replicas replicate. This Replica needs to compare it with its
+    ///   own, restricted in the same way, and, if they differ, start an alignment.
+    ///
+    /// If the filters of the remote Replica differ, they are provided as `replica_filters` and the
+    /// comparisons are restricted to the key expressions replicated by both Replicas.
     #[tracing::instrument(skip_all, fields(storage = self.storage_key_expr.as_str(), replica = replica_aligner_ke.as_str(), sample, t))]
     pub(crate) async fn process_alignment_reply(
         &self,
         replica_aligner_ke: OwnedKeyExpr,
         alignment_reply: AlignmentReply,
         sample: Sample,
+        replica_filters: Option<&Filters>,
     ) {
         match alignment_reply {
             AlignmentReply::Discovery(replica_zid) => {
@@ -130,7 +141,7 @@ impl Replication {
                 tracing::debug!("Performing initial alignment with Replica < {replica_zid} >");
 
                 if let Err(e) = self
-                    .spawn_query_replica_aligner(replica_aligner_ke, AlignmentQuery::All)
+                    .spawn_query_replica_aligner(replica_aligner_ke, AlignmentQuery::All, None)
                     .await
                 {
                     tracing::error!("Error returned while performing the initial alignment: {e:?}");
@@ -143,7 +154,11 @@ impl Replication {
                     replica_intervals
                         .into_iter()
                         .filter(|(idx, fp)| match replication_log_guard.intervals.get(idx) {
-                            Some(interval) => interval.fingerprint() != *fp,
+                            Some(interval) => {
+                                replication_log_guard
+                                    .interval_fingerprint(interval, replica_filters)
+                                    != *fp
+                            }
                             None => true,
                         })
                         .map(|(idx, _)| idx)
@@ -154,6 +169,7 @@ impl Replication {
                     self.spawn_query_replica_aligner(
                         replica_aligner_ke,
                         AlignmentQuery::Intervals(intervals_diff),
+                        replica_filters.cloned(),
                     );
                 }
             }
@@ -177,7 +193,10 @@ impl Replication {
                                         match interval.sub_interval_at(sub_idx) {
                                             None => true,
                                             Some(sub_interval) => {
-                                                sub_interval.fingerprint() != *sub_fp
+                                                replication_log_guard.sub_interval_fingerprint(
+                                                    sub_interval,
+                                                    replica_filters,
+                                                ) != *sub_fp
                                             }
                                         }
                                     })
@@ -195,6 +214,7 @@ impl Replication {
                     self.spawn_query_replica_aligner(
                         replica_aligner_ke,
                         AlignmentQuery::SubIntervals(sub_intervals_diff),
+                        replica_filters.cloned(),
                     );
                 }
             }
@@ -202,6 +222,14 @@ impl Replication {
                 tracing::trace!("Processing `AlignmentReply::EventsMetadata`");
                 let mut diff_events = Vec::default();
 
+                let replica_events = {
+                    let replication_log_guard = self.replication_log.read().await;
+                    replica_events
+                        .into_iter()
+                        .filter(|event| replication_log_guard.replicates(event, None))
+                        .collect::<Vec<_>>()
+                };
+
                 for replica_event in replica_events {
                     tracing::trace!("Checking if < {replica_event:?} > is missing");
                     if let Some(missing_event_metadata) =
@@ -219,11 +247,61 @@ impl Replication {
                     self.spawn_query_replica_aligner(
                         replica_aligner_ke,
                         AlignmentQuery::Events(diff_events),
+                        replica_filters.cloned(),
                     );
                 }
             }
             AlignmentReply::Retrieval(replica_event) => {
-                self.process_event_retrieval(replica_event, sample).await;
+                if !self
+                    .replication_log
+                    .read()
+                    .await
+                    .replicates(&replica_event, None)
+                {
+                    tracing::trace!("Skipping < {replica_event:?} >: it is not replicated");
+                    return;
+                }
+
+                if self.process_event_retrieval(replica_event, sample).await {
+                    self.status.event_pulled();
+                }
+            }
+            AlignmentReply::Digest(replica_digest) => {
+                tracing::trace!("Processing `AlignmentReply::Digest`");
+                let digest = {
+                    let replication_log_guard = self.replication_log.read().await;
+                    match replica_filters {
+                        Some(replica_filters) => replication_log_guard.digest_for(replica_filters),
+                        None => replication_log_guard.digest(),
+                    }
+                };
+                let digest = match digest {
+                    Ok(digest) => digest,
+                    Err(e) => {
+                        tracing::error!("Fatal error, failed to compute local Digest: {e:?}");
+                        return;
+                    }
+                };
+
+                let digest_diff = digest.diff(replica_digest);
+                match aligner_key_expr_formatter::parse(&replica_aligner_ke) {
+                    Ok(parsed_ke) => self
+                        .status
+                        .digests_compared(parsed_ke.zid().as_str(), digest_diff.as_ref()),
+                    Err(e) => tracing::error!(
+                        "Failed to parse < {replica_aligner_ke} > as a valid Aligner key \
+                         expression: {e:?}"
+                    ),
+                }
+
+                if let Some(digest_diff) = digest_diff {
+                    tracing::debug!("Potential misalignment detected: {digest_diff:?}");
+                    self.spawn_query_replica_aligner(
+                        replica_aligner_ke,
+                        AlignmentQuery::Diff(digest_diff),
+                        replica_filters.cloned(),
+                    );
+                }
             }
         }
     }
@@ -308,6 +386,8 @@ impl Replication {
     /// Processes the [EventMetadata] and [Sample] sent by the remote Replica, adding it to our
     /// Storage if needed.
     ///
+    /// Returns `true` if the event was applied.
+    ///
     /// # Special case: initial alignment
     ///
     /// Outside of the initial alignment, an [EventMetadata] with an action set to `Delete` will be
@@ -318,7 +398,7 @@ impl Replication {
     /// That fact is true except for the initial alignment: the initial alignment bypasses all these
     /// steps and the Replica goes straight to sending all its Replication Log and data in its
     /// Storage. Including for the deleted events.
-    async fn process_event_retrieval(&self, replica_event: EventMetadata, sample: Sample) {
+    async fn process_event_retrieval(&self, replica_event: EventMetadata, sample: Sample) -> bool {
         tracing::trace!("Processing `AlignmentReply::Retrieval` for < {replica_event:?} >");
 
         if self
@@ -328,7 +408,7 @@ impl Replication {
             .get(&replica_event.log_key())
             .is_some_and(|latest_event| latest_event.timestamp() >= replica_event.timestamp())
         {
-            return;
+            return false;
         }
 
         let mut replication_log_guard = self.replication_log.write().await;
@@ -337,7 +417,7 @@ impl Replication {
             .needs_further_processing(&mut replication_log_guard, &replica_event)
             .await
         {
-            return;
+            return false;
         }
 
         // The Event is newer than what we have and is not overridden by a Wildcard Update, we
@@ -386,7 +466,7 @@ impl Replication {
                     // before we have time to store this one.
                     //
                     // In that scenario the Storage should either return an error or `Outdated`.
-                    return;
+                    return false;
                 }
             }
             Action::WildcardPut(_) => {
@@ -406,6 +486,7 @@ impl Replication {
         // NOTE: We can only safely call `insert_event_unchecked` because we called earlier
         // `replication_log_guard.remove_older`.
         replication_log_guard.insert_event_unchecked(replica_event.into());
+        true
     }
 
     /// Returns `true` if the provided `replica_event` requires more processing.
@@ -14,12 +14,16 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    io::Write,
     ops::{BitXor, BitXorAssign, Deref},
 };
 
 use serde::{Deserialize, Serialize};
 
-use super::classification::{IntervalIdx, SubIntervalIdx};
+use super::{
+    classification::{IntervalIdx, SubIntervalIdx},
+    filters::Filters,
+};
 
 /// A [Fingerprint] is a 64 bits hash of the content it "represents".
 ///
@@ -70,6 +74,12 @@ impl From<u64> for Fingerprint {
 /// Eras are further divided into [Interval]s and [SubInterval]s — which duration and number can be
 /// configured.
 ///
+/// A `Digest` also carries the [Filters] of the Replica that generated it: two Replicas with
+/// different [Filters] compare the Digests restricted to the key expressions they both replicate,
+/// which they request to each other. When it is published, a `Digest` is encoded with
+/// [Digest::encode] such that Replicas without filters keep exchanging Digests with the versions
+/// that do not support them.
+///
 /// [Event]: super::log::Event
 /// [Timestamp]: zenoh::time::Timestamp
 /// [Interval]: super::classification::Interval
@@ -80,6 +90,7 @@ pub struct Digest {
     pub(crate) cold_era_fingerprint: Fingerprint,
     pub(crate) warm_era_fingerprints: HashMap<IntervalIdx, Fingerprint>,
     pub(crate) hot_era_fingerprints: HashMap<IntervalIdx, HashMap<SubIntervalIdx, Fingerprint>>,
+    pub(crate) filters: Filters,
 }
 
 /// The `DigestDiff` summarises the differences between two [Digest]s.
@@ -98,6 +109,52 @@ pub struct DigestDiff {
 }
 
 impl Digest {
+    /// Serializes the `Digest` into the `writer`, as it is published.
+    ///
+    /// The [Filters] are serialized after the other fields and only if some are configured: the
+    /// `Digest` of a Replica without filters is serialized as by the versions that do not support
+    /// them, and these versions, ignoring the trailing bytes, can deserialize the `Digest` of a
+    /// Replica with filters.
+    pub(crate) fn encode(&self, mut writer: impl Write) -> bincode::Result<()> {
+        bincode::serialize_into(
+            &mut writer,
+            &(
+                &self.configuration_fingerprint,
+                &self.cold_era_fingerprint,
+                &self.warm_era_fingerprints,
+                &self.hot_era_fingerprints,
+            ),
+        )?;
+        if !self.filters.is_empty() {
+            bincode::serialize_into(writer, &self.filters)?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes a `Digest` serialized with [Digest::encode], or by a version that does not
+    /// support [Filters].
+    pub(crate) fn decode(mut bytes: &[u8]) -> bincode::Result<Self> {
+        let (
+            configuration_fingerprint,
+            cold_era_fingerprint,
+            warm_era_fingerprints,
+            hot_era_fingerprints,
+        ) = bincode::deserialize_from(&mut bytes)?;
+        let filters = if bytes.is_empty() {
+            Filters::default()
+        } else {
+            bincode::deserialize(bytes)?
+        };
+
+        Ok(Self {
+            configuration_fingerprint,
+            cold_era_fingerprint,
+            warm_era_fingerprints,
+            hot_era_fingerprints,
+            filters,
+        })
+    }
+
     /// Returns a [DigestDiff] if the two [Digest] differ, `None` otherwise.
     ///
     /// Two Digests are considered different if any of the following is true:
@@ -0,0 +1,139 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use serde::{Deserialize, Serialize};
+use zenoh::key_expr::{keyexpr, OwnedKeyExpr};
+use zenoh_backend_traits::config::{ReplicationFilter, ReplicationFilterKind};
+
+use super::log::{Action, EventMetadata};
+
+/// The `Filters` restrict the key expressions a Replica aligns with the other Replicas.
+///
+/// A key expression is replicated according to the matching filter with the highest priority, an
+/// `exclude` filter winning over an `include` filter of same priority. A key expression matching
+/// no filter is replicated only if no `include` filter is configured — hence, without any filter,
+/// everything is replicated.
+///
+/// The filters are sorted by decreasing priority, the `exclude` filters first for the same
+/// priority, such that the first matching filter decides and such that two Replicas configured
+/// with the same filters, in any order, have equal `Filters`.
+///
+/// The `Filters` are exchanged between Replicas: two Replicas with different `Filters` only align
+/// the key expressions they both replicate.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub(crate) struct Filters {
+    filters: Vec<Filter>,
+    has_include: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+struct Filter {
+    key_expr: OwnedKeyExpr,
+    exclude: bool,
+    priority: u32,
+}
+
+impl Filter {
+    /// Returns true if the filter applies to the provided key expression.
+    ///
+    /// A key expression can contain wildcards if it is the one of a Wildcard Update: an `include`
+    /// filter applies if they intersect (some of the keys it updates are replicated), an `exclude`
+    /// filter only if it includes it (none of the keys it updates are replicated).
+    fn matches(&self, key_expr: &keyexpr) -> bool {
+        if self.exclude {
+            self.key_expr.includes(key_expr)
+        } else {
+            self.key_expr.intersects(key_expr)
+        }
+    }
+}
+
+impl From<&[ReplicationFilter]> for Filters {
+    fn from(replication_filters: &[ReplicationFilter]) -> Self {
+        let mut filters = replication_filters
+            .iter()
+            .map(|filter| Filter {
+                key_expr: filter.key_expr.clone(),
+                exclude: filter.kind == ReplicationFilterKind::Exclude,
+                priority: filter.priority,
+            })
+            .collect::<Vec<_>>();
+        filters.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(b.exclude.cmp(&a.exclude))
+                .then(a.key_expr.as_str().cmp(b.key_expr.as_str()))
+        });
+        filters.dedup();
+
+        Self {
+            has_include: filters.iter().any(|filter| !filter.exclude),
+            filters,
+        }
+    }
+}
+
+impl Filters {
+    /// Returns true if no filter is configured, i.e. if everything is replicated.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Returns true if the provided (full) key expression is replicated.
+    pub(crate) fn replicates(&self, key_expr: &keyexpr) -> bool {
+        match self.filters.iter().find(|filter| filter.matches(key_expr)) {
+            Some(filter) => !filter.exclude,
+            None => !self.has_include,
+        }
+    }
+
+    /// Returns true if the key expression of the provided [EventMetadata] is replicated.
+    ///
+    /// As the key expression of an [EventMetadata] is stripped, the `prefix` of the Storage is
+    /// added back before applying the filters — except for Wildcard Updates that keep their full
+    /// key expression.
+    pub(crate) fn replicates_event(
+        &self,
+        prefix: Option<&OwnedKeyExpr>,
+        event: &EventMetadata,
+    ) -> bool {
+        if self.filters.is_empty() {
+            return true;
+        }
+
+        match &event.action {
+            Action::WildcardPut(wildcard_ke) | Action::WildcardDelete(wildcard_ke) => {
+                self.replicates(wildcard_ke)
+            }
+            Action::Put | Action::Delete => {
+                match crate::prefix(prefix, event.stripped_key.as_ref()) {
+                    Ok(key_expr) => self.replicates(&key_expr),
+                    Err(e) => {
+                        tracing::error!(
+                            "Internal error while attempting to prefix < {:?} > with < {:?} >: \
+                             {e:?}",
+                            event.stripped_key,
+                            prefix
+                        );
+                        false
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/filters.test.rs"]
+mod tests;
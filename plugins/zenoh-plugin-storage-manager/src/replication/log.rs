@@ -15,17 +15,23 @@
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     ops::Deref,
+    sync::Mutex,
 };
 
 use bloomfilter::Bloom;
 use serde::{Deserialize, Serialize};
-use zenoh::{key_expr::OwnedKeyExpr, sample::SampleKind, time::Timestamp, Result as ZResult};
+use zenoh::{
+    internal::zlock, key_expr::OwnedKeyExpr, sample::SampleKind, time::Timestamp, Result as ZResult,
+};
 use zenoh_backend_traits::config::ReplicaConfig;
 
 use super::{
-    classification::{EventLookup, EventRemoval, Interval, IntervalIdx},
+    classification::{
+        EventLookup, EventRemoval, Interval, IntervalIdx, SubInterval, SubIntervalIdx,
+    },
     configuration::Configuration,
     digest::{Digest, Fingerprint},
+    filters::Filters,
 };
 
 /// The `Action` enumeration facilitates dealing with Wildcard Updates. It is a super-set of
@@ -307,8 +313,18 @@ pub struct LogLatest {
     pub(crate) configuration: Configuration,
     pub(crate) intervals: BTreeMap<IntervalIdx, Interval>,
     pub(crate) bloom_filter_event: Bloom<LogLatestKey>,
+    /// The [Fingerprint]s of the [SubInterval]s restricted to the key expressions replicated with
+    /// the [Filters] of remote Replicas, indexed by their unrestricted [Fingerprint] — which only
+    /// changes with their content.
+    ///
+    /// [SubInterval]: super::classification::SubInterval
+    filtered_fingerprints: Mutex<HashMap<Filters, HashMap<Fingerprint, Fingerprint>>>,
 }
 
+/// The maximum number of sets of [Filters] whose restricted [Fingerprint]s are cached by the
+/// [LogLatest]. Beyond, the cache is cleared.
+const FILTERED_FINGERPRINTS_CAPACITY: usize = 16;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct LogLatestKey {
     maybe_stripped_key: Option<OwnedKeyExpr>,
@@ -352,6 +368,7 @@ impl LogLatest {
             //
             // 2 << 22 = 4_194_304 items.
             bloom_filter_event: Bloom::new_for_fp_rate(2 << 22, 0.01),
+            filtered_fingerprints: Mutex::new(HashMap::default()),
         }
     }
 
@@ -483,6 +500,13 @@ impl LogLatest {
 
         tracing::trace!("Inserting < {:?} > in Replication Log", event);
 
+        // The Events that are not replicated are kept in the Replication Log, as it is also used
+        // to discard outdated publications, but must not contribute to its Fingerprints.
+        let mut event = event;
+        if !self.configuration.replicates(&event) {
+            event.fingerprint = Fingerprint::default();
+        }
+
         self.bloom_filter_event.set(&event.log_key());
 
         self.intervals
@@ -547,6 +571,139 @@ impl LogLatest {
         Ok(self.digest_from(last_elapsed_interval))
     }
 
+    /// Retrieves the latest [Digest] restricted to the key expressions that the Replica with the
+    /// provided `replica_filters` also replicates.
+    ///
+    /// Contrary to the [Digest] returned by [digest], its [Fingerprint]s are computed by going
+    /// through the [Event]s of the Replication Log. They are cached for each set of
+    /// `replica_filters`, such that only the SubIntervals whose content changed since the previous
+    /// call are visited again.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if the index of the last elapsed interval is superior to
+    /// [u64::MAX]. In theory, this should not happen but if it does, **it is an error that cannot
+    /// be recovered from (⚠️)**.
+    ///
+    /// [digest]: LogLatest::digest()
+    pub fn digest_for(&self, replica_filters: &Filters) -> ZResult<Digest> {
+        let last_elapsed_interval = self.configuration.last_elapsed_interval()?;
+        let digest = self.filtered_digest_from(last_elapsed_interval, Some(replica_filters));
+
+        // Discard the cached Fingerprints of the SubIntervals whose content changed.
+        let sub_intervals_fingerprints = self
+            .intervals
+            .values()
+            .flat_map(|interval| interval.sub_intervals())
+            .map(|(_, sub_interval)| sub_interval.fingerprint())
+            .collect::<HashSet<_>>();
+        if let Some(fingerprints) = zlock!(self.filtered_fingerprints).get_mut(replica_filters) {
+            fingerprints.retain(|fingerprint, _| sub_intervals_fingerprints.contains(fingerprint));
+        }
+
+        Ok(digest)
+    }
+
+    /// Returns true if the [EventMetadata] is replicated by this Replica and, if they are
+    /// provided, with the `replica_filters` of a remote Replica.
+    pub(crate) fn replicates(
+        &self,
+        event: &EventMetadata,
+        replica_filters: Option<&Filters>,
+    ) -> bool {
+        self.configuration.replicates(event)
+            && replica_filters.map_or(true, |replica_filters| {
+                replica_filters.replicates_event(self.configuration.prefix(), event)
+            })
+    }
+
+    /// Returns the [Fingerprint] of the [Interval], restricted, if they are provided, to the key
+    /// expressions replicated with the `replica_filters` of a remote Replica.
+    pub(crate) fn interval_fingerprint(
+        &self,
+        interval: &Interval,
+        replica_filters: Option<&Filters>,
+    ) -> Fingerprint {
+        match replica_filters {
+            None => interval.fingerprint(),
+            Some(_) => interval.sub_intervals().fold(
+                Fingerprint::default(),
+                |fingerprint, (_, sub_interval)| {
+                    fingerprint ^ self.sub_interval_fingerprint(sub_interval, replica_filters)
+                },
+            ),
+        }
+    }
+
+    /// Returns the [Fingerprint] of the [SubInterval], restricted, if they are provided, to the
+    /// key expressions replicated with the `replica_filters` of a remote Replica.
+    pub(crate) fn sub_interval_fingerprint(
+        &self,
+        sub_interval: &SubInterval,
+        replica_filters: Option<&Filters>,
+    ) -> Fingerprint {
+        let fingerprint = sub_interval.fingerprint();
+        let Some(replica_filters) = replica_filters else {
+            return fingerprint;
+        };
+        if fingerprint == Fingerprint::default() {
+            return fingerprint;
+        }
+
+        let mut filtered_fingerprints = zlock!(self.filtered_fingerprints);
+        if let Some(filtered_fingerprint) = filtered_fingerprints
+            .get(replica_filters)
+            .and_then(|fingerprints| fingerprints.get(&fingerprint))
+        {
+            return *filtered_fingerprint;
+        }
+
+        // NOTE: The Fingerprint of the Events this Replica does not replicate is the default
+        //       one, they hence do not need to be filtered out.
+        let filtered_fingerprint = sub_interval
+            .events()
+            .filter(|event| replica_filters.replicates_event(self.configuration.prefix(), event))
+            .fold(Fingerprint::default(), |fingerprint, event| {
+                fingerprint ^ event.fingerprint()
+            });
+
+        if !filtered_fingerprints.contains_key(replica_filters)
+            && filtered_fingerprints.len() >= FILTERED_FINGERPRINTS_CAPACITY
+        {
+            filtered_fingerprints.clear();
+        }
+        filtered_fingerprints
+            .entry(replica_filters.clone())
+            .or_default()
+            .insert(fingerprint, filtered_fingerprint);
+
+        filtered_fingerprint
+    }
+
+    /// Returns the [Fingerprint]s of the [SubInterval]s of the [Interval], restricted, if they are
+    /// provided, to the key expressions replicated with the `replica_filters` of a remote Replica.
+    ///
+    /// The [SubInterval]s with a default [Fingerprint] are omitted.
+    pub(crate) fn sub_intervals_fingerprints(
+        &self,
+        interval: &Interval,
+        replica_filters: Option<&Filters>,
+    ) -> HashMap<SubIntervalIdx, Fingerprint> {
+        match replica_filters {
+            None => interval.sub_intervals_fingerprints(),
+            Some(_) => interval
+                .sub_intervals()
+                .map(|(sub_interval_idx, sub_interval)| {
+                    (
+                        *sub_interval_idx,
+                        self.sub_interval_fingerprint(sub_interval, replica_filters),
+                    )
+                })
+                .filter(|(_, fingerprint)| *fingerprint != Fingerprint::default())
+                .collect(),
+        }
+    }
+
     /// Considering the upper bound of the hot era, generates a [Digest] of the [LogLatest].
     ///
     /// Passing the upper bound of the hot era allows generating a [Digest] that can be compared
@@ -555,6 +712,17 @@ impl LogLatest {
     // NOTE: One of the advantages of having that method take an upper bound is to facilitate unit
     //       testing.
     fn digest_from(&self, hot_era_upper_bound: IntervalIdx) -> Digest {
+        self.filtered_digest_from(hot_era_upper_bound, None)
+    }
+
+    /// Considering the upper bound of the hot era, generates a [Digest] of the [LogLatest]
+    /// restricted, if they are provided, to the key expressions replicated with the
+    /// `replica_filters` of a remote Replica.
+    fn filtered_digest_from(
+        &self,
+        hot_era_upper_bound: IntervalIdx,
+        replica_filters: Option<&Filters>,
+    ) -> Digest {
         let hot_era_lower_bound = self.configuration.hot_era_lower_bound(hot_era_upper_bound);
         let warm_era_lower_bound = self.configuration.warm_era_lower_bound(hot_era_upper_bound);
 
@@ -571,18 +739,23 @@ impl LogLatest {
             //       (i.e. with a lower interval index), the order of the comparisons should
             //       minimise their number to generate the Digest.
             if *interval_idx < warm_era_lower_bound {
-                cold_era_fingerprint ^= interval.fingerprint();
+                cold_era_fingerprint ^= self.interval_fingerprint(interval, replica_filters);
             } else if *interval_idx < hot_era_lower_bound {
-                if interval.fingerprint() != Fingerprint::default() {
-                    warm_era_fingerprints.insert(*interval_idx, interval.fingerprint());
+                let interval_fingerprint = self.interval_fingerprint(interval, replica_filters);
+                if interval_fingerprint != Fingerprint::default() {
+                    warm_era_fingerprints.insert(*interval_idx, interval_fingerprint);
                 }
             } else {
-                hot_era_fingerprints.insert(*interval_idx, interval.sub_intervals_fingerprints());
+                hot_era_fingerprints.insert(
+                    *interval_idx,
+                    self.sub_intervals_fingerprints(interval, replica_filters),
+                );
             }
         }
 
         Digest {
             configuration_fingerprint: self.configuration.fingerprint(),
+            filters: self.configuration.filters().clone(),
             cold_era_fingerprint,
             warm_era_fingerprints,
             hot_era_fingerprints,
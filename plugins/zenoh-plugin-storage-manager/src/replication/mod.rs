@@ -30,8 +30,11 @@ mod classification;
 mod configuration;
 mod core;
 mod digest;
+mod filters;
 mod log;
 mod service;
+mod status;
 
 pub(crate) use log::{Action, Event, LogLatest, LogLatestKey};
 pub(crate) use service::ReplicationService;
+pub(crate) use status::ReplicationStatus;
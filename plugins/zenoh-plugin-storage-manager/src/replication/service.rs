@@ -20,13 +20,14 @@ use tokio::{
 };
 use zenoh::{key_expr::OwnedKeyExpr, session::Session};
 
-use super::{core::Replication, LogLatest};
+use super::{core::Replication, LogLatest, ReplicationStatus};
 use crate::storages_mgt::{LatestUpdates, StorageMessage, StorageService};
 
 pub(crate) struct ReplicationService {
     digest_publisher_handle: JoinHandle<()>,
     digest_subscriber_handle: JoinHandle<()>,
     aligner_queryable_handle: JoinHandle<()>,
+    alignment_requests_handle: JoinHandle<()>,
 }
 
 impl ReplicationService {
@@ -40,11 +41,12 @@ impl ReplicationService {
     ///
     /// # Tasks spawned
     ///
-    /// This function will spawn four long-lived tasks:
+    /// This function will spawn five long-lived tasks:
     /// 1. One to publish the [Digest].
     /// 2. One to receive the [Digest] of other Replica.
     /// 3. One to receive alignment queries of other Replica.
-    /// 4. One to wait on the provided [Receiver] in order to stop the Replication Service,
+    /// 4. One to handle the alignments requested through the admin space.
+    /// 5. One to wait on the provided [Receiver] in order to stop the Replication Service,
     ///    attempting to abort all the tasks that were spawned, once a Stop message has been
     ///    received.
     pub async fn spawn_start(
@@ -53,6 +55,7 @@ impl ReplicationService {
         storage_key_expr: OwnedKeyExpr,
         replication_log: Arc<RwLock<LogLatest>>,
         latest_updates: Arc<RwLock<LatestUpdates>>,
+        status: Arc<ReplicationStatus>,
         mut rx: Receiver<StorageMessage>,
     ) {
         let replication = Replication {
@@ -61,6 +64,7 @@ impl ReplicationService {
             storage_key_expr,
             latest_updates,
             storage_service,
+            status,
         };
 
         if replication
@@ -78,6 +82,7 @@ impl ReplicationService {
                 digest_publisher_handle: replication.spawn_digest_publisher(),
                 digest_subscriber_handle: replication.spawn_digest_subscriber(),
                 aligner_queryable_handle: replication.spawn_aligner_queryable(),
+                alignment_requests_handle: replication.spawn_alignment_requests_handler(),
             };
 
             while let Ok(storage_message) = rx.recv().await {
@@ -94,5 +99,6 @@ impl ReplicationService {
         self.digest_publisher_handle.abort();
         self.digest_subscriber_handle.abort();
         self.aligner_queryable_handle.abort();
+        self.alignment_requests_handle.abort();
     }
 }
@@ -0,0 +1,245 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::sync::Notify;
+use zenoh::Result as ZResult;
+
+use super::{digest::DigestDiff, filters::Filters, log::LogLatest};
+
+/// The `ReplicationStatus` gathers the information exposed in the admin space about the
+/// replication of a Storage: the Replicas it exchanges Digests with, how much they diverge and how
+/// many events were exchanged to align them.
+///
+/// It is shared between the Replication, which updates it, and the Storage, which exposes it and
+/// requests alignments through it.
+///
+/// A Replica that did not publish a Digest for [MISSED_DIGESTS_BEFORE_EXPIRY] Digest periods is
+/// considered gone and is forgotten.
+pub(crate) struct ReplicationStatus {
+    replica_expiry: Duration,
+    last_digest_published: Mutex<Option<SystemTime>>,
+    replicas: Mutex<HashMap<String, ReplicaStatus>>,
+    events_pulled: AtomicU64,
+    events_pushed: AtomicU64,
+    alignment_requested: Notify,
+}
+
+/// What is known about a remote Replica, from the Digests it published.
+struct ReplicaStatus {
+    filters: Filters,
+    last_digest_received: SystemTime,
+    digests_received: u64,
+    last_comparison: Option<SystemTime>,
+    cold_era_differs: bool,
+    differing_intervals: usize,
+}
+
+/// The number of Digest periods a Replica can miss before it is forgotten.
+const MISSED_DIGESTS_BEFORE_EXPIRY: u32 = 3;
+
+impl ReplicationStatus {
+    /// Creates the `ReplicationStatus` of a Replica publishing its Digest every `digest_period`,
+    /// as the remote Replicas do.
+    pub(crate) fn new(digest_period: Duration) -> Self {
+        Self {
+            replica_expiry: digest_period * MISSED_DIGESTS_BEFORE_EXPIRY,
+            last_digest_published: Mutex::default(),
+            replicas: Mutex::default(),
+            events_pulled: AtomicU64::default(),
+            events_pushed: AtomicU64::default(),
+            alignment_requested: Notify::default(),
+        }
+    }
+
+    /// Locks and returns the remote Replicas, after forgetting the ones whose last Digest is too
+    /// old.
+    fn live_replicas(&self) -> MutexGuard<'_, HashMap<String, ReplicaStatus>> {
+        let mut replicas = self.replicas.lock().unwrap();
+        let now = SystemTime::now();
+        replicas.retain(|zid, replica| {
+            let alive = now
+                .duration_since(replica.last_digest_received)
+                .map_or(true, |elapsed| elapsed <= self.replica_expiry);
+            if !alive {
+                tracing::debug!("Replica < {zid} > expired: no Digest received recently");
+            }
+            alive
+        });
+        replicas
+    }
+
+    /// Records the publication of the Digest of this Replica.
+    pub(crate) fn digest_published(&self) {
+        *self.last_digest_published.lock().unwrap() = Some(SystemTime::now());
+    }
+
+    /// Records the reception of a Digest published by the Replica `zid`, with the provided
+    /// `filters`.
+    pub(crate) fn digest_received(&self, zid: &str, filters: &Filters) {
+        let now = SystemTime::now();
+        let mut replicas = self.live_replicas();
+        let replica = replicas
+            .entry(zid.to_string())
+            .or_insert_with(|| ReplicaStatus {
+                filters: filters.clone(),
+                last_digest_received: now,
+                digests_received: 0,
+                last_comparison: None,
+                cold_era_differs: false,
+                differing_intervals: 0,
+            });
+        replica.filters = filters.clone();
+        replica.last_digest_received = now;
+        replica.digests_received += 1;
+    }
+
+    /// Records the outcome of the comparison of the Digest of this Replica with the one of the
+    /// Replica `zid`.
+    pub(crate) fn digests_compared(&self, zid: &str, digest_diff: Option<&DigestDiff>) {
+        if let Some(replica) = self.live_replicas().get_mut(zid) {
+            replica.last_comparison = Some(SystemTime::now());
+            replica.cold_era_differs = digest_diff.is_some_and(|diff| diff.cold_eras_differ);
+            replica.differing_intervals = digest_diff.map_or(0, |diff| {
+                diff.warm_eras_differences.len() + diff.hot_eras_differences.len()
+            });
+        }
+    }
+
+    /// Records that the payload of an event was retrieved from a Replica and applied during an
+    /// alignment.
+    pub(crate) fn event_pulled(&self) {
+        self.events_pulled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records that an event was sent to a Replica during an alignment.
+    pub(crate) fn event_pushed(&self) {
+        self.events_pushed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the Replicas from which a Digest was received, along with their filters.
+    pub(crate) fn replicas(&self) -> Vec<(String, Filters)> {
+        self.live_replicas()
+            .iter()
+            .map(|(zid, replica)| (zid.clone(), replica.filters.clone()))
+            .collect()
+    }
+
+    /// Requests an alignment with all the known Replicas, without waiting for their next Digest.
+    ///
+    /// Returns the Zenoh ID of these Replicas.
+    pub(crate) fn request_alignment(&self) -> Vec<String> {
+        self.alignment_requested.notify_one();
+        let mut replicas = self
+            .replicas()
+            .into_iter()
+            .map(|(zid, _)| zid)
+            .collect::<Vec<_>>();
+        replicas.sort();
+        replicas
+    }
+
+    /// Waits until an alignment is requested.
+    pub(crate) async fn alignment_requested(&self) {
+        self.alignment_requested.notified().await
+    }
+
+    /// Returns the status as exposed in the admin space, including the size of the eras of the
+    /// provided Replication Log.
+    pub(crate) fn to_json_value(&self, log: &LogLatest) -> Value {
+        let format_time = |time: SystemTime| humantime::format_rfc3339_millis(time).to_string();
+        let replicas = self
+            .live_replicas()
+            .iter()
+            .map(|(zid, replica)| {
+                (
+                    zid.clone(),
+                    json!({
+                        "last_digest_received": format_time(replica.last_digest_received),
+                        "digests_received": replica.digests_received,
+                        "same_filters": replica.filters == *log.configuration().filters(),
+                        "last_comparison": replica.last_comparison.map(format_time),
+                        "cold_era_differs": replica.cold_era_differs,
+                        "differing_intervals": replica.differing_intervals,
+                    }),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        let eras = match eras_sizes(log) {
+            Ok([hot, warm, cold]) => json!({ "hot": hot, "warm": warm, "cold": cold }),
+            Err(e) => {
+                tracing::error!("Failed to compute the size of the eras: {e:?}");
+                Value::Null
+            }
+        };
+
+        json!({
+            "last_digest_published": self.last_digest_published.lock().unwrap().map(format_time),
+            "eras": eras,
+            "events_pulled": self.events_pulled.load(Ordering::Relaxed),
+            "events_pushed": self.events_pushed.load(Ordering::Relaxed),
+            "replicas": replicas,
+        })
+    }
+}
+
+/// The number of Intervals, and of Events they contain, in an era of the Replication Log.
+#[derive(Default, Serialize)]
+struct EraSize {
+    intervals: usize,
+    events: usize,
+}
+
+/// Returns the size of the Hot, Warm and Cold eras of the Replication Log.
+///
+/// The Intervals that have not elapsed yet are counted in the Hot era.
+fn eras_sizes(log: &LogLatest) -> ZResult<[EraSize; 3]> {
+    let configuration = log.configuration();
+    let last_elapsed_interval = configuration.last_elapsed_interval()?;
+    let hot_era_lower_bound = configuration.hot_era_lower_bound(last_elapsed_interval);
+    let warm_era_lower_bound = configuration.warm_era_lower_bound(last_elapsed_interval);
+
+    let [mut hot, mut warm, mut cold] = <[EraSize; 3]>::default();
+    for (interval_idx, interval) in &log.intervals {
+        let era = if *interval_idx < warm_era_lower_bound {
+            &mut cold
+        } else if *interval_idx < hot_era_lower_bound {
+            &mut warm
+        } else {
+            &mut hot
+        };
+        era.intervals += 1;
+        era.events += interval
+            .sub_intervals()
+            .map(|(_, sub_interval)| sub_interval.events().count())
+            .sum::<usize>();
+    }
+
+    Ok([hot, warm, cold])
+}
+
+#[cfg(test)]
+#[path = "tests/status.test.rs"]
+mod tests;
@@ -15,6 +15,7 @@
 use std::{str::FromStr, time::Duration};
 
 use uhlc::HLC;
+use zenoh_backend_traits::config::{ReplicationFilter, ReplicationFilterKind};
 
 use super::*;
 
@@ -29,6 +30,7 @@ fn test_lower_bounds() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            filters: Vec::new(),
         },
     );
 
@@ -50,6 +52,7 @@ fn test_difference() {
         hot: 1,
         warm: 5,
         propagation_delay: Duration::from_millis(250),
+        filters: Vec::new(),
     };
 
     let configuration_a = Configuration::new(
@@ -73,6 +76,23 @@ fn test_difference() {
     );
 
     assert_ne!(configuration_a.fingerprint, configuration_c.fingerprint);
+
+    // Replicas with different filters must still exchange their Digest.
+    let configuration_d = Configuration::new(
+        configuration_c.storage_key_expr.clone(),
+        configuration_c.prefix.clone(),
+        ReplicaConfig {
+            filters: vec![ReplicationFilter {
+                key_expr: OwnedKeyExpr::from_str("replication/test/*/summary/**").unwrap(),
+                kind: ReplicationFilterKind::Include,
+                priority: 0,
+            }],
+            ..configuration_c.replica_config.clone()
+        },
+    );
+
+    assert_eq!(configuration_c.fingerprint, configuration_d.fingerprint);
+    assert_ne!(configuration_c.filters, configuration_d.filters);
 }
 
 #[test]
@@ -86,6 +106,7 @@ fn test_get_classification() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            filters: Vec::new(),
         },
     );
 
@@ -12,12 +12,20 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+use zenoh::key_expr::OwnedKeyExpr;
+use zenoh_backend_traits::config::{ReplicationFilter, ReplicationFilterKind};
 
 use super::{Digest, Fingerprint};
 use crate::replication::{
     classification::{IntervalIdx, SubIntervalIdx},
     digest::DigestDiff,
+    filters::Filters,
 };
 
 #[test]
@@ -25,6 +33,7 @@ fn test_diff() {
     // Base Digest. The actual values of the Fingerprints do not matter.
     let digest = Digest {
         configuration_fingerprint: Fingerprint(15),
+        filters: Filters::default(),
         cold_era_fingerprint: Fingerprint(10),
         warm_era_fingerprints: HashMap::from([
             (IntervalIdx(1), Fingerprint(1)),
@@ -179,3 +188,55 @@ fn test_diff() {
     });
     assert_eq!(expected_diff, digest.diff(other_digest));
 }
+
+/// The layout of a Digest in the versions that do not support filters.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct LegacyDigest {
+    configuration_fingerprint: Fingerprint,
+    cold_era_fingerprint: Fingerprint,
+    warm_era_fingerprints: HashMap<IntervalIdx, Fingerprint>,
+    hot_era_fingerprints: HashMap<IntervalIdx, HashMap<SubIntervalIdx, Fingerprint>>,
+}
+
+#[test]
+fn test_encode() {
+    let legacy_digest = LegacyDigest {
+        configuration_fingerprint: Fingerprint(15),
+        cold_era_fingerprint: Fingerprint(10),
+        warm_era_fingerprints: HashMap::from([(IntervalIdx(1), Fingerprint(1))]),
+        hot_era_fingerprints: HashMap::from([(
+            IntervalIdx(2),
+            HashMap::from([(SubIntervalIdx(1), Fingerprint(1))]),
+        )]),
+    };
+    let mut digest = Digest {
+        configuration_fingerprint: Fingerprint(15),
+        cold_era_fingerprint: Fingerprint(10),
+        warm_era_fingerprints: legacy_digest.warm_era_fingerprints.clone(),
+        hot_era_fingerprints: legacy_digest.hot_era_fingerprints.clone(),
+        filters: Filters::default(),
+    };
+
+    // Without filters, the Digest is encoded as by the versions that do not support them.
+    let mut buffer = Vec::new();
+    digest.encode(&mut buffer).unwrap();
+    assert_eq!(buffer, bincode::serialize(&legacy_digest).unwrap());
+    assert_eq!(Digest::decode(&buffer).unwrap(), digest);
+
+    // With filters, the versions that do not support them still decode the Digest.
+    digest.filters = Filters::from(
+        [ReplicationFilter {
+            key_expr: OwnedKeyExpr::from_str("test/**").unwrap(),
+            kind: ReplicationFilterKind::Include,
+            priority: 0,
+        }]
+        .as_slice(),
+    );
+    let mut buffer = Vec::new();
+    digest.encode(&mut buffer).unwrap();
+    assert_eq!(Digest::decode(&buffer).unwrap(), digest);
+    assert_eq!(
+        bincode::deserialize::<LegacyDigest>(&buffer).unwrap(),
+        legacy_digest
+    );
+}
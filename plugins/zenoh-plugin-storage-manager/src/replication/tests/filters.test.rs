@@ -0,0 +1,153 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::str::FromStr;
+
+use uhlc::HLC;
+use zenoh::key_expr::{keyexpr, OwnedKeyExpr};
+use zenoh_backend_traits::config::{ReplicationFilter, ReplicationFilterKind};
+
+use super::Filters;
+use crate::replication::log::{Action, EventMetadata};
+
+fn filter(kind: ReplicationFilterKind, key_expr: &str, priority: u32) -> ReplicationFilter {
+    ReplicationFilter {
+        key_expr: OwnedKeyExpr::from_str(key_expr).unwrap(),
+        kind,
+        priority,
+    }
+}
+
+fn replicates(filters: &Filters, key_expr: &str) -> bool {
+    filters.replicates(keyexpr::new(key_expr).unwrap())
+}
+
+#[test]
+fn test_replicates() {
+    use ReplicationFilterKind::*;
+
+    // Without any filter, everything is replicated.
+    let filters = Filters::default();
+    assert!(replicates(&filters, "fleet/car-1/summary/speed"));
+
+    // With only `exclude` filters, what they do not match is replicated.
+    let filters = Filters::from([filter(Exclude, "fleet/*/raw/**", 0)].as_slice());
+    assert!(replicates(&filters, "fleet/car-1/summary/speed"));
+    assert!(!replicates(&filters, "fleet/car-1/raw/lidar"));
+
+    // With `include` filters, what they do not match is not replicated.
+    let filters = Filters::from(
+        [
+            filter(Include, "fleet/*/summary/**", 0),
+            filter(Exclude, "fleet/*/summary/debug/**", 1),
+        ]
+        .as_slice(),
+    );
+    assert!(replicates(&filters, "fleet/car-1/summary/speed"));
+    assert!(!replicates(&filters, "fleet/car-1/summary/debug/trace"));
+    assert!(!replicates(&filters, "fleet/car-1/raw/lidar"));
+
+    // The filter with the highest priority wins, an `exclude` winning over an `include` of the same
+    // priority.
+    let filters = Filters::from(
+        [
+            filter(Exclude, "fleet/**", 0),
+            filter(Include, "fleet/*/summary/**", 1),
+        ]
+        .as_slice(),
+    );
+    assert!(replicates(&filters, "fleet/car-1/summary/speed"));
+    assert!(!replicates(&filters, "fleet/car-1/raw/lidar"));
+
+    let filters = Filters::from(
+        [
+            filter(Include, "fleet/*/summary/**", 1),
+            filter(Exclude, "fleet/**", 1),
+        ]
+        .as_slice(),
+    );
+    assert!(!replicates(&filters, "fleet/car-1/summary/speed"));
+
+    // Wildcard Updates are replicated if they update some of the replicated keys.
+    let filters = Filters::from(
+        [
+            filter(Include, "fleet/*/summary/**", 0),
+            filter(Exclude, "fleet/car-2/**", 1),
+        ]
+        .as_slice(),
+    );
+    assert!(replicates(&filters, "fleet/**"));
+    assert!(!replicates(&filters, "fleet/car-2/**"));
+    assert!(!replicates(&filters, "fleet/*/raw/**"));
+}
+
+#[test]
+fn test_order_does_not_matter() {
+    use ReplicationFilterKind::*;
+
+    let filters = [
+        filter(Include, "fleet/*/summary/**", 1),
+        filter(Exclude, "fleet/**", 0),
+        filter(Exclude, "fleet/car-2/**", 1),
+    ];
+    let mut reversed_filters = filters.clone();
+    reversed_filters.reverse();
+
+    assert_eq!(
+        Filters::from(filters.as_slice()),
+        Filters::from(reversed_filters.as_slice())
+    );
+}
+
+#[test]
+fn test_replicates_event() {
+    let hlc = HLC::default();
+    let prefix = OwnedKeyExpr::from_str("fleet").unwrap();
+    let filters = Filters::from(
+        [filter(
+            ReplicationFilterKind::Include,
+            "fleet/*/summary/**",
+            0,
+        )]
+        .as_slice(),
+    );
+
+    let event = |stripped_key: Option<&str>, action: Action| EventMetadata {
+        stripped_key: stripped_key.map(|key| OwnedKeyExpr::from_str(key).unwrap()),
+        timestamp: hlc.new_timestamp(),
+        timestamp_last_non_wildcard_update: None,
+        action,
+    };
+
+    // The prefix is added back to the stripped key expression.
+    assert!(filters.replicates_event(
+        Some(&prefix),
+        &event(Some("car-1/summary/speed"), Action::Put)
+    ));
+    assert!(!filters.replicates_event(
+        Some(&prefix),
+        &event(Some("car-1/raw/lidar"), Action::Delete)
+    ));
+    assert!(!filters.replicates_event(Some(&prefix), &event(None, Action::Put)));
+
+    // Wildcard Updates keep their full key expression.
+    let wildcard_ke = OwnedKeyExpr::from_str("fleet/*/summary/**").unwrap();
+    assert!(filters.replicates_event(
+        Some(&prefix),
+        &event(
+            Some(wildcard_ke.as_str()),
+            Action::WildcardPut(wildcard_ke.clone())
+        )
+    ));
+}
@@ -22,6 +22,7 @@ use super::{Event, EventMetadata, LogLatest};
 use crate::replication::{
     classification::{Interval, IntervalIdx, SubInterval, SubIntervalIdx},
     digest::{Digest, Fingerprint},
+    filters::Filters,
     log::{Action, EventInsertion},
 };
 
@@ -54,6 +55,7 @@ fn test_insert() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            filters: Vec::new(),
         },
     );
 
@@ -118,6 +120,7 @@ fn test_digest() {
             hot: 1,
             warm: 5,
             propagation_delay: Duration::from_millis(250),
+            filters: Vec::new(),
         },
     );
 
@@ -198,6 +201,7 @@ fn test_digest() {
     // - 4 <= cold
     let mut expected_digest = Digest {
         configuration_fingerprint: log.configuration.fingerprint(),
+        filters: Filters::default(),
         cold_era_fingerprint: Fingerprint::default(),
         warm_era_fingerprints: HashMap::from([
             (IntervalIdx(5), event_warm_5_1_0.fingerprint()),
@@ -287,6 +291,7 @@ fn test_digest() {
         ^ event_warm_6_2_1.fingerprint();
     let expected_digest = Digest {
         configuration_fingerprint: log.configuration.fingerprint(),
+        filters: Filters::default(),
         cold_era_fingerprint: expected_cold_fingerprint,
         warm_era_fingerprints: HashMap::from([
             (IntervalIdx(10), event_hot_10_4_1.fingerprint()),
@@ -402,3 +407,116 @@ fn test_convert_metadata_to_event() {
     let converted_event: Event = event_metadata_put.into();
     assert_eq!(expected_put_event, converted_event);
 }
+
+#[test]
+fn test_filtered_digest() {
+    use zenoh_backend_traits::config::{ReplicationFilter, ReplicationFilterKind};
+
+    let hlc = HLC::default();
+    let mut log = LogLatest::new(
+        OwnedKeyExpr::from_str("fleet/**").unwrap(),
+        None,
+        ReplicaConfig {
+            interval: Duration::from_secs(10),
+            sub_intervals: 5,
+            hot: 1,
+            warm: 5,
+            propagation_delay: Duration::from_millis(250),
+            filters: vec![ReplicationFilter {
+                key_expr: OwnedKeyExpr::from_str("fleet/*/summary/**").unwrap(),
+                kind: ReplicationFilterKind::Include,
+                priority: 0,
+            }],
+        },
+    );
+
+    let event_summary_1 = Event::new(
+        Some(OwnedKeyExpr::from_str("fleet/car-1/summary/speed").unwrap()),
+        generate_timestamp_matching(&log, &hlc, 10, 1, 0),
+        &Action::Put,
+    );
+    let event_summary_2 = Event::new(
+        Some(OwnedKeyExpr::from_str("fleet/car-2/summary/speed").unwrap()),
+        generate_timestamp_matching(&log, &hlc, 10, 1, 1),
+        &Action::Put,
+    );
+    let event_raw = Event::new(
+        Some(OwnedKeyExpr::from_str("fleet/car-1/raw/lidar").unwrap()),
+        generate_timestamp_matching(&log, &hlc, 10, 2, 0),
+        &Action::Put,
+    );
+    log.update(
+        [
+            event_summary_1.clone(),
+            event_summary_2.clone(),
+            event_raw.clone(),
+        ]
+        .into_iter(),
+    );
+
+    // The Event that is not replicated is still kept in the Replication Log...
+    assert!(log.lookup_newer(&(&event_raw).into()).is_some());
+
+    // ... but does not contribute to the Digest.
+    let expected_digest = Digest {
+        configuration_fingerprint: log.configuration.fingerprint(),
+        filters: log.configuration.filters().clone(),
+        cold_era_fingerprint: Fingerprint::default(),
+        warm_era_fingerprints: HashMap::default(),
+        hot_era_fingerprints: HashMap::from([(
+            IntervalIdx(10),
+            HashMap::from([(
+                SubIntervalIdx(1),
+                event_summary_1.fingerprint() ^ event_summary_2.fingerprint(),
+            )]),
+        )]),
+    };
+    assert_eq!(expected_digest, log.digest_from(IntervalIdx(10)));
+
+    // The Digest restricted to the key expressions also replicated by a Replica excluding
+    // "fleet/car-2/**" only contains the first summary.
+    let replica_filters = Filters::from(
+        [ReplicationFilter {
+            key_expr: OwnedKeyExpr::from_str("fleet/car-2/**").unwrap(),
+            kind: ReplicationFilterKind::Exclude,
+            priority: 0,
+        }]
+        .as_slice(),
+    );
+    let expected_digest = Digest {
+        hot_era_fingerprints: HashMap::from([(
+            IntervalIdx(10),
+            HashMap::from([(SubIntervalIdx(1), event_summary_1.fingerprint())]),
+        )]),
+        ..expected_digest
+    };
+    assert_eq!(
+        expected_digest,
+        log.filtered_digest_from(IntervalIdx(10), Some(&replica_filters))
+    );
+    assert!(log.replicates(&(&event_summary_1).into(), Some(&replica_filters)));
+    assert!(!log.replicates(&(&event_summary_2).into(), Some(&replica_filters)));
+    assert!(!log.replicates(&(&event_raw).into(), None));
+
+    // The restricted Fingerprints are cached until the content of their SubInterval changes.
+    let event_summary_3 = Event::new(
+        Some(OwnedKeyExpr::from_str("fleet/car-3/summary/speed").unwrap()),
+        generate_timestamp_matching(&log, &hlc, 10, 1, 2),
+        &Action::Put,
+    );
+    log.update([event_summary_3.clone()].into_iter());
+    let expected_digest = Digest {
+        hot_era_fingerprints: HashMap::from([(
+            IntervalIdx(10),
+            HashMap::from([(
+                SubIntervalIdx(1),
+                event_summary_1.fingerprint() ^ event_summary_3.fingerprint(),
+            )]),
+        )]),
+        ..expected_digest
+    };
+    assert_eq!(
+        expected_digest,
+        log.filtered_digest_from(IntervalIdx(10), Some(&replica_filters))
+    );
+}
@@ -0,0 +1,33 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{thread::sleep, time::Duration};
+
+use super::ReplicationStatus;
+use crate::replication::filters::Filters;
+
+#[test]
+fn test_replica_expiry() {
+    let status = ReplicationStatus::new(Duration::from_millis(20));
+    status.digest_received("replica-1", &Filters::default());
+    status.digest_received("replica-2", &Filters::default());
+    assert_eq!(status.request_alignment(), ["replica-1", "replica-2"]);
+
+    // A Replica that keeps publishing its Digest is kept, the other one is forgotten once it missed
+    // enough Digest periods.
+    sleep(Duration::from_millis(40));
+    status.digest_received("replica-1", &Filters::default());
+    sleep(Duration::from_millis(40));
+    assert_eq!(status.request_alignment(), ["replica-1"]);
+}
@@ -0,0 +1,109 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    time::{Duration, SystemTime},
+};
+
+use zenoh::{
+    key_expr::OwnedKeyExpr,
+    time::{Timestamp, NTP64},
+};
+
+/// The keys of a storage whose value has a time-to-live, indexed by expiry.
+///
+/// The expiry of a value is the timestamp of the delete issued once it has expired: the time of
+/// its timestamp plus its time-to-live, with the same HLC identifier. Hence all the replicas of a
+/// storage delete it with the same timestamp.
+#[derive(Default)]
+pub(crate) struct Expirations {
+    by_key: HashMap<OwnedKeyExpr, Timestamp>,
+    by_expiry: BTreeMap<Timestamp, HashSet<OwnedKeyExpr>>,
+}
+
+impl Expirations {
+    /// Returns the expiry of a value with the `timestamp`, living for `ttl`, or `None` if it is
+    /// beyond the range of the timestamps.
+    pub(crate) fn expiry(timestamp: &Timestamp, ttl: Duration) -> Option<Timestamp> {
+        // Beyond this duration, the conversion of `ttl` to a NTP64 overflows
+        if ttl.as_secs() > u64::from(u32::MAX) {
+            return None;
+        }
+        let time = timestamp
+            .get_time()
+            .as_u64()
+            .checked_add(NTP64::from(ttl).as_u64())?;
+        Some(Timestamp::new(NTP64(time), *timestamp.get_id()))
+    }
+
+    /// Sets the expiry of the value of `key`, replacing the previous one.
+    pub(crate) fn insert(&mut self, key: OwnedKeyExpr, expiry: Timestamp) {
+        self.remove(&key);
+        self.by_expiry
+            .entry(expiry)
+            .or_default()
+            .insert(key.clone());
+        self.by_key.insert(key, expiry);
+    }
+
+    /// Returns the expiry of the value of `key`, if it has a time-to-live.
+    pub(crate) fn get(&self, key: &OwnedKeyExpr) -> Option<&Timestamp> {
+        self.by_key.get(key)
+    }
+
+    /// Removes the expiry of the value of `key`, e.g. once it has been deleted or replaced by a
+    /// value without time-to-live.
+    pub(crate) fn remove(&mut self, key: &OwnedKeyExpr) {
+        if let Some(expiry) = self.by_key.remove(key) {
+            if let Some(keys) = self.by_expiry.get_mut(&expiry) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.by_expiry.remove(&expiry);
+                }
+            }
+        }
+    }
+
+    /// Returns the duration until the next expiry, if any.
+    pub(crate) fn next_expiry(&self) -> Option<Duration> {
+        let (expiry, _) = self.by_expiry.first_key_value()?;
+        Some(
+            (expiry.get_time().to_system_time())
+                .duration_since(SystemTime::now())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Removes and returns the keys whose value has expired, with their expiry.
+    pub(crate) fn pop_expired(&mut self) -> Vec<(OwnedKeyExpr, Timestamp)> {
+        let now = SystemTime::now();
+        let mut expired = Vec::new();
+        while let Some(entry) = self.by_expiry.first_entry() {
+            if entry.key().get_time().to_system_time() > now {
+                break;
+            }
+            let (expiry, keys) = entry.remove_entry();
+            for key in keys {
+                self.by_key.remove(&key);
+                expired.push((key, expiry));
+            }
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/expirations.test.rs"]
+mod tests;
@@ -12,16 +12,24 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use tokio::sync::{broadcast::Sender, Mutex, RwLock};
 use zenoh::{internal::bail, session::Session, Result as ZResult};
 use zenoh_backend_traits::{config::StorageConfig, History, VolumeInstance};
 
-use crate::replication::{Action, Event, LogLatest, LogLatestKey, ReplicationService};
+use crate::replication::{
+    Action, Event, LogLatest, LogLatestKey, ReplicationService, ReplicationStatus,
+};
 
 pub(crate) mod service;
 pub(crate) use service::StorageService;
+mod expirations;
+mod snapshot;
 
 #[derive(Clone)]
 pub enum StorageMessage {
@@ -54,6 +62,7 @@ pub(crate) async fn create_and_start_storage(
     config: StorageConfig,
     backend: &VolumeInstance,
     zenoh_session: Arc<Session>,
+    snapshots_dir: PathBuf,
 ) -> ZResult<Sender<StorageMessage>> {
     tracing::trace!("Create storage '{}'", &admin_key);
     let capability = backend.get_capability();
@@ -79,6 +88,26 @@ pub(crate) async fn create_and_start_storage(
             .collect::<HashMap<_, _>>(),
         Err(e) => bail!("`get_all_entries` failed with: {e:?}"),
     };
+    // The deletions kept by the storage, e.g. restored by a durable storage, are known too so
+    // that older puts do not bring the deleted keys back
+    match storage.get_all_tombstones().await {
+        Ok(tombstones) => {
+            for (stripped_key, ts) in tombstones {
+                let event = Event::new(stripped_key, ts, &Action::Delete);
+                match entries.entry(event.log_key()) {
+                    Entry::Occupied(mut entry) => {
+                        if entry.get().timestamp() < event.timestamp() {
+                            entry.insert(event);
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(event);
+                    }
+                }
+            }
+        }
+        Err(e) => bail!("`get_all_tombstones` failed with: {e:?}"),
+    }
 
     let mut replication_log = None;
     let mut latest_updates = HashMap::default();
@@ -104,6 +133,10 @@ pub(crate) async fn create_and_start_storage(
     }
 
     let latest_updates = Arc::new(RwLock::new(latest_updates));
+    let replication_status = config
+        .replication
+        .as_ref()
+        .map(|replica_config| Arc::new(ReplicationStatus::new(replica_config.interval)));
 
     let storage = Arc::new(Mutex::new(storage));
 
@@ -119,16 +152,21 @@ pub(crate) async fn create_and_start_storage(
                 zenoh_session.clone(),
                 config.clone(),
                 &name,
+                admin_key,
+                snapshots_dir,
                 storage,
                 capability,
                 CacheLatest::new(latest_updates.clone(), replication_log.clone()),
+                replication_status.clone(),
             )
             .await,
         );
 
         // Testing if the `replication_log` is set is equivalent to testing if the `replication` is
         // set: the `replication_log` is only set when the latter is.
-        if let Some(replication_log) = replication_log {
+        if let (Some(replication_log), Some(replication_status)) =
+            (replication_log, replication_status)
+        {
             tracing::debug!(
                 "Starting replication of storage '{}' on keyexpr '{}'",
                 name,
@@ -141,6 +179,7 @@ pub(crate) async fn create_and_start_storage(
                 config.key_expr,
                 replication_log,
                 latest_updates,
+                replication_status,
                 rx_replication,
             )
             .await;
@@ -13,10 +13,11 @@
 //
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
     str::{self},
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use async_trait::async_trait;
@@ -31,6 +32,7 @@ use zenoh::{
         },
         OwnedKeyExpr,
     },
+    query::{Parameters, ZenohParameters},
     sample::{Sample, SampleBuilder, SampleFields, SampleKind},
     session::Session,
     time::{Timestamp, NTP64},
@@ -41,9 +43,13 @@ use zenoh_backend_traits::{
     Capability, History, StorageInsertionResult, StoredData,
 };
 
-use super::LatestUpdates;
+use super::{
+    expirations::Expirations,
+    snapshot::{snapshot_path, SnapshotEntry, SnapshotHeader, SnapshotReader, SnapshotWriter},
+    LatestUpdates,
+};
 use crate::{
-    replication::{Action, Event},
+    replication::{Action, Event, ReplicationStatus},
     storages_mgt::{CacheLatest, StorageMessage},
 };
 
@@ -82,31 +88,46 @@ pub struct StorageService {
     session: Arc<Session>,
     pub(crate) configuration: StorageConfig,
     name: String,
+    admin_key: String,
+    snapshots_dir: PathBuf,
     pub(crate) storage: Arc<Mutex<Box<dyn zenoh_backend_traits::Storage>>>,
     capability: Capability,
     pub(crate) wildcard_deletes: Arc<RwLock<KeBoxTree<Update, UnknownWildness, KeyedSetProvider>>>,
     pub(crate) wildcard_puts: Arc<RwLock<KeBoxTree<Update, UnknownWildness, KeyedSetProvider>>>,
     cache_latest: CacheLatest,
+    expirations: Arc<Mutex<Expirations>>,
+    replication_status: Option<Arc<ReplicationStatus>>,
 }
 
+/// The parameter of the attachment of a sample setting its time-to-live, in seconds.
+const TTL_PARAMETER: &str = "ttl";
+
 impl StorageService {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         session: Arc<Session>,
         config: StorageConfig,
         name: &str,
+        admin_key: String,
+        snapshots_dir: PathBuf,
         storage: Arc<Mutex<Box<dyn zenoh_backend_traits::Storage>>>,
         capability: Capability,
         cache_latest: CacheLatest,
+        replication_status: Option<Arc<ReplicationStatus>>,
     ) -> Self {
         StorageService {
             session,
             configuration: config,
             name: name.to_string(),
+            admin_key,
+            snapshots_dir,
             storage,
             capability,
             wildcard_deletes: Arc::new(RwLock::new(KeBoxTree::default())),
             wildcard_puts: Arc::new(RwLock::new(KeBoxTree::default())),
             cache_latest,
+            expirations: Arc::new(Mutex::new(Expirations::default())),
+            replication_status,
         }
     }
 
@@ -136,6 +157,14 @@ impl StorageService {
         );
         t.add_async(gc).await;
 
+        if let Err(e) = self.restore_expirations().await {
+            tracing::error!(
+                "Storage '{}' failed to restore the expiry of its values: {}",
+                self.name,
+                e
+            );
+        }
+
         let storage_key_expr = &self.configuration.key_expr;
 
         // subscribe on key_expr
@@ -161,6 +190,19 @@ impl StorageService {
             }
         };
 
+        // answer to the snapshot operations in the admin space of the storage
+        let admin_queryable = match self
+            .session
+            .declare_queryable(format!("{}/*", self.admin_key))
+            .await
+        {
+            Ok(admin_queryable) => admin_queryable,
+            Err(e) => {
+                tracing::error!("Error starting storage '{}': {}", self.name, e);
+                return;
+            }
+        };
+
         tracing::debug!(
             "Starting storage '{}' on keyexpr '{}'",
             self.name,
@@ -169,6 +211,7 @@ impl StorageService {
 
         tokio::task::spawn(async move {
             loop {
+                let next_expiry = self.expirations.lock().await.next_expiry();
                 tokio::select!(
                     // on sample for key_expr
                     sample = storage_sub.recv_async() => {
@@ -189,6 +232,14 @@ impl StorageService {
                     query = storage_queryable.recv_async() => {
                         self.reply_query(query).await;
                     },
+                    // on snapshot operation, processed in between samples to be consistent
+                    query = admin_queryable.recv_async() => {
+                        self.reply_admin_query(query).await;
+                    },
+                    // on expiry of values having a time-to-live
+                    _ = tokio::time::sleep(next_expiry.unwrap_or_default()), if next_expiry.is_some() => {
+                        self.expire_values().await;
+                    },
                     // on storage handle drop
                     Ok(message) = rx.recv() => {
                         match message {
@@ -197,8 +248,7 @@ impl StorageService {
                                 return
                             },
                             StorageMessage::GetStatus(tx) => {
-                                let storage = self.storage.lock().await;
-                                std::mem::drop(tx.send(storage.get_admin_status().into()).await);
+                                std::mem::drop(tx.send(self.admin_status().await).await);
                             }
                         };
                     },
@@ -209,7 +259,19 @@ impl StorageService {
 
     // The storage should only simply save the key, sample pair while put and retrieve the same
     // during get the trimming during PUT and GET should be handled by the plugin
-    pub(crate) async fn process_sample(&self, sample: Sample) -> ZResult<()> {
+    //
+    // Returns the number of keys updated by the sample, outdated samples being ignored.
+    pub(crate) async fn process_sample(&self, sample: Sample) -> ZResult<usize> {
+        self.process_sample_with_expiry(sample, None).await
+    }
+
+    /// Processes a sample like [`Self::process_sample`], its value expiring at `fixed_expiry` if
+    /// set rather than after its time-to-live, e.g. for the values imported from a snapshot.
+    async fn process_sample_with_expiry(
+        &self,
+        sample: Sample,
+        fixed_expiry: Option<Timestamp>,
+    ) -> ZResult<usize> {
         tracing::trace!("[STORAGE] Processing sample: {:?}", sample.key_expr());
         let SampleFields {
             key_expr,
@@ -227,6 +289,11 @@ impl StorageService {
             bail!("Discarding Sample without a Timestamp: {:?}", sample);
         };
 
+        let ttl = match kind {
+            SampleKind::Put if fixed_expiry.is_none() => self.sample_ttl(&sample),
+            _ => None,
+        };
+
         let mut action: Action = kind.into();
         // if wildcard, update wildcard_updates
         if key_expr.is_wild() {
@@ -266,6 +333,7 @@ impl StorageService {
 
         let prefix = self.configuration.strip_prefix.as_ref();
 
+        let mut updated = 0;
         for k in matching_keys {
             // there might be the case that the actual update was outdated due to a wild card
             // update, but not stored yet in the storage. get the relevant wild
@@ -327,9 +395,32 @@ impl StorageService {
                 }
             }
 
+            let expiry = fixed_expiry.or_else(|| {
+                let ttl = ttl?;
+                let expiry = Expirations::expiry(&sample_to_store_timestamp, ttl);
+                if expiry.is_none() {
+                    tracing::warn!(
+                        "Storage '{}' ignores the time-to-live of < {} >: its expiry overflows",
+                        self.name,
+                        k
+                    );
+                }
+                expiry
+            });
             let mut storage = self.storage.lock().await;
-            let storage_result = match sample.kind() {
-                SampleKind::Put => {
+            let storage_result = match (sample.kind(), expiry) {
+                (SampleKind::Put, Some(expiry)) => {
+                    storage
+                        .put_with_expiry(
+                            stripped_key.clone(),
+                            sample_to_store.payload().clone(),
+                            sample_to_store.encoding().clone(),
+                            sample_to_store_timestamp,
+                            expiry,
+                        )
+                        .await
+                }
+                (SampleKind::Put, None) => {
                     storage
                         .put(
                             stripped_key.clone(),
@@ -339,7 +430,7 @@ impl StorageService {
                         )
                         .await
                 }
-                SampleKind::Delete => {
+                (SampleKind::Delete, _) => {
                     storage
                         .delete(stripped_key.clone(), sample_to_store_timestamp)
                         .await
@@ -356,6 +447,12 @@ impl StorageService {
                     if let Some(mut cache_guard) = cache_guard {
                         cache_guard.insert(new_event.log_key(), new_event);
                     }
+                    let mut expirations = self.expirations.lock().await;
+                    match expiry {
+                        Some(expiry) => expirations.insert(k.clone(), expiry),
+                        None => expirations.remove(&k),
+                    }
+                    updated += 1;
                 }
                 Err(e) => {
                     // TODO In case of a wildcard update, multiple keys can be updated. What should
@@ -365,9 +462,55 @@ impl StorageService {
             }
         }
 
+        Ok(updated)
+    }
+
+    /// Returns the time-to-live of the value of a sample: the `ttl` parameter of its attachment,
+    /// or else the default time-to-live of the storage, clamped to its maximum time-to-live.
+    fn sample_ttl(&self, sample: &Sample) -> Option<Duration> {
+        let attachment = sample.attachment().and_then(|a| a.try_to_string().ok());
+        if let Some(ttl) = (attachment.as_deref()).and_then(|attachment| {
+            Parameters::from(attachment)
+                .get(TTL_PARAMETER)
+                .map(str::to_owned)
+        }) {
+            match ttl.parse::<f64>().map(Duration::try_from_secs_f64) {
+                Ok(Ok(ttl)) if !ttl.is_zero() => return Some(ttl.min(self.configuration.max_ttl)),
+                _ => tracing::warn!(
+                    "Storage '{}' ignores invalid time-to-live `{}` of < {} >",
+                    self.name,
+                    ttl,
+                    sample.key_expr()
+                ),
+            }
+        }
+        self.configuration.ttl
+    }
+
+    /// Restores the expiry of the values persisted by the backend, so that the values stored before
+    /// the start of the storage also expire.
+    async fn restore_expirations(&self) -> ZResult<()> {
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let expiries = self.storage.lock().await.get_all_expiries().await?;
+        let mut expirations = self.expirations.lock().await;
+        for (stripped_key, expiry) in expiries {
+            expirations.insert(crate::prefix(prefix, stripped_key.as_ref())?, expiry);
+        }
         Ok(())
     }
 
+    /// Deletes the values whose time-to-live has elapsed.
+    async fn expire_values(&self) {
+        let expired = self.expirations.lock().await.pop_expired();
+        for (key, expiry) in expired {
+            tracing::trace!("Expiring < {} >", key);
+            let sample = SampleBuilder::delete(key).timestamp(expiry).into();
+            if let Err(e) = self.process_sample(sample).await {
+                tracing::error!("{e:?}");
+            }
+        }
+    }
+
     /// Registers a Wildcard Update, storing it in a dedicated in-memory structure and on disk if
     /// the Storage persistence capability is set to `Durable`.
     ///
@@ -552,11 +695,32 @@ impl StorageService {
         };
         tracing::trace!("[STORAGE] Processing query on key_expr: {}", q.key_expr());
 
+        // Storages keeping all the history filter their values on the time range of the query
+        let mut time_travel = false;
+        if self.capability.history == History::All {
+            time_travel = q.parameters().time_range().is_some();
+            if let Some(Err(e)) = q.parameters().time_range() {
+                if let Err(e) = q.reply_err(format!("Invalid time range: {e}")).await {
+                    tracing::warn!(
+                        "Storage '{}' raised an error replying a query: {}",
+                        self.name,
+                        e
+                    )
+                }
+                return;
+            }
+        }
+
         let prefix = self.configuration.strip_prefix.as_ref();
 
         if q.key_expr().is_wild() {
             // resolve key expr into individual keys
-            let matching_keys = self.get_matching_keys(q.key_expr()).await;
+            let matching_keys = if time_travel {
+                // the keys deleted since may have values in the time range
+                self.get_matching_history_keys(q.key_expr()).await
+            } else {
+                self.get_matching_keys(q.key_expr()).await
+            };
             let mut storage = self.storage.lock().await;
             for key in matching_keys {
                 let stripped_key = match crate::strip_prefix(prefix, &key.clone().into()) {
@@ -655,6 +819,221 @@ impl StorageService {
         }
         result
     }
+
+    /// Returns the keys matching `key_expr` which have values in the history of the storage.
+    async fn get_matching_history_keys(&self, key_expr: &keyexpr) -> Vec<OwnedKeyExpr> {
+        let prefix = self.configuration.strip_prefix.as_ref();
+        match self.storage.lock().await.get_all_history_keys().await {
+            Ok(keys) => keys
+                .into_iter()
+                .filter_map(|k| crate::prefix(prefix, k.as_ref()).ok())
+                .filter(|full_key| key_expr.intersects(full_key))
+                .collect(),
+            Err(e) => {
+                tracing::warn!(
+                    "Storage '{}' raised an error while retrieving keys: {}",
+                    self.name,
+                    e
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the status of the storage, as exposed in the admin space: the one reported by its
+    /// backend and, if it is replicated, the status of its replication.
+    async fn admin_status(&self) -> serde_json::Value {
+        let mut status: serde_json::Value = self.storage.lock().await.get_admin_status().into();
+        if let (Some(replication_status), Some(replication_log), Some(status)) = (
+            &self.replication_status,
+            &self.cache_latest.replication_log,
+            status.as_object_mut(),
+        ) {
+            let replication_log_guard = replication_log.read().await;
+            status.insert(
+                "replication".to_string(),
+                replication_status.to_json_value(&replication_log_guard),
+            );
+        }
+        status
+    }
+
+    /// Replies to the operations on the admin key of the storage, e.g.
+    /// `@/<zid>/router/status/plugins/storage_manager/storages/<name>/export?file=backup.jsonl`:
+    /// - `export` and `import` write and load a snapshot of the storage. The `file` parameter is
+    ///   relative to the snapshots directory of the storage manager, and defaults to
+    ///   `<name>.jsonl`.
+    /// - `align` requests an immediate alignment with the other Replicas of the storage, without
+    ///   waiting for their next Digest.
+    async fn reply_admin_query(&self, query: ZResult<zenoh::query::Query>) {
+        let q = match query {
+            Ok(q) => q,
+            Err(e) => {
+                tracing::error!("Error in query: {}", e);
+                return;
+            }
+        };
+        // Wildcard queries browsing the admin space must not trigger any operation
+        if q.key_expr().is_wild() {
+            return;
+        }
+        let result = match q.key_expr().as_str().rsplit('/').next() {
+            Some("export") => match self.snapshot_path(q.parameters()) {
+                Ok(path) => self.export_snapshot(&path).await,
+                Err(e) => Err(e),
+            },
+            Some("import") => match self.snapshot_path(q.parameters()) {
+                Ok(path) => self.import_snapshot(&path).await,
+                Err(e) => Err(e),
+            },
+            Some("align") => self.request_alignment(),
+            _ => return,
+        };
+        let reply = match result {
+            Ok(status) => {
+                q.reply(q.key_expr().clone(), status.to_string())
+                    .encoding(Encoding::APPLICATION_JSON)
+                    .await
+            }
+            Err(e) => {
+                tracing::warn!("Storage '{}' failed on {}: {}", self.name, q.key_expr(), e);
+                q.reply_err(e.to_string()).await
+            }
+        };
+        if let Err(e) = reply {
+            tracing::warn!(
+                "Storage '{}' raised an error replying a query: {}",
+                self.name,
+                e
+            )
+        }
+    }
+
+    /// Returns the path of the snapshot designated by the `file` parameter of an admin query.
+    fn snapshot_path(&self, parameters: &Parameters) -> ZResult<PathBuf> {
+        let file = match parameters.get("file") {
+            Some(file) => file.to_string(),
+            None => format!("{}.jsonl", self.configuration.name),
+        };
+        snapshot_path(&self.snapshots_dir, &file)
+    }
+
+    /// Requests an alignment with the Replicas of the storage, returning the ones that will be
+    /// queried.
+    fn request_alignment(&self) -> ZResult<serde_json::Value> {
+        let Some(replication_status) = &self.replication_status else {
+            bail!("Replication is not enabled for storage '{}'", self.name);
+        };
+        tracing::debug!("Storage '{}': alignment requested", self.name);
+        Ok(serde_json::json!({
+            "replicas": replication_status.request_alignment(),
+        }))
+    }
+
+    /// Writes the latest value of every key of the storage to the snapshot at `path`, with its
+    /// expiry if it has a time-to-live.
+    ///
+    /// The values are collected while the storage is locked, and no sample is processed meanwhile,
+    /// hence the snapshot is consistent. The file is written once the lock is released.
+    async fn export_snapshot(&self, path: &Path) -> ZResult<serde_json::Value> {
+        let prefix = self.configuration.strip_prefix.as_ref();
+        let mut entries = Vec::new();
+        {
+            let mut storage = self.storage.lock().await;
+            // The expiries persisted by the backend, completed by the ones tracked by the storage
+            // manager for the backends not persisting them
+            let mut expiries = HashMap::new();
+            for (stripped_key, expiry) in storage.get_all_expiries().await? {
+                expiries.insert(crate::prefix(prefix, stripped_key.as_ref())?, expiry);
+            }
+            let expirations = self.expirations.lock().await;
+            for (stripped_key, timestamp) in storage.get_all_entries().await? {
+                let key = crate::prefix(prefix, stripped_key.as_ref())?;
+                // Keys whose latest update is a delete have no value with the latest timestamp
+                let Ok(stored_data) = storage.get(stripped_key, "").await else {
+                    continue;
+                };
+                if let Some(data) = stored_data.iter().find(|data| data.timestamp == timestamp) {
+                    let expiry = expiries.get(&key).or_else(|| expirations.get(&key));
+                    entries.push(SnapshotEntry::new(key, data, expiry));
+                }
+            }
+        }
+        let count = entries.len();
+        let header = SnapshotHeader::new(&self.configuration);
+        let file = path.to_owned();
+        tokio::task::spawn_blocking(move || {
+            let mut writer = SnapshotWriter::create(&file, &header)?;
+            for entry in &entries {
+                writer.write(entry)?;
+            }
+            writer.finish()
+        })
+        .await??;
+        tracing::debug!(
+            "Storage '{}': exported {} entries to {}",
+            self.name,
+            count,
+            path.display()
+        );
+        Ok(serde_json::json!({
+            "file": path,
+            "entries": count,
+        }))
+    }
+
+    /// Loads the snapshot at `path` in the storage, as if its values were received as samples:
+    /// values older than the ones of the storage are ignored, as well as keys that do not match
+    /// the key expression of the storage and values that have already expired. The other values
+    /// keep their expiry.
+    ///
+    /// The whole snapshot is read and validated before any of its values is applied, so that an
+    /// invalid snapshot leaves the storage unchanged.
+    async fn import_snapshot(&self, path: &Path) -> ZResult<serde_json::Value> {
+        let file = path.to_owned();
+        let (header, samples) = tokio::task::spawn_blocking(move || {
+            let (header, reader) = SnapshotReader::open(&file)?;
+            let samples = reader
+                .map(|entry| entry?.into_sample())
+                .collect::<ZResult<Vec<_>>>()?;
+            ZResult::Ok((header, samples))
+        })
+        .await??;
+        let (mut imported, mut outdated, mut skipped, mut expired) = (0, 0, 0, 0);
+        let now = SystemTime::now();
+        for (sample, expiry) in samples {
+            if !self.configuration.key_expr.includes(sample.key_expr()) {
+                skipped += 1;
+                continue;
+            }
+            if expiry.is_some_and(|expiry| expiry.get_time().to_system_time() <= now) {
+                expired += 1;
+                continue;
+            }
+            match self.process_sample_with_expiry(sample, expiry).await? {
+                0 => outdated += 1,
+                _ => imported += 1,
+            }
+        }
+        tracing::debug!(
+            "Storage '{}': imported {} entries from {} ({} outdated, {} skipped, {} expired)",
+            self.name,
+            imported,
+            path.display(),
+            outdated,
+            skipped,
+            expired
+        );
+        Ok(serde_json::json!({
+            "file": path,
+            "storage": header.storage,
+            "key_expr": header.key_expr,
+            "imported": imported,
+            "outdated": outdated,
+            "skipped": skipped,
+            "expired": expired,
+        }))
+    }
 }
 
 // Periodic event cleaning-up data info for old metadata
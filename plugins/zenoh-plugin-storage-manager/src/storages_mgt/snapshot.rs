@@ -0,0 +1,213 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Snapshots of the contents of a storage, in a portable JSON Lines file.
+//!
+//! The first line of a snapshot is a [`SnapshotHeader`], and each following line a
+//! [`SnapshotEntry`] holding the latest value of a key, with its expiry if it has a time-to-live.
+//! Keys are stored with their prefix, so that a snapshot can be imported in a storage configured
+//! with another `strip_prefix`, and payloads are encoded in base64.
+
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader, BufWriter, Lines, Write},
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::bail,
+    key_expr::OwnedKeyExpr,
+    sample::{Sample, SampleBuilder},
+    time::Timestamp,
+    Result as ZResult,
+};
+use zenoh_backend_traits::{config::StorageConfig, StoredData};
+
+/// The version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+/// The extension of the temporary file a snapshot is written to before being renamed.
+const TMP_EXTENSION: &str = "tmp";
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SnapshotHeader {
+    version: u32,
+    pub(crate) storage: String,
+    pub(crate) key_expr: OwnedKeyExpr,
+}
+
+impl SnapshotHeader {
+    pub(crate) fn new(config: &StorageConfig) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            storage: config.name.clone(),
+            key_expr: config.key_expr.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SnapshotEntry {
+    key: OwnedKeyExpr,
+    timestamp: String,
+    encoding: String,
+    payload: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expiry: Option<String>,
+}
+
+impl SnapshotEntry {
+    pub(crate) fn new(key: OwnedKeyExpr, data: &StoredData, expiry: Option<&Timestamp>) -> Self {
+        Self {
+            key,
+            timestamp: data.timestamp.to_string(),
+            encoding: data.encoding.to_string(),
+            payload: STANDARD.encode(data.payload.to_bytes()),
+            expiry: expiry.map(Timestamp::to_string),
+        }
+    }
+
+    /// Returns the sample of the entry, with the expiry of its value if it has a time-to-live.
+    pub(crate) fn into_sample(self) -> ZResult<(Sample, Option<Timestamp>)> {
+        let timestamp = Timestamp::from_str(&self.timestamp)
+            .map_err(|e| format!("Invalid timestamp of key `{}`: {}", self.key, e.cause))?;
+        let expiry = self
+            .expiry
+            .map(|expiry| Timestamp::from_str(&expiry))
+            .transpose()
+            .map_err(|e| format!("Invalid expiry of key `{}`: {}", self.key, e.cause))?;
+        let payload = STANDARD
+            .decode(&self.payload)
+            .map_err(|e| format!("Invalid payload of key `{}`: {e}", self.key))?;
+        let sample = SampleBuilder::put(self.key, ZBytes::from(payload))
+            .encoding(Encoding::from(self.encoding))
+            .timestamp(timestamp)
+            .into();
+        Ok((sample, expiry))
+    }
+}
+
+/// Returns the path of the snapshot `file`, which must be relative to the snapshots directory.
+pub(crate) fn snapshot_path(dir: &Path, file: &str) -> ZResult<PathBuf> {
+    let path = Path::new(file);
+    if file.is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        bail!("Snapshot file must be a relative path without `..`, found `{file}`");
+    }
+    Ok(dir.join(path))
+}
+
+/// Writes a snapshot to a temporary file, which replaces the target file once complete.
+pub(crate) struct SnapshotWriter {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl SnapshotWriter {
+    pub(crate) fn create(path: &Path, header: &SnapshotHeader) -> ZResult<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".");
+        tmp_path.push(TMP_EXTENSION);
+        let tmp_path = PathBuf::from(tmp_path);
+        let mut writer = Self {
+            path: path.to_owned(),
+            writer: BufWriter::new(File::create(&tmp_path)?),
+            tmp_path,
+        };
+        writer.write_line(header)?;
+        Ok(writer)
+    }
+
+    pub(crate) fn write(&mut self, entry: &SnapshotEntry) -> ZResult<()> {
+        self.write_line(entry)
+    }
+
+    fn write_line(&mut self, value: &impl Serialize) -> ZResult<()> {
+        serde_json::to_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    pub(crate) fn finish(self) -> ZResult<()> {
+        self.writer
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()?;
+        fs::rename(&self.tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Reads the entries of a snapshot, after its header.
+pub(crate) struct SnapshotReader {
+    path: PathBuf,
+    lines: Lines<BufReader<File>>,
+    line: usize,
+}
+
+impl SnapshotReader {
+    pub(crate) fn open(path: &Path) -> ZResult<(SnapshotHeader, Self)> {
+        let file = File::open(path)
+            .map_err(|e| format!("Cannot open snapshot {}: {e}", path.display()))?;
+        let mut reader = Self {
+            path: path.to_owned(),
+            lines: BufReader::new(file).lines(),
+            line: 0,
+        };
+        let Some(header) = reader.next_line::<SnapshotHeader>() else {
+            bail!("Snapshot {} is empty", path.display());
+        };
+        let header = header?;
+        if header.version != SNAPSHOT_VERSION {
+            bail!(
+                "Snapshot {} has unsupported version {}, expected {}",
+                path.display(),
+                header.version,
+                SNAPSHOT_VERSION
+            );
+        }
+        Ok((header, reader))
+    }
+
+    fn next_line<T: for<'de> Deserialize<'de>>(&mut self) -> Option<ZResult<T>> {
+        let line = self.lines.next()?;
+        self.line += 1;
+        let result = match line {
+            Ok(line) => serde_json::from_str(&line).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        Some(result.map_err(|e| {
+            format!(
+                "Invalid line {} of snapshot {}: {e}",
+                self.line,
+                self.path.display()
+            )
+            .into()
+        }))
+    }
+}
+
+impl Iterator for SnapshotReader {
+    type Item = ZResult<SnapshotEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_line()
+    }
+}
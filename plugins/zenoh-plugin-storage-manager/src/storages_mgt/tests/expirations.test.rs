@@ -0,0 +1,38 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+use std::time::Duration;
+
+use uhlc::HLC;
+use zenoh::time::{Timestamp, NTP64};
+
+use super::Expirations;
+
+#[test]
+fn test_expiry() {
+    let hlc = HLC::default();
+    let timestamp = hlc.new_timestamp();
+
+    let expiry = Expirations::expiry(&timestamp, Duration::from_secs(10)).unwrap();
+    assert_eq!(
+        *expiry.get_time(),
+        *timestamp.get_time() + NTP64::from(Duration::from_secs(10))
+    );
+    assert_eq!(expiry.get_id(), timestamp.get_id());
+
+    // The expiry of a value overflowing the range of the timestamps is discarded
+    let latest = Timestamp::new(NTP64(u64::MAX - 1), *hlc.get_id());
+    assert!(Expirations::expiry(&latest, Duration::from_secs(1)).is_none());
+    assert!(Expirations::expiry(&timestamp, Duration::from_secs(u64::MAX)).is_none());
+}
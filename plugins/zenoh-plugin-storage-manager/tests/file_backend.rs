@@ -0,0 +1,175 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the durability of the file backend:
+// 1. values and timestamps are restored after a restart of the storage manager
+// 2. a corrupted tail of the log is discarded
+// 3. a put older than the deletion of its key does not bring it back after a restart
+
+use std::{collections::HashMap, io::Write, path::Path, thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{internal::plugins::RunningPlugin, time::Timestamp, Config, Session};
+use zenoh_plugin_trait::Plugin;
+
+async fn start_storage(root: &Path) -> (RunningPlugin, Session) {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            &format!(
+                r#"{{
+                    volumes: {{
+                        file: {{
+                            root: {root:?}
+                        }}
+                    }},
+                    storages: {{
+                        file_test: {{
+                            key_expr: "file/test/**",
+                            strip_prefix: "file/test",
+                            volume: {{
+                                id: "file"
+                            }}
+                        }}
+                    }}
+                }}"#
+            ),
+        )
+        .unwrap();
+    config
+        .insert_json5("timestamping", r#"{ enabled: { peer: true } }"#)
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+    let session = zenoh::session::init(runtime).await.unwrap();
+    sleep(Duration::from_secs(1));
+    (storage, session)
+}
+
+async fn get_data(session: &Session) -> HashMap<String, (String, Timestamp)> {
+    let replies = session.get("file/test/**").await.unwrap();
+    let mut data = HashMap::new();
+    while let Ok(reply) = replies.recv_async().await {
+        let sample = reply.into_result().unwrap();
+        data.insert(
+            sample.key_expr().to_string(),
+            (
+                sample.payload().try_to_string().unwrap().into_owned(),
+                *sample.timestamp().unwrap(),
+            ),
+        );
+    }
+    data
+}
+
+async fn test_file_backend_restart(root: &Path) {
+    let (storage, session) = start_storage(root).await;
+    session.put("file/test/a", "1").await.unwrap();
+    session.put("file/test/b", "2").await.unwrap();
+    session.put("file/test", "root").await.unwrap();
+    session.delete("file/test/b").await.unwrap();
+    session.put("file/test/a", "3").await.unwrap();
+    sleep(Duration::from_millis(100));
+
+    let before = get_data(&session).await;
+    assert_eq!(before.len(), 2);
+    assert_eq!(before["file/test/a"].0, "3");
+    assert_eq!(before["file/test"].0, "root");
+    session.close().await.unwrap();
+    drop(storage);
+
+    // Simulate a power loss while appending a record
+    let log = root.join("file_test").join("data.log");
+    let mut file = std::fs::OpenOptions::new().append(true).open(&log).unwrap();
+    file.write_all(&[42, 0, 0, 0, 1, 2, 3]).unwrap();
+    drop(file);
+
+    let (storage, session) = start_storage(root).await;
+    let after = get_data(&session).await;
+    assert_eq!(after, before);
+
+    // The log is still writable after the corrupted tail has been discarded
+    session.put("file/test/c", "4").await.unwrap();
+    sleep(Duration::from_millis(100));
+    session.close().await.unwrap();
+    drop(storage);
+
+    let (storage, session) = start_storage(root).await;
+    let after = get_data(&session).await;
+    assert_eq!(after.len(), 3);
+    assert_eq!(after["file/test/c"].0, "4");
+    assert_eq!(after["file/test/a"], before["file/test/a"]);
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+async fn test_file_backend_deleted_key_restart(root: &Path) {
+    let (storage, session) = start_storage(root).await;
+    session.put("file/test/a", "1").await.unwrap();
+    sleep(Duration::from_millis(100));
+    let (_, put_timestamp) = get_data(&session).await["file/test/a"];
+    session.delete("file/test/a").await.unwrap();
+    sleep(Duration::from_millis(100));
+    assert!(get_data(&session).await.is_empty());
+    session.close().await.unwrap();
+    drop(storage);
+
+    // A put older than the deletion, e.g. pushed by a replica, arrives after the restart
+    let (storage, session) = start_storage(root).await;
+    session
+        .put("file/test/a", "stale")
+        .timestamp(put_timestamp)
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(100));
+    assert!(get_data(&session).await.is_empty());
+    session.close().await.unwrap();
+    drop(storage);
+
+    // The key is not brought back by the stale put after another restart either
+    let (storage, session) = start_storage(root).await;
+    assert!(get_data(&session).await.is_empty());
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+#[test]
+fn file_backend_restart_test() {
+    let root = std::env::temp_dir().join(format!("zenoh_backend_file_test_{}", std::process::id()));
+    let rt = Runtime::new().unwrap();
+    rt.block_on(test_file_backend_restart(&root));
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn file_backend_deleted_key_restart_test() {
+    let root = std::env::temp_dir().join(format!(
+        "zenoh_backend_file_deleted_test_{}",
+        std::process::id()
+    ));
+    let rt = Runtime::new().unwrap();
+    rt.block_on(test_file_backend_deleted_key_restart(&root));
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,199 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test time-travel queries:
+// 1. a storage keeping all the history replies with every value in the `_time` range
+// 2. a storage keeping the latest values only ignores the `_time` range
+// 3. the history is bounded by the garbage collection lifespan
+
+use std::{thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{Config, Session};
+use zenoh_plugin_trait::Plugin;
+
+async fn get_values(session: &Session, selector: &str) -> Vec<String> {
+    let replies = session.get(selector).await.unwrap();
+    let mut values = Vec::new();
+    while let Ok(reply) = replies.recv_async().await {
+        let sample = reply.into_result().unwrap();
+        values.push(format!(
+            "{}={}",
+            sample.key_expr(),
+            sample.payload().try_to_string().unwrap()
+        ));
+    }
+    values.sort();
+    values
+}
+
+async fn test_time_travel() {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    volumes: {
+                        memory: {
+                            history: "all"
+                        }
+                    },
+                    storages: {
+                        history_test: {
+                            key_expr: "history/test/**",
+                            volume: "memory",
+                            garbage_collection: {
+                                period: 1,
+                                lifespan: 3
+                            }
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5("timestamping", r#"{ enabled: { peer: true } }"#)
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+    let session = zenoh::session::init(runtime).await.unwrap();
+    sleep(Duration::from_secs(1));
+
+    session.put("history/test/a", "1").await.unwrap();
+    session.put("history/test/a", "2").await.unwrap();
+    session.put("history/test/b", "1").await.unwrap();
+    session.delete("history/test/b").await.unwrap();
+    sleep(Duration::from_secs(2));
+    session.put("history/test/a", "3").await.unwrap();
+    sleep(Duration::from_millis(100));
+
+    // Without time range, only the latest values are returned
+    assert_eq!(
+        get_values(&session, "history/test/**").await,
+        ["history/test/a=3"]
+    );
+    // The values of the keys deleted since are returned as well
+    assert_eq!(
+        get_values(&session, "history/test/**?_time=[now(-1m)..]").await,
+        [
+            "history/test/a=1",
+            "history/test/a=2",
+            "history/test/a=3",
+            "history/test/b=1"
+        ]
+    );
+    assert_eq!(
+        get_values(&session, "history/test/*?_time=[..now(-1s)]").await,
+        ["history/test/a=1", "history/test/a=2", "history/test/b=1"]
+    );
+    assert_eq!(
+        get_values(&session, "history/test/b?_time=[now(-1m)..]").await,
+        ["history/test/b=1"]
+    );
+    assert_eq!(
+        get_values(&session, "history/test/a?_time=[now(-1s)..]").await,
+        ["history/test/a=3"]
+    );
+    assert_eq!(
+        get_values(&session, "history/test/a?_time=[..now(-1s)]").await,
+        ["history/test/a=1", "history/test/a=2"]
+    );
+
+    // An invalid time range is reported as an error
+    let replies = session.get("history/test/a?_time=[x]").await.unwrap();
+    assert!(replies.recv_async().await.unwrap().result().is_err());
+
+    // Once the lifespan has elapsed, only the latest value of the remaining keys is kept
+    sleep(Duration::from_secs(2));
+    session.put("history/test/c", "1").await.unwrap();
+    sleep(Duration::from_millis(100));
+    assert_eq!(
+        get_values(&session, "history/test/**?_time=[now(-1m)..]").await,
+        ["history/test/a=3", "history/test/c=1"]
+    );
+
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+async fn test_time_range_latest() {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                    storages: {
+                        latest_test: {
+                            key_expr: "latest/test/**",
+                            volume: "memory"
+                        }
+                    }
+                }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5("timestamping", r#"{ enabled: { peer: true } }"#)
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+    let session = zenoh::session::init(runtime).await.unwrap();
+    sleep(Duration::from_secs(1));
+
+    session.put("latest/test/a", "1").await.unwrap();
+    session.put("latest/test/a", "2").await.unwrap();
+    sleep(Duration::from_millis(100));
+
+    assert_eq!(
+        get_values(&session, "latest/test/a?_time=[now(-1m)..]").await,
+        ["latest/test/a=2"]
+    );
+    assert_eq!(
+        get_values(&session, "latest/test/a?_time=[..now(-1m)]").await,
+        ["latest/test/a=2"]
+    );
+    assert_eq!(
+        get_values(&session, "latest/test/a?_time=[x]").await,
+        ["latest/test/a=2"]
+    );
+
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+#[test]
+fn time_travel_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(test_time_travel());
+    rt.block_on(test_time_range_latest());
+}
@@ -0,0 +1,143 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the replication status exposed in the admin space:
+// 1. the status of a replicated storage includes the status of its replication
+// 2. an alignment can be requested on a replicated storage, and only on a replicated storage
+
+use std::{thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{
+    bytes::Encoding, internal::plugins::RunningPlugin, key_expr::KeyExpr, Config, Session,
+};
+use zenoh_plugin_trait::Plugin;
+
+async fn start_storages() -> (RunningPlugin, Session) {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            r#"{
+                storages: {
+                    replicated: {
+                        key_expr: "replicated/**",
+                        volume: "memory",
+                        replication: {
+                            interval: 1.0,
+                            sub_intervals: 5,
+                            hot: 6,
+                            warm: 30,
+                            propagation_delay: 100,
+                        },
+                    },
+                    standalone: { key_expr: "standalone/**", volume: "memory" },
+                }
+            }"#,
+        )
+        .unwrap();
+    config
+        .insert_json5("timestamping", r#"{ enabled: { peer: true } }"#)
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+    let session = zenoh::session::init(runtime).await.unwrap();
+    sleep(Duration::from_secs(1));
+    (storage, session)
+}
+
+/// Returns the status of `storage`, as exposed in the admin space.
+fn storage_status(plugin: &RunningPlugin, session: &Session, storage: &str) -> serde_json::Value {
+    let plugin_status_key = format!("@/{}/peer/status/plugins/storage-manager", session.zid());
+    let key_expr = KeyExpr::try_from(format!("{plugin_status_key}/storages/{storage}")).unwrap();
+    let mut responses = plugin
+        .adminspace_getter(&key_expr, &plugin_status_key)
+        .unwrap();
+    assert_eq!(responses.len(), 1);
+    responses.remove(0).value.into()
+}
+
+/// Queries `operation` on the admin key of `storage`.
+async fn admin_operation(
+    session: &Session,
+    storage: &str,
+    operation: &str,
+) -> Result<serde_json::Value, String> {
+    let selector = format!(
+        "@/{}/peer/status/plugins/storage-manager/storages/{storage}/{operation}",
+        session.zid()
+    );
+    let replies = session.get(selector).await.unwrap();
+    let reply = replies.recv_async().await.unwrap();
+    match reply.into_result() {
+        Ok(sample) => {
+            assert_eq!(sample.encoding(), &Encoding::APPLICATION_JSON);
+            Ok(serde_json::from_slice(&sample.payload().to_bytes()).unwrap())
+        }
+        Err(e) => Err(e.payload().try_to_string().unwrap().into_owned()),
+    }
+}
+
+async fn test_replication_status() {
+    let (storage, session) = start_storages().await;
+    session.put("replicated/a", "1").await.unwrap();
+    session.put("replicated/b", "2").await.unwrap();
+    session.delete("replicated/b").await.unwrap();
+    // Let the replication publish its Digest at least once
+    sleep(Duration::from_millis(2500));
+
+    let status = storage_status(&storage, &session, "replicated");
+    let replication = &status["replication"];
+    assert!(replication["last_digest_published"].is_string());
+    let eras = &replication["eras"];
+    let events = ["hot", "warm", "cold"]
+        .iter()
+        .map(|era| eras[era]["events"].as_u64().unwrap())
+        .sum::<u64>();
+    assert_eq!(events, 2);
+    assert_eq!(replication["events_pulled"], 0);
+    assert_eq!(replication["events_pushed"], 0);
+    assert_eq!(replication["replicas"], serde_json::json!({}));
+
+    let status = storage_status(&storage, &session, "standalone");
+    assert!(status.get("replication").is_none());
+
+    // Without any other Replica, no Replica is queried
+    let reply = admin_operation(&session, "replicated", "align")
+        .await
+        .unwrap();
+    assert_eq!(reply["replicas"], serde_json::json!([]));
+    assert!(admin_operation(&session, "standalone", "align")
+        .await
+        .is_err());
+
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+#[test]
+fn replication_status_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(test_replication_status());
+}
@@ -0,0 +1,279 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the export and import of storage snapshots:
+// 1. a snapshot holds the latest values, encodings and timestamps of the keys of a storage
+// 2. a snapshot can be imported in a storage with another `strip_prefix`
+// 3. values older than the ones of the storage, and keys it doesn't store, are not imported
+// 4. an invalid snapshot is not imported at all
+// 5. the values having a time-to-live keep their expiry, and are not imported once expired
+
+use std::{collections::HashMap, path::Path, thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{bytes::Encoding, internal::plugins::RunningPlugin, time::Timestamp, Config, Session};
+use zenoh_plugin_trait::Plugin;
+
+async fn start_storage(snapshots_dir: &Path, storage: &str) -> (RunningPlugin, Session) {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "plugins/storage-manager",
+            &format!(
+                r#"{{
+                    snapshots_dir: {snapshots_dir:?},
+                    storages: {{
+                        {storage}
+                    }}
+                }}"#
+            ),
+        )
+        .unwrap();
+    config
+        .insert_json5("timestamping", r#"{ enabled: { peer: true } }"#)
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+    let session = zenoh::session::init(runtime).await.unwrap();
+    sleep(Duration::from_secs(1));
+    (storage, session)
+}
+
+async fn get_data(
+    session: &Session,
+    selector: &str,
+) -> HashMap<String, (String, Encoding, Timestamp)> {
+    let replies = session.get(selector).await.unwrap();
+    let mut data = HashMap::new();
+    while let Ok(reply) = replies.recv_async().await {
+        let sample = reply.into_result().unwrap();
+        data.insert(
+            sample.key_expr().to_string(),
+            (
+                sample.payload().try_to_string().unwrap().into_owned(),
+                sample.encoding().clone(),
+                *sample.timestamp().unwrap(),
+            ),
+        );
+    }
+    data
+}
+
+/// Queries a snapshot operation on the admin key of `storage`.
+async fn snapshot_operation(
+    session: &Session,
+    storage: &str,
+    operation: &str,
+) -> Result<serde_json::Value, String> {
+    let selector = format!(
+        "@/{}/peer/status/plugins/storage-manager/storages/{storage}/{operation}",
+        session.zid()
+    );
+    let replies = session.get(selector).await.unwrap();
+    let reply = replies.recv_async().await.unwrap();
+    match reply.into_result() {
+        Ok(sample) => {
+            assert_eq!(sample.encoding(), &Encoding::APPLICATION_JSON);
+            Ok(serde_json::from_slice(&sample.payload().to_bytes()).unwrap())
+        }
+        Err(e) => Err(e.payload().try_to_string().unwrap().into_owned()),
+    }
+}
+
+async fn test_snapshot(snapshots_dir: &Path) {
+    let (storage, session) = start_storage(
+        snapshots_dir,
+        r#"source: { key_expr: "snapshot/**", volume: "memory" }"#,
+    )
+    .await;
+    session
+        .put("snapshot/a", "1")
+        .encoding(Encoding::TEXT_PLAIN)
+        .await
+        .unwrap();
+    session.put("snapshot/a/b", "2").await.unwrap();
+    session.put("snapshot/c", "3").await.unwrap();
+    session.put("snapshot/d", "4").await.unwrap();
+    session.delete("snapshot/d").await.unwrap();
+    sleep(Duration::from_millis(100));
+    let source = get_data(&session, "snapshot/**").await;
+    assert_eq!(source.len(), 3);
+
+    let status = snapshot_operation(&session, "source", "export")
+        .await
+        .unwrap();
+    assert_eq!(status["entries"], 3);
+    assert!(snapshots_dir.join("source.jsonl").exists());
+
+    // Browsing the admin space doesn't trigger any operation
+    std::fs::remove_file(snapshots_dir.join("source.jsonl")).unwrap();
+    let replies = session
+        .get(format!("@/{}/peer/status/plugins/**", session.zid()))
+        .await
+        .unwrap();
+    while replies.recv_async().await.is_ok() {}
+    assert!(!snapshots_dir.join("source.jsonl").exists());
+
+    let status = snapshot_operation(&session, "source", "export?file=backups/source.jsonl")
+        .await
+        .unwrap();
+    assert_eq!(status["entries"], 3);
+
+    // Invalid files are reported as errors
+    assert!(
+        snapshot_operation(&session, "source", "import?file=missing.jsonl")
+            .await
+            .is_err()
+    );
+    assert!(
+        snapshot_operation(&session, "source", "export?file=../source.jsonl")
+            .await
+            .is_err()
+    );
+    session.close().await.unwrap();
+    drop(storage);
+
+    let (storage, session) = start_storage(
+        snapshots_dir,
+        r#"restored: {
+            key_expr: "snapshot/a/**",
+            strip_prefix: "snapshot/a",
+            volume: "memory"
+        }"#,
+    )
+    .await;
+    // The storage already has a newer value than the snapshot for `snapshot/a/b`
+    session.put("snapshot/a/b", "5").await.unwrap();
+    sleep(Duration::from_millis(100));
+    let newer = get_data(&session, "snapshot/a/b").await;
+
+    // A snapshot with an invalid entry leaves the storage unchanged, even if its first entries
+    // are valid
+    let mut corrupted =
+        std::fs::read_to_string(snapshots_dir.join("backups/source.jsonl")).unwrap();
+    corrupted.push_str("{ invalid entry\n");
+    std::fs::write(snapshots_dir.join("backups/corrupted.jsonl"), corrupted).unwrap();
+    assert!(
+        snapshot_operation(&session, "restored", "import?file=backups/corrupted.jsonl")
+            .await
+            .is_err()
+    );
+    assert_eq!(get_data(&session, "snapshot/**").await, newer);
+
+    let status = snapshot_operation(&session, "restored", "import?file=backups/source.jsonl")
+        .await
+        .unwrap();
+    assert_eq!(status["storage"], "source");
+    assert_eq!(status["imported"], 1);
+    assert_eq!(status["outdated"], 1);
+    assert_eq!(status["skipped"], 1);
+
+    let restored = get_data(&session, "snapshot/**").await;
+    assert_eq!(restored.len(), 2);
+    assert_eq!(restored["snapshot/a"], source["snapshot/a"]);
+    assert_eq!(restored["snapshot/a"].1, Encoding::TEXT_PLAIN);
+    assert_eq!(restored["snapshot/a/b"], newer["snapshot/a/b"]);
+
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+async fn test_snapshot_expiry(snapshots_dir: &Path) {
+    let (storage, session) = start_storage(
+        snapshots_dir,
+        r#"ttl_source: { key_expr: "expiry/**", volume: "memory" }"#,
+    )
+    .await;
+    session.put("expiry/none", "1").await.unwrap();
+    session
+        .put("expiry/short", "2")
+        .attachment("ttl=1")
+        .await
+        .unwrap();
+    session
+        .put("expiry/long", "3")
+        .attachment("ttl=5")
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(100));
+    let status = snapshot_operation(&session, "ttl_source", "export")
+        .await
+        .unwrap();
+    assert_eq!(status["entries"], 3);
+    let snapshot = std::fs::read_to_string(snapshots_dir.join("ttl_source.jsonl")).unwrap();
+    let expiries = snapshot
+        .lines()
+        .skip(1)
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .map(|entry| (entry["key"].clone(), entry.get("expiry").is_some()))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(expiries.len(), 3);
+    assert!(!expiries[&serde_json::json!("expiry/none")]);
+    assert!(expiries[&serde_json::json!("expiry/short")]);
+    assert!(expiries[&serde_json::json!("expiry/long")]);
+    session.close().await.unwrap();
+    drop(storage);
+
+    // The values which have expired since the export are not imported
+    sleep(Duration::from_secs(1));
+    let (storage, session) = start_storage(
+        snapshots_dir,
+        r#"ttl_restored: { key_expr: "expiry/**", volume: "memory" }"#,
+    )
+    .await;
+    let status = snapshot_operation(&session, "ttl_restored", "import?file=ttl_source.jsonl")
+        .await
+        .unwrap();
+    assert_eq!(status["imported"], 2);
+    assert_eq!(status["expired"], 1);
+    let mut keys = get_data(&session, "expiry/**")
+        .await
+        .into_keys()
+        .collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, ["expiry/long", "expiry/none"]);
+
+    // The imported values expire as they would have in the exported storage
+    sleep(Duration::from_secs(4));
+    let keys = get_data(&session, "expiry/**")
+        .await
+        .into_keys()
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["expiry/none"]);
+
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+#[test]
+fn snapshot_test() {
+    let snapshots_dir = std::env::temp_dir().join(format!(
+        "zenoh_storage_snapshot_test_{}",
+        std::process::id()
+    ));
+    let rt = Runtime::new().unwrap();
+    rt.block_on(test_snapshot(&snapshots_dir));
+    rt.block_on(test_snapshot_expiry(&snapshots_dir));
+    std::fs::remove_dir_all(&snapshots_dir).unwrap();
+}
@@ -0,0 +1,202 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+// Test the expiry of values:
+// 1. values expire after the default time-to-live of the storage, unless refreshed
+// 2. the time-to-live of a sample can be set in its attachment, up to the maximum of the storage
+// 3. the file backend expires values natively, including while it is not running
+
+use std::{path::Path, thread::sleep, time::Duration};
+
+use tokio::runtime::Runtime;
+use zenoh::{internal::plugins::RunningPlugin, Config, Session};
+use zenoh_plugin_trait::Plugin;
+
+async fn start_storage(plugin_config: &str) -> (RunningPlugin, Session) {
+    let mut config = Config::default();
+    config
+        .insert_json5("plugins/storage-manager", plugin_config)
+        .unwrap();
+    config
+        .insert_json5("timestamping", r#"{ enabled: { peer: true } }"#)
+        .unwrap();
+    config.insert_json5("listen/endpoints", "[]").unwrap();
+    config
+        .insert_json5("scouting/multicast/enabled", "false")
+        .unwrap();
+
+    let runtime = zenoh::internal::runtime::RuntimeBuilder::new(config)
+        .build()
+        .await
+        .unwrap()
+        .into();
+    let storage =
+        zenoh_plugin_storage_manager::StoragesPlugin::start("storage-manager", &runtime).unwrap();
+    let session = zenoh::session::init(runtime).await.unwrap();
+    sleep(Duration::from_secs(1));
+    (storage, session)
+}
+
+async fn get_keys(session: &Session, selector: &str) -> Vec<String> {
+    let replies = session.get(selector).await.unwrap();
+    let mut keys = Vec::new();
+    while let Ok(reply) = replies.recv_async().await {
+        keys.push(reply.into_result().unwrap().key_expr().to_string());
+    }
+    keys.sort();
+    keys
+}
+
+async fn test_ttl() {
+    let (storage, session) = start_storage(
+        r#"{
+            storages: {
+                default_ttl: { key_expr: "ttl/default/**", volume: "memory", ttl: 1 },
+                no_ttl: { key_expr: "ttl/none/**", volume: "memory" },
+                max_ttl: { key_expr: "ttl/max/**", volume: "memory", max_ttl: 0.5 }
+            }
+        }"#,
+    )
+    .await;
+
+    session.put("ttl/default/a", "1").await.unwrap();
+    session.put("ttl/default/refreshed", "1").await.unwrap();
+    session
+        .put("ttl/default/longer", "1")
+        .attachment("ttl=10")
+        .await
+        .unwrap();
+    session
+        .put("ttl/none/a", "1")
+        .attachment("ttl=0.5")
+        .await
+        .unwrap();
+    session.put("ttl/none/b", "1").await.unwrap();
+    session
+        .put("ttl/max/a", "1")
+        .attachment("ttl=60")
+        .await
+        .unwrap();
+    // A time-to-live beyond the range of the timestamps is clamped as well
+    session
+        .put("ttl/max/b", "1")
+        .attachment("ttl=1e15")
+        .await
+        .unwrap();
+    // Invalid time-to-live and unrelated attachments are ignored
+    session
+        .put("ttl/none/c", "1")
+        .attachment("ttl=0")
+        .await
+        .unwrap();
+    session
+        .put("ttl/none/d", "1")
+        .attachment("not a time-to-live")
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(200));
+    assert_eq!(
+        get_keys(&session, "ttl/**").await,
+        [
+            "ttl/default/a",
+            "ttl/default/longer",
+            "ttl/default/refreshed",
+            "ttl/max/a",
+            "ttl/max/b",
+            "ttl/none/a",
+            "ttl/none/b",
+            "ttl/none/c",
+            "ttl/none/d"
+        ]
+    );
+
+    sleep(Duration::from_millis(500));
+    session.put("ttl/default/refreshed", "2").await.unwrap();
+    sleep(Duration::from_millis(500));
+    assert_eq!(
+        get_keys(&session, "ttl/**").await,
+        [
+            "ttl/default/longer",
+            "ttl/default/refreshed",
+            "ttl/none/b",
+            "ttl/none/c",
+            "ttl/none/d"
+        ]
+    );
+
+    // A value without time-to-live doesn't expire anymore
+    session
+        .put("ttl/none/b", "2")
+        .attachment("ttl=0.5")
+        .await
+        .unwrap();
+    session.put("ttl/none/b", "3").await.unwrap();
+    sleep(Duration::from_secs(1));
+    assert_eq!(
+        get_keys(&session, "ttl/**").await,
+        [
+            "ttl/default/longer",
+            "ttl/none/b",
+            "ttl/none/c",
+            "ttl/none/d"
+        ]
+    );
+
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+async fn test_ttl_file_backend(root: &Path) {
+    let plugin_config = format!(
+        r#"{{
+            volumes: {{ file: {{ root: {root:?} }} }},
+            storages: {{ file_ttl: {{ key_expr: "ttl/file/**", volume: "file" }} }}
+        }}"#
+    );
+    let (storage, session) = start_storage(&plugin_config).await;
+    session
+        .put("ttl/file/a", "1")
+        .attachment("ttl=1")
+        .await
+        .unwrap();
+    session
+        .put("ttl/file/b", "1")
+        .attachment("ttl=60")
+        .await
+        .unwrap();
+    session.put("ttl/file/c", "1").await.unwrap();
+    sleep(Duration::from_millis(200));
+    session.close().await.unwrap();
+    drop(storage);
+
+    // The value expires while the storage manager is not running
+    sleep(Duration::from_secs(1));
+    let (storage, session) = start_storage(&plugin_config).await;
+    assert_eq!(
+        get_keys(&session, "ttl/file/**").await,
+        ["ttl/file/b", "ttl/file/c"]
+    );
+    session.close().await.unwrap();
+    drop(storage);
+}
+
+#[test]
+fn ttl_test() {
+    let rt = Runtime::new().unwrap();
+    rt.block_on(test_ttl());
+
+    let root = std::env::temp_dir().join(format!("zenoh_ttl_test_{}", std::process::id()));
+    rt.block_on(test_ttl_file_backend(&root));
+    std::fs::remove_dir_all(&root).unwrap();
+}
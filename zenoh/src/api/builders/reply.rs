@@ -289,6 +289,7 @@ impl Wait for ReplyErrBuilder<'_> {
                 eid: self.query.eid,
             }),
             ext_ts_stack: None,
+            ext_trace_context: self.query.inner.reply_trace_context(),
         };
         #[cfg(feature = "unstable")]
         {
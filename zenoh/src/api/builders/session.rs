@@ -12,7 +12,7 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-#[cfg(feature = "shared-memory")]
+#[cfg(any(feature = "shared-memory", feature = "unstable"))]
 use std::sync::Arc;
 use std::{
     fmt,
@@ -30,7 +30,10 @@ use crate::api::session::Session;
 #[cfg(feature = "internal")]
 use crate::net::runtime::DynamicRuntime;
 #[cfg(feature = "unstable")]
-use crate::{api::timestamp_stack::GetTimestampCallback, timestamp_stack::TimestampContext};
+use crate::{
+    api::{interceptor::InterceptorFactory, timestamp_stack::GetTimestampCallback},
+    timestamp_stack::TimestampContext,
+};
 
 /// A builder returned by [`crate::open`] used to open a zenoh [`Session`].
 ///
@@ -53,6 +56,8 @@ where
     shm_clients: Option<Arc<ShmClientStorage>>,
     #[cfg(feature = "unstable")]
     timestamp_callback: Option<GetTimestampCallback>,
+    #[cfg(feature = "unstable")]
+    interceptor_factories: Vec<Arc<dyn InterceptorFactory>>,
 }
 
 impl<TryIntoConfig> fmt::Debug for OpenBuilder<TryIntoConfig>
@@ -70,6 +75,15 @@ where
             "timestamp_callback",
             &self.timestamp_callback.as_ref().map(|_| ".."),
         );
+        #[cfg(feature = "unstable")]
+        debug.field(
+            "interceptor_factories",
+            &self
+                .interceptor_factories
+                .iter()
+                .map(|_| "..")
+                .collect::<Vec<_>>(),
+        );
         debug.finish()
     }
 }
@@ -86,6 +100,8 @@ where
             shm_clients: None,
             #[cfg(feature = "unstable")]
             timestamp_callback: None,
+            #[cfg(feature = "unstable")]
+            interceptor_factories: vec![],
         }
     }
 }
@@ -122,6 +138,16 @@ where
         self.timestamp_callback = Some(Box::new(cb));
         self
     }
+
+    /// Registers a user-defined interceptor factory.
+    ///
+    /// It is applied to every transport of the session, after the built-in interceptors.
+    /// See the [`interceptor`](crate::interceptor) module.
+    #[zenoh_macros::unstable]
+    pub fn with_interceptor_factory<F: InterceptorFactory + 'static>(mut self, factory: F) -> Self {
+        self.interceptor_factories.push(Arc::new(factory));
+        self
+    }
 }
 
 impl<TryIntoConfig> Resolvable for OpenBuilder<TryIntoConfig>
@@ -148,6 +174,8 @@ where
             self.shm_clients,
             #[cfg(feature = "unstable")]
             self.timestamp_callback,
+            #[cfg(feature = "unstable")]
+            self.interceptor_factories,
         )
         .wait()
     }
@@ -19,7 +19,9 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 use zenoh_config::ExpandedConfig;
-use zenoh_result::{bail, ZResult};
+use zenoh_result::{bail, zerror, ZResult};
+
+use crate::net::routing::interceptor::{acl_audit::validate_audit_update, validate_acl_config};
 
 /// Zenoh configuration.
 ///
@@ -176,11 +178,16 @@ impl<T> Clone for Notifier<T> {
     }
 }
 
+/// Returns `true` if `key` designates the `access_control` configuration or one of its fields.
+pub(crate) fn is_access_control_key(key: &str) -> bool {
+    key.trim_start_matches('/').split('/').next() == Some("access_control")
+}
+
 fn ensure_config_key_is_dynamically_writable(key: &str) -> ZResult<()> {
-    if !key.starts_with("plugins/") {
+    if !key.starts_with("plugins/") && !is_access_control_key(key) {
         bail!(
             "Error inserting conf value {} : updating config is only \
-                supported for keys starting with `plugins/`",
+                supported for keys starting with `plugins/` or `access_control`",
             key
         );
     }
@@ -197,7 +204,6 @@ impl Notifier<ExpandedConfig> {
         }
     }
 
-    #[cfg(feature = "plugins")]
     pub fn subscribe(&self) -> flume::Receiver<Notification> {
         let (tx, rx) = flume::unbounded();
         self.lock_subscribers().push(tx);
@@ -239,16 +245,44 @@ impl Notifier<ExpandedConfig> {
             .expect("acquiring Notifier's Config Mutex should not fail")
     }
 
+    /// Applies `f` to the config at `key`.
+    ///
+    /// Changes of the access control configuration are applied to a copy, which is only
+    /// committed if the resulting policy is valid, so that the config never diverges
+    /// from the enforced policy.
+    fn modify<R>(
+        &self,
+        key: &str,
+        f: impl FnOnce(&mut ExpandedConfig) -> ZResult<R>,
+    ) -> ZResult<R> {
+        let mut config = self.lock_config();
+        if !is_access_control_key(key) {
+            return f(&mut config);
+        }
+        let mut updated = config.clone();
+        let res = f(&mut updated)?;
+        validate_acl_config(updated.access_control())
+            .and_then(|_| {
+                validate_audit_update(
+                    &config.access_control().audit,
+                    &updated.access_control().audit,
+                )
+            })
+            .map_err(|e| zerror!("Invalid access control configuration: {e}"))?;
+        *config = updated;
+        Ok(res)
+    }
+
     pub fn remove<K: AsRef<str>>(&self, key: K) -> ZResult<()> {
-        self.lock_config().remove(key.as_ref())?;
+        self.modify(key.as_ref(), |config| config.remove(key.as_ref()))?;
         self.notify(key);
         Ok(())
     }
 
     pub fn try_remove_json5_array_item<K: AsRef<str>>(&self, key: K) -> ZResult<bool> {
-        let applied = self
-            .lock_config()
-            .try_remove_json5_array_item(key.as_ref())?;
+        let applied = self.modify(key.as_ref(), |config| {
+            config.try_remove_json5_array_item(key.as_ref())
+        })?;
         if applied {
             self.notify(key);
         }
@@ -257,14 +291,16 @@ impl Notifier<ExpandedConfig> {
 
     pub fn insert_json5(&self, key: &str, value: &str) -> ZResult<()> {
         ensure_config_key_is_dynamically_writable(key)?;
-        self.lock_config().insert_json5(key, value)?;
+        self.modify(key, |config| Ok(config.insert_json5(key, value)?))?;
         self.notify(key);
         Ok(())
     }
 
     pub fn try_insert_json5_array_item(&self, key: &str, value: &str) -> ZResult<bool> {
         ensure_config_key_is_dynamically_writable(key)?;
-        let applied = self.lock_config().try_insert_json5_array_item(key, value)?;
+        let applied = self.modify(key, |config| {
+            Ok(config.try_insert_json5_array_item(key, value)?)
+        })?;
         if applied {
             self.notify(key);
         }
@@ -302,6 +338,51 @@ mod tests {
         assert_eq!(config.lock().get_json("qos/network").unwrap(), before);
     }
 
+    #[test]
+    fn runtime_insert_json5_accepts_access_control_keys() {
+        let config = super::Notifier::new(zenoh_config::Config::default().expanded());
+        let rx = config.subscribe();
+
+        config
+            .insert_json5("access_control/default_permission", r#""allow""#)
+            .unwrap();
+
+        assert_eq!(
+            config
+                .lock()
+                .get_json("access_control/default_permission")
+                .unwrap(),
+            r#""allow""#
+        );
+        assert_eq!(
+            &*rx.try_recv().unwrap(),
+            "access_control/default_permission"
+        );
+    }
+
+    #[test]
+    fn runtime_insert_json5_rejects_invalid_access_control() {
+        let config = super::Notifier::new(zenoh_config::Config::default().expanded());
+        let rx = config.subscribe();
+        let before = config.lock().get_json("access_control").unwrap();
+
+        assert!(config
+            .insert_json5(
+                "access_control",
+                r#"{
+                    enabled: true,
+                    policies: [{ rules: ["unknown"], subjects: ["unknown"] }],
+                }"#,
+            )
+            .is_err());
+        assert!(config
+            .insert_json5("access_control_foo", r#""allow""#)
+            .is_err());
+
+        assert_eq!(config.lock().get_json("access_control").unwrap(), before);
+        assert!(rx.try_recv().is_err());
+    }
+
     #[test]
     fn insert_remove_json5_array_item() {
         let mut config = Config::default();
@@ -0,0 +1,245 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{any::Any, fmt, sync::Arc};
+
+use zenoh_keyexpr::keyexpr;
+use zenoh_protocol::network::NetworkMessageMut;
+use zenoh_result::ZResult;
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
+
+use crate::{
+    api::{info::Transport, key_expr::KeyExpr},
+    net::routing::interceptor::{
+        self as routing, InterceptorContext as RoutingContext, InterceptorFactoryTrait,
+        InterceptorTrait,
+    },
+};
+
+/// An interceptor of the network messages of a transport, in a given flow (ingress or egress).
+pub trait Interceptor: Send + Sync {
+    /// Computes a cache for the given key expression. It is computed once per resource
+    /// and then made available to [`Interceptor::intercept`] through
+    /// [`InterceptorContext::cache`].
+    fn compute_keyexpr_cache(&self, _key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+        None
+    }
+
+    /// Intercepts the given message, possibly modifying it.
+    /// Returns `false` if the message should be dropped.
+    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &InterceptorContext) -> bool;
+}
+
+/// An [`Interceptor`] of the messages received on a transport.
+pub type IngressInterceptor = Box<dyn Interceptor>;
+/// An [`Interceptor`] of the messages sent on a transport.
+pub type EgressInterceptor = Box<dyn Interceptor>;
+
+/// A factory of [`Interceptor`]s, called each time a transport is established.
+///
+/// The interceptors it returns run after the built-in ones, e.g. the access control.
+pub trait InterceptorFactory: Send + Sync {
+    /// Returns the ingress and egress interceptors to attach to a new unicast transport.
+    fn new_transport_unicast(
+        &self,
+        transport: &InterceptedTransport,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>);
+
+    /// Returns the egress interceptor to attach to a new multicast transport.
+    fn new_transport_multicast(
+        &self,
+        _transport: &InterceptedTransport,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    /// Returns the ingress interceptor to attach to a new peer of a multicast transport.
+    fn new_peer_multicast(&self, _transport: &InterceptedTransport) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+/// The transport an [`InterceptorFactory`] attaches interceptors to, with the properties of
+/// its links and of the authentication of the remote node.
+#[derive(Debug)]
+pub struct InterceptedTransport {
+    transport: Option<Transport>,
+    interfaces: Vec<String>,
+    cert_common_names: Vec<String>,
+    username: Option<String>,
+}
+
+impl InterceptedTransport {
+    fn unicast(transport: &TransportUnicast) -> ZResult<Self> {
+        let peer = transport.get_peer()?;
+        let auth_ids = transport.get_auth_ids()?;
+        Ok(Self {
+            transport: Some(Transport::new(&peer, false)),
+            interfaces: interfaces(&peer.links),
+            cert_common_names: auth_ids
+                .link_auth_ids()
+                .iter()
+                .filter_map(|auth_id| auth_id.get_cert_common_name().map(String::from))
+                .collect(),
+            username: auth_ids.username().cloned(),
+        })
+    }
+
+    fn multicast(transport: &TransportMulticast) -> ZResult<Self> {
+        Ok(Self {
+            transport: None,
+            interfaces: interfaces(&[transport.get_link()?]),
+            cert_common_names: vec![],
+            username: None,
+        })
+    }
+
+    fn peer_multicast(peer: &TransportPeer) -> Self {
+        Self {
+            transport: Some(Transport::new(peer, true)),
+            interfaces: interfaces(&peer.links),
+            cert_common_names: peer
+                .links
+                .iter()
+                .filter_map(|link| {
+                    link.auth_identifier
+                        .get_cert_common_name()
+                        .map(String::from)
+                })
+                .collect(),
+            username: None,
+        }
+    }
+
+    /// Returns the remote node of the transport, or `None` for a multicast transport,
+    /// whose egress is shared by all the nodes of the group.
+    pub fn transport(&self) -> Option<&Transport> {
+        self.transport.as_ref()
+    }
+
+    /// Returns the network interfaces of the links of the transport.
+    pub fn interfaces(&self) -> &[String] {
+        &self.interfaces
+    }
+
+    /// Returns the common names of the certificates of the remote node, on TLS and QUIC links.
+    pub fn cert_common_names(&self) -> &[String] {
+        &self.cert_common_names
+    }
+
+    /// Returns the username the remote node authenticated with, if any.
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+}
+
+fn interfaces(links: &[zenoh_link::Link]) -> Vec<String> {
+    links
+        .iter()
+        .flat_map(|link| link.interfaces.iter().cloned())
+        .collect()
+}
+
+/// The context of a message intercepted by an [`Interceptor`].
+pub struct InterceptorContext<'a> {
+    inner: &'a dyn RoutingContext,
+}
+
+impl fmt::Debug for InterceptorContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InterceptorContext").finish_non_exhaustive()
+    }
+}
+
+impl InterceptorContext<'_> {
+    /// Returns the full key expression of the intercepted message, if any.
+    pub fn key_expr(&self, msg: &NetworkMessageMut) -> Option<KeyExpr<'_>> {
+        self.inner.full_keyexpr(msg)
+    }
+
+    /// Returns the cache computed by [`Interceptor::compute_keyexpr_cache`] for the key
+    /// expression of the intercepted message, if any.
+    pub fn cache(&self, msg: &NetworkMessageMut) -> Option<&(dyn Any + Send + Sync)> {
+        self.inner.get_cache(msg).map(|cache| &**cache)
+    }
+}
+
+/// Attaches the interceptors of a user-defined [`InterceptorFactory`] to the transports.
+pub(crate) struct UserInterceptors(pub(crate) Arc<dyn InterceptorFactory>);
+
+impl InterceptorFactoryTrait for UserInterceptors {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (
+        Option<routing::IngressInterceptor>,
+        Option<routing::EgressInterceptor>,
+    ) {
+        let transport = match InterceptedTransport::unicast(transport) {
+            Ok(transport) => transport,
+            Err(e) => {
+                tracing::error!("Unable to get the properties of transport unicast: {}", e);
+                return (None, None);
+            }
+        };
+        let (ingress, egress) = self.0.new_transport_unicast(&transport);
+        (
+            ingress.map(UserInterceptor::boxed),
+            egress.map(UserInterceptor::boxed),
+        )
+    }
+
+    fn new_transport_multicast(
+        &self,
+        transport: &TransportMulticast,
+    ) -> Option<routing::EgressInterceptor> {
+        let transport = match InterceptedTransport::multicast(transport) {
+            Ok(transport) => transport,
+            Err(e) => {
+                tracing::error!("Unable to get the properties of transport multicast: {}", e);
+                return None;
+            }
+        };
+        self.0
+            .new_transport_multicast(&transport)
+            .map(UserInterceptor::boxed)
+    }
+
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        peer: &TransportPeer,
+    ) -> Option<routing::IngressInterceptor> {
+        self.0
+            .new_peer_multicast(&InterceptedTransport::peer_multicast(peer))
+            .map(UserInterceptor::boxed)
+    }
+}
+
+struct UserInterceptor(Box<dyn Interceptor>);
+
+impl UserInterceptor {
+    fn boxed(interceptor: Box<dyn Interceptor>) -> routing::Interceptor {
+        Box::new(Self(interceptor))
+    }
+}
+
+impl InterceptorTrait for UserInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+        self.0.compute_keyexpr_cache(key_expr)
+    }
+
+    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn RoutingContext) -> bool {
+        self.0.intercept(msg, &InterceptorContext { inner: ctx })
+    }
+}
@@ -23,6 +23,8 @@ pub(crate) mod connectivity;
 pub(crate) mod encoding;
 pub(crate) mod handlers;
 pub(crate) mod info;
+#[cfg(feature = "unstable")]
+pub(crate) mod interceptor;
 pub(crate) mod key_expr;
 pub(crate) mod liveliness;
 #[cfg(feature = "plugins")]
@@ -41,3 +43,4 @@ pub(crate) mod session;
 pub(crate) mod subscriber;
 #[cfg(feature = "unstable")]
 pub(crate) mod timestamp_stack;
+pub(crate) mod trace_context;
@@ -244,6 +244,9 @@ pub(crate) struct QueryState {
     pub(crate) replies: Option<HashMap<OwnedKeyExpr, Reply>>,
     pub(crate) callback: Callback<Reply>,
     pub(crate) querier_id: Option<Id>,
+    /// The span in which the query was sent, in which consolidated replies are delivered.
+    #[cfg(feature = "tracing-instrument")]
+    pub(crate) span: tracing::Span,
 }
 /// The kinds of accepted query replies.
 ///
@@ -49,7 +49,7 @@ use crate::{
         sample::{Locality, QoS, Sample, SampleKind},
         selector::{Selector, REPLY_KEY_EXPR_ANY_SEL_PARAM},
         session::{UndeclarableSealed, WeakSession},
-        Id,
+        trace_context, Id,
     },
     handlers::Callback,
     net::primitives::Primitives,
@@ -126,6 +126,10 @@ pub(crate) struct QueryInner {
     pub(crate) runtime: Option<WeakDynamicRuntime>,
     #[cfg(feature = "unstable")]
     pub(crate) query_ts_stack: Option<TimestampStack>,
+    /// The span in which the query was received, used as the trace context of replies sent
+    /// outside of it (e.g. from a channel handler).
+    #[cfg(feature = "tracing-instrument")]
+    pub(crate) span: tracing::Span,
 }
 
 impl QueryInner {
@@ -144,8 +148,19 @@ impl QueryInner {
             runtime: None,
             #[cfg(feature = "unstable")]
             query_ts_stack: None,
+            #[cfg(feature = "tracing-instrument")]
+            span: tracing::Span::none(),
         }
     }
+
+    /// Returns the trace context to send with a reply to this query: the one of the current
+    /// span, or the one of the span in which the query was received.
+    pub(crate) fn reply_trace_context(&self) -> Option<response::ext::TraceContextType> {
+        let trace_context = trace_context::current();
+        #[cfg(feature = "tracing-instrument")]
+        let trace_context = trace_context.or_else(|| trace_context::inject(&self.span));
+        trace_context
+    }
 }
 
 impl Drop for QueryInner {
@@ -621,6 +636,7 @@ impl Query {
                 eid: self.eid,
             }),
             ext_ts_stack: None,
+            ext_trace_context: self.inner.reply_trace_context(),
         };
         #[cfg(feature = "unstable")]
         {
@@ -76,8 +76,8 @@ use super::{
 use crate::api::timestamp_stack::{push_ts_interception, TimestampInstrumentation, TimestampStack};
 #[cfg(feature = "unstable")]
 use crate::api::{
-    cancellation::CancellationToken, sample::SourceInfo, selector::ZenohParameters,
-    timestamp_stack::GetTimestampCallback,
+    cancellation::CancellationToken, interceptor::InterceptorFactory, sample::SourceInfo,
+    selector::ZenohParameters, timestamp_stack::GetTimestampCallback,
 };
 #[cfg(feature = "internal")]
 use crate::net::runtime::Runtime;
@@ -115,7 +115,7 @@ use crate::{
         sample::{Locality, QoS, Sample, SampleKind},
         selector::{Selector, REPLY_KEY_EXPR_ANY_SEL_PARAM},
         subscriber::{SubscriberKind, SubscriberState},
-        Id,
+        trace_context, Id,
     },
     net::{
         primitives::Primitives,
@@ -1452,6 +1452,7 @@ impl Session {
         config: Config,
         #[cfg(feature = "shared-memory")] shm_clients: Option<Arc<ShmClientStorage>>,
         #[cfg(feature = "unstable")] timestamp_callback: Option<GetTimestampCallback>,
+        #[cfg(feature = "unstable")] interceptor_factories: Vec<Arc<dyn InterceptorFactory>>,
     ) -> impl Resolve<ZResult<Session>> {
         ResolveFuture::new(async move {
             tracing::debug!("Config: {:?}", &config);
@@ -1465,7 +1466,9 @@ impl Session {
             }
             #[cfg(feature = "unstable")]
             {
-                runtime = runtime.timestamp_callback(timestamp_callback);
+                runtime = runtime
+                    .timestamp_callback(timestamp_callback)
+                    .interceptor_factories(interceptor_factories);
             }
             let mut runtime = runtime.build().await?;
 
@@ -2517,6 +2520,7 @@ impl Session {
         #[cfg(feature = "unstable")] timestamp_instrumentation: Option<TimestampInstrumentation>,
     ) -> ZResult<()> {
         trace!("write({:?}, [...])", key_expr);
+        let ext_trace_context = trace_context::current();
         let state = zread!(self.0.state);
         let primitives = state.primitives()?;
         let wire_expr = key_expr.to_wire(self);
@@ -2573,6 +2577,7 @@ impl Session {
             }
             push.ext_ts_stack = ext_ts_stack;
         }
+        push.ext_trace_context = ext_trace_context;
         let has_local_callbacks = !callbacks.is_empty();
         if destination != Locality::SessionLocal {
             primitives.send_push_consume(
@@ -2616,6 +2621,8 @@ impl Session {
             }
             #[cfg(feature = "unstable")]
             let timestamp_stack = push.ext_ts_stack.as_ref().map(|ts| ts.ts_stack.clone());
+            let span = trace_context::receive_span("push", &push.ext_trace_context);
+            let _enter = span.enter();
             call_local(
                 callbacks,
                 &mut push,
@@ -2711,6 +2718,9 @@ impl Session {
             target,
             consolidation
         );
+        #[cfg(feature = "tracing-instrument")]
+        let span = tracing::Span::current();
+        let ext_trace_context = trace_context::current();
         let mut state = zwrite!(self.0.state);
         let consolidation = match consolidation.mode {
             #[cfg(feature = "unstable")]
@@ -2751,6 +2761,8 @@ impl Session {
                             if let Some(query) = state.queries.remove(&qid) {
                                 std::mem::drop(state);
                                 tracing::debug!("Timeout on query {}! Send error and close.", qid);
+                                #[cfg(feature = "tracing-instrument")]
+                                let _enter = query.span.enter();
                                 if query.reception_mode == ConsolidationMode::Latest {
                                     for (_, reply) in query.replies.unwrap().into_iter() {
                                         query.callback.call(reply);
@@ -2779,6 +2791,8 @@ impl Session {
                 replies: (consolidation != ConsolidationMode::None).then(HashMap::new),
                 callback,
                 querier_id,
+                #[cfg(feature = "tracing-instrument")]
+                span,
             },
         );
         drop(state);
@@ -2831,6 +2845,7 @@ impl Session {
                     ext_unknown: vec![],
                 }),
                 ext_ts_stack: ext_ts_stack.clone(),
+                ext_trace_context: ext_trace_context.clone(),
             });
         }
         if destination != Locality::Remote {
@@ -2843,6 +2858,8 @@ impl Session {
                     zenoh_protocol::network::timestamp_stack::interception_point::RECEIVE,
                 );
             }
+            let span = trace_context::receive_span("request", &ext_trace_context);
+            let _enter = span.enter();
             self.handle_query(
                 zread!(self.0.state),
                 true,
@@ -2983,6 +3000,8 @@ impl Session {
             zenoh_protocol::network::timestamp_stack::TimestampStack,
         >,
     ) {
+        #[cfg(feature = "tracing-instrument")]
+        let span = tracing::Span::current();
         let Ok(primitives) = state.primitives() else {
             return;
         };
@@ -3024,8 +3043,12 @@ impl Session {
             runtime: Some(self.0.runtime.downgrade()),
             #[cfg(feature = "unstable")]
             query_ts_stack,
+            #[cfg(feature = "tracing-instrument")]
+            span: span.clone(),
         });
         if !queryables.is_empty() {
+            #[cfg(feature = "tracing-instrument")]
+            let _enter = span.enter();
             let mut query = Query {
                 inner: query_inner,
                 eid: 0,
@@ -3063,6 +3086,28 @@ impl Session {
         }
         PublisherQoSConfig::default()
     }
+
+    #[cfg(all(feature = "unstable", feature = "stats"))]
+    fn observe_hop_latency<const ID: u8>(
+        &self,
+        wire_expr: &WireExpr,
+        ext_ts_stack: &Option<zenoh_protocol::network::timestamp_stack::TsStackType<ID>>,
+    ) {
+        let Some(ts_stack) = ext_ts_stack else {
+            return;
+        };
+        let runtime = self.0.runtime.get_inner();
+        let Some(hop_latency) = runtime.hop_latency() else {
+            return;
+        };
+        if !hop_latency.sample() {
+            return;
+        }
+        let state = zread!(self.0.state);
+        if let Ok(key_expr) = state.wireexpr_to_keyexpr(wire_expr, false) {
+            hop_latency.observe(&key_expr, &ts_stack.ts_stack);
+        }
+    }
 }
 
 impl Primitives for WeakSession {
@@ -3348,6 +3393,10 @@ impl Primitives for WeakSession {
                 zenoh_protocol::network::timestamp_stack::interception_point::RECEIVE,
             );
         }
+        #[cfg(all(feature = "unstable", feature = "stats"))]
+        self.observe_hop_latency(&msg.wire_expr, &msg.ext_ts_stack);
+        let span = trace_context::receive_span("push", &msg.ext_trace_context);
+        let _enter = span.enter();
         callbacks.call(
             consume,
             msg.ext_qos,
@@ -3370,6 +3419,10 @@ impl Primitives for WeakSession {
                 zenoh_protocol::network::timestamp_stack::interception_point::RECEIVE,
             );
         }
+        #[cfg(all(feature = "unstable", feature = "stats"))]
+        self.observe_hop_latency(&msg.wire_expr, &msg.ext_ts_stack);
+        let span = trace_context::receive_span("request", &msg.ext_trace_context);
+        let _enter = span.enter();
         match &mut msg.payload {
             RequestBody::Query(m) => {
                 let state = zread!(self.0.state);
@@ -3414,6 +3467,10 @@ impl Primitives for WeakSession {
                 zenoh_protocol::network::timestamp_stack::interception_point::RECEIVE,
             );
         }
+        #[cfg(all(feature = "unstable", feature = "stats"))]
+        self.observe_hop_latency(&msg.wire_expr, &msg.ext_ts_stack);
+        let span = trace_context::receive_span("response", &msg.ext_trace_context);
+        let _enter = span.enter();
         match &mut msg.payload {
             ResponseBody::Err(e) => {
                 let mut state = zwrite!(self.0.state);
@@ -3599,6 +3656,8 @@ impl Primitives for WeakSession {
                     let query = state.queries.remove(&msg.rid).unwrap();
                     std::mem::drop(state);
                     if query.reception_mode == ConsolidationMode::Latest {
+                        #[cfg(feature = "tracing-instrument")]
+                        let _enter = query.span.enter();
                         for (_, reply) in query.replies.unwrap().into_iter() {
                             query.callback.call(reply);
                         }
@@ -531,3 +531,81 @@ impl From<&TimestampStack> for zenoh_protocol::network::timestamp_stack::Timesta
         }
     }
 }
+
+/// Samples the timestamp stacks of received messages to feed the per-hop latency stats.
+#[cfg(all(feature = "unstable", feature = "stats"))]
+pub(crate) struct HopLatencyAnalytics {
+    stats: zenoh_stats::HopLatencyStats,
+    sampling: f64,
+    prefix_chunks: usize,
+}
+
+#[cfg(all(feature = "unstable", feature = "stats"))]
+impl HopLatencyAnalytics {
+    pub(crate) fn new(
+        config: &zenoh_config::Config,
+        stats: zenoh_stats::HopLatencyStats,
+    ) -> zenoh_result::ZResult<Option<Self>> {
+        use zenoh_config::unwrap_or_default;
+
+        if !unwrap_or_default!(config.stats().latency().enabled()) {
+            return Ok(None);
+        }
+        let sampling = unwrap_or_default!(config.stats().latency().sampling()).get();
+        if !(sampling > 0.0 && sampling <= 1.0) {
+            bail!("Invalid stats/latency config: sampling {sampling} is not in ]0, 1]");
+        }
+        let prefix_chunks = unwrap_or_default!(config.stats().latency().prefix_chunks());
+        if prefix_chunks == 0 {
+            bail!("Invalid stats/latency config: prefix_chunks must be greater than 0");
+        }
+        let max_series = unwrap_or_default!(config.stats().latency().max_series());
+        if max_series == 0 {
+            bail!("Invalid stats/latency config: max_series must be greater than 0");
+        }
+        stats.set_max_series(max_series);
+        Ok(Some(Self {
+            stats,
+            sampling,
+            prefix_chunks,
+        }))
+    }
+
+    /// Returns true if the next instrumented message should be analyzed.
+    pub(crate) fn sample(&self) -> bool {
+        self.sampling >= 1.0 || rand::random::<f64>() < self.sampling
+    }
+
+    /// Feeds the stats with the UHLC timestamps of the stack. Stacks containing custom
+    /// timestamps are ignored, as their format is unknown.
+    pub(crate) fn observe(
+        &self,
+        key_expr: &zenoh_keyexpr::keyexpr,
+        ts_stack: &zenoh_protocol::network::timestamp_stack::TimestampStack,
+    ) {
+        use zenoh_buffers::reader::HasReader;
+        use zenoh_codec::{RCodec, Zenoh080};
+
+        let Some(timestamps) = ts_stack
+            .stack
+            .iter()
+            .map(|record| {
+                if record.flags & interception_point::IS_CUSTOM_TS != 0 {
+                    return None;
+                }
+                let mut reader = (&record.timestamp).reader();
+                Zenoh080.read(&mut reader).ok()
+            })
+            .collect::<Option<Vec<uhlc::Timestamp>>>()
+        else {
+            return;
+        };
+        let prefix = key_expr
+            .as_str()
+            .split('/')
+            .take(self.prefix_chunks)
+            .collect::<Vec<_>>()
+            .join("/");
+        self.stats.observe(&prefix, &timestamps);
+    }
+}
@@ -0,0 +1,131 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Propagation of the [W3C trace context](https://www.w3.org/TR/trace-context/) of `tracing`
+//! spans across zenoh messages.
+//!
+//! With the `tracing-instrument` feature, the trace context of the span in which a put, a get
+//! or a reply is sent is carried in the trace context extension of the network message. The
+//! receiving session then calls the subscriber, queryable or reply callbacks in a span whose
+//! parent is the remote one. Without the feature, these functions are no-ops.
+
+use zenoh_protocol::network::trace_context::TraceContextType;
+
+#[cfg(feature = "tracing-instrument")]
+mod otel {
+    use std::str::FromStr;
+
+    use opentelemetry::{
+        trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState},
+        Context,
+    };
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use zenoh_protocol::network::trace_context::TraceContextType;
+
+    const TRACEPARENT_VERSION: &str = "00";
+
+    pub(crate) fn inject<const ID: u8>(span: &tracing::Span) -> Option<TraceContextType<ID>> {
+        let context = span.context();
+        let span_ref = context.span();
+        let span_context = span_ref.span_context();
+        if !span_context.is_valid() {
+            return None;
+        }
+        Some(TraceContextType {
+            traceparent: format!(
+                "{TRACEPARENT_VERSION}-{}-{}-{:02x}",
+                span_context.trace_id(),
+                span_context.span_id(),
+                span_context.trace_flags().to_u8()
+            ),
+            tracestate: span_context.trace_state().header(),
+        })
+    }
+
+    pub(crate) fn extract<const ID: u8>(ext: &TraceContextType<ID>) -> Option<Context> {
+        let mut fields = ext.traceparent.split('-');
+        let (Some(version), Some(trace_id), Some(span_id), Some(flags)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return None;
+        };
+        // Future versions may append fields, but version "ff" is forbidden
+        if version.len() != 2 || version == "ff" || (version == "00" && fields.next().is_some()) {
+            return None;
+        }
+        if trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+            return None;
+        }
+        let span_context = SpanContext::new(
+            TraceId::from_hex(trace_id).ok()?,
+            SpanId::from_hex(span_id).ok()?,
+            TraceFlags::new(u8::from_str_radix(flags, 16).ok()?),
+            true,
+            TraceState::from_str(&ext.tracestate).unwrap_or_default(),
+        );
+        span_context
+            .is_valid()
+            .then(|| Context::new().with_remote_span_context(span_context))
+    }
+}
+
+/// Returns the trace context of the current span to be sent in a message, if it belongs to a
+/// valid trace.
+///
+/// It must be called before entering the session span, i.e. before `SessionState::primitives`.
+#[cfg(feature = "tracing-instrument")]
+#[inline]
+pub(crate) fn current<const ID: u8>() -> Option<TraceContextType<ID>> {
+    otel::inject(&tracing::Span::current())
+}
+
+#[cfg(not(feature = "tracing-instrument"))]
+#[inline(always)]
+pub(crate) fn current<const ID: u8>() -> Option<TraceContextType<ID>> {
+    None
+}
+
+/// Returns the trace context of `span` to be sent in a message, if it belongs to a valid trace.
+#[cfg(feature = "tracing-instrument")]
+pub(crate) fn inject<const ID: u8>(span: &tracing::Span) -> Option<TraceContextType<ID>> {
+    otel::inject(span)
+}
+
+/// Returns the span in which the callbacks of a received message must be called.
+///
+/// If the message carries a valid trace context, the span continues the remote trace,
+/// otherwise it is [`tracing::Span::none`].
+#[cfg(feature = "tracing-instrument")]
+pub(crate) fn receive_span<const ID: u8>(
+    message: &'static str,
+    ext: &Option<TraceContextType<ID>>,
+) -> tracing::Span {
+    let Some(context) = ext.as_ref().and_then(otel::extract) else {
+        return tracing::Span::none();
+    };
+    let span = tracing::info_span!("zenoh::receive", message);
+    if let Err(e) = tracing_opentelemetry::OpenTelemetrySpanExt::set_parent(&span, context) {
+        tracing::trace!("Unable to continue remote trace: {e}");
+    }
+    span
+}
+
+#[cfg(not(feature = "tracing-instrument"))]
+#[inline(always)]
+pub(crate) fn receive_span<const ID: u8>(
+    _message: &'static str,
+    _ext: &Option<TraceContextType<ID>>,
+) -> tracing::Span {
+    tracing::Span::none()
+}
@@ -179,11 +179,15 @@
 //!
 //! * `stats`
 //!
-//!   Enable collection of statistical data. This data becomes available in "adminspace" (by key `@/<zenoh_id>/router/metrics`)
+//!   Enable collection of statistical data. This data becomes available in "adminspace" (by key `@/<zenoh_id>/router/metrics`),
+//!   and can be served to Prometheus by an embedded HTTP listener configured in `stats/scrape` of the [`Config`]
 //!
 //! * `tracing-instrument`
 //!
-//!   Developer feature - enable tracing of asynchronous tasks for debugging
+//!   Developer feature - enable tracing of asynchronous tasks for debugging. Also propagates the
+//!   W3C trace context of the current [`tracing`](https://docs.rs/tracing) span on `put`, `get` and replies,
+//!   so that subscriber, queryable and reply callbacks execute in a span continuing the remote trace
+//!   (requires a [`tracing-opentelemetry`](https://docs.rs/tracing-opentelemetry) layer)
 //!
 //! * `transport-compression`
 //!
@@ -1082,6 +1086,10 @@ pub mod internal {
 #[zenoh_macros::unstable]
 #[cfg(feature = "shared-memory")]
 pub mod shm {
+    #[cfg(target_os = "linux")]
+    pub use zenoh_shm::api::protocol_implementations::memfd::{
+        memfd_shm_client::MemfdShmClient, memfd_shm_provider_backend::*,
+    };
     pub use zenoh_shm::api::{
         buffer::{
             traits::{
@@ -1107,6 +1115,7 @@ pub mod shm {
         provider::{
             chunk::{AllocatedChunk, ChunkDescriptor},
             memory_layout::{MemoryLayout, TypedLayout},
+            shm_buffer_pool::{ShmBufferPool, ShmBufferPoolBuilder},
             shm_provider::{
                 AllocBuilder, AllocPolicy, AsyncAllocPolicy, BlockOn, ConstBool, ConstPolicy,
                 ConstUsize, Deallocate, Defragment, GarbageCollect, JustAlloc, PolicyValue,
@@ -1192,6 +1201,62 @@ pub mod timestamp_stack {
     };
 }
 
+/// User-defined interceptors of network messages.
+///
+/// An [`InterceptorFactory`](crate::interceptor::InterceptorFactory) is called each time a
+/// transport is established, and returns the [`Interceptor`](crate::interceptor::Interceptor)s
+/// of its ingress and egress flows. They may modify or drop the network messages of the
+/// transport, after the built-in interceptors (e.g. access control).
+///
+/// The factories are registered when opening a session, with
+/// [`OpenBuilder::with_interceptor_factory`](crate::session::OpenBuilder::with_interceptor_factory),
+/// or by a plugin on the runtime of the router.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::interceptor::{
+///     EgressInterceptor, IngressInterceptor, InterceptedTransport, Interceptor,
+///     InterceptorContext, InterceptorFactory, NetworkMessageMut,
+/// };
+///
+/// // Drops the messages received on the `private/**` key expressions.
+/// struct DropPrivate;
+///
+/// impl Interceptor for DropPrivate {
+///     fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &InterceptorContext) -> bool {
+///         !ctx.key_expr(msg).is_some_and(|k| k.starts_with("private/"))
+///     }
+/// }
+///
+/// struct DropPrivateFactory;
+///
+/// impl InterceptorFactory for DropPrivateFactory {
+///     fn new_transport_unicast(
+///         &self,
+///         _transport: &InterceptedTransport,
+///     ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+///         (Some(Box::new(DropPrivate)), None)
+///     }
+/// }
+///
+/// let session = zenoh::open(zenoh::Config::default())
+///     .with_interceptor_factory(DropPrivateFactory)
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub mod interceptor {
+    pub use zenoh_protocol::network::{NetworkBodyMut, NetworkMessageMut};
+
+    pub use crate::api::interceptor::{
+        EgressInterceptor, IngressInterceptor, InterceptedTransport, Interceptor,
+        InterceptorContext, InterceptorFactory,
+    };
+}
+
 /// Functionality for interrupting queries.
 #[zenoh_macros::unstable]
 pub mod cancellation {
@@ -30,7 +30,9 @@ use crate::net::routing::{
     dispatcher::face::Face,
     gateway::{InterceptorCacheValueType, Resource},
     hat::{DispatcherContext, HatTrait},
-    interceptor::{has_interceptor, InterceptorContext, InterceptorTrait, InterceptorsChain},
+    interceptor::{
+        has_interceptor, InterceptorContext, InterceptorTrait, InterceptorsChain, MessageSink,
+    },
     RoutingContext,
 };
 
@@ -114,6 +116,26 @@ impl InterceptorContext for DeMuxContext<'_> {
         }
         self.cache.get().and_then(|c| c.get_ref().as_ref())
     }
+
+    fn sink(&self) -> Option<MessageSink> {
+        let face = self.demux.face.downgrade();
+        Some(Arc::new(move |msg| {
+            let Some(face) = face.upgrade() else {
+                return false;
+            };
+            match msg.body {
+                NetworkBodyMut::Push(m) => face.send_push(m, msg.reliability),
+                NetworkBodyMut::Declare(m) => face.send_declare(m),
+                NetworkBodyMut::Interest(m) => face.send_interest(m),
+                NetworkBodyMut::Request(m) => face.send_request(m),
+                NetworkBodyMut::Response(m) => face.send_response(m),
+                NetworkBodyMut::ResponseFinal(m) => face.send_response_final(m),
+                // OAM messages are handled by the demux itself
+                NetworkBodyMut::OAM(_) => return false,
+            }
+            true
+        }))
+    }
 }
 
 impl TransportPeerEventHandler for DeMux {
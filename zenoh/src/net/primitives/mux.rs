@@ -31,7 +31,9 @@ use super::{EPrimitives, Primitives};
 use crate::net::routing::{
     dispatcher::face::{Face, WeakFace},
     gateway::{InterceptorCacheValueType, Resource},
-    interceptor::{has_interceptor, InterceptorContext, InterceptorTrait, InterceptorsChain},
+    interceptor::{
+        has_interceptor, InterceptorContext, InterceptorTrait, InterceptorsChain, MessageSink,
+    },
     RoutingContext,
 };
 
@@ -135,6 +137,11 @@ impl InterceptorContext for MuxContext<'_> {
         }
         self.cache.get().and_then(|c| c.get_ref().as_ref())
     }
+
+    fn sink(&self) -> Option<MessageSink> {
+        let handler = self.mux.handler.clone();
+        Some(Arc::new(move |msg| handler.schedule(msg).unwrap_or(false)))
+    }
 }
 
 impl EPrimitives for Mux {
@@ -15,7 +15,7 @@ use std::{
     any::Any,
     collections::HashMap,
     fmt::{self, Debug},
-    sync::{Arc, Weak},
+    sync::{Arc, Mutex, Weak},
     time::Duration,
 };
 
@@ -34,7 +34,7 @@ use zenoh_protocol::{
 };
 use zenoh_sync::get_mut_unchecked;
 use zenoh_task::TaskController;
-use zenoh_transport::multicast::TransportMulticast;
+use zenoh_transport::{multicast::TransportMulticast, TransportPeer};
 
 use super::{
     super::gateway::*, interests::PendingCurrentInterest, resource::*, tables::TablesLock,
@@ -53,8 +53,8 @@ use crate::net::{
         },
         hat::{DispatcherContext, UnregisterFaceEntitiesResult},
         interceptor::{
-            EgressInterceptor, IngressInterceptor, InterceptorFactory, InterceptorTrait,
-            InterceptorsChain,
+            InterceptorFactories, InterceptorFactory, InterceptorTrait, InterceptorsChain,
+            SharedInterceptor,
         },
     },
 };
@@ -133,7 +133,11 @@ pub struct FaceState {
     /// acquiring the lock.
     pub(crate) pending_queries: HashMap<RequestId, (Arc<Query>, CancellationToken)>,
     pub(crate) mcast_group: Option<TransportMulticast>,
+    /// The remote peer of a face on a multicast transport.
+    pub(crate) mcast_peer: Option<TransportPeer>,
     pub(crate) in_interceptors: Option<Arc<ArcSwapOption<InterceptorsChain>>>,
+    /// The interceptors of this face, by factory, reused when the chains are rebuilt.
+    pub(crate) interceptors: Mutex<Vec<FactoryInterceptors>>,
     /// Map from `Region` to `HatFace`.
     pub(crate) hats: RegionMap<Box<dyn Any + Send + Sync>>,
     pub(crate) task_controller: TaskController,
@@ -168,7 +172,9 @@ impl FaceStateBuilder {
             next_qid: 0,
             pending_queries: HashMap::new(),
             mcast_group: None,
+            mcast_peer: None,
             in_interceptors: None,
+            interceptors: Mutex::new(vec![]),
             hats,
             task_controller: TaskController::default(),
             is_local: false,
@@ -195,6 +201,11 @@ impl FaceStateBuilder {
         self
     }
 
+    pub(crate) fn multicast_peer(mut self, mcast_peer: TransportPeer) -> Self {
+        self.0.mcast_peer = Some(mcast_peer);
+        self
+    }
+
     pub(crate) fn local(mut self, is_local: bool) -> Self {
         self.0.is_local = is_local;
         self
@@ -299,50 +310,87 @@ impl FaceState {
         }
     }
 
+    /// Builds the interceptors chains of this face from `factories`.
+    ///
+    /// The interceptors built by factories already applied to this face are reused.
     pub(crate) fn set_interceptors_from_factories(
         &self,
-        factories: &[InterceptorFactory],
+        factories: &InterceptorFactories,
         version: usize,
     ) {
+        let mut interceptors = zlock!(self.interceptors);
+        let previous = std::mem::take(&mut *interceptors);
+        *interceptors = factories
+            .iter()
+            .map(|factory| {
+                previous
+                    .iter()
+                    .find(|itor| Arc::ptr_eq(&itor.factory, factory))
+                    .cloned()
+                    .unwrap_or_else(|| self.new_interceptors(factory))
+            })
+            .collect();
+        let ingress = || {
+            InterceptorsChain::new(
+                interceptors
+                    .iter()
+                    .filter_map(|itor| itor.ingress.clone())
+                    .collect(),
+                version,
+            )
+        };
+        let egress = || {
+            InterceptorsChain::new(
+                interceptors
+                    .iter()
+                    .filter_map(|itor| itor.egress.clone())
+                    .collect(),
+                version,
+            )
+        };
         if let Some(mux) = self.primitives.as_any().downcast_ref::<Mux>() {
-            let (ingress, egress): (Vec<_>, Vec<_>) = factories
-                .iter()
-                .map(|itor| itor.new_transport_unicast(&mux.handler))
-                .unzip();
-            let (ingress, egress) = (
-                InterceptorsChain::new(ingress.into_iter().flatten().collect::<Vec<_>>(), version),
-                InterceptorsChain::new(egress.into_iter().flatten().collect::<Vec<_>>(), version),
-            );
+            let egress = egress();
             mux.interceptor
                 .store((!egress.is_empty()).then(|| egress.into()));
             self.in_interceptors
                 .as_ref()
                 .expect("face in_interceptors should not be None when primitives are Mux")
-                .store(ingress.into());
+                .store(ingress().into());
         } else if let Some(mux) = self.primitives.as_any().downcast_ref::<McastMux>() {
-            let interceptor = InterceptorsChain::new(
-                factories
-                    .iter()
-                    .filter_map(|itor| itor.new_transport_multicast(&mux.handler))
-                    .collect::<Vec<EgressInterceptor>>(),
-                version,
-            );
-            mux.interceptor.store(interceptor.into());
+            mux.interceptor.store(egress().into());
             debug_assert!(self.in_interceptors.is_none());
-        } else if let Some(transport) = &self.mcast_group {
-            let interceptor = InterceptorsChain::new(
-                factories
-                    .iter()
-                    .filter_map(|itor| itor.new_peer_multicast(transport))
-                    .collect::<Vec<IngressInterceptor>>(),
-                version,
-            );
+        } else if self.mcast_group.is_some() {
             self.in_interceptors
                 .as_ref()
                 .expect("face in_interceptors should not be None when mcast_group is set")
-                .store(interceptor.into());
+                .store(ingress().into());
         }
     }
+
+    fn new_interceptors(&self, factory: &InterceptorFactory) -> FactoryInterceptors {
+        let (ingress, egress) = if let Some(mux) = self.primitives.as_any().downcast_ref::<Mux>() {
+            factory.new_transport_unicast(&mux.handler)
+        } else if let Some(mux) = self.primitives.as_any().downcast_ref::<McastMux>() {
+            (None, factory.new_transport_multicast(&mux.handler))
+        } else if let (Some(transport), Some(peer)) = (&self.mcast_group, &self.mcast_peer) {
+            (factory.new_peer_multicast(transport, peer), None)
+        } else {
+            (None, None)
+        };
+        FactoryInterceptors {
+            factory: factory.clone(),
+            ingress: ingress.map(Arc::from),
+            egress: egress.map(Arc::from),
+        }
+    }
+}
+
+/// The interceptors built by a factory for a face.
+#[derive(Clone)]
+pub(crate) struct FactoryInterceptors {
+    factory: InterceptorFactory,
+    ingress: Option<SharedInterceptor>,
+    egress: Option<SharedInterceptor>,
 }
 
 impl fmt::Display for FaceState {
@@ -358,6 +358,7 @@ pub fn route_data(
                         node_id: dir.node_id,
                     },
                     ext_ts_stack: msg.ext_ts_stack.clone(),
+                    ext_trace_context: msg.ext_trace_context.clone(),
                     payload: msg.payload.clone(),
                 };
                 #[cfg(feature = "unstable")]
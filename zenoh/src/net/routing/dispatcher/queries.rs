@@ -326,6 +326,7 @@ impl Face {
                             ext_budget: msg.ext_budget,
                             ext_timeout: msg.ext_timeout,
                             ext_ts_stack: msg.ext_ts_stack.clone(),
+                            ext_trace_context: msg.ext_trace_context.clone(),
                             payload: msg.payload.clone(),
                         };
 
@@ -499,6 +500,7 @@ impl Timed for QueryCleanup {
                     ext_respid,
                     // TODO: Maybe this should be set?
                     ext_ts_stack: None,
+                    ext_trace_context: None,
                 },
             );
             let queries_lock = zwrite!(self.tables.queries_lock);
@@ -5,7 +5,7 @@ use zenoh_protocol::{
     network::{Push, Request, Response},
     zenoh::{PushBody, RequestBody, ResponseBody},
 };
-use zenoh_stats::{MessageLabel, SpaceLabel, StatsDirection, StatsKeys};
+use zenoh_stats::{KeyTrafficPrefix, MessageLabel, SpaceLabel, StatsDirection, StatsKeys};
 
 use crate::net::routing::dispatcher::{
     face::FaceState,
@@ -134,6 +134,7 @@ pub(super) struct PayloadObserver<Msg> {
     payload_size: usize,
     space: SpaceLabel,
     keys: StatsKeys,
+    key_traffic: Option<KeyTrafficPrefix>,
     _phantom: PhantomData<Msg>,
 }
 
@@ -151,6 +152,10 @@ impl<Msg: ObservableMessage> PayloadObserver<Msg> {
                 || expr.key_expr(),
             )
         });
+        let key_traffic = tables.data.stats.key_traffic();
+        let key_traffic = expr
+            .filter(|_| key_traffic.is_enabled())
+            .and_then(|expr| key_traffic.prefix(expr.key_expr()?));
         Self {
             message: msg.message(),
             priority: msg.priority(),
@@ -158,6 +163,7 @@ impl<Msg: ObservableMessage> PayloadObserver<Msg> {
             space,
             // SAFETY: the tree is always the table's one
             keys,
+            key_traffic,
             _phantom: PhantomData,
         }
     }
@@ -176,7 +182,10 @@ impl<Msg: ObservableMessage> PayloadObserver<Msg> {
                 self.space,
                 &self.keys,
                 shm,
-            )
+            );
+            if let Some(prefix) = self.key_traffic {
+                stats.observe_key_traffic(direction, prefix, self.payload_size);
+            }
         }
     }
 }
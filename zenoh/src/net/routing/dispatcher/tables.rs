@@ -34,11 +34,16 @@ use zenoh_result::ZResult;
 
 use super::face::FaceState;
 pub use super::resource::*;
+#[cfg(feature = "unstable")]
+use crate::net::routing::interceptor::UserInterceptorFactory;
 use crate::net::{
     routing::{
         dispatcher::{face::FaceId, region::RegionMap},
         hat::{HatTrait, Sources},
-        interceptor::{interceptor_factories, InterceptorFactory},
+        interceptor::{
+            acl_audit::AclAuditLog, keyexpr_remapping::KeyExprRemapping, rate_limit::RateLimiter,
+            InterceptorFactories,
+        },
     },
     runtime::WeakRuntime,
 };
@@ -134,7 +139,12 @@ pub(crate) struct TablesData {
     pub(crate) face_counter: FaceId,
 
     pub(crate) next_interceptor_version: AtomicUsize,
-    pub(crate) interceptors: Vec<InterceptorFactory>,
+    pub(crate) interceptors: InterceptorFactories,
+    #[cfg(feature = "unstable")]
+    pub(crate) user_interceptors: Vec<UserInterceptorFactory>,
+    pub(crate) keyexpr_remapping: Option<Arc<KeyExprRemapping>>,
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    pub(crate) acl_audit: Option<Arc<AclAuditLog>>,
 
     pub(crate) faces: HashMap<FaceId, Arc<FaceState>>,
 
@@ -176,6 +186,7 @@ impl TablesData {
         hlc: Option<Arc<HLC>>,
         config: &Config,
         hat: RegionMap<HatTablesData>,
+        #[cfg(feature = "unstable")] user_interceptors: Vec<UserInterceptorFactory>,
         #[cfg(feature = "stats")] stats: zenoh_stats::StatsRegistry,
     ) -> ZResult<Self> {
         let drop_future_timestamp =
@@ -184,6 +195,9 @@ impl TablesData {
             Duration::from_millis(unwrap_or_default!(config.queries_default_timeout()));
         let interests_timeout =
             Duration::from_millis(unwrap_or_default!(config.routing().interests().timeout()));
+        let keyexpr_remapping = KeyExprRemapping::new(config.keyexpr_remapping())?;
+        let rate_limiter = RateLimiter::new(config.rate_limit())?;
+        let acl_audit = AclAuditLog::new(&config.access_control().audit)?;
         #[cfg(feature = "stats")]
         let mut stats_keys = zenoh_stats::StatsKeysTree::default();
         #[cfg(feature = "stats")]
@@ -200,7 +214,19 @@ impl TablesData {
             queries_default_timeout,
             interests_timeout,
             root_res: Resource::root(),
-            interceptors: interceptor_factories(config)?,
+            interceptors: InterceptorFactories::new(
+                config,
+                keyexpr_remapping.as_ref(),
+                acl_audit.as_ref(),
+                rate_limiter.as_ref(),
+                #[cfg(feature = "unstable")]
+                &user_interceptors,
+            )?,
+            #[cfg(feature = "unstable")]
+            user_interceptors,
+            keyexpr_remapping,
+            rate_limiter,
+            acl_audit,
             next_interceptor_version: AtomicUsize::new(0),
             hats: hat,
             face_counter: 0,
@@ -522,9 +548,21 @@ impl Debug for Tables {
 }
 
 impl TablesLock {
-    #[allow(dead_code)]
+    /// Applies `config` to the tables, rebuilding the interceptor factories and swapping
+    /// the resulting chains into every face.
+    ///
+    /// The interceptor factories, the key expression remapping, the rate limiter and the audit
+    /// log are kept when their configuration is unchanged, and so are the interceptors the
+    /// faces built from those factories. The access control interceptors are always rebuilt.
+    /// On error, the tables are left untouched.
     pub(crate) fn update_config(&self, config: &Config) -> ZResult<()> {
         let mut tables = zwrite!(self.tables);
+        Self::rebuild_interceptors(
+            &mut tables,
+            config,
+            #[cfg(feature = "unstable")]
+            None,
+        )?;
         #[cfg(feature = "stats")]
         {
             let tables = &mut *tables;
@@ -533,8 +571,68 @@ impl TablesLock {
                 config.stats.filters().iter().map(|k| &*k.key),
             );
         }
-        tables.data.interceptors = interceptor_factories(config)?;
         drop(tables);
+        self.refresh_interceptors();
+        Ok(())
+    }
+
+    /// Registers a user-defined interceptor factory and applies it to every face.
+    ///
+    /// The factory is only registered if the interceptors could be rebuilt with it.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn add_interceptor_factory(
+        &self,
+        config: &Config,
+        factory: UserInterceptorFactory,
+    ) -> ZResult<()> {
+        let mut tables = zwrite!(self.tables);
+        Self::rebuild_interceptors(&mut tables, config, Some(factory))?;
+        drop(tables);
+        self.refresh_interceptors();
+        Ok(())
+    }
+
+    fn rebuild_interceptors(
+        tables: &mut Tables,
+        config: &Config,
+        #[cfg(feature = "unstable")] user_factory: Option<UserInterceptorFactory>,
+    ) -> ZResult<()> {
+        let keyexpr_remapping = KeyExprRemapping::update(
+            tables.data.keyexpr_remapping.as_ref(),
+            config.keyexpr_remapping(),
+        )?;
+        let rate_limiter =
+            RateLimiter::update(tables.data.rate_limiter.as_ref(), config.rate_limit())?;
+        let acl_audit = AclAuditLog::update(
+            tables.data.acl_audit.as_ref(),
+            &config.access_control().audit,
+        )?;
+        #[cfg(feature = "unstable")]
+        let user_interceptors = {
+            let mut user_interceptors = tables.data.user_interceptors.clone();
+            user_interceptors.extend(user_factory);
+            user_interceptors
+        };
+        tables.data.interceptors = InterceptorFactories::update(
+            Some(&tables.data.interceptors),
+            config,
+            keyexpr_remapping.as_ref(),
+            acl_audit.as_ref(),
+            rate_limiter.as_ref(),
+            #[cfg(feature = "unstable")]
+            &user_interceptors,
+        )?;
+        #[cfg(feature = "unstable")]
+        {
+            tables.data.user_interceptors = user_interceptors;
+        }
+        tables.data.keyexpr_remapping = keyexpr_remapping;
+        tables.data.rate_limiter = rate_limiter;
+        tables.data.acl_audit = acl_audit;
+        Ok(())
+    }
+
+    fn refresh_interceptors(&self) {
         let tables = zread!(self.tables);
         let version = tables
             .data
@@ -543,6 +641,5 @@ impl TablesLock {
         tables.data.faces.values().for_each(|face| {
             face.set_interceptors_from_factories(&tables.data.interceptors, version + 1);
         });
-        Ok(())
     }
 }
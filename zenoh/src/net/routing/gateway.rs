@@ -36,6 +36,8 @@ use super::{
     interceptor::InterceptorsChain,
     runtime::Runtime,
 };
+#[cfg(feature = "unstable")]
+use crate::net::routing::interceptor::UserInterceptorFactory;
 use crate::net::{
     primitives::{DeMux, DummyPrimitives, EPrimitives, McastMux, Mux},
     routing::{
@@ -51,6 +53,8 @@ use crate::net::{
 pub struct GatewayBuilder<'c> {
     config: &'c ExpandedConfig,
     hlc: Option<Arc<HLC>>,
+    #[cfg(feature = "unstable")]
+    interceptor_factories: Vec<UserInterceptorFactory>,
     #[cfg(feature = "stats")]
     stats: Option<zenoh_stats::StatsRegistry>,
     #[cfg(test)]
@@ -64,6 +68,8 @@ impl<'conf> GatewayBuilder<'conf> {
         Self {
             config,
             hlc: None,
+            #[cfg(feature = "unstable")]
+            interceptor_factories: vec![],
             #[cfg(feature = "stats")]
             stats: None,
             #[cfg(test)]
@@ -78,6 +84,12 @@ impl<'conf> GatewayBuilder<'conf> {
         self
     }
 
+    #[cfg(feature = "unstable")]
+    pub fn interceptor_factories(mut self, factories: Vec<UserInterceptorFactory>) -> Self {
+        self.interceptor_factories = factories;
+        self
+    }
+
     #[cfg(test)]
     pub fn subregions(mut self, subregions: Vec<Region>) -> Self {
         self.subregions.replace(subregions);
@@ -184,6 +196,8 @@ impl<'conf> GatewayBuilder<'conf> {
                 .copied()
                 .map(|b| (b, tables::HatTablesData::new()))
                 .collect(),
+            #[cfg(feature = "unstable")]
+            self.interceptor_factories,
             #[cfg(feature = "stats")]
             stats,
         )?;
@@ -433,6 +447,7 @@ impl Gateway {
             tables.hats.map_ref(|hat| hat.new_face()),
         )
         .multicast_group(transport)
+        .multicast_peer(peer)
         .ingress_interceptors(interceptor.clone());
 
         #[cfg(feature = "stats")]
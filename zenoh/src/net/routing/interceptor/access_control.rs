@@ -36,11 +36,13 @@ use zenoh_protocol::{
     zenoh::{PushBody, RequestBody},
 };
 use zenoh_result::ZResult;
-use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
 
 use super::{
-    authorization::PolicyEnforcer, EgressInterceptor, IngressInterceptor, InterceptorFactory,
-    InterceptorFactoryTrait, InterceptorLinkWrapper, InterceptorTrait,
+    acl_audit::{AclAuditLog, AuditSubject},
+    authorization::{AclDecision, PolicyEnforcer},
+    EgressInterceptor, IngressInterceptor, InterceptorFactory, InterceptorFactoryTrait,
+    InterceptorLinkWrapper, InterceptorTrait,
 };
 use crate::{
     key_expr::KeyExpr,
@@ -48,6 +50,7 @@ use crate::{
 };
 pub struct AclEnforcer {
     enforcer: Arc<PolicyEnforcer>,
+    audit: Option<Arc<AclAuditLog>>,
 }
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AuthSubject {
@@ -55,10 +58,40 @@ pub struct AuthSubject {
     name: String,
 }
 
+/// The audit log of a transport's decisions, with the properties of its remote node.
+#[derive(Clone)]
+struct AclAudit {
+    log: Arc<AclAuditLog>,
+    subject: Arc<AuditSubject>,
+}
+
+impl AclAudit {
+    fn record(
+        &self,
+        policy_enforcer: &PolicyEnforcer,
+        flow: InterceptorFlow,
+        message: AclMessage,
+        key_expr: &keyexpr,
+        decision: AclDecision,
+    ) {
+        self.log.record(
+            &self.subject,
+            flow,
+            message,
+            key_expr,
+            decision.permission,
+            decision
+                .source
+                .and_then(|source| policy_enforcer.rule_sources.get(source)),
+        );
+    }
+}
+
 struct EgressAclEnforcer {
     policy_enforcer: Arc<PolicyEnforcer>,
     subject: Vec<AuthSubject>,
     zid: ZenohIdProto,
+    audit: Option<AclAudit>,
     #[cfg(feature = "stats")]
     stats: zenoh_stats::DropStats,
 }
@@ -67,14 +100,14 @@ impl EgressAclEnforcer {
     #[inline]
     fn cached_result_or_action(
         &self,
-        cached_permission: Option<Permission>,
+        cached_decision: Option<AclDecision>,
         action: AclMessage,
         log_msg: &str,
         key_expr: KeyExpr,
     ) -> Permission {
-        match cached_permission {
-            Some(p) => {
-                match p {
+        let decision = match cached_decision {
+            Some(d) => {
+                match d.permission {
                     Permission::Allow => tracing::trace!(
                         "Using cached result: {} is authorized to {} on {}",
                         self.zid(),
@@ -88,10 +121,20 @@ impl EgressAclEnforcer {
                         key_expr
                     ),
                 }
-                p
+                d
             }
             None => self.action(action, log_msg, &key_expr),
+        };
+        if let Some(audit) = &self.audit {
+            audit.record(
+                &self.policy_enforcer,
+                self.flow(),
+                action,
+                &key_expr,
+                decision,
+            );
         }
+        decision.permission
     }
 
     fn filter_message(
@@ -368,6 +411,7 @@ struct IngressAclEnforcer {
     policy_enforcer: Arc<PolicyEnforcer>,
     subject: Vec<AuthSubject>,
     zid: ZenohIdProto,
+    audit: Option<AclAudit>,
     #[cfg(feature = "stats")]
     stats: zenoh_stats::DropStats,
 }
@@ -376,14 +420,14 @@ impl IngressAclEnforcer {
     #[inline]
     fn cached_result_or_action(
         &self,
-        cached_permission: Option<Permission>,
+        cached_decision: Option<AclDecision>,
         action: AclMessage,
         log_msg: &str,
         key_expr: KeyExpr,
     ) -> Permission {
-        match cached_permission {
-            Some(p) => {
-                match p {
+        let decision = match cached_decision {
+            Some(d) => {
+                match d.permission {
                     Permission::Allow => tracing::trace!(
                         "Using cached result: {} is authorized to {} on {}",
                         self.zid(),
@@ -397,23 +441,33 @@ impl IngressAclEnforcer {
                         key_expr
                     ),
                 }
-                p
+                d
             }
             None => self.action(action, log_msg, &key_expr),
+        };
+        if let Some(audit) = &self.audit {
+            audit.record(
+                &self.policy_enforcer,
+                self.flow(),
+                action,
+                &key_expr,
+                decision,
+            );
         }
+        decision.permission
     }
 
     #[inline]
     fn cached_result_or_action_undecl(
         &self,
-        cached_permission: Option<Permission>,
+        cached_decision: Option<AclDecision>,
         action: AclMessage,
         log_msg: &str,
         key_expr: Option<KeyExpr>,
     ) -> Permission {
-        match cached_permission {
-            Some(p) => {
-                match p {
+        let decision = match cached_decision {
+            Some(d) => {
+                match d.permission {
                     Permission::Allow => tracing::trace!(
                         "Using cached result: {} is authorized to {} on {:?}",
                         self.zid(),
@@ -427,18 +481,28 @@ impl IngressAclEnforcer {
                         key_expr
                     ),
                 }
-                p
+                d
             }
             None => {
                 // Undeclarations in ingress are only filtered if the ext_wire_expr is set.
                 // If it's not set, we let the undeclaration pass, it will be rejected by the routing logic
                 // if its associated declaration was denied.
-                match key_expr {
-                    Some(token) => self.action(action, log_msg, &token),
-                    None => Permission::Allow,
+                match &key_expr {
+                    Some(token) => self.action(action, log_msg, token),
+                    None => return Permission::Allow,
                 }
             }
+        };
+        if let (Some(audit), Some(key_expr)) = (&self.audit, &key_expr) {
+            audit.record(
+                &self.policy_enforcer,
+                self.flow(),
+                action,
+                key_expr,
+                decision,
+            );
         }
+        decision.permission
     }
 
     fn filter_message(
@@ -694,8 +758,17 @@ impl IngressAclEnforcer {
     }
 }
 
+/// Checks that `acl_config` can be enforced, without building any interceptor.
+pub(crate) fn validate_acl_config(acl_config: &AclConfig) -> ZResult<()> {
+    if acl_config.enabled {
+        PolicyEnforcer::new().init(acl_config)?;
+    }
+    Ok(())
+}
+
 pub(crate) fn acl_interceptor_factories(
     acl_config: &AclConfig,
+    audit: Option<&Arc<AclAuditLog>>,
 ) -> ZResult<Vec<InterceptorFactory>> {
     let mut res: Vec<InterceptorFactory> = vec![];
 
@@ -704,8 +777,9 @@ pub(crate) fn acl_interceptor_factories(
         match policy_enforcer.init(acl_config) {
             Ok(_) => {
                 tracing::debug!("Access control is enabled");
-                res.push(Box::new(AclEnforcer {
+                res.push(Arc::new(AclEnforcer {
                     enforcer: Arc::new(policy_enforcer),
+                    audit: audit.cloned(),
                 }))
             }
             Err(e) => bail!("Access control not enabled due to: {}", e),
@@ -772,6 +846,21 @@ impl InterceptorFactoryTrait for AclEnforcer {
             tracing::warn!("Transport returned multiple network interfaces, current ACL logic might incorrectly apply filters in this case!");
         }
 
+        let audit = self.audit.as_ref().map(|log| AclAudit {
+            log: log.clone(),
+            subject: Arc::new(AuditSubject {
+                zid: *auth_ids.zid(),
+                username: username.as_ref().map(|u| u.0.clone()),
+                cert_common_names: cert_common_names
+                    .iter()
+                    .flatten()
+                    .map(|c| c.0.clone())
+                    .collect(),
+                interfaces: interfaces.iter().flatten().map(|i| i.0.clone()).collect(),
+                link_protocols: link_protocols.iter().flatten().cloned().collect(),
+            }),
+        });
+
         let mut auth_subjects = HashSet::new();
 
         for ((((username, interface), cert_common_name), link_protocol), zid) in
@@ -824,6 +913,7 @@ impl InterceptorFactoryTrait for AclEnforcer {
             policy_enforcer: self.enforcer.clone(),
             zid,
             subject: auth_subjects.clone(),
+            audit: audit.clone(),
             #[cfg(feature = "stats")]
             stats: stats.clone(),
         });
@@ -831,6 +921,7 @@ impl InterceptorFactoryTrait for AclEnforcer {
             policy_enforcer: self.enforcer.clone(),
             zid,
             subject: auth_subjects,
+            audit,
             #[cfg(feature = "stats")]
             stats: stats.clone(),
         });
@@ -854,22 +945,26 @@ impl InterceptorFactoryTrait for AclEnforcer {
         None
     }
 
-    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
         tracing::debug!("Peer Multicast is disabled in interceptor");
         None
     }
 }
 
 struct Cache {
-    query: Permission,
-    reply: Permission,
-    put: Permission,
-    delete: Permission,
-    declare_subscriber: Permission,
-    declare_queryable: Permission,
-    declare_token: Permission,
-    query_token: Permission,
-    declare_liveliness_subscriber: Permission,
+    query: AclDecision,
+    reply: AclDecision,
+    put: AclDecision,
+    delete: AclDecision,
+    declare_subscriber: AclDecision,
+    declare_queryable: AclDecision,
+    declare_token: AclDecision,
+    query_token: AclDecision,
+    declare_liveliness_subscriber: AclDecision,
 }
 
 impl InterceptorTrait for IngressAclEnforcer {
@@ -975,14 +1070,19 @@ pub trait AclActionMethods {
     fn zid(&self) -> &ZenohIdProto;
     fn flow(&self) -> InterceptorFlow;
     fn authn_ids(&self) -> &Vec<AuthSubject>;
-    fn action(&self, action: AclMessage, log_msg: &str, key_expr: &keyexpr) -> Permission {
+    fn action(&self, action: AclMessage, log_msg: &str, key_expr: &keyexpr) -> AclDecision {
         let policy_enforcer = self.policy_enforcer();
         let authn_ids = self.authn_ids();
         let zid = self.zid();
-        let mut decision = policy_enforcer.default_permission;
+        let mut decision = AclDecision::default_permission(policy_enforcer.default_permission);
         for subject in authn_ids {
             match policy_enforcer.policy_decision_point(subject.id, self.flow(), action, key_expr) {
-                Ok(Permission::Allow) => {
+                Ok(
+                    d @ AclDecision {
+                        permission: Permission::Allow,
+                        ..
+                    },
+                ) => {
                     tracing::trace!(
                         "{} on {} is authorized to {} on {}",
                         zid,
@@ -990,10 +1090,15 @@ pub trait AclActionMethods {
                         log_msg,
                         key_expr
                     );
-                    decision = Permission::Allow;
+                    decision = d;
                     break;
                 }
-                Ok(Permission::Deny) => {
+                Ok(
+                    d @ AclDecision {
+                        permission: Permission::Deny,
+                        ..
+                    },
+                ) => {
                     tracing::trace!(
                         "{} on {} is unauthorized to {} on {}",
                         zid,
@@ -1002,7 +1107,7 @@ pub trait AclActionMethods {
                         key_expr
                     );
 
-                    decision = Permission::Deny;
+                    decision = d;
                     continue;
                 }
                 Err(e) => {
@@ -1014,7 +1119,7 @@ pub trait AclActionMethods {
                         key_expr,
                         e
                     );
-                    return Permission::Deny;
+                    return AclDecision::default_permission(Permission::Deny);
                 }
             }
         }
@@ -0,0 +1,495 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Read, Seek, SeekFrom, Write},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::SystemTime,
+};
+
+use serde_json::{json, Value};
+use sha3::{Digest, Sha3_256};
+use zenoh_config::{AclAuditConf, AclMessage, InterceptorFlow, InterceptorLink, Permission};
+use zenoh_core::zlock;
+use zenoh_keyexpr::keyexpr;
+use zenoh_protocol::core::ZenohIdProto;
+use zenoh_result::ZResult;
+
+use super::authorization::AclRuleSource;
+
+/// The `prev_hash` of the first record of an audit log.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Number of decisions waiting to be recorded beyond which new allow decisions are dropped.
+const QUEUE_SIZE: usize = 4096;
+
+/// Size of the chunks read backwards from the end of an audit file to find its last record.
+const READ_CHUNK_SIZE: u64 = 4096;
+
+/// The properties of the remote node an access control decision applies to.
+#[derive(Debug)]
+pub(crate) struct AuditSubject {
+    pub(crate) zid: ZenohIdProto,
+    pub(crate) username: Option<String>,
+    pub(crate) cert_common_names: Vec<String>,
+    pub(crate) interfaces: Vec<String>,
+    pub(crate) link_protocols: Vec<InterceptorLink>,
+}
+
+/// Records access control decisions as JSON records, to a JSON-lines file
+/// and to a bounded in-memory buffer served on the adminspace.
+///
+/// Each record carries the SHA3-256 hash of the previous one (`prev_hash`) and its own
+/// `hash`, computed over the record serialized without its `hash` field, so that
+/// removing or altering a record breaks the chain.
+///
+/// Decisions are queued to a background thread which serializes, hashes and writes them,
+/// so that recording never blocks the routing. Deny decisions are always queued, while allow
+/// decisions exceeding the queue capacity are dropped, and their number is reported in the
+/// `dropped` field of the next record.
+pub(crate) struct AclAuditLog {
+    conf: AclAuditConf,
+    sink: Arc<AuditSink>,
+}
+
+struct AuditSink {
+    tx: flume::Sender<AuditEvent>,
+    records: Arc<Mutex<VecDeque<Value>>>,
+    adminspace_records: Arc<AtomicUsize>,
+    dropped: Arc<AtomicU64>,
+}
+
+struct AuditEvent {
+    timestamp: SystemTime,
+    subject: Arc<AuditSubject>,
+    flow: InterceptorFlow,
+    message: AclMessage,
+    key_expr: String,
+    permission: Permission,
+    source: Option<AclRuleSource>,
+}
+
+impl AclAuditLog {
+    pub(crate) fn new(conf: &AclAuditConf) -> ZResult<Option<Arc<Self>>> {
+        if !conf.enabled {
+            return Ok(None);
+        }
+        Ok(Some(Arc::new(Self {
+            conf: conf.clone(),
+            sink: Arc::new(AuditSink::new(conf)?),
+        })))
+    }
+
+    /// Returns `current` if its configuration is unchanged, a new audit log otherwise.
+    ///
+    /// The records of `current` are continued if its file settings are unchanged.
+    pub(crate) fn update(
+        current: Option<&Arc<Self>>,
+        conf: &AclAuditConf,
+    ) -> ZResult<Option<Arc<Self>>> {
+        match current {
+            Some(current) if current.conf == *conf => Ok(Some(current.clone())),
+            Some(current) if conf.enabled && same_file_settings(&current.conf, conf) => {
+                current
+                    .sink
+                    .adminspace_records
+                    .store(conf.adminspace_records, Ordering::Relaxed);
+                Ok(Some(Arc::new(Self {
+                    conf: conf.clone(),
+                    sink: current.sink.clone(),
+                })))
+            }
+            _ => Self::new(conf),
+        }
+    }
+
+    pub(crate) fn record(
+        &self,
+        subject: &Arc<AuditSubject>,
+        flow: InterceptorFlow,
+        message: AclMessage,
+        key_expr: &keyexpr,
+        permission: Permission,
+        source: Option<&AclRuleSource>,
+    ) {
+        if permission == Permission::Allow {
+            if !self.conf.log_allowed {
+                return;
+            }
+            // Only allow decisions are shed, deny decisions must not be lost
+            if self.sink.tx.len() >= QUEUE_SIZE {
+                self.sink.dropped.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        }
+        let event = AuditEvent {
+            timestamp: SystemTime::now(),
+            subject: subject.clone(),
+            flow,
+            message,
+            key_expr: key_expr.as_str().to_string(),
+            permission,
+            source: source.cloned(),
+        };
+        if self.sink.tx.send(event).is_err() {
+            tracing::error!("Unable to record ACL decision: the audit log is stopped");
+        }
+    }
+
+    /// Returns the most recent records, oldest first.
+    pub(crate) fn to_json(&self) -> Value {
+        Value::Array(zlock!(self.sink.records).iter().cloned().collect())
+    }
+}
+
+/// Returns an error if `updated` changes the file settings of `current`,
+/// which can only be set at startup.
+pub(crate) fn validate_audit_update(current: &AclAuditConf, updated: &AclAuditConf) -> ZResult<()> {
+    if !same_file_settings(current, updated) {
+        bail!("The file settings of the ACL audit log can only be set at startup");
+    }
+    Ok(())
+}
+
+fn same_file_settings(a: &AclAuditConf, b: &AclAuditConf) -> bool {
+    a.file == b.file && a.max_file_size == b.max_file_size && a.max_files == b.max_files
+}
+
+impl AuditSink {
+    fn new(conf: &AclAuditConf) -> ZResult<Self> {
+        let mut writer = AuditWriter {
+            seq: 0,
+            last_hash: GENESIS_HASH.to_string(),
+            file: None,
+            records: Arc::new(Mutex::new(VecDeque::new())),
+            adminspace_records: Arc::new(AtomicUsize::new(conf.adminspace_records)),
+            dropped: Arc::new(AtomicU64::new(0)),
+        };
+        if let Some(path) = &conf.file {
+            // Continue the chain of an existing file
+            if let Some((last_seq, hash)) = last_record(path)? {
+                writer.seq = last_seq;
+                writer.last_hash = hash;
+            }
+            writer.file = Some(AuditFile::open(
+                path.clone(),
+                conf.max_file_size,
+                conf.max_files,
+            )?);
+        }
+        let (tx, rx) = flume::unbounded();
+        let sink = Self {
+            tx,
+            records: writer.records.clone(),
+            adminspace_records: writer.adminspace_records.clone(),
+            dropped: writer.dropped.clone(),
+        };
+        std::thread::Builder::new()
+            .name("acl-audit".to_string())
+            .spawn(move || writer.run(rx))
+            .map_err(|e| zerror!("Unable to start the ACL audit log: {}", e))?;
+        Ok(sink)
+    }
+}
+
+/// The background state of an audit log, ending when all its senders are dropped.
+struct AuditWriter {
+    seq: u64,
+    last_hash: String,
+    file: Option<AuditFile>,
+    records: Arc<Mutex<VecDeque<Value>>>,
+    adminspace_records: Arc<AtomicUsize>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl AuditWriter {
+    fn run(mut self, rx: flume::Receiver<AuditEvent>) {
+        while let Ok(event) = rx.recv() {
+            self.write(event);
+            if rx.is_empty() {
+                if let Some(file) = self.file.as_mut() {
+                    if let Err(e) = file.writer.flush() {
+                        tracing::error!("Unable to write ACL audit record: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn write(&mut self, event: AuditEvent) {
+        self.seq += 1;
+        let subject = &event.subject;
+        let mut record = json!({
+            "seq": self.seq,
+            "timestamp": humantime::format_rfc3339_micros(event.timestamp).to_string(),
+            "zid": subject.zid.to_string(),
+            "username": subject.username,
+            "cert_common_names": subject.cert_common_names,
+            "interfaces": subject.interfaces,
+            "link_protocols": subject.link_protocols,
+            "key_expr": event.key_expr,
+            "message": event.message,
+            "flow": event.flow,
+            "permission": event.permission,
+            "rule": event.source.as_ref().map(|s| &s.rule_id),
+            "policy": event.source.as_ref().map(|s| &s.policy),
+            "dropped": self.dropped.swap(0, Ordering::Relaxed),
+            "prev_hash": self.last_hash,
+        });
+        let hash = record_hash(&record);
+        record["hash"] = json!(hash);
+        if let Some(file) = self.file.as_mut() {
+            if let Err(e) = file.write(&record) {
+                tracing::error!("Unable to write ACL audit record: {}", e);
+            }
+        }
+        self.last_hash = hash;
+        let adminspace_records = self.adminspace_records.load(Ordering::Relaxed);
+        let mut records = zlock!(self.records);
+        while records.len() >= adminspace_records.max(1) {
+            records.pop_front();
+        }
+        if adminspace_records > 0 {
+            records.push_back(record);
+        }
+    }
+}
+
+/// An audit file, rotated to `<path>.1`, `<path>.2`, ... once it exceeds `max_size` bytes.
+struct AuditFile {
+    path: String,
+    writer: BufWriter<File>,
+    size: u64,
+    max_size: Option<u64>,
+    max_files: usize,
+}
+
+impl AuditFile {
+    fn open(path: String, max_size: Option<u64>, max_files: usize) -> ZResult<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| zerror!("Unable to open ACL audit file '{}': {}", path, e))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            size,
+            max_size,
+            max_files,
+        })
+    }
+
+    fn write(&mut self, record: &Value) -> ZResult<()> {
+        let line = format!("{record}\n");
+        if let Some(max_size) = self.max_size {
+            if self.size > 0 && self.size + line.len() as u64 > max_size {
+                self.rotate()?;
+            }
+        }
+        self.writer.write_all(line.as_bytes())?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> ZResult<()> {
+        self.writer.flush()?;
+        for i in (1..self.max_files).rev() {
+            let from = format!("{}.{}", self.path, i);
+            if std::path::Path::new(&from).exists() {
+                std::fs::rename(&from, format!("{}.{}", self.path, i + 1))?;
+            }
+        }
+        if self.max_files > 0 {
+            std::fs::rename(&self.path, format!("{}.1", self.path))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.writer = BufWriter::new(file);
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn record_hash(record: &Value) -> String {
+    Sha3_256::digest(record.to_string().as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Returns the `seq` and `hash` of the last record of the audit file at `path`,
+/// or of its last rotated file if it is empty, if any.
+fn last_record(path: &str) -> ZResult<Option<(u64, String)>> {
+    match last_record_in(path)? {
+        Some(last) => Ok(Some(last)),
+        None => last_record_in(&format!("{path}.1")),
+    }
+}
+
+fn last_record_in(path: &str) -> ZResult<Option<(u64, String)>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => bail!("Unable to read ACL audit file '{}': {}", path, e),
+    };
+    let Some(line) = last_line(&mut file)
+        .map_err(|e| zerror!("Unable to read ACL audit file '{}': {}", path, e))?
+    else {
+        return Ok(None);
+    };
+    let record: Value = serde_json::from_slice(&line)
+        .map_err(|e| zerror!("Invalid last record in ACL audit file '{}': {}", path, e))?;
+    match (record["seq"].as_u64(), record["hash"].as_str()) {
+        (Some(seq), Some(hash)) => Ok(Some((seq, hash.to_string()))),
+        _ => bail!(
+            "Invalid last record in ACL audit file '{}': missing seq or hash",
+            path
+        ),
+    }
+}
+
+/// Returns the last non-blank line of `file`, read backwards from its end.
+fn last_line(file: &mut File) -> std::io::Result<Option<Vec<u8>>> {
+    let mut pos = file.seek(SeekFrom::End(0))?;
+    let mut tail = Vec::new();
+    loop {
+        let end = tail
+            .iter()
+            .rposition(|b: &u8| !b.is_ascii_whitespace())
+            .map_or(0, |i| i + 1);
+        if end > 0 {
+            if let Some(start) = tail[..end].iter().rposition(|&b| b == b'\n') {
+                return Ok(Some(tail[start + 1..end].to_vec()));
+            }
+        }
+        if pos == 0 {
+            tail.truncate(end);
+            return Ok((!tail.is_empty()).then_some(tail));
+        }
+        let len = READ_CHUNK_SIZE.min(pos);
+        pos -= len;
+        file.seek(SeekFrom::Start(pos))?;
+        let mut chunk = vec![0; len as usize];
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audit_log(log_allowed: bool) -> (AclAuditLog, flume::Receiver<AuditEvent>) {
+        let (tx, rx) = flume::unbounded();
+        let conf = AclAuditConf {
+            enabled: true,
+            log_allowed,
+            file: None,
+            max_file_size: None,
+            max_files: 0,
+            adminspace_records: 0,
+        };
+        let sink = AuditSink {
+            tx,
+            records: Arc::new(Mutex::new(VecDeque::new())),
+            adminspace_records: Arc::new(AtomicUsize::new(0)),
+            dropped: Arc::new(AtomicU64::new(0)),
+        };
+        let log = AclAuditLog {
+            conf,
+            sink: Arc::new(sink),
+        };
+        (log, rx)
+    }
+
+    #[test]
+    fn deny_decisions_are_not_dropped() {
+        let (log, rx) = audit_log(true);
+        let subject = Arc::new(AuditSubject {
+            zid: ZenohIdProto::default(),
+            username: None,
+            cert_common_names: vec![],
+            interfaces: vec![],
+            link_protocols: vec![],
+        });
+        let key_expr = keyexpr::new("test/audit").unwrap();
+        let record = |permission| {
+            log.record(
+                &subject,
+                InterceptorFlow::Ingress,
+                AclMessage::Put,
+                key_expr,
+                permission,
+                None,
+            )
+        };
+        for _ in 0..QUEUE_SIZE {
+            record(Permission::Allow);
+        }
+        record(Permission::Allow);
+        record(Permission::Deny);
+        assert_eq!(rx.len(), QUEUE_SIZE + 1);
+        assert_eq!(log.sink.dropped.load(Ordering::Relaxed), 1);
+        assert_eq!(rx.drain().last().unwrap().permission, Permission::Deny);
+    }
+
+    #[test]
+    fn last_record_is_read_from_the_end() {
+        let path = std::env::temp_dir().join(format!(
+            "zenoh_acl_audit_last_record_{}.jsonl",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+        assert_eq!(last_record_in(path).unwrap(), None);
+
+        // The records span several chunks, and the file ends with blank lines
+        let mut content = String::new();
+        for seq in 1..=1000 {
+            content.push_str(&format!(
+                "{}\n",
+                json!({"seq": seq, "hash": format!("h{seq}")})
+            ));
+        }
+        content.push_str("\n  \n");
+        std::fs::write(path, &content).unwrap();
+        assert_eq!(
+            last_record_in(path).unwrap(),
+            Some((1000, "h1000".to_string()))
+        );
+
+        // A single record without a trailing newline
+        std::fs::write(path, json!({"seq": 1, "hash": "h1"}).to_string()).unwrap();
+        assert_eq!(last_record_in(path).unwrap(), Some((1, "h1".to_string())));
+
+        std::fs::write(path, "\n\n").unwrap();
+        assert_eq!(last_record_in(path).unwrap(), None);
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -172,7 +172,34 @@ impl SubjectMapBuilder {
     }
 }
 
-type KeTreeRule = KeBoxTree<bool>;
+/// Weighted with the index of the [`AclRuleSource`] of the first rule inserted on a key expression.
+type KeTreeRule = KeBoxTree<usize>;
+
+/// The configured rule and policy a [`PolicyRule`] was created from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AclRuleSource {
+    pub(crate) rule_id: String,
+    /// The policy id, or its position in the policies list (e.g. `#0`) if it has none.
+    pub(crate) policy: String,
+}
+
+/// The outcome of a policy decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AclDecision {
+    pub(crate) permission: Permission,
+    /// Index of the matching rule in [`PolicyEnforcer::rule_sources`],
+    /// `None` if the default permission was applied.
+    pub(crate) source: Option<usize>,
+}
+
+impl AclDecision {
+    pub(crate) fn default_permission(permission: Permission) -> Self {
+        Self {
+            permission,
+            source: None,
+        }
+    }
+}
 
 #[derive(Default)]
 struct PermissionPolicy {
@@ -264,12 +291,15 @@ pub struct PolicyEnforcer {
     pub(crate) subject_store: SubjectStore,
     pub(crate) policy_map: PolicyMap,
     pub(crate) interface_enabled: InterfaceEnabled,
+    pub(crate) rule_sources: Vec<AclRuleSource>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PolicyInformation {
     subject_map: SubjectStore,
-    policy_rules: Vec<PolicyRule>,
+    /// Each policy rule with the index of its source in `rule_sources`
+    policy_rules: Vec<(PolicyRule, usize)>,
+    rule_sources: Vec<AclRuleSource>,
 }
 
 impl PolicyEnforcer {
@@ -280,6 +310,7 @@ impl PolicyEnforcer {
             subject_store: SubjectStore::default(),
             policy_map: PolicyMap::default(),
             interface_enabled: InterfaceEnabled::default(),
+            rule_sources: Vec::new(),
         }
     }
 
@@ -340,13 +371,15 @@ impl PolicyEnforcer {
                         self.policy_information_point(subjects, rules, policies)?;
 
                     let mut main_policy: PolicyMap = PolicyMap::default();
-                    for rule in policy_information.policy_rules {
+                    for (rule, source) in policy_information.policy_rules {
                         let subject_policy = main_policy.entry(rule.subject_id).or_default();
-                        subject_policy
+                        let tree = subject_policy
                             .flow_mut(rule.flow)
                             .action_mut(rule.message)
-                            .permission_mut(rule.permission)
-                            .insert(&rule.key_expr, true);
+                            .permission_mut(rule.permission);
+                        if tree.weight_at(&rule.key_expr).is_none() {
+                            tree.insert(&rule.key_expr, source);
+                        }
 
                         if self.default_permission == Permission::Deny {
                             self.interface_enabled = InterfaceEnabled {
@@ -366,6 +399,7 @@ impl PolicyEnforcer {
                     }
                     self.policy_map = main_policy;
                     self.subject_store = policy_information.subject_map;
+                    self.rule_sources = policy_information.rule_sources;
                 }
             } else {
                 bail!("All ACL rules/subjects/policies config lists must be provided");
@@ -383,7 +417,8 @@ impl PolicyEnforcer {
         rules: Vec<AclConfigRule>,
         policies: Vec<AclConfigPolicyEntry>,
     ) -> ZResult<PolicyInformation> {
-        let mut policy_rules: Vec<PolicyRule> = Vec::new();
+        let mut policy_rules: Vec<(PolicyRule, usize)> = Vec::new();
+        let mut rule_sources: Vec<AclRuleSource> = Vec::new();
         let mut rule_map = HashMap::new();
         let mut subject_id_map = HashMap::<String, Vec<usize>>::new();
         let mut policy_id_set = HashSet::<String>::new();
@@ -553,6 +588,11 @@ impl PolicyEnforcer {
                     rule_id,
                     entry_id
                 ))?;
+                let source = rule_sources.len();
+                rule_sources.push(AclRuleSource {
+                    rule_id: rule_id.clone(),
+                    policy: entry.id.clone().unwrap_or_else(|| format!("#{entry_id}")),
+                });
                 for subject_config_id in &entry.subjects {
                     let subject_combination_ids = subject_id_map
                         .get(subject_config_id)
@@ -565,13 +605,16 @@ impl PolicyEnforcer {
                         {
                             for message in &rule.messages {
                                 for key_expr in &rule.key_exprs {
-                                    policy_rules.push(PolicyRule {
-                                        subject_id: *subject_id,
-                                        key_expr: key_expr.clone(),
-                                        message: *message,
-                                        permission: rule.permission,
-                                        flow: *flow,
-                                    });
+                                    policy_rules.push((
+                                        PolicyRule {
+                                            subject_id: *subject_id,
+                                            key_expr: key_expr.clone(),
+                                            message: *message,
+                                            permission: rule.permission,
+                                            flow: *flow,
+                                        },
+                                        source,
+                                    ));
                                 }
                             }
                         }
@@ -582,6 +625,7 @@ impl PolicyEnforcer {
         Ok(PolicyInformation {
             subject_map: subject_map_builder.build(),
             policy_rules,
+            rule_sources,
         })
     }
 
@@ -594,10 +638,10 @@ impl PolicyEnforcer {
         flow: InterceptorFlow,
         message: AclMessage,
         key_expr: &keyexpr,
-    ) -> ZResult<Permission> {
+    ) -> ZResult<AclDecision> {
         let policy_map = &self.policy_map;
         if policy_map.is_empty() {
-            return Ok(self.default_permission);
+            return Ok(AclDecision::default_permission(self.default_permission));
         }
         match policy_map.get(&subject) {
             Some(single_policy) => {
@@ -606,28 +650,33 @@ impl PolicyEnforcer {
                     .action(message)
                     .deny
                     .nodes_including(key_expr)
-                    .any(|n| n.weight().is_some());
-                if deny_result {
-                    return Ok(Permission::Deny);
+                    .find_map(|n| n.weight().copied());
+                if let Some(source) = deny_result {
+                    return Ok(AclDecision {
+                        permission: Permission::Deny,
+                        source: Some(source),
+                    });
                 }
                 if self.default_permission == Permission::Allow {
-                    Ok(Permission::Allow)
+                    Ok(AclDecision::default_permission(Permission::Allow))
                 } else {
                     let allow_result = single_policy
                         .flow(flow)
                         .action(message)
                         .allow
                         .nodes_including(key_expr)
-                        .any(|n| n.weight().is_some());
-
-                    if allow_result {
-                        Ok(Permission::Allow)
-                    } else {
-                        Ok(Permission::Deny)
+                        .find_map(|n| n.weight().copied());
+
+                    match allow_result {
+                        Some(source) => Ok(AclDecision {
+                            permission: Permission::Allow,
+                            source: Some(source),
+                        }),
+                        None => Ok(AclDecision::default_permission(Permission::Deny)),
                     }
                 }
             }
-            None => Ok(self.default_permission),
+            None => Ok(AclDecision::default_permission(self.default_permission)),
         }
     }
 }
@@ -54,7 +54,7 @@ pub(crate) fn downsampling_interceptor_factories(
             }
         }
 
-        res.push(Box::new(DownsamplingInterceptorFactory::new(ds.clone())));
+        res.push(Arc::new(DownsamplingInterceptorFactory::new(ds.clone())));
     }
 
     Ok(res)
@@ -155,7 +155,11 @@ impl InterceptorFactoryTrait for DownsamplingInterceptorFactory {
         None
     }
 
-    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
         None
     }
 }
@@ -0,0 +1,495 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+//!
+//! Remapping of key expressions between the key space of a remote node and the local one.
+//!
+//! Ingress messages are remapped from the remote key space to the local one, and egress
+//! messages from the local key space to the remote one. Remapped messages are sent with
+//! their complete key expression: since the key expressions declared with `DeclareKeyExpr`
+//! are remapped too, the ids of remapped declarations are tracked per transport to resolve
+//! the wire expressions referencing them.
+
+use std::{
+    any::Any,
+    collections::HashSet,
+    sync::{Arc, Mutex, RwLock, Weak},
+};
+
+use ahash::HashMap;
+use zenoh_config::{InterceptorFlow, KeyExprRemappingConf, KeyExprRemappingRuleConf, ZenohId};
+use zenoh_keyexpr::{keyexpr, OwnedKeyExpr};
+use zenoh_protocol::{
+    core::{ExprId, WireExpr, EMPTY_EXPR_ID},
+    network::{DeclareBody, Mapping, NetworkBodyMut, NetworkMessageExt, NetworkMessageMut},
+};
+use zenoh_result::ZResult;
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
+
+use super::{
+    EgressInterceptor, IngressInterceptor, InterceptorContext, InterceptorFactory,
+    InterceptorFactoryTrait, InterceptorTrait,
+};
+
+/// Returns the factories of the ingress and egress interceptors, which are respectively
+/// the first and last ones of their chains.
+pub(crate) fn keyexpr_remapping_interceptor_factories(
+    remapping: Option<&Arc<KeyExprRemapping>>,
+) -> (Vec<InterceptorFactory>, Vec<InterceptorFactory>) {
+    let mut ingress: Vec<InterceptorFactory> = vec![];
+    let mut egress: Vec<InterceptorFactory> = vec![];
+
+    if let Some(remapping) = remapping {
+        ingress.push(Arc::new(KeyExprRemappingInterceptorFactory {
+            remapping: remapping.clone(),
+            flow: InterceptorFlow::Ingress,
+        }));
+        egress.push(Arc::new(KeyExprRemappingInterceptorFactory {
+            remapping: remapping.clone(),
+            flow: InterceptorFlow::Egress,
+        }));
+    }
+
+    (ingress, egress)
+}
+
+fn validate_config(config: &[KeyExprRemappingConf]) -> ZResult<Vec<RemappingItem>> {
+    let mut id_set = HashSet::new();
+    let mut items = vec![];
+    for item in config {
+        if let Some(id) = &item.id {
+            if !id_set.insert(id.clone()) {
+                bail!("id '{id}' is repeated");
+            }
+        }
+        let rules = item
+            .rules
+            .iter()
+            .map(RemappingRule::new)
+            .collect::<ZResult<Vec<_>>>()?;
+        let flows = item.flows.as_ref();
+        let has_flow = |flow| flows.map_or(true, |flows| flows.iter().any(|f| *f == flow));
+        items.push(RemappingItem {
+            interfaces: item
+                .interfaces
+                .as_ref()
+                .map(|v| v.iter().cloned().collect()),
+            zids: item.zids.as_ref().map(|v| v.iter().copied().collect()),
+            ingress: has_flow(InterceptorFlow::Ingress),
+            egress: has_flow(InterceptorFlow::Egress),
+            rules,
+        });
+    }
+    Ok(items)
+}
+
+/// A remapping rule, between keys equal to `remote` and `local`, or below them if `prefix`.
+#[derive(Clone)]
+struct RemappingRule {
+    remote: String,
+    local: String,
+    prefix: bool,
+    /// The remote and local key spaces of the rule, as configured.
+    remote_space: OwnedKeyExpr,
+    local_space: OwnedKeyExpr,
+}
+
+impl RemappingRule {
+    fn new(conf: &KeyExprRemappingRuleConf) -> ZResult<Self> {
+        fn parse(ke: &keyexpr) -> ZResult<(String, bool)> {
+            let (key, prefix) = match ke.as_str().strip_suffix("/**") {
+                Some(key) => (key, true),
+                None => (ke.as_str(), false),
+            };
+            if key.contains(['*', '$']) {
+                bail!(
+                    "'{ke}' is neither a key without wildcards nor a key without wildcards followed by '/**'"
+                );
+            }
+            Ok((key.to_string(), prefix))
+        }
+
+        let (remote, remote_prefix) = parse(&conf.remote)?;
+        let (local, local_prefix) = parse(&conf.local)?;
+        if remote_prefix != local_prefix {
+            bail!(
+                "rule '{}' -> '{}' maps a single key to a key space",
+                conf.remote,
+                conf.local
+            );
+        }
+        Ok(Self {
+            remote,
+            local,
+            prefix: remote_prefix,
+            remote_space: conf.remote.clone(),
+            local_space: conf.local.clone(),
+        })
+    }
+
+    /// Remaps `key` from the `from` key space to the `to` key space, if it is included in it.
+    fn remap(key: &str, from: &str, to: &str, prefix: bool) -> Option<String> {
+        if key == from {
+            return Some(to.to_string());
+        }
+        if prefix {
+            if let Some(tail) = key.strip_prefix(from).and_then(|t| t.strip_prefix('/')) {
+                return Some(format!("{to}/{tail}"));
+            }
+        }
+        None
+    }
+
+    fn remap_flow(&self, key: &str, flow: InterceptorFlow) -> Option<String> {
+        match flow {
+            InterceptorFlow::Ingress => Self::remap(key, &self.remote, &self.local, self.prefix),
+            InterceptorFlow::Egress => Self::remap(key, &self.local, &self.remote, self.prefix),
+        }
+    }
+
+    /// Returns the key space keys are remapped from in the given flow.
+    fn space(&self, flow: InterceptorFlow) -> &keyexpr {
+        match flow {
+            InterceptorFlow::Ingress => &self.remote_space,
+            InterceptorFlow::Egress => &self.local_space,
+        }
+    }
+}
+
+struct RemappingItem {
+    interfaces: Option<Vec<String>>,
+    zids: Option<Vec<ZenohId>>,
+    ingress: bool,
+    egress: bool,
+    rules: Vec<RemappingRule>,
+}
+
+/// Shared state of the key expression remapping interceptors: the configuration items
+/// and the remapped declarations of each transport.
+pub(crate) struct KeyExprRemapping {
+    conf: Vec<KeyExprRemappingConf>,
+    items: Vec<RemappingItem>,
+    /// The declarations of each transport, shared by its ingress and egress interceptors.
+    declarations: Mutex<HashMap<ZenohId, Weak<RemappedDeclarations>>>,
+}
+
+impl KeyExprRemapping {
+    pub(crate) fn new(config: &[KeyExprRemappingConf]) -> ZResult<Option<Arc<Self>>> {
+        if config.is_empty() {
+            return Ok(None);
+        }
+        let items = validate_config(config)
+            .map_err(|e| format!("Invalid key expression remapping config: {e}"))?;
+        Ok(Some(Arc::new(Self {
+            conf: config.to_vec(),
+            items,
+            declarations: Mutex::default(),
+        })))
+    }
+
+    /// Returns `current` if its configuration is unchanged, a new remapping otherwise.
+    pub(crate) fn update(
+        current: Option<&Arc<Self>>,
+        config: &[KeyExprRemappingConf],
+    ) -> ZResult<Option<Arc<Self>>> {
+        match current {
+            Some(current) if current.conf == config => Ok(Some(current.clone())),
+            _ => Self::new(config),
+        }
+    }
+
+    fn rules(&self, interfaces: &[String], zid: &ZenohId, flow: InterceptorFlow) -> Vec<usize> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                item.interfaces
+                    .as_ref()
+                    .map_or(true, |v| v.iter().any(|i| interfaces.contains(i)))
+                    && item.zids.as_ref().map_or(true, |v| v.contains(zid))
+                    && match flow {
+                        InterceptorFlow::Ingress => item.ingress,
+                        InterceptorFlow::Egress => item.egress,
+                    }
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the declarations of the transport with the given remote node, which are kept
+    /// as long as one of its interceptors is alive (e.g. across interceptors rebuilds).
+    fn declarations(&self, zid: ZenohId) -> Arc<RemappedDeclarations> {
+        let mut declarations = zlock!(self.declarations);
+        if let Some(existing) = declarations.get(&zid).and_then(Weak::upgrade) {
+            return existing;
+        }
+        declarations.retain(|_, d| d.strong_count() > 0);
+        let new = Arc::new(RemappedDeclarations::default());
+        declarations.insert(zid, Arc::downgrade(&new));
+        new
+    }
+}
+
+struct KeyExprRemappingInterceptorFactory {
+    remapping: Arc<KeyExprRemapping>,
+    flow: InterceptorFlow,
+}
+
+impl InterceptorFactoryTrait for KeyExprRemappingInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        tracing::debug!(
+            "New key expression remapping transport unicast {:?}",
+            transport
+        );
+        let links = match transport.get_links() {
+            Ok(links) => links,
+            Err(e) => {
+                tracing::error!("Unable to get links from transport {:?}: {e}", transport);
+                return (None, None);
+            }
+        };
+        let zid = match transport.get_zid() {
+            Ok(zid) => ZenohId::from(zid),
+            Err(e) => {
+                tracing::error!("Unable to get zid from transport {:?}: {e}", transport);
+                return (None, None);
+            }
+        };
+        let interfaces = links
+            .into_iter()
+            .flat_map(|link| link.interfaces)
+            .collect::<Vec<_>>();
+
+        let remapping = &self.remapping;
+        let rules = |flow| {
+            remapping
+                .rules(&interfaces, &zid, flow)
+                .into_iter()
+                .flat_map(|i| remapping.items[i].rules.iter().cloned())
+                .collect::<Vec<_>>()
+        };
+        let ingress_rules = rules(InterceptorFlow::Ingress);
+        let egress_rules = rules(InterceptorFlow::Egress);
+        if ingress_rules.is_empty() && egress_rules.is_empty() {
+            return (None, None);
+        }
+        // Both flows are intercepted as soon as one of them is remapped, since messages of
+        // one flow may reference key expressions declared (and remapped) by the other one.
+        let interceptor = KeyExprRemappingInterceptor {
+            rules: match self.flow {
+                InterceptorFlow::Ingress => ingress_rules,
+                InterceptorFlow::Egress => egress_rules,
+            },
+            flow: self.flow,
+            declarations: remapping.declarations(zid),
+        };
+        match self.flow {
+            InterceptorFlow::Ingress => (Some(Box::new(interceptor)), None),
+            InterceptorFlow::Egress => (None, Some(Box::new(interceptor))),
+        }
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+/// The key expressions declared on a transport with a remapped `DeclareKeyExpr`,
+/// by id, as seen by the remote node.
+#[derive(Default)]
+struct RemappedDeclarations {
+    ingress: RwLock<HashMap<ExprId, String>>,
+    egress: RwLock<HashMap<ExprId, String>>,
+}
+
+struct KeyExprRemappingInterceptor {
+    rules: Vec<RemappingRule>,
+    flow: InterceptorFlow,
+    declarations: Arc<RemappedDeclarations>,
+}
+
+impl KeyExprRemappingInterceptor {
+    fn remap(&self, key: &str) -> Option<String> {
+        let remapped = self.rules.iter().find_map(|r| r.remap_flow(key, self.flow));
+        if remapped.is_none() {
+            self.warn_not_remapped(key);
+        }
+        remapped
+    }
+
+    /// Warns about a key expression with wildcards intersecting the key space of a rule without
+    /// being included in it: it is not remapped, as it may also match keys outside of this key space.
+    fn warn_not_remapped(&self, key: &str) {
+        let Ok(key_expr) = keyexpr::new(key) else {
+            return;
+        };
+        if let Some(rule) = self
+            .rules
+            .iter()
+            .find(|r| key_expr.intersects(r.space(self.flow)))
+        {
+            tracing::warn!(
+                "{flow:?} key expression '{key_expr}' is not remapped: it intersects the remapped key space '{space}' without being included in it",
+                flow = self.flow,
+                space = rule.space(self.flow),
+            );
+        }
+    }
+
+    /// Returns the declarations the given wire expression scope refers to.
+    fn declarations(&self, mapping: Mapping) -> &RwLock<HashMap<ExprId, String>> {
+        match (self.flow, mapping) {
+            (InterceptorFlow::Ingress, Mapping::Sender)
+            | (InterceptorFlow::Egress, Mapping::Receiver) => &self.declarations.ingress,
+            (InterceptorFlow::Ingress, Mapping::Receiver)
+            | (InterceptorFlow::Egress, Mapping::Sender) => &self.declarations.egress,
+        }
+    }
+
+    /// Returns the complete key expression of the wire expression as seen by the sender of
+    /// the message, and whether it refers to a remapped declaration.
+    fn sender_key(&self, wire_expr: &WireExpr, full_expr: Option<&str>) -> (Option<String>, bool) {
+        if wire_expr.scope == EMPTY_EXPR_ID {
+            return (Some(wire_expr.suffix.to_string()), false);
+        }
+        match zread!(self.declarations(wire_expr.mapping)).get(&wire_expr.scope) {
+            // The routing tables know the local key expression of remapped declarations,
+            // which is the key expression as seen by the sender of egress messages
+            Some(remote) => match self.flow {
+                InterceptorFlow::Ingress => {
+                    (Some(remote.clone() + wire_expr.suffix.as_ref()), true)
+                }
+                InterceptorFlow::Egress => (full_expr.map(str::to_string), true),
+            },
+            None => (full_expr.map(str::to_string), false),
+        }
+    }
+
+    /// Remaps the wire expression, returning the remapped key expression, if any.
+    fn remap_wire_expr(&self, wire_expr: &mut WireExpr, full_expr: Option<&str>) -> Option<String> {
+        let (Some(key), remapped_declaration) = self.sender_key(wire_expr, full_expr) else {
+            return None;
+        };
+        let remapped = self.remap(&key);
+        if remapped.is_some() || remapped_declaration {
+            *wire_expr = WireExpr {
+                scope: EMPTY_EXPR_ID,
+                suffix: remapped.clone().unwrap_or(key).into(),
+                mapping: Mapping::DEFAULT,
+            };
+        }
+        remapped
+    }
+
+    fn remap_declaration(&self, id: ExprId, wire_expr: &mut WireExpr, full_expr: Option<&str>) {
+        let (sender_key, _) = self.sender_key(wire_expr, full_expr);
+        let remapped = self.remap_wire_expr(wire_expr, full_expr);
+        let declarations = match self.flow {
+            InterceptorFlow::Ingress => &self.declarations.ingress,
+            InterceptorFlow::Egress => &self.declarations.egress,
+        };
+        // Record the key expression as seen by the remote node
+        let remote = match self.flow {
+            InterceptorFlow::Ingress => remapped.and(sender_key),
+            InterceptorFlow::Egress => remapped,
+        };
+        match remote {
+            Some(remote) => zwrite!(declarations).insert(id, remote),
+            None => zwrite!(declarations).remove(&id),
+        };
+    }
+}
+
+impl InterceptorTrait for KeyExprRemappingInterceptor {
+    fn compute_keyexpr_cache(&self, _key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+        None
+    }
+
+    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool {
+        // Complete wire expressions are remapped without resolving them
+        let full_expr = msg
+            .wire_expr()
+            .is_some_and(|wire_expr| wire_expr.scope != EMPTY_EXPR_ID)
+            .then(|| ctx.full_expr(msg))
+            .flatten();
+        match &mut msg.body {
+            NetworkBodyMut::Push(m) => {
+                self.remap_wire_expr(&mut m.wire_expr, full_expr);
+            }
+            NetworkBodyMut::Request(m) => {
+                self.remap_wire_expr(&mut m.wire_expr, full_expr);
+            }
+            NetworkBodyMut::Response(m) => {
+                self.remap_wire_expr(&mut m.wire_expr, full_expr);
+            }
+            NetworkBodyMut::Interest(m) => {
+                if let Some(wire_expr) = &mut m.wire_expr {
+                    self.remap_wire_expr(wire_expr, full_expr);
+                }
+            }
+            NetworkBodyMut::Declare(m) => match &mut m.body {
+                DeclareBody::DeclareKeyExpr(m) => {
+                    self.remap_declaration(m.id, &mut m.wire_expr, full_expr)
+                }
+                DeclareBody::UndeclareKeyExpr(m) => {
+                    let declarations = match self.flow {
+                        InterceptorFlow::Ingress => &self.declarations.ingress,
+                        InterceptorFlow::Egress => &self.declarations.egress,
+                    };
+                    zwrite!(declarations).remove(&m.id);
+                }
+                DeclareBody::DeclareSubscriber(m) => {
+                    self.remap_wire_expr(&mut m.wire_expr, full_expr);
+                }
+                DeclareBody::UndeclareSubscriber(m) => {
+                    self.remap_wire_expr(&mut m.ext_wire_expr.wire_expr, full_expr);
+                }
+                DeclareBody::DeclareQueryable(m) => {
+                    self.remap_wire_expr(&mut m.wire_expr, full_expr);
+                }
+                DeclareBody::UndeclareQueryable(m) => {
+                    self.remap_wire_expr(&mut m.ext_wire_expr.wire_expr, full_expr);
+                }
+                DeclareBody::DeclareToken(m) => {
+                    self.remap_wire_expr(&mut m.wire_expr, full_expr);
+                }
+                DeclareBody::UndeclareToken(m) => {
+                    self.remap_wire_expr(&mut m.ext_wire_expr.wire_expr, full_expr);
+                }
+                DeclareBody::DeclareFinal(_) => {}
+            },
+            NetworkBodyMut::ResponseFinal(_) | NetworkBodyMut::OAM(_) => {}
+        }
+        true
+    }
+}
@@ -18,27 +18,25 @@
 //!
 //! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
 
-use std::{any::Any, collections::HashSet, iter, sync::Arc};
+use std::{any::Any, sync::Arc};
 
-use ahash::HashMap;
-use itertools::Itertools;
-use nonempty_collections::nev;
 use zenoh_buffers::buffer::Buffer;
-use zenoh_config::{DataMessage, InterceptorFlow, InterceptorLink, LowPassFilterConf};
+use zenoh_config::{DataMessage, InterceptorFlow, LowPassFilterConf};
 use zenoh_keyexpr::{
     keyexpr,
-    keyexpr_tree::{IKeyExprTree, IKeyExprTreeMut, IKeyExprTreeNode, KeBoxTree},
+    keyexpr_tree::{IKeyExprTree, IKeyExprTreeMut, IKeyExprTreeNode},
 };
 use zenoh_protocol::{
     network::{NetworkBodyMut, NetworkMessageMut, Push, Request, Response},
     zenoh::{PushBody, Reply, RequestBody, ResponseBody},
 };
 use zenoh_result::ZResult;
-use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
 
 use super::{
-    authorization::SubjectProperty, EgressInterceptor, IngressInterceptor, InterceptorFactory,
-    InterceptorFactoryTrait, InterceptorLinkWrapper, InterceptorTrait, InterfaceEnabled,
+    scoped_filter::{validate_ids, RuleScope, ScopedFilters},
+    EgressInterceptor, IngressInterceptor, InterceptorFactory, InterceptorFactoryTrait,
+    InterceptorTrait,
 };
 use crate::net::routing::interceptor::InterceptorContext;
 
@@ -49,22 +47,14 @@ pub(crate) fn low_pass_interceptor_factories(
 
     if !config.is_empty() {
         validate_config(config).map_err(|e| format!("Invalid low-pass filter config: {e}"))?;
-        res.push(Box::new(LowPassInterceptorFactory::new(config)));
+        res.push(Arc::new(LowPassInterceptorFactory::new(config)));
     }
 
     Ok(res)
 }
 
-fn validate_config(config: &Vec<LowPassFilterConf>) -> ZResult<()> {
-    let mut id_set = HashSet::new();
-    for lpf in config {
-        if let Some(id) = &lpf.id {
-            if !id_set.insert(id.clone()) {
-                bail!("id '{id}' is repeated");
-            }
-        }
-    }
-    Ok(())
+fn validate_config(config: &[LowPassFilterConf]) -> ZResult<()> {
+    validate_ids(config.iter().map(|lpf| lpf.id.as_ref()))
 }
 
 #[derive(Default)]
@@ -77,60 +67,16 @@ impl LowPassInterceptorFactory {
         let mut low_pass_filter = LowPassFilter::default();
 
         for lpf_config in config {
-            let mut lpf_config = lpf_config.clone();
-            let flows = lpf_config
-                .flows
-                .get_or_insert(nev![InterceptorFlow::Ingress, InterceptorFlow::Egress]);
-            let interfaces = lpf_config
-                .interfaces
-                .map(|v| {
-                    v.iter()
-                        .map(|face| SubjectProperty::Exactly(face.clone()))
-                        .collect_vec()
-                })
-                .unwrap_or(vec![SubjectProperty::Wildcard]);
-            let link_protocols = lpf_config
-                .link_protocols
-                .map(|v| {
-                    v.iter()
-                        .map(|proto| SubjectProperty::Exactly(proto.clone()))
-                        .collect_vec()
-                })
-                .unwrap_or(vec![SubjectProperty::Wildcard]);
-            let rule_subject_ids = interfaces
-                .into_iter()
-                .cartesian_product(link_protocols)
-                .map(|(interface, link_type)| {
-                    let subject = LowPassSubject {
-                        interface,
-                        link_type,
-                    };
-                    low_pass_filter.subjects.get_or_insert(&subject)
-                })
-                .collect_vec();
-            for message in &lpf_config.messages {
-                for key_expr in &lpf_config.key_exprs {
-                    for flow in &*flows {
-                        for id in &rule_subject_ids {
-                            low_pass_filter
-                                .filters
-                                .entry(*id)
-                                .or_default()
-                                .flow_mut(flow)
-                                .message_mut(message)
-                                .insert(key_expr, LowPassFilterRule::new(lpf_config.size_limit));
-                            match flow {
-                                InterceptorFlow::Egress => {
-                                    low_pass_filter.interface_enabled.egress = true
-                                }
-                                InterceptorFlow::Ingress => {
-                                    low_pass_filter.interface_enabled.ingress = true
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            let scope = RuleScope {
+                interfaces: lpf_config.interfaces.as_ref(),
+                link_protocols: lpf_config.link_protocols.as_ref(),
+                flows: lpf_config.flows.as_ref(),
+                messages: &lpf_config.messages,
+                key_exprs: &lpf_config.key_exprs,
+            };
+            low_pass_filter.insert(scope, |tree, key_expr| {
+                tree.insert(key_expr, LowPassFilterRule::new(lpf_config.size_limit));
+            });
         }
         Self {
             state: low_pass_filter.into(),
@@ -143,75 +89,21 @@ impl InterceptorFactoryTrait for LowPassInterceptorFactory {
         &self,
         transport: &TransportUnicast,
     ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
-        tracing::debug!("New low-pass filter transport unicast {:?}", transport);
-        let links = match transport.get_links() {
-            Ok(links) => links,
-            Err(e) => {
-                tracing::error!("Unable to get links from transport {:?}: {e}", transport);
-                return (None, None);
-            }
-        };
-        let auth_ids = match transport.get_auth_ids() {
-            Ok(auth_ids) => auth_ids,
-            Err(e) => {
-                tracing::error!("Unable to get auth_ids for transport {:?}: {e}", transport);
-                return (None, None);
-            }
-        };
-
-        let interfaces = links
-            .into_iter()
-            .flat_map(|link| link.interfaces)
-            .map(SubjectProperty::Exactly)
-            .chain(iter::once(SubjectProperty::Wildcard));
-        let link_types = auth_ids
-            .link_auth_ids()
-            .iter()
-            .map(|auth_id| SubjectProperty::Exactly(InterceptorLinkWrapper::from(auth_id).0))
-            .chain(iter::once(SubjectProperty::Wildcard));
-
-        let subject_ids = interfaces
-            .cartesian_product(link_types)
-            .filter_map(|(interface, link_type)| {
-                let subject = LowPassSubject {
-                    interface,
-                    link_type,
-                };
-                self.state.subjects.get_subject_id(&subject)
-            })
-            .collect_vec();
-        if !subject_ids.is_empty() {
-            let subject_ids = Arc::new(subject_ids);
+        self.state.new_transport_unicast(
+            transport,
+            "low-pass filter",
             #[cfg(feature = "stats")]
-            let Ok(stats) = transport
-                .get_stats()
-                .map(|stats| stats.drop_stats(zenoh_stats::ReasonLabel::LowPass))
-            else {
-                // `get_stats` returning an error means the transport is closed
-                return (None, None);
-            };
-            return (
-                self.state.interface_enabled.ingress.then(|| {
-                    Box::new(LowPassInterceptor::new(
-                        self.state.clone(),
-                        subject_ids.clone(),
-                        InterceptorFlow::Ingress,
-                        #[cfg(feature = "stats")]
-                        stats.clone(),
-                    )) as IngressInterceptor
-                }),
-                self.state.interface_enabled.egress.then(|| {
-                    Box::new(LowPassInterceptor::new(
-                        self.state.clone(),
-                        subject_ids,
-                        InterceptorFlow::Egress,
-                        #[cfg(feature = "stats")]
-                        stats.clone(),
-                    )) as EgressInterceptor
-                }),
-            );
-        }
-        (None, None)
+            zenoh_stats::ReasonLabel::LowPass,
+            |subjects, flow, #[cfg(feature = "stats")] stats| {
+                LowPassInterceptor::new(
+                    self.state.clone(),
+                    subjects,
+                    flow,
+                    #[cfg(feature = "stats")]
+                    stats,
+                )
+            },
+        )
     }
 
     fn new_transport_multicast(
@@ -221,7 +113,11 @@ impl InterceptorFactoryTrait for LowPassInterceptorFactory {
         None
     }
 
-    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
         None
     }
 }
@@ -367,28 +263,17 @@ impl LowPassInterceptor {
     }
 
     fn get_max_allowed_message_size(&self, message: DataMessage, key_expr: &keyexpr) -> usize {
-        match self
-            .subjects
-            .iter()
-            .filter_map(|s| {
-                // get min of matching nodes within the KeBoxTree of the subject/flow/message
-                self.inner
-                    .filters
-                    .get(s)
-                    .expect("subject should have entry in map")
-                    .flow(&self.flow)
-                    .message(&message)
-                    .nodes_including(key_expr)
-                    .filter(|node| node.weight().is_some())
-                    .min_by_key(|node| node.weight().expect("weight should not be none").0)
+        self.inner
+            .trees(&self.subjects, &self.flow, &message)
+            // get min of matching nodes within the KeBoxTree of the subject/flow/message
+            .filter_map(|tree| {
+                tree.nodes_including(key_expr)
+                    .filter_map(|node| node.weight().map(|w| w.0))
+                    .min()
             })
             // get min of matching nodes from different KeBoxTrees
-            .min_by_key(|node| node.weight().expect("weight should not be none").0)
-            .map(|node| node.weight().expect("weight should not be none"))
-        {
-            Some(w) => w.0,
-            None => usize::MAX,
-        }
+            .min()
+            .unwrap_or(usize::MAX)
     }
 }
 
@@ -422,91 +307,7 @@ impl InterceptorTrait for LowPassInterceptor {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct LowPassSubject {
-    interface: SubjectProperty<String>,
-    link_type: SubjectProperty<InterceptorLink>,
-}
-
-#[derive(Default)]
-struct SubjectStore {
-    id: usize,
-    subjects: HashMap<LowPassSubject, usize>,
-}
-
-impl SubjectStore {
-    fn get_or_insert(&mut self, subject: &LowPassSubject) -> usize {
-        if let Some(id) = self.subjects.get(subject) {
-            return *id;
-        }
-        self.id += 1;
-        self.subjects.insert(subject.clone(), self.id);
-        self.id
-    }
-
-    fn get_subject_id(&self, subject: &LowPassSubject) -> Option<usize> {
-        self.subjects.get(subject).copied()
-    }
-}
-
-#[derive(Default)]
-struct LowPassFilter {
-    subjects: SubjectStore,
-    filters: HashMap<usize, LowPassFilterFlows>,
-    interface_enabled: InterfaceEnabled,
-}
-
-#[derive(Default)]
-struct LowPassFilterFlows {
-    ingress: LowPassFilterMessages,
-    egress: LowPassFilterMessages,
-}
-
-impl LowPassFilterFlows {
-    fn flow(&self, flow: &InterceptorFlow) -> &LowPassFilterMessages {
-        match flow {
-            InterceptorFlow::Egress => &self.egress,
-            InterceptorFlow::Ingress => &self.ingress,
-        }
-    }
-
-    fn flow_mut(&mut self, flow: &InterceptorFlow) -> &mut LowPassFilterMessages {
-        match flow {
-            InterceptorFlow::Egress => &mut self.egress,
-            InterceptorFlow::Ingress => &mut self.ingress,
-        }
-    }
-}
-
-#[derive(Default)]
-struct LowPassFilterMessages {
-    put: LowPassFilterTree,
-    delete: LowPassFilterTree,
-    query: LowPassFilterTree,
-    reply: LowPassFilterTree,
-}
-
-impl LowPassFilterMessages {
-    fn message(&self, message: &DataMessage) -> &LowPassFilterTree {
-        match message {
-            DataMessage::Put => &self.put,
-            DataMessage::Delete => &self.delete,
-            DataMessage::Query => &self.query,
-            DataMessage::Reply => &self.reply,
-        }
-    }
-
-    fn message_mut(&mut self, message: &DataMessage) -> &mut LowPassFilterTree {
-        match message {
-            DataMessage::Put => &mut self.put,
-            DataMessage::Delete => &mut self.delete,
-            DataMessage::Query => &mut self.query,
-            DataMessage::Reply => &mut self.reply,
-        }
-    }
-}
-
-type LowPassFilterTree = KeBoxTree<LowPassFilterRule>;
+type LowPassFilter = ScopedFilters<LowPassFilterRule>;
 
 #[derive(Default)]
 struct LowPassFilterRule(usize);
@@ -20,6 +20,10 @@
 //!
 mod access_control;
 use access_control::acl_interceptor_factories;
+pub(crate) use access_control::validate_acl_config;
+
+pub(crate) mod acl_audit;
+use acl_audit::AclAuditLog;
 use nonempty_collections::NEVec;
 use zenoh_link::LinkAuthId;
 
@@ -34,15 +38,30 @@ use std::{
 
 use arc_swap::ArcSwapOption;
 
+pub(crate) mod keyexpr_remapping;
+use keyexpr_remapping::{keyexpr_remapping_interceptor_factories, KeyExprRemapping};
+
 mod low_pass;
+mod scoped_filter;
 use low_pass::low_pass_interceptor_factories;
-use zenoh_config::{Config, InterceptorFlow, InterceptorLink};
+
+mod payload_filter;
+use payload_filter::payload_filter_interceptor_factories;
+use zenoh_config::{
+    Config, DownsamplingItemConf, InterceptorFlow, InterceptorLink, LowPassFilterConf,
+    PayloadFilterConf, QosOverwriteItemConf,
+};
 use zenoh_keyexpr::{keyexpr, OwnedKeyExpr};
-use zenoh_protocol::network::NetworkMessageMut;
+use zenoh_protocol::{
+    core::{ExprId, EMPTY_EXPR_ID},
+    network::{Mapping, NetworkMessage, NetworkMessageExt, NetworkMessageMut},
+};
 use zenoh_result::ZResult;
-use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
 
 pub mod downsampling;
+#[cfg(feature = "unstable")]
+use crate::api::interceptor::UserInterceptors;
 use crate::{
     key_expr::KeyExpr,
     net::routing::{
@@ -53,6 +72,9 @@ use crate::{
 pub mod qos_overwrite;
 use crate::net::routing::interceptor::qos_overwrite::qos_overwrite_interceptor_factories;
 
+pub(crate) mod rate_limit;
+use rate_limit::{rate_limit_interceptor_factories, RateLimiter};
+
 #[derive(Default, Debug)]
 pub struct InterfaceEnabled {
     pub ingress: bool,
@@ -94,59 +116,309 @@ impl From<&LinkAuthId> for InterceptorLinkWrapper {
     }
 }
 
+/// Context made available to an [`InterceptorTrait`] while it intercepts a message.
 pub(crate) trait InterceptorContext {
     #[allow(dead_code)]
     fn face(&self) -> Option<Face>;
+    /// Returns the full key expression of the intercepted message, if any.
     fn full_expr(&self, msg: &NetworkMessageMut) -> Option<&str>;
+    /// Returns the full key expression of the intercepted message as a [`KeyExpr`], if any.
     #[inline]
     fn full_keyexpr(&self, msg: &NetworkMessageMut) -> Option<KeyExpr<'_>> {
         let full_expr = self.full_expr(msg)?;
         KeyExpr::new(full_expr).ok()
     }
+    /// Returns the cache computed by [`InterceptorTrait::compute_keyexpr_cache`]
+    /// for the key expression of the intercepted message, if any.
     fn get_cache(&self, msg: &NetworkMessageMut) -> Option<&Box<dyn Any + Send + Sync>>;
+    /// Returns a copy of the intercepted message that can be sent later through the
+    /// interceptors following the current one, if the context allows it.
+    fn defer(&self, _msg: &NetworkMessageMut) -> Option<DeferredMessage> {
+        None
+    }
+    /// Returns the sink the intercepted messages are sent to, if messages can be sent later.
+    fn sink(&self) -> Option<MessageSink> {
+        None
+    }
+}
+
+/// Sends an intercepted message to its destination, returning `false` if it could not be sent.
+pub(crate) type MessageSink = Arc<dyn Fn(NetworkMessageMut) -> bool + Send + Sync>;
+
+/// A message held back by an interceptor, see [`InterceptorContext::defer`].
+pub(crate) struct DeferredMessage {
+    msg: NetworkMessage,
+    expr: Option<String>,
+    interceptors: Arc<Vec<SharedInterceptor>>,
+    index: usize,
+    sink: MessageSink,
+}
+
+impl DeferredMessage {
+    /// Sends the message through the remaining interceptors, then to its destination.
+    pub(crate) fn send(mut self) -> bool {
+        let mut msg = self.msg.as_mut();
+        let mut ctx = DeferredContext {
+            expr: self.expr,
+            sink: self.sink.clone(),
+        };
+        InterceptorsChain::intercept_from(&self.interceptors, self.index, &mut msg, &mut ctx)
+            && (self.sink)(msg)
+    }
+}
+
+struct DeferredContext {
+    expr: Option<String>,
+    sink: MessageSink,
+}
+
+impl InterceptorContext for DeferredContext {
+    fn face(&self) -> Option<Face> {
+        None
+    }
+
+    fn full_expr(&self, _msg: &NetworkMessageMut) -> Option<&str> {
+        self.expr.as_deref()
+    }
+
+    fn get_cache(&self, _msg: &NetworkMessageMut) -> Option<&Box<dyn Any + Send + Sync>> {
+        None
+    }
+
+    fn sink(&self) -> Option<MessageSink> {
+        Some(self.sink.clone())
+    }
 }
 
+/// An interceptor of network messages, attached to a transport in a given flow (ingress or egress).
 pub(crate) trait InterceptorTrait {
+    /// Computes a per key expression cache. It is computed once per resource
+    /// and then made available to [`InterceptorTrait::intercept`] through
+    /// [`InterceptorContext::get_cache`].
     fn compute_keyexpr_cache(&self, key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>>;
 
+    /// Intercepts the given message, possibly modifying it.
+    /// Returns `false` if the message should be dropped.
     fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool;
 }
 
 pub(crate) type Interceptor = Box<dyn InterceptorTrait + Send + Sync>;
 pub(crate) type IngressInterceptor = Interceptor;
 pub(crate) type EgressInterceptor = Interceptor;
+/// An interceptor attached to a face, shared by the successive chains of the face so that
+/// its state survives the rebuilds of the other interceptors.
+pub(crate) type SharedInterceptor = Arc<dyn InterceptorTrait + Send + Sync>;
 
+/// A factory of interceptors, called each time a new transport is established.
 pub(crate) trait InterceptorFactoryTrait {
+    /// Returns the ingress and egress interceptors to attach to a new unicast transport.
     fn new_transport_unicast(
         &self,
         transport: &TransportUnicast,
     ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>);
+    /// Returns the egress interceptor to attach to a new multicast transport.
     fn new_transport_multicast(&self, transport: &TransportMulticast) -> Option<EgressInterceptor>;
-    fn new_peer_multicast(&self, transport: &TransportMulticast) -> Option<IngressInterceptor>;
+    /// Returns the ingress interceptor to attach to a new peer on a multicast transport.
+    fn new_peer_multicast(
+        &self,
+        transport: &TransportMulticast,
+        peer: &TransportPeer,
+    ) -> Option<IngressInterceptor>;
 }
 
-pub(crate) type InterceptorFactory = Box<dyn InterceptorFactoryTrait + Send + Sync>;
+pub(crate) type InterceptorFactory = Arc<dyn InterceptorFactoryTrait + Send + Sync>;
+
+/// A user-defined [`InterceptorFactory`](crate::api::interceptor::InterceptorFactory), registered
+/// through [`IRuntime::add_interceptor_factory`](crate::net::runtime::IRuntime::add_interceptor_factory).
+#[cfg(feature = "unstable")]
+pub(crate) type UserInterceptorFactory = Arc<dyn crate::api::interceptor::InterceptorFactory>;
 
-pub(crate) fn interceptor_factories(config: &Config) -> ZResult<Vec<InterceptorFactory>> {
-    let mut res: Vec<InterceptorFactory> = vec![];
-    // Uncomment to log the interceptors initialisation
-    // res.push(Box::new(LoggerInterceptor {}));
+/// The interceptor factories of the tables, grouped by configuration section.
+///
+/// When the configuration is updated, the factories of the sections whose configuration is
+/// unchanged are kept, so that the faces keep the interceptors built from them along with
+/// their state (e.g. the downsampling timestamps or the key expression declarations).
+pub(crate) struct InterceptorFactories {
+    remapping_ingress: FactoriesSection<Option<Arc<KeyExprRemapping>>>,
     #[cfg(test)]
-    if let Some(id) = config.id() {
-        if let Some(test_interceptors) = tests::ID_TO_INTERCEPTOR_FACTORIES.lock().unwrap().get(id)
-        {
-            res.extend((test_interceptors.as_ref())());
-        }
+    test: Vec<InterceptorFactory>,
+    downsampling: FactoriesSection<Vec<DownsamplingItemConf>>,
+    access_control: Vec<InterceptorFactory>,
+    qos_overwrite: FactoriesSection<Vec<QosOverwriteItemConf>>,
+    low_pass: FactoriesSection<Vec<LowPassFilterConf>>,
+    payload_filter: FactoriesSection<Vec<PayloadFilterConf>>,
+    rate_limit: FactoriesSection<Option<Arc<RateLimiter>>>,
+    #[cfg(feature = "unstable")]
+    user: Vec<(UserInterceptorFactory, InterceptorFactory)>,
+    remapping_egress: FactoriesSection<Option<Arc<KeyExprRemapping>>>,
+}
+
+impl InterceptorFactories {
+    pub(crate) fn new(
+        config: &Config,
+        keyexpr_remapping: Option<&Arc<KeyExprRemapping>>,
+        acl_audit: Option<&Arc<AclAuditLog>>,
+        rate_limiter: Option<&Arc<RateLimiter>>,
+        #[cfg(feature = "unstable")] user_factories: &[UserInterceptorFactory],
+    ) -> ZResult<Self> {
+        Self::update(
+            None,
+            config,
+            keyexpr_remapping,
+            acl_audit,
+            rate_limiter,
+            #[cfg(feature = "unstable")]
+            user_factories,
+        )
+    }
+
+    /// Builds the factories for `config`, reusing the sections of `current` whose
+    /// configuration is unchanged.
+    ///
+    /// The access control factories are always rebuilt.
+    pub(crate) fn update(
+        current: Option<&Self>,
+        config: &Config,
+        keyexpr_remapping: Option<&Arc<KeyExprRemapping>>,
+        acl_audit: Option<&Arc<AclAuditLog>>,
+        rate_limiter: Option<&Arc<RateLimiter>>,
+        #[cfg(feature = "unstable")] user_factories: &[UserInterceptorFactory],
+    ) -> ZResult<Self> {
+        // Key expressions are remapped first on ingress and last on egress so that the other
+        // interceptors (e.g. access control) see them in the key space of this node.
+        let remapping_ingress = FactoriesSection::update(
+            current.map(|c| &c.remapping_ingress),
+            keyexpr_remapping.cloned(),
+            same_arc,
+            |remapping| Ok(keyexpr_remapping_interceptor_factories(remapping.as_ref()).0),
+        )?;
+        let remapping_egress = FactoriesSection::update(
+            current.map(|c| &c.remapping_egress),
+            keyexpr_remapping.cloned(),
+            same_arc,
+            |remapping| Ok(keyexpr_remapping_interceptor_factories(remapping.as_ref()).1),
+        )?;
+        #[cfg(test)]
+        let test = match config.id() {
+            Some(id) => tests::ID_TO_INTERCEPTOR_FACTORIES
+                .lock()
+                .unwrap()
+                .get(id)
+                .map(|test_interceptors| (test_interceptors.as_ref())())
+                .unwrap_or_default(),
+            None => vec![],
+        };
+        let downsampling = FactoriesSection::update(
+            current.map(|c| &c.downsampling),
+            config.downsampling().clone(),
+            PartialEq::eq,
+            downsampling_interceptor_factories,
+        )?;
+        let access_control = acl_interceptor_factories(config.access_control(), acl_audit)?;
+        let qos_overwrite = FactoriesSection::update(
+            current.map(|c| &c.qos_overwrite),
+            config.qos().network().clone(),
+            PartialEq::eq,
+            qos_overwrite_interceptor_factories,
+        )?;
+        let low_pass = FactoriesSection::update(
+            current.map(|c| &c.low_pass),
+            config.low_pass_filter().clone(),
+            PartialEq::eq,
+            low_pass_interceptor_factories,
+        )?;
+        let payload_filter = FactoriesSection::update(
+            current.map(|c| &c.payload_filter),
+            config.payload_filter().clone(),
+            PartialEq::eq,
+            |config| payload_filter_interceptor_factories(config),
+        )?;
+        let rate_limit = FactoriesSection::update(
+            current.map(|c| &c.rate_limit),
+            rate_limiter.cloned(),
+            same_arc,
+            |rate_limiter| Ok(rate_limit_interceptor_factories(rate_limiter.as_ref())),
+        )?;
+        #[cfg(feature = "unstable")]
+        let user = user_factories
+            .iter()
+            .map(|user_factory| {
+                let factory = current
+                    .and_then(|c| c.user.iter().find(|(f, _)| Arc::ptr_eq(f, user_factory)))
+                    .map(|(_, factory)| factory.clone())
+                    .unwrap_or_else(|| Arc::new(UserInterceptors(user_factory.clone())));
+                (user_factory.clone(), factory)
+            })
+            .collect();
+        Ok(InterceptorFactories {
+            remapping_ingress,
+            #[cfg(test)]
+            test,
+            downsampling,
+            access_control,
+            qos_overwrite,
+            low_pass,
+            payload_filter,
+            rate_limit,
+            #[cfg(feature = "unstable")]
+            user,
+            remapping_egress,
+        })
+    }
+
+    /// Returns the factories in the order their interceptors are chained.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &InterceptorFactory> {
+        let factories = self.remapping_ingress.factories.iter();
+        #[cfg(test)]
+        let factories = factories.chain(&self.test);
+        let factories = factories
+            .chain(&self.downsampling.factories)
+            .chain(&self.access_control)
+            .chain(&self.qos_overwrite.factories)
+            .chain(&self.low_pass.factories)
+            .chain(&self.payload_filter.factories)
+            .chain(&self.rate_limit.factories);
+        // User-defined interceptors come last so that they only see messages
+        // admitted by the built-in ones (e.g. access control).
+        #[cfg(feature = "unstable")]
+        let factories = factories.chain(self.user.iter().map(|(_, factory)| factory));
+        factories.chain(&self.remapping_egress.factories)
+    }
+}
+
+/// The factories built from a configuration section.
+struct FactoriesSection<C> {
+    conf: C,
+    factories: Vec<InterceptorFactory>,
+}
+
+impl<C> FactoriesSection<C> {
+    /// Returns the factories of `current` if `same` holds for its configuration and `conf`,
+    /// or builds new ones from `conf`.
+    fn update(
+        current: Option<&Self>,
+        conf: C,
+        same: fn(&C, &C) -> bool,
+        build: impl FnOnce(&C) -> ZResult<Vec<InterceptorFactory>>,
+    ) -> ZResult<Self> {
+        let factories = match current {
+            Some(current) if same(&current.conf, &conf) => current.factories.clone(),
+            _ => build(&conf)?,
+        };
+        Ok(FactoriesSection { conf, factories })
+    }
+}
+
+fn same_arc<T>(a: &Option<Arc<T>>, b: &Option<Arc<T>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+        (None, None) => true,
+        _ => false,
     }
-    res.extend(downsampling_interceptor_factories(config.downsampling())?);
-    res.extend(acl_interceptor_factories(config.access_control())?);
-    res.extend(qos_overwrite_interceptor_factories(config.qos().network())?);
-    res.extend(low_pass_interceptor_factories(config.low_pass_filter())?);
-    Ok(res)
 }
 
 pub(crate) struct InterceptorsChain {
-    pub(crate) interceptors: Vec<Interceptor>,
+    pub(crate) interceptors: Arc<Vec<SharedInterceptor>>,
     pub(crate) version: usize,
 }
 
@@ -154,7 +426,7 @@ impl InterceptorsChain {
     #[allow(dead_code)]
     pub(crate) fn empty() -> Self {
         Self {
-            interceptors: vec![],
+            interceptors: Arc::new(vec![]),
             version: 0,
         }
     }
@@ -163,12 +435,48 @@ impl InterceptorsChain {
         self.interceptors.is_empty()
     }
 
-    pub(crate) fn new(interceptors: Vec<Interceptor>, version: usize) -> Self {
+    pub(crate) fn new(interceptors: Vec<SharedInterceptor>, version: usize) -> Self {
         InterceptorsChain {
-            interceptors,
+            interceptors: Arc::new(interceptors),
             version,
         }
     }
+
+    /// Runs the message through the interceptors starting at `index`.
+    fn intercept_from(
+        interceptors: &Arc<Vec<SharedInterceptor>>,
+        index: usize,
+        msg: &mut NetworkMessageMut,
+        ctx: &mut dyn InterceptorContext,
+    ) -> bool {
+        let mut ctx = ChainContext {
+            ctx,
+            interceptors,
+            index,
+            rewritten: None,
+        };
+        let mut wire_expr = WireExprSnapshot::default();
+        for interceptor in &interceptors[index..] {
+            // The last interceptor's rewrites don't matter to the chain
+            let is_last = ctx.index + 1 == interceptors.len();
+            let recorded = !is_last && wire_expr.record(msg);
+            if !interceptor.intercept(msg, &mut ctx as &mut dyn InterceptorContext) {
+                tracing::trace!("Msg intercepted!");
+                return false;
+            }
+            if recorded && !wire_expr.matches(msg) {
+                // The key expression and cache resolved by the context are those of the
+                // original wire expression
+                ctx.rewritten = Some(
+                    msg.wire_expr()
+                        .filter(|wire_expr| wire_expr.scope == EMPTY_EXPR_ID)
+                        .map(|wire_expr| wire_expr.suffix.to_string()),
+                );
+            }
+            ctx.index += 1;
+        }
+        true
+    }
 }
 
 impl From<InterceptorsChain> for Option<Arc<InterceptorsChain>> {
@@ -187,16 +495,8 @@ impl InterceptorTrait for InterceptorsChain {
         ))
     }
 
-    fn intercept<'a>(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool {
-        let mut ctx = ChainContext { ctx, index: 0 };
-        for interceptor in &self.interceptors {
-            if !interceptor.intercept(msg, &mut ctx as &mut dyn InterceptorContext) {
-                tracing::trace!("Msg intercepted!");
-                return false;
-            }
-            ctx.index += 1;
-        }
-        true
+    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool {
+        Self::intercept_from(&self.interceptors, 0, msg, ctx)
     }
 }
 
@@ -221,9 +521,41 @@ pub(crate) fn has_interceptor(interceptor: &ArcSwapOption<InterceptorsChain>) ->
     !atomic_ptr.load(Ordering::Relaxed).is_null()
 }
 
+/// A copy of the wire expression of a message, to detect the interceptors rewriting it, in place
+/// or not. The suffix buffer is reused from one interceptor of the chain to the next.
+#[derive(Default)]
+struct WireExprSnapshot {
+    expr: Option<(ExprId, Mapping)>,
+    suffix: String,
+}
+
+impl WireExprSnapshot {
+    /// Records the wire expression of the message, returning false if it has none.
+    fn record(&mut self, msg: &NetworkMessageMut) -> bool {
+        let Some(wire_expr) = msg.wire_expr() else {
+            return false;
+        };
+        self.expr = Some((wire_expr.scope, wire_expr.mapping));
+        self.suffix.clear();
+        self.suffix.push_str(&wire_expr.suffix);
+        true
+    }
+
+    fn matches(&self, msg: &NetworkMessageMut) -> bool {
+        msg.wire_expr().is_some_and(|wire_expr| {
+            self.expr == Some((wire_expr.scope, wire_expr.mapping))
+                && self.suffix == wire_expr.suffix
+        })
+    }
+}
+
 struct ChainContext<'a> {
     ctx: &'a mut dyn InterceptorContext,
+    interceptors: &'a Arc<Vec<SharedInterceptor>>,
     index: usize,
+    /// The complete key expression of the message, if any, once its wire expression has been
+    /// rewritten by an interceptor of the chain.
+    rewritten: Option<Option<String>>,
 }
 
 impl InterceptorContext for ChainContext<'_> {
@@ -232,18 +564,34 @@ impl InterceptorContext for ChainContext<'_> {
     }
 
     fn full_expr(&self, msg: &NetworkMessageMut) -> Option<&str> {
-        self.ctx.full_expr(msg)
-    }
-
-    fn full_keyexpr(&self, msg: &NetworkMessageMut) -> Option<KeyExpr<'_>> {
-        self.ctx.full_keyexpr(msg)
+        match &self.rewritten {
+            Some(expr) => expr.as_deref(),
+            None => self.ctx.full_expr(msg),
+        }
     }
 
     fn get_cache(&self, msg: &NetworkMessageMut) -> Option<&Box<dyn Any + Send + Sync>> {
+        if self.rewritten.is_some() {
+            return None;
+        }
         let caches = self.ctx.get_cache(msg)?;
         let caches = caches.downcast_ref::<Vec<Option<Box<dyn Any + Send + Sync>>>>()?;
         caches[self.index].as_ref()
     }
+
+    fn defer(&self, msg: &NetworkMessageMut) -> Option<DeferredMessage> {
+        Some(DeferredMessage {
+            msg: msg.to_owned(),
+            expr: self.full_expr(msg).map(str::to_string),
+            interceptors: self.interceptors.clone(),
+            index: self.index + 1,
+            sink: self.ctx.sink()?,
+        })
+    }
+
+    fn sink(&self) -> Option<MessageSink> {
+        self.ctx.sink()
+    }
 }
 
 #[allow(dead_code)]
@@ -317,8 +665,12 @@ impl InterceptorFactoryTrait for LoggerInterceptor {
         Some(Box::new(EgressMsgLogger {}))
     }
 
-    fn new_peer_multicast(&self, transport: &TransportMulticast) -> Option<IngressInterceptor> {
-        tracing::debug!("New peer multicast {:?}", transport);
+    fn new_peer_multicast(
+        &self,
+        transport: &TransportMulticast,
+        peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
+        tracing::debug!("New peer multicast {:?} on {:?}", peer, transport);
         Some(Box::new(IngressMsgLogger {}))
     }
 }
@@ -338,13 +690,79 @@ pub(crate) mod tests {
         sync::{Arc, Mutex},
     };
 
+    use std::any::Any;
+
     use once_cell::sync::Lazy;
     use zenoh_config::ZenohId;
+    use zenoh_keyexpr::keyexpr;
+    use zenoh_protocol::{
+        core::WireExpr,
+        network::{NetworkBodyMut, NetworkMessage, NetworkMessageMut, Push},
+    };
 
-    use super::InterceptorFactory;
+    use super::{
+        DeferredContext, InterceptorContext, InterceptorFactory, InterceptorTrait,
+        InterceptorsChain,
+    };
 
     #[allow(clippy::type_complexity)]
     pub(crate) static ID_TO_INTERCEPTOR_FACTORIES: Lazy<
         Arc<Mutex<HashMap<ZenohId, Box<dyn Fn() -> Vec<InterceptorFactory> + Sync + Send>>>>,
     > = Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+
+    /// Rewrites the key expression of the pushes in place, keeping its length.
+    struct UppercaseInPlace;
+
+    impl InterceptorTrait for UppercaseInPlace {
+        fn compute_keyexpr_cache(&self, _: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+            None
+        }
+
+        fn intercept(&self, msg: &mut NetworkMessageMut, _: &mut dyn InterceptorContext) -> bool {
+            if let NetworkBodyMut::Push(push) = &mut msg.body {
+                push.wire_expr.suffix.to_mut().make_ascii_uppercase();
+            }
+            true
+        }
+    }
+
+    /// Records the key expressions of the messages, as resolved by the context.
+    struct ExprRecorder(Arc<Mutex<Vec<Option<String>>>>);
+
+    impl InterceptorTrait for ExprRecorder {
+        fn compute_keyexpr_cache(&self, _: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+            None
+        }
+
+        fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool {
+            let expr = ctx.full_expr(msg).map(str::to_string);
+            self.0.lock().unwrap().push(expr);
+            true
+        }
+    }
+
+    #[test]
+    fn chain_detects_in_place_rewrites() {
+        let exprs = Arc::new(Mutex::new(vec![]));
+        let chain = InterceptorsChain::new(
+            vec![
+                Arc::new(ExprRecorder(exprs.clone())),
+                Arc::new(UppercaseInPlace),
+                Arc::new(ExprRecorder(exprs.clone())),
+            ],
+            0,
+        );
+        let mut push = Push::from(vec![0u8]);
+        push.wire_expr = WireExpr::from("demo/a").to_owned();
+        let mut msg = NetworkMessage::from(push);
+        let mut ctx = DeferredContext {
+            expr: Some("demo/a".to_string()),
+            sink: Arc::new(|_| true),
+        };
+        assert!(chain.intercept(&mut msg.as_mut(), &mut ctx));
+        assert_eq!(
+            *exprs.lock().unwrap(),
+            [Some("demo/a".to_string()), Some("DEMO/A".to_string())]
+        );
+    }
 }
@@ -0,0 +1,411 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::{any::Any, sync::Arc};
+
+use itertools::Itertools;
+use zenoh_buffers::{buffer::SplitBuffer, ZBuf};
+use zenoh_config::{DataMessage, InterceptorFlow, PayloadFilterConf};
+use zenoh_keyexpr::{
+    keyexpr,
+    keyexpr_tree::{IKeyExprTree, IKeyExprTreeMut, IKeyExprTreeNode},
+};
+use zenoh_protocol::{
+    core::Encoding,
+    network::{NetworkBodyMut, NetworkMessageMut, Push, Request, Response},
+    zenoh::{PushBody, Reply, RequestBody, ResponseBody},
+};
+use zenoh_result::ZResult;
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
+
+use super::{
+    scoped_filter::{validate_ids, RuleScope, ScopedFilters},
+    EgressInterceptor, IngressInterceptor, InterceptorFactory, InterceptorFactoryTrait,
+    InterceptorTrait,
+};
+use crate::net::routing::interceptor::InterceptorContext;
+
+pub(crate) fn payload_filter_interceptor_factories(
+    config: &[PayloadFilterConf],
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+
+    if !config.is_empty() {
+        validate_config(config).map_err(|e| format!("Invalid payload filter config: {e}"))?;
+        res.push(Arc::new(PayloadFilterInterceptorFactory::new(config)));
+    }
+
+    Ok(res)
+}
+
+fn validate_config(config: &[PayloadFilterConf]) -> ZResult<()> {
+    validate_ids(config.iter().map(|pf| pf.id.as_ref()))?;
+    for pf in config {
+        if pf.encodings.is_none() && pf.attachment.is_none() {
+            bail!(
+                "filter '{}' has neither encodings nor attachment",
+                pf.id.as_deref().unwrap_or("<unnamed>")
+            );
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct PayloadFilterInterceptorFactory {
+    state: Arc<PayloadFilter>,
+}
+
+impl PayloadFilterInterceptorFactory {
+    pub fn new(config: &[PayloadFilterConf]) -> Self {
+        let mut payload_filter = PayloadFilter::default();
+
+        for pf_config in config {
+            let rule_id = payload_filter.rules.len();
+            payload_filter.rules.push(PayloadFilterRule::new(pf_config));
+            let scope = RuleScope {
+                interfaces: pf_config.interfaces.as_ref(),
+                link_protocols: pf_config.link_protocols.as_ref(),
+                flows: pf_config.flows.as_ref(),
+                messages: &pf_config.messages,
+                key_exprs: &pf_config.key_exprs,
+            };
+            payload_filter.filters.insert(scope, |tree, key_expr| {
+                match tree.weight_at_mut(key_expr) {
+                    Some(rule_ids) => rule_ids.push(rule_id),
+                    None => {
+                        tree.insert(key_expr, vec![rule_id]);
+                    }
+                }
+            });
+        }
+        Self {
+            state: payload_filter.into(),
+        }
+    }
+}
+
+impl InterceptorFactoryTrait for PayloadFilterInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        self.state.filters.new_transport_unicast(
+            transport,
+            "payload filter",
+            #[cfg(feature = "stats")]
+            zenoh_stats::ReasonLabel::PayloadFilter,
+            |subjects, flow, #[cfg(feature = "stats")] stats| {
+                PayloadFilterInterceptor::new(
+                    self.state.clone(),
+                    subjects,
+                    flow,
+                    #[cfg(feature = "stats")]
+                    stats,
+                )
+            },
+        )
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+pub struct PayloadFilterInterceptor {
+    inner: Arc<PayloadFilter>,
+    subjects: Arc<Vec<usize>>,
+    flow: InterceptorFlow,
+    #[cfg(feature = "stats")]
+    stats: zenoh_stats::DropStats,
+}
+
+impl PayloadFilterInterceptor {
+    fn new(
+        inner: Arc<PayloadFilter>,
+        subjects: Arc<Vec<usize>>,
+        flow: InterceptorFlow,
+        #[cfg(feature = "stats")] stats: zenoh_stats::DropStats,
+    ) -> Self {
+        Self {
+            inner,
+            subjects,
+            flow,
+            #[cfg(feature = "stats")]
+            stats,
+        }
+    }
+
+    fn message_passes_filters(
+        &self,
+        msg: &mut NetworkMessageMut,
+        ctx: &dyn InterceptorContext,
+        cache: Option<&Cache>,
+    ) -> bool {
+        let encoding: Option<&Encoding>;
+        let attachment: Option<&ZBuf>;
+        let message_type: DataMessage;
+
+        match &msg.body {
+            NetworkBodyMut::Request(Request {
+                payload: RequestBody::Query(query),
+                ..
+            }) => {
+                message_type = DataMessage::Query;
+                encoding = query.ext_body.as_ref().map(|body| &body.encoding);
+                attachment = query.ext_attachment.as_ref().map(|att| &att.buffer);
+            }
+            NetworkBodyMut::Response(Response {
+                payload:
+                    ResponseBody::Reply(Reply {
+                        payload: PushBody::Put(put),
+                        ..
+                    }),
+                ..
+            }) => {
+                message_type = DataMessage::Reply;
+                encoding = Some(&put.encoding);
+                attachment = put.ext_attachment.as_ref().map(|att| &att.buffer);
+            }
+            NetworkBodyMut::Response(Response {
+                payload:
+                    ResponseBody::Reply(Reply {
+                        payload: PushBody::Del(delete),
+                        ..
+                    }),
+                ..
+            }) => {
+                message_type = DataMessage::Reply;
+                encoding = None;
+                attachment = delete.ext_attachment.as_ref().map(|att| &att.buffer);
+            }
+            NetworkBodyMut::Push(Push {
+                payload: PushBody::Put(put),
+                ..
+            }) => {
+                message_type = DataMessage::Put;
+                encoding = Some(&put.encoding);
+                attachment = put.ext_attachment.as_ref().map(|att| &att.buffer);
+            }
+            NetworkBodyMut::Push(Push {
+                payload: PushBody::Del(delete),
+                ..
+            }) => {
+                message_type = DataMessage::Delete;
+                encoding = None;
+                attachment = delete.ext_attachment.as_ref().map(|att| &att.buffer);
+            }
+            NetworkBodyMut::Response(Response {
+                payload: ResponseBody::Err(err),
+                ..
+            }) => {
+                message_type = DataMessage::Reply;
+                encoding = Some(&err.encoding);
+                attachment = None;
+            }
+            NetworkBodyMut::ResponseFinal(_) => return true,
+            NetworkBodyMut::Interest(_) => return true,
+            NetworkBodyMut::Declare(_) => return true,
+            NetworkBodyMut::OAM(_) => return true,
+        }
+        let computed;
+        let rule_ids = match cache {
+            Some(cache) => cache.message(&message_type),
+            None => {
+                computed = match ctx.full_keyexpr(msg).as_ref() {
+                    Some(ke) => self.get_rule_ids(message_type, ke),
+                    None => vec![],
+                };
+                &computed
+            }
+        };
+        if rule_ids.is_empty() {
+            return true;
+        }
+        let attachment = attachment.map(|att| att.contiguous());
+        let entries = attachment.as_deref().and_then(attachment_entries);
+        rule_ids
+            .iter()
+            .all(|id| self.inner.rules[*id].admits(encoding, entries.as_deref()))
+    }
+
+    /// Returns the ids of the rules of all subjects matching the given key expression.
+    fn get_rule_ids(&self, message: DataMessage, key_expr: &keyexpr) -> Vec<usize> {
+        self.inner
+            .filters
+            .trees(&self.subjects, &self.flow, &message)
+            .flat_map(|tree| {
+                tree.nodes_including(key_expr)
+                    .filter_map(|node| node.weight().cloned())
+                    .flatten()
+                    .collect_vec()
+            })
+            .sorted_unstable()
+            .dedup()
+            .collect()
+    }
+}
+
+struct Cache {
+    put: Vec<usize>,
+    delete: Vec<usize>,
+    query: Vec<usize>,
+    reply: Vec<usize>,
+}
+
+impl Cache {
+    fn message(&self, message: &DataMessage) -> &Vec<usize> {
+        match message {
+            DataMessage::Put => &self.put,
+            DataMessage::Delete => &self.delete,
+            DataMessage::Query => &self.query,
+            DataMessage::Reply => &self.reply,
+        }
+    }
+}
+
+impl InterceptorTrait for PayloadFilterInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(Cache {
+            put: self.get_rule_ids(DataMessage::Put, key_expr),
+            delete: self.get_rule_ids(DataMessage::Delete, key_expr),
+            query: self.get_rule_ids(DataMessage::Query, key_expr),
+            reply: self.get_rule_ids(DataMessage::Reply, key_expr),
+        }))
+    }
+
+    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool {
+        let cache = ctx.get_cache(msg).and_then(|i| i.downcast_ref::<Cache>());
+
+        let kept = self.message_passes_filters(msg, ctx, cache);
+        #[cfg(feature = "stats")]
+        if !kept {
+            self.stats
+                .observe_network_message_dropped_payload(super::stats_direction(self.flow), msg);
+        }
+        kept
+    }
+}
+
+/// Decodes an attachment serialized as a map of strings (see the zenoh serialization format),
+/// returning its entries as raw bytes. Returns None if the attachment is not such a map.
+fn attachment_entries(mut bytes: &[u8]) -> Option<Vec<(&[u8], &[u8])>> {
+    fn read_len(bytes: &mut &[u8]) -> Option<usize> {
+        // LEB128 encoded length
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = bytes.split_first()?;
+            *bytes = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return usize::try_from(value).ok();
+            }
+        }
+        None
+    }
+
+    fn read_bytes<'a>(bytes: &mut &'a [u8]) -> Option<&'a [u8]> {
+        let len = read_len(bytes)?;
+        if len > bytes.len() {
+            return None;
+        }
+        let (value, rest) = bytes.split_at(len);
+        *bytes = rest;
+        Some(value)
+    }
+
+    let len = read_len(&mut bytes)?;
+    let mut entries = Vec::with_capacity(len.min(bytes.len()));
+    for _ in 0..len {
+        let key = read_bytes(&mut bytes)?;
+        let value = read_bytes(&mut bytes)?;
+        entries.push((key, value));
+    }
+    bytes.is_empty().then_some(entries)
+}
+
+struct PayloadFilterRule {
+    encodings: Option<Vec<Encoding>>,
+    attachment: Option<Vec<(String, Option<String>)>>,
+}
+
+impl PayloadFilterRule {
+    fn new(config: &PayloadFilterConf) -> Self {
+        Self {
+            encodings: config.encodings.as_ref().map(|encodings| {
+                encodings
+                    .iter()
+                    .map(|e| crate::bytes::Encoding::from(e.as_str()).into())
+                    .collect()
+            }),
+            attachment: config.attachment.as_ref().map(|entries| {
+                entries
+                    .iter()
+                    .map(|entry| (entry.key.clone(), entry.value.clone()))
+                    .collect()
+            }),
+        }
+    }
+
+    /// Messages without payload (deletes and queries without body) are not subject to the
+    /// encodings condition.
+    fn admits(&self, encoding: Option<&Encoding>, entries: Option<&[(&[u8], &[u8])]>) -> bool {
+        if let (Some(encodings), Some(encoding)) = (&self.encodings, encoding) {
+            if !encodings.iter().any(|e| {
+                e.id == encoding.id
+                    && e.schema
+                        .as_ref()
+                        .map_or(true, |s| Some(s) == encoding.schema.as_ref())
+            }) {
+                return false;
+            }
+        }
+        if let Some(required) = &self.attachment {
+            let Some(entries) = entries else {
+                return false;
+            };
+            return required.iter().all(|(key, value)| {
+                entries.iter().any(|(k, v)| {
+                    *k == key.as_bytes()
+                        && value.as_ref().map_or(true, |value| *v == value.as_bytes())
+                })
+            });
+        }
+        true
+    }
+}
+
+/// The rules, and the ids of those applying to each key expression.
+#[derive(Default)]
+struct PayloadFilter {
+    filters: ScopedFilters<Vec<usize>>,
+    rules: Vec<PayloadFilterRule>,
+}
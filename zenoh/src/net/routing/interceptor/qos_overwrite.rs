@@ -49,7 +49,7 @@ pub(crate) fn qos_overwrite_interceptor_factories(
             }
         }
         // check for undefined flows and initialize them
-        res.push(Box::new(QosOverwriteFactory::new(q.clone())));
+        res.push(Arc::new(QosOverwriteFactory::new(q.clone())));
     }
 
     Ok(res)
@@ -182,7 +182,11 @@ impl InterceptorFactoryTrait for QosOverwriteFactory {
         None
     }
 
-    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
         None
     }
 }
@@ -0,0 +1,819 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use serde_json::json;
+use zenoh_buffers::buffer::Buffer;
+use zenoh_config::{
+    CertCommonName, DataMessage, InterceptorFlow, Interface, RateLimitAction, RateLimitItemConf,
+    Username, ZenohId,
+};
+use zenoh_core::zlock;
+use zenoh_protocol::{
+    core::ZenohIdProto,
+    network::{NetworkBodyMut, Push, Request, Response},
+    zenoh::{ext::AttachmentType, PushBody, Reply, RequestBody, ResponseBody},
+};
+use zenoh_result::ZResult;
+
+use crate::net::routing::interceptor::*;
+
+const DEFAULT_MAX_BURST_MS: u64 = 1000;
+/// Maximum number of messages held back per rule with the "delay" action.
+const MAX_DELAYED_MSGS: usize = 1024;
+
+pub(crate) fn rate_limit_interceptor_factories(
+    rate_limiter: Option<&Arc<RateLimiter>>,
+) -> Vec<InterceptorFactory> {
+    rate_limiter
+        .map(|rate_limiter| {
+            Arc::new(RateLimitInterceptorFactory {
+                state: rate_limiter.clone(),
+            }) as InterceptorFactory
+        })
+        .into_iter()
+        .collect()
+}
+
+type BudgetKey = (usize, ZenohIdProto, Flow);
+
+/// Shared state of the rate limit interceptors: the configuration items
+/// and the budgets and counters of each remote node they apply to.
+pub(crate) struct RateLimiter {
+    items: Vec<RateLimitItem>,
+    budgets: Mutex<HashMap<BudgetKey, Arc<Vec<RuleBudget>>>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: &[RateLimitItemConf]) -> ZResult<Option<Arc<Self>>> {
+        if config.is_empty() {
+            return Ok(None);
+        }
+        let mut id_set = HashSet::new();
+        let mut items = Vec::with_capacity(config.len());
+        for (idx, conf) in config.iter().enumerate() {
+            // check unicity of rule id
+            if let Some(id) = &conf.id {
+                if !id_set.insert(id.clone()) {
+                    bail!("Invalid RateLimit config: id '{id}' is repeated");
+                }
+            }
+            for rule in &conf.rules {
+                if rule.bytes_per_sec.is_none() && rule.msgs_per_sec.is_none() {
+                    bail!(
+                        "Invalid RateLimit config: rule on '{}' has neither bytes_per_sec nor msgs_per_sec",
+                        rule.key_expr
+                    );
+                }
+                if rule.bytes_per_sec == Some(0) || rule.msgs_per_sec == Some(0) {
+                    bail!(
+                        "Invalid RateLimit config: rule on '{}' has a null budget",
+                        rule.key_expr
+                    );
+                }
+            }
+            items.push(RateLimitItem::new(idx, conf.clone()));
+        }
+        Ok(Some(Arc::new(Self {
+            items,
+            budgets: Mutex::new(HashMap::new()),
+        })))
+    }
+
+    /// Returns `current` if its configuration is unchanged, a new rate limiter otherwise.
+    pub(crate) fn update(
+        current: Option<&Arc<Self>>,
+        config: &[RateLimitItemConf],
+    ) -> ZResult<Option<Arc<Self>>> {
+        match current {
+            Some(current) if current.items.iter().map(|item| &item.conf).eq(config) => {
+                Ok(Some(current.clone()))
+            }
+            _ => Self::new(config),
+        }
+    }
+
+    fn budgets(&self, item: usize, zid: ZenohIdProto, flow: Flow) -> Arc<Vec<RuleBudget>> {
+        zlock!(self.budgets)
+            .entry((item, zid, flow))
+            .or_insert_with(|| {
+                Arc::new(
+                    self.items[item]
+                        .conf
+                        .rules
+                        .iter()
+                        .map(|rule| RuleBudget::new(rule.msgs_per_sec, rule.bytes_per_sec))
+                        .collect(),
+                )
+            })
+            .clone()
+    }
+
+    /// Removes the given budgets of a remote node once no interceptor uses them anymore,
+    /// e.g. when its transport is closed.
+    fn release(&self, items: &[(usize, Arc<Vec<RuleBudget>>)], zid: ZenohIdProto, flow: Flow) {
+        let mut budgets = zlock!(self.budgets);
+        for (item, rules) in items {
+            let key = (*item, zid, flow);
+            // The map and the interceptor being dropped hold the only references
+            if budgets
+                .get(&key)
+                .is_some_and(|b| Arc::ptr_eq(b, rules) && Arc::strong_count(b) == 2)
+            {
+                budgets.remove(&key);
+            }
+        }
+    }
+
+    /// Returns the counters of every (item, remote node, flow) budget as JSON.
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        let budgets = zlock!(self.budgets);
+        budgets
+            .iter()
+            .map(|((item, zid, flow), rules)| {
+                let item = &self.items[*item];
+                json!({
+                    "id": item.id,
+                    "zid": zid.to_string(),
+                    "flow": match flow {
+                        Flow::Ingress => "ingress",
+                        Flow::Egress => "egress",
+                    },
+                    "rules": item
+                        .conf
+                        .rules
+                        .iter()
+                        .zip(rules.iter())
+                        .map(|(conf, budget)| {
+                            let mut json = budget.counters.to_json();
+                            json["key_expr"] = json!(conf.key_expr.as_str());
+                            json
+                        })
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Flow {
+    Ingress,
+    Egress,
+}
+
+struct RateLimitItem {
+    id: String,
+    conf: RateLimitItemConf,
+    flows: InterfaceEnabled,
+    max_burst: Duration,
+}
+
+impl RateLimitItem {
+    fn new(idx: usize, conf: RateLimitItemConf) -> Self {
+        Self {
+            id: conf.id.clone().unwrap_or_else(|| idx.to_string()),
+            flows: conf
+                .flows
+                .as_ref()
+                .map(|f| f.into())
+                .unwrap_or(InterfaceEnabled {
+                    ingress: true,
+                    egress: true,
+                }),
+            max_burst: Duration::from_millis(conf.max_burst_ms.unwrap_or(DEFAULT_MAX_BURST_MS)),
+            conf,
+        }
+    }
+
+    fn matches(&self, subject: &TransportSubject) -> bool {
+        fn matches<T: PartialEq>(conf: Option<&NEVec<T>>, values: &[T]) -> bool {
+            conf.map_or(true, |conf| values.iter().any(|v| conf.contains(v)))
+        }
+        matches(self.conf.interfaces.as_ref(), &subject.interfaces)
+            && matches(
+                self.conf.cert_common_names.as_ref(),
+                &subject.cert_common_names,
+            )
+            && matches(self.conf.usernames.as_ref(), &subject.usernames)
+            && matches(self.conf.link_protocols.as_ref(), &subject.link_protocols)
+            && matches(self.conf.zids.as_ref(), &subject.zids)
+    }
+
+    fn rules_for(&self, key_expr: &keyexpr) -> Vec<usize> {
+        self.conf
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.key_expr.includes(key_expr))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+/// The subject properties of a transport, as used by [`AclConfigSubjects`](zenoh_config::AclConfigSubjects).
+struct TransportSubject {
+    interfaces: Vec<Interface>,
+    cert_common_names: Vec<CertCommonName>,
+    usernames: Vec<Username>,
+    link_protocols: Vec<InterceptorLink>,
+    zids: Vec<ZenohId>,
+}
+
+impl TransportSubject {
+    fn new(transport: &TransportUnicast) -> ZResult<Self> {
+        let auth_ids = transport.get_auth_ids()?;
+        let links = transport.get_links()?;
+        let mut cert_common_names = vec![];
+        let mut link_protocols = vec![];
+        for auth_id in auth_ids.link_auth_ids() {
+            match auth_id {
+                LinkAuthId::Tls(Some(value)) | LinkAuthId::Quic(Some(value)) => {
+                    cert_common_names.push(CertCommonName(value.clone()));
+                }
+                _ => {}
+            }
+            link_protocols.push(InterceptorLinkWrapper::from(auth_id).0);
+        }
+        Ok(Self {
+            interfaces: links
+                .into_iter()
+                .flat_map(|link| link.interfaces.into_iter().map(Interface))
+                .collect(),
+            cert_common_names,
+            usernames: auth_ids
+                .username()
+                .cloned()
+                .map(Username)
+                .into_iter()
+                .collect(),
+            link_protocols,
+            zids: vec![(*auth_ids.zid()).into()],
+        })
+    }
+}
+
+pub(crate) struct RateLimitInterceptorFactory {
+    state: Arc<RateLimiter>,
+}
+
+impl InterceptorFactoryTrait for RateLimitInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        let subject = match TransportSubject::new(transport) {
+            Ok(subject) => subject,
+            Err(e) => {
+                tracing::error!("Unable to get subject of transport {:?}: {e}", transport);
+                return (None, None);
+            }
+        };
+        let Ok(zid) = transport.get_zid() else {
+            return (None, None);
+        };
+        let items = self
+            .state
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.matches(&subject))
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            return (None, None);
+        }
+
+        #[cfg(feature = "stats")]
+        let Ok(stats) = transport
+            .get_stats()
+            .map(|stats| stats.drop_stats(zenoh_stats::ReasonLabel::RateLimit))
+        else {
+            // `get_stats` returning an error means the transport is closed
+            return (None, None);
+        };
+        let interceptor = |flow: InterceptorFlow| {
+            let (state_flow, enabled): (Flow, fn(&InterfaceEnabled) -> bool) = match flow {
+                InterceptorFlow::Ingress => (Flow::Ingress, |f| f.ingress),
+                InterceptorFlow::Egress => (Flow::Egress, |f| f.egress),
+            };
+            let items = items
+                .iter()
+                .filter(|(_, item)| enabled(&item.flows))
+                .map(|(idx, _)| (*idx, self.state.budgets(*idx, zid, state_flow)))
+                .collect::<Vec<_>>();
+            (!items.is_empty()).then(|| {
+                tracing::debug!("New {flow:?} rate limiter on transport unicast {transport:?}");
+                Box::new(RateLimitInterceptor {
+                    state: self.state.clone(),
+                    items,
+                    zid,
+                    flow,
+                    #[cfg(feature = "stats")]
+                    stats: stats.clone(),
+                }) as Interceptor
+            })
+        };
+        (
+            interceptor(InterceptorFlow::Ingress),
+            interceptor(InterceptorFlow::Egress),
+        )
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+#[derive(Default)]
+struct RateLimitCounters {
+    passed_msgs: AtomicU64,
+    passed_bytes: AtomicU64,
+    delayed_msgs: AtomicU64,
+    dropped_msgs: AtomicU64,
+    dropped_bytes: AtomicU64,
+}
+
+impl RateLimitCounters {
+    fn count(&self, verdict: Verdict, size: usize) {
+        match verdict {
+            Verdict::Passed | Verdict::Delayed => {
+                self.passed_msgs.fetch_add(1, Ordering::Relaxed);
+                self.passed_bytes.fetch_add(size as u64, Ordering::Relaxed);
+                if verdict == Verdict::Delayed {
+                    self.delayed_msgs.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Verdict::Dropped => {
+                self.dropped_msgs.fetch_add(1, Ordering::Relaxed);
+                self.dropped_bytes.fetch_add(size as u64, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "passed_msgs": self.passed_msgs.load(Ordering::Relaxed),
+            "passed_bytes": self.passed_bytes.load(Ordering::Relaxed),
+            "delayed_msgs": self.delayed_msgs.load(Ordering::Relaxed),
+            "dropped_msgs": self.dropped_msgs.load(Ordering::Relaxed),
+            "dropped_bytes": self.dropped_bytes.load(Ordering::Relaxed),
+        })
+    }
+}
+
+/// A token bucket replenished at `rate` tokens per second, holding at most
+/// one second worth of tokens. Its balance becomes negative when messages are
+/// delayed, as they reserve the tokens they will be sent with, so that following
+/// messages are delayed further.
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+}
+
+impl TokenBucket {
+    fn new(rate: u64) -> Self {
+        Self {
+            rate: rate as f64,
+            tokens: rate as f64,
+        }
+    }
+
+    fn refill(&mut self, elapsed: Duration) {
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.rate);
+    }
+
+    fn wait_for(&self, amount: f64) -> Duration {
+        if self.tokens >= amount {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((amount - self.tokens) / self.rate)
+        }
+    }
+}
+
+struct Buckets {
+    msgs: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+    last_refill: Instant,
+}
+
+impl Buckets {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.last_refill = now;
+        if let Some(msgs) = &mut self.msgs {
+            msgs.refill(elapsed);
+        }
+        if let Some(bytes) = &mut self.bytes {
+            bytes.refill(elapsed);
+        }
+    }
+
+    fn wait_for(&self, size: usize) -> Duration {
+        let msgs = self
+            .msgs
+            .as_ref()
+            .map(|b| b.wait_for(1.))
+            .unwrap_or_default();
+        let bytes = self
+            .bytes
+            .as_ref()
+            .map(|b| b.wait_for(size as f64))
+            .unwrap_or_default();
+        msgs.max(bytes)
+    }
+
+    fn consume(&mut self, size: usize) {
+        if let Some(msgs) = &mut self.msgs {
+            msgs.tokens -= 1.;
+        }
+        if let Some(bytes) = &mut self.bytes {
+            bytes.tokens -= size as f64;
+        }
+    }
+}
+
+struct RuleBudget {
+    buckets: Mutex<Buckets>,
+    delayed: Arc<DelayQueue>,
+    counters: RateLimitCounters,
+}
+
+impl RuleBudget {
+    fn new(msgs_per_sec: Option<u64>, bytes_per_sec: Option<u64>) -> Self {
+        Self {
+            buckets: Mutex::new(Buckets {
+                msgs: msgs_per_sec.map(TokenBucket::new),
+                bytes: bytes_per_sec.map(TokenBucket::new),
+                last_refill: Instant::now(),
+            }),
+            delayed: Arc::default(),
+            counters: RateLimitCounters::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct DelayedMessages {
+    msgs: VecDeque<(Instant, DeferredMessage)>,
+    releasing: bool,
+}
+
+/// The messages held back by a rule with the "delay" action, sent in order
+/// by a single task once their budget is replenished.
+#[derive(Default)]
+struct DelayQueue {
+    inner: Mutex<DelayedMessages>,
+}
+
+impl DelayQueue {
+    /// Queues the message to be sent at `due`, returning `false` if the queue is full.
+    fn push(self: &Arc<Self>, due: Instant, msg: DeferredMessage) -> bool {
+        let mut inner = zlock!(self.inner);
+        if inner.msgs.len() >= MAX_DELAYED_MSGS {
+            return false;
+        }
+        inner.msgs.push_back((due, msg));
+        if !inner.releasing {
+            inner.releasing = true;
+            zenoh_runtime::ZRuntime::Net.spawn(self.clone().release());
+        }
+        true
+    }
+
+    async fn release(self: Arc<Self>) {
+        loop {
+            let due = {
+                let mut inner = zlock!(self.inner);
+                match inner.msgs.front() {
+                    Some((due, _)) => *due,
+                    None => {
+                        inner.releasing = false;
+                        return;
+                    }
+                }
+            };
+            tokio::time::sleep_until(due.into()).await;
+            // Only this task pops messages, the front one is still the one waited for
+            let msg = zlock!(self.inner).msgs.pop_front();
+            if let Some((_, msg)) = msg {
+                if !msg.send() {
+                    tracing::trace!("Delayed message could not be sent");
+                }
+            }
+        }
+    }
+}
+
+/// The locked budgets of the rules of an item matching a message, consumed once the message
+/// is sent or queued to be sent later. Dropping it leaves the budgets untouched.
+struct Reservation<'a> {
+    buckets: Vec<MutexGuard<'a, Buckets>>,
+    size: usize,
+}
+
+impl Reservation<'_> {
+    fn commit(mut self) {
+        for buckets in &mut self.buckets {
+            buckets.consume(self.size);
+        }
+    }
+}
+
+/// Outcome of a message on the budgets of an item.
+enum Admission {
+    Passed,
+    /// The message has to be held back until `due`, in the queue of the given rule.
+    Delayed {
+        due: Instant,
+        rule: usize,
+    },
+    Dropped,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Passed,
+    Delayed,
+    Dropped,
+}
+
+pub(crate) struct RateLimitInterceptor {
+    state: Arc<RateLimiter>,
+    items: Vec<(usize, Arc<Vec<RuleBudget>>)>,
+    zid: ZenohIdProto,
+    flow: InterceptorFlow,
+    #[cfg(feature = "stats")]
+    stats: zenoh_stats::DropStats,
+}
+
+// The flag is used to print a message only once
+static INFO_FLAG: AtomicBool = AtomicBool::new(false);
+
+impl RateLimitInterceptor {
+    fn rules_for(&self, key_expr: &keyexpr) -> Vec<Vec<usize>> {
+        self.items
+            .iter()
+            .map(|(item, _)| self.state.items[*item].rules_for(key_expr))
+            .collect()
+    }
+
+    /// Checks the message against the matching budgets of the given item, without consuming them.
+    ///
+    /// The budgets stay locked in the returned [`Reservation`], to be consumed only if the message
+    /// is eventually sent. With the "delay" action, a message exceeding the budget reserves the
+    /// tokens it will be sent with, as long as it is held back for at most `max_burst`. It is never
+    /// held back here, as the interceptors run on the routing path, but queued by the caller.
+    fn admit<'a>(
+        item: &RateLimitItem,
+        budgets: &'a [RuleBudget],
+        rules: &[usize],
+        size: usize,
+        now: Instant,
+        can_delay: bool,
+    ) -> (Admission, Reservation<'a>) {
+        // Locks are always taken in increasing item order, then in increasing rule order
+        let mut buckets = rules
+            .iter()
+            .map(|r| zlock!(budgets[*r].buckets))
+            .collect::<Vec<_>>();
+        let (rule, wait) = rules
+            .iter()
+            .zip(&mut buckets)
+            .map(|(rule, buckets)| {
+                buckets.refill(now);
+                (*rule, buckets.wait_for(size))
+            })
+            .max_by_key(|(_, wait)| *wait)
+            .unwrap_or_default();
+        let admission = if wait.is_zero() {
+            Admission::Passed
+        } else if can_delay && item.conf.action == RateLimitAction::Delay && wait <= item.max_burst
+        {
+            Admission::Delayed {
+                due: now + wait,
+                rule,
+            }
+        } else {
+            Admission::Dropped
+        };
+        (admission, Reservation { buckets, size })
+    }
+
+    fn log_dropped(
+        &self,
+        item: &RateLimitItem,
+        msg: &NetworkMessageMut,
+        ctx: &dyn InterceptorContext,
+    ) {
+        if !INFO_FLAG.swap(true, Ordering::Relaxed) {
+            tracing::info!("Some message(s) have been dropped by the rate limit interceptor. Enable trace level tracing for more details.");
+        }
+        tracing::trace!(
+            "Message dropped by the rate limit interceptor '{id}': {msg}({key_expr}) {flow:?}",
+            id = item.id,
+            key_expr = ctx.full_expr(msg).unwrap_or_default(),
+            flow = self.flow,
+        );
+        #[cfg(feature = "stats")]
+        self.stats
+            .observe_network_message_dropped_payload(stats_direction(self.flow), msg);
+    }
+}
+
+impl Drop for RateLimitInterceptor {
+    fn drop(&mut self) {
+        let flow = match self.flow {
+            InterceptorFlow::Ingress => Flow::Ingress,
+            InterceptorFlow::Egress => Flow::Egress,
+        };
+        self.state.release(&self.items, self.zid, flow);
+    }
+}
+
+impl InterceptorTrait for RateLimitInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(self.rules_for(key_expr)))
+    }
+
+    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool {
+        let Some((message, size)) = data_message_size(msg) else {
+            return true;
+        };
+        let cached = ctx
+            .get_cache(msg)
+            .and_then(|c| c.downcast_ref::<Vec<Vec<usize>>>());
+        let computed;
+        let rules = match cached {
+            Some(rules) => rules,
+            None => {
+                let Some(key_expr) = ctx.full_keyexpr(msg) else {
+                    return true;
+                };
+                computed = self.rules_for(&key_expr);
+                &computed
+            }
+        };
+        // Only push messages can be held back: the final response of a query would
+        // overtake a delayed query or reply
+        let can_delay = matches!(message, DataMessage::Put | DataMessage::Delete);
+        let now = Instant::now();
+        let mut checked = 0;
+        let mut dropped = None;
+        let mut delayed: Option<(Instant, &RateLimitItem, &RuleBudget)> = None;
+        let mut reservations = vec![];
+        for ((item, budgets), rules) in self.items.iter().zip(rules) {
+            let item = &self.state.items[*item];
+            checked += 1;
+            if rules.is_empty() || !item.conf.messages.contains(&message) {
+                continue;
+            }
+            let (admission, reservation) = Self::admit(item, budgets, rules, size, now, can_delay);
+            match admission {
+                Admission::Passed => {}
+                Admission::Delayed { due, rule } => {
+                    if delayed.map_or(true, |(d, _, _)| due > d) {
+                        delayed = Some((due, item, &budgets[rule]));
+                    }
+                }
+                Admission::Dropped => {
+                    dropped = Some(item);
+                    break;
+                }
+            }
+            reservations.push(reservation);
+        }
+        let verdict = match (dropped, delayed) {
+            (Some(item), _) => {
+                self.log_dropped(item, msg, &*ctx);
+                Verdict::Dropped
+            }
+            (None, None) => Verdict::Passed,
+            (None, Some((due, item, budget))) => match ctx
+                .defer(msg)
+                .map(|deferred| budget.delayed.push(due, deferred))
+            {
+                Some(true) => {
+                    tracing::trace!(
+                        "Message delayed by {delay:?} by the rate limit interceptor '{id}': {msg}",
+                        delay = due.saturating_duration_since(now),
+                        id = item.id,
+                    );
+                    Verdict::Delayed
+                }
+                _ => {
+                    self.log_dropped(item, msg, &*ctx);
+                    Verdict::Dropped
+                }
+            },
+        };
+        // Only the messages sent now or later use up the budgets
+        if verdict != Verdict::Dropped {
+            reservations.into_iter().for_each(Reservation::commit);
+        }
+        for ((item, budgets), rules) in self.items.iter().zip(rules).take(checked) {
+            if self.state.items[*item].conf.messages.contains(&message) {
+                for rule in rules {
+                    budgets[*rule].counters.count(verdict, size);
+                }
+            }
+        }
+        // A delayed message is sent later through the following interceptors
+        verdict == Verdict::Passed
+    }
+}
+
+/// Returns the type of a data message with the size of its serialized payload + serialized attachment.
+fn data_message_size(msg: &NetworkMessageMut) -> Option<(DataMessage, usize)> {
+    fn attachment_len<const ID: u8>(att: &Option<AttachmentType<ID>>) -> usize {
+        att.as_ref().map(|att| att.buffer.len()).unwrap_or(0)
+    }
+    match &msg.body {
+        NetworkBodyMut::Push(Push {
+            payload: PushBody::Put(put),
+            ..
+        }) => Some((
+            DataMessage::Put,
+            put.payload.len() + attachment_len(&put.ext_attachment),
+        )),
+        NetworkBodyMut::Push(Push {
+            payload: PushBody::Del(delete),
+            ..
+        }) => Some((DataMessage::Delete, attachment_len(&delete.ext_attachment))),
+        NetworkBodyMut::Request(Request {
+            payload: RequestBody::Query(query),
+            ..
+        }) => Some((
+            DataMessage::Query,
+            query
+                .ext_body
+                .as_ref()
+                .map(|body| body.payload.len())
+                .unwrap_or(0)
+                + attachment_len(&query.ext_attachment),
+        )),
+        NetworkBodyMut::Response(Response {
+            payload:
+                ResponseBody::Reply(Reply {
+                    payload: PushBody::Put(put),
+                    ..
+                }),
+            ..
+        }) => Some((
+            DataMessage::Reply,
+            put.payload.len() + attachment_len(&put.ext_attachment),
+        )),
+        NetworkBodyMut::Response(Response {
+            payload:
+                ResponseBody::Reply(Reply {
+                    payload: PushBody::Del(delete),
+                    ..
+                }),
+            ..
+        }) => Some((DataMessage::Reply, attachment_len(&delete.ext_attachment))),
+        NetworkBodyMut::Response(Response {
+            payload: ResponseBody::Err(zenoh_protocol::zenoh::Err { payload, .. }),
+            ..
+        }) => Some((DataMessage::Reply, payload.len())),
+        NetworkBodyMut::ResponseFinal(_)
+        | NetworkBodyMut::Interest(_)
+        | NetworkBodyMut::Declare(_)
+        | NetworkBodyMut::OAM(_) => None,
+    }
+}
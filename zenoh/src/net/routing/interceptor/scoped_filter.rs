@@ -0,0 +1,303 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+//!
+//! Scoping shared by the low-pass and payload filters: a rule applies to the transports
+//! matching its interfaces and link protocols, and to the messages matching its flows,
+//! message types and key expressions.
+
+use std::{collections::HashSet, iter, sync::Arc};
+
+use ahash::HashMap;
+use itertools::Itertools;
+use nonempty_collections::NEVec;
+use zenoh_config::{DataMessage, InterceptorFlow, InterceptorLink};
+use zenoh_keyexpr::{keyexpr, keyexpr_tree::KeBoxTree, OwnedKeyExpr};
+use zenoh_result::ZResult;
+use zenoh_transport::unicast::TransportUnicast;
+
+use super::{
+    authorization::SubjectProperty, EgressInterceptor, IngressInterceptor, InterceptorLinkWrapper,
+    InterceptorTrait, InterfaceEnabled,
+};
+
+/// The scope of a filter rule, as found in its configuration.
+pub(crate) struct RuleScope<'a> {
+    pub(crate) interfaces: Option<&'a NEVec<String>>,
+    pub(crate) link_protocols: Option<&'a NEVec<InterceptorLink>>,
+    pub(crate) flows: Option<&'a NEVec<InterceptorFlow>>,
+    pub(crate) messages: &'a NEVec<DataMessage>,
+    pub(crate) key_exprs: &'a NEVec<OwnedKeyExpr>,
+}
+
+/// Checks the unicity of the given rule ids.
+pub(crate) fn validate_ids<'a>(ids: impl IntoIterator<Item = Option<&'a String>>) -> ZResult<()> {
+    let mut id_set = HashSet::new();
+    for id in ids.into_iter().flatten() {
+        if !id_set.insert(id) {
+            bail!("id '{id}' is repeated");
+        }
+    }
+    Ok(())
+}
+
+/// Per key expression weights `W` of the rules, indexed by subject, flow and message type.
+#[derive(Default)]
+pub(crate) struct ScopedFilters<W: 'static> {
+    subjects: SubjectStore,
+    filters: HashMap<usize, FilterFlows<W>>,
+    interface_enabled: InterfaceEnabled,
+}
+
+impl<W: Default + 'static> ScopedFilters<W> {
+    /// Calls `insert` with the tree of each subject, flow and message type of `scope`,
+    /// for each of its key expressions.
+    pub(crate) fn insert(
+        &mut self,
+        scope: RuleScope,
+        mut insert: impl FnMut(&mut KeBoxTree<W>, &keyexpr),
+    ) {
+        let flows = scope
+            .flows
+            .map(|flows| flows.iter().copied().collect_vec())
+            .unwrap_or(vec![InterceptorFlow::Ingress, InterceptorFlow::Egress]);
+        let interfaces = scope
+            .interfaces
+            .map(|v| {
+                v.iter()
+                    .map(|face| SubjectProperty::Exactly(face.clone()))
+                    .collect_vec()
+            })
+            .unwrap_or(vec![SubjectProperty::Wildcard]);
+        let link_protocols = scope
+            .link_protocols
+            .map(|v| {
+                v.iter()
+                    .map(|proto| SubjectProperty::Exactly(proto.clone()))
+                    .collect_vec()
+            })
+            .unwrap_or(vec![SubjectProperty::Wildcard]);
+        let subject_ids = interfaces
+            .into_iter()
+            .cartesian_product(link_protocols)
+            .map(|(interface, link_type)| {
+                let subject = FilterSubject {
+                    interface,
+                    link_type,
+                };
+                self.subjects.get_or_insert(&subject)
+            })
+            .collect_vec();
+        for message in scope.messages {
+            for key_expr in scope.key_exprs {
+                for flow in &flows {
+                    for id in &subject_ids {
+                        insert(
+                            self.filters
+                                .entry(*id)
+                                .or_default()
+                                .flow_mut(flow)
+                                .message_mut(message),
+                            key_expr,
+                        );
+                        match flow {
+                            InterceptorFlow::Egress => self.interface_enabled.egress = true,
+                            InterceptorFlow::Ingress => self.interface_enabled.ingress = true,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<W: 'static> ScopedFilters<W> {
+    /// Returns the trees of the given subjects for a flow and message type.
+    pub(crate) fn trees<'a>(
+        &'a self,
+        subjects: &'a [usize],
+        flow: &'a InterceptorFlow,
+        message: &'a DataMessage,
+    ) -> impl Iterator<Item = &'a KeBoxTree<W>> + 'a {
+        subjects.iter().map(move |s| {
+            self.filters
+                .get(s)
+                .expect("subject should have entry in map")
+                .flow(flow)
+                .message(message)
+        })
+    }
+
+    /// Returns the interceptors built by `new` for the subjects of `transport`, if any,
+    /// in the flows some rules apply to.
+    pub(crate) fn new_transport_unicast<I: InterceptorTrait + Send + Sync + 'static>(
+        &self,
+        transport: &TransportUnicast,
+        name: &str,
+        #[cfg(feature = "stats")] reason: zenoh_stats::ReasonLabel,
+        #[cfg(feature = "stats")] new: impl Fn(
+            Arc<Vec<usize>>,
+            InterceptorFlow,
+            zenoh_stats::DropStats,
+        ) -> I,
+        #[cfg(not(feature = "stats"))] new: impl Fn(Arc<Vec<usize>>, InterceptorFlow) -> I,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        tracing::debug!("New {name} transport unicast {:?}", transport);
+        let links = match transport.get_links() {
+            Ok(links) => links,
+            Err(e) => {
+                tracing::error!("Unable to get links from transport {:?}: {e}", transport);
+                return (None, None);
+            }
+        };
+        let auth_ids = match transport.get_auth_ids() {
+            Ok(auth_ids) => auth_ids,
+            Err(e) => {
+                tracing::error!("Unable to get auth_ids for transport {:?}: {e}", transport);
+                return (None, None);
+            }
+        };
+
+        let interfaces = links
+            .into_iter()
+            .flat_map(|link| link.interfaces)
+            .map(SubjectProperty::Exactly)
+            .chain(iter::once(SubjectProperty::Wildcard));
+        let link_types = auth_ids
+            .link_auth_ids()
+            .iter()
+            .map(|auth_id| SubjectProperty::Exactly(InterceptorLinkWrapper::from(auth_id).0))
+            .chain(iter::once(SubjectProperty::Wildcard));
+
+        let subject_ids = interfaces
+            .cartesian_product(link_types)
+            .filter_map(|(interface, link_type)| {
+                let subject = FilterSubject {
+                    interface,
+                    link_type,
+                };
+                self.subjects.get_subject_id(&subject)
+            })
+            .collect_vec();
+        if subject_ids.is_empty() {
+            return (None, None);
+        }
+        let subject_ids = Arc::new(subject_ids);
+        #[cfg(feature = "stats")]
+        let Ok(stats) = transport.get_stats().map(|stats| stats.drop_stats(reason)) else {
+            // `get_stats` returning an error means the transport is closed
+            return (None, None);
+        };
+        (
+            self.interface_enabled.ingress.then(|| {
+                Box::new(new(
+                    subject_ids.clone(),
+                    InterceptorFlow::Ingress,
+                    #[cfg(feature = "stats")]
+                    stats.clone(),
+                )) as IngressInterceptor
+            }),
+            self.interface_enabled.egress.then(|| {
+                Box::new(new(
+                    subject_ids.clone(),
+                    InterceptorFlow::Egress,
+                    #[cfg(feature = "stats")]
+                    stats.clone(),
+                )) as EgressInterceptor
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct FilterSubject {
+    interface: SubjectProperty<String>,
+    link_type: SubjectProperty<InterceptorLink>,
+}
+
+#[derive(Default)]
+struct SubjectStore {
+    id: usize,
+    subjects: HashMap<FilterSubject, usize>,
+}
+
+impl SubjectStore {
+    fn get_or_insert(&mut self, subject: &FilterSubject) -> usize {
+        if let Some(id) = self.subjects.get(subject) {
+            return *id;
+        }
+        self.id += 1;
+        self.subjects.insert(subject.clone(), self.id);
+        self.id
+    }
+
+    fn get_subject_id(&self, subject: &FilterSubject) -> Option<usize> {
+        self.subjects.get(subject).copied()
+    }
+}
+
+#[derive(Default)]
+struct FilterFlows<W: 'static> {
+    ingress: FilterMessages<W>,
+    egress: FilterMessages<W>,
+}
+
+impl<W: 'static> FilterFlows<W> {
+    fn flow(&self, flow: &InterceptorFlow) -> &FilterMessages<W> {
+        match flow {
+            InterceptorFlow::Egress => &self.egress,
+            InterceptorFlow::Ingress => &self.ingress,
+        }
+    }
+
+    fn flow_mut(&mut self, flow: &InterceptorFlow) -> &mut FilterMessages<W> {
+        match flow {
+            InterceptorFlow::Egress => &mut self.egress,
+            InterceptorFlow::Ingress => &mut self.ingress,
+        }
+    }
+}
+
+#[derive(Default)]
+struct FilterMessages<W: 'static> {
+    put: KeBoxTree<W>,
+    delete: KeBoxTree<W>,
+    query: KeBoxTree<W>,
+    reply: KeBoxTree<W>,
+}
+
+impl<W: 'static> FilterMessages<W> {
+    fn message(&self, message: &DataMessage) -> &KeBoxTree<W> {
+        match message {
+            DataMessage::Put => &self.put,
+            DataMessage::Delete => &self.delete,
+            DataMessage::Query => &self.query,
+            DataMessage::Reply => &self.reply,
+        }
+    }
+
+    fn message_mut(&mut self, message: &DataMessage) -> &mut KeBoxTree<W> {
+        match message {
+            DataMessage::Put => &mut self.put,
+            DataMessage::Delete => &mut self.delete,
+            DataMessage::Query => &mut self.query,
+            DataMessage::Reply => &mut self.reply,
+        }
+    }
+}
@@ -217,6 +217,12 @@ impl AdminSpace {
         add_handler!("querier", "**", queriers_data);
         add_handler!("token", "**", tokens_data);
         add_handler!("route/successor", "**", route_successor);
+        add_handler!("rate_limit", rate_limit_data);
+        add_handler!("access_control/audit", acl_audit_data);
+        #[cfg(feature = "stats")]
+        add_handler!("stats/latency", hop_latency_data);
+        #[cfg(feature = "stats")]
+        add_handler!("stats/top", key_traffic_data);
 
         #[cfg(feature = "plugins")]
         add_handler!("plugins", "**", plugins_data);
@@ -546,6 +552,8 @@ impl Primitives for AdminSpace {
                         ),
                         #[cfg(feature = "unstable")]
                         query_ts_stack: None,
+                        #[cfg(feature = "tracing-instrument")]
+                        span: tracing::Span::none(),
                     }),
                     eid: self.queryable_id,
                     value: mem::take(&mut query.ext_body)
@@ -926,6 +934,74 @@ fn route_successor(prefix: &keyexpr, context: &AdminContext, query: Query) {
     }
 }
 
+/// Replies to the query with the JSON serialization of `json`, traced as the data of `name`.
+fn reply_json(prefix: &keyexpr, query: &Query, name: &str, json: &serde_json::Value) {
+    tracing::trace!("AdminSpace {name}: {:?}", json);
+    let payload = match serde_json::to_vec(json) {
+        Ok(bytes) => ZBytes::from(bytes),
+        Err(e) => {
+            tracing::error!("Error serializing AdminSpace reply: {:?}", e);
+            return;
+        }
+    };
+    if let Err(e) = query
+        .reply(prefix, payload)
+        .encoding(Encoding::APPLICATION_JSON)
+        .wait()
+    {
+        tracing::error!("Error sending AdminSpace reply: {:?}", e);
+    }
+}
+
+#[tracing::instrument(level = "trace", skip_all)]
+fn rate_limit_data(prefix: &keyexpr, context: &AdminContext, query: Query) {
+    let tables = &context.runtime.state.router.tables;
+    let rate_limiter = zread!(tables.tables).data.rate_limiter.clone();
+    let json = match rate_limiter {
+        Some(rate_limiter) => rate_limiter.to_json(),
+        None => json!([]),
+    };
+    reply_json(prefix, &query, "rate_limit_data", &json);
+}
+
+#[cfg(feature = "stats")]
+#[tracing::instrument(level = "trace", skip_all)]
+fn hop_latency_data(prefix: &keyexpr, context: &AdminContext, query: Query) {
+    let json = context.runtime.stats().hop_latency().to_json();
+    reply_json(prefix, &query, "hop_latency_data", &json);
+}
+
+#[cfg(feature = "stats")]
+#[tracing::instrument(level = "trace", skip_all)]
+fn key_traffic_data(prefix: &keyexpr, context: &AdminContext, query: Query) {
+    let n = match query.parameters().get("n").map(str::parse) {
+        Some(Ok(n)) => n,
+        Some(Err(e)) => {
+            if let Err(e) = query.reply_err(format!("Invalid n parameter: {e}")).wait() {
+                tracing::error!("Error sending AdminSpace reply: {:?}", e);
+            }
+            return;
+        }
+        None => {
+            let config = context.runtime.config().lock();
+            zenoh_config::unwrap_or_default!(config.stats().top().n())
+        }
+    };
+    let json = context.runtime.stats().key_traffic().to_json(n);
+    reply_json(prefix, &query, "key_traffic_data", &json);
+}
+
+#[tracing::instrument(level = "trace", skip_all)]
+fn acl_audit_data(prefix: &keyexpr, context: &AdminContext, query: Query) {
+    let tables = &context.runtime.state.router.tables;
+    let acl_audit = zread!(tables.tables).data.acl_audit.clone();
+    let json = match acl_audit {
+        Some(acl_audit) => acl_audit.to_json(),
+        None => json!([]),
+    };
+    reply_json(prefix, &query, "acl_audit_data", &json);
+}
+
 #[cfg(feature = "plugins")]
 #[tracing::instrument(level = "trace", skip_all)]
 fn plugins_data(prefix: &keyexpr, context: &AdminContext, query: Query) {
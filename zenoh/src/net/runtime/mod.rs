@@ -20,6 +20,8 @@
 mod adminspace;
 pub mod orchestrator;
 mod region;
+#[cfg(feature = "stats")]
+mod scrape;
 
 #[cfg(all(feature = "unstable", feature = "shared-memory"))]
 use std::future::IntoFuture;
@@ -89,14 +91,18 @@ use super::{
 use crate::api::loader::{load_plugins, start_plugins};
 #[cfg(feature = "plugins")]
 use crate::api::plugins::PluginsManager;
+#[cfg(all(feature = "unstable", feature = "stats"))]
+use crate::api::timestamp_stack::HopLatencyAnalytics;
 #[cfg(feature = "unstable")]
 use crate::api::timestamp_stack::{GetTimestampCallback, TimestampContext};
+#[cfg(feature = "unstable")]
+use crate::net::routing::interceptor::UserInterceptorFactory;
 #[cfg(feature = "internal")]
 use crate::session::CloseBuilder;
 use crate::{
     api::{
         builders::close::{Closeable, Closee},
-        config::{Config, Notifier},
+        config::{is_access_control_key, Config, Notifier},
         info::{Link, Transport},
     },
     net::routing::{
@@ -191,6 +197,8 @@ pub(crate) struct RuntimeState {
     namespace: Option<OwnedNonWildKeyExpr>,
     #[cfg(feature = "stats")]
     stats: zenoh_stats::StatsRegistry,
+    #[cfg(all(feature = "unstable", feature = "stats"))]
+    hop_latency: Option<HopLatencyAnalytics>,
     span: tracing::Span,
 }
 
@@ -206,10 +214,17 @@ pub trait IRuntime: Send + Sync {
     /// is the result of a custom user-callback or a Zenoh UHLC timestamp.
     #[cfg(feature = "unstable")]
     fn get_ts_stack_timestamp(&self, context: TimestampContext) -> (Vec<u8>, bool);
+    /// Returns the per-hop latency analytics of received timestamp stacks, if enabled.
+    #[cfg(all(feature = "unstable", feature = "stats"))]
+    fn hop_latency(&self) -> Option<&HopLatencyAnalytics>;
     fn get_locators(&self) -> Vec<Locator>;
     fn get_locators_noloopback(&self) -> Vec<Locator>;
     fn get_zids(&self, whatami: WhatAmI) -> Box<dyn Iterator<Item = ZenohId> + Send + Sync>;
     fn new_handler(&self, handler: Arc<dyn TransportEventHandler>);
+    /// Registers a user-defined interceptor factory, applied after the built-in interceptors
+    /// to the established transports and to the ones established afterwards.
+    #[cfg(feature = "unstable")]
+    fn add_interceptor_factory(&self, factory: UserInterceptorFactory) -> ZResult<()>;
 
     #[cfg(feature = "shared-memory")]
     #[zenoh_macros::unstable]
@@ -297,6 +312,11 @@ impl IRuntime for RuntimeState {
         (buf, false)
     }
 
+    #[cfg(all(feature = "unstable", feature = "stats"))]
+    fn hop_latency(&self) -> Option<&HopLatencyAnalytics> {
+        self.hop_latency.as_ref()
+    }
+
     fn get_locators(&self) -> Vec<Locator> {
         self.locators.read().unwrap().clone()
     }
@@ -335,6 +355,12 @@ impl IRuntime for RuntimeState {
         zwrite!(self.transport_handlers).push(handler);
     }
 
+    #[cfg(feature = "unstable")]
+    fn add_interceptor_factory(&self, factory: UserInterceptorFactory) -> ZResult<()> {
+        let config = self.config.lock().clone();
+        self.router.tables.add_interceptor_factory(&config, factory)
+    }
+
     fn get_transports(&self) -> Box<dyn Iterator<Item = Transport> + Send + Sync> {
         let unicast_transports = zenoh_runtime::ZRuntime::Net
             .block_in_place(self.manager.get_transports_unicast())
@@ -649,6 +675,8 @@ pub struct RuntimeBuilder {
     shm_clients: Option<Arc<ShmClientStorage>>,
     #[cfg(feature = "unstable")]
     timestamp_callback: Option<GetTimestampCallback>,
+    #[cfg(feature = "unstable")]
+    interceptor_factories: Vec<UserInterceptorFactory>,
     #[cfg(test)]
     subregions: Option<Vec<Region>>,
     #[cfg(test)]
@@ -687,6 +715,8 @@ impl RuntimeBuilder {
             shm_clients: None,
             #[cfg(feature = "unstable")]
             timestamp_callback: None,
+            #[cfg(feature = "unstable")]
+            interceptor_factories: vec![],
             #[cfg(test)]
             subregions: None,
             #[cfg(test)]
@@ -712,6 +742,14 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Sets the user-defined interceptor factories, applied to every transport
+    /// of the built runtime after the built-in interceptors.
+    #[cfg(feature = "unstable")]
+    pub fn interceptor_factories(mut self, factories: Vec<UserInterceptorFactory>) -> Self {
+        self.interceptor_factories = factories;
+        self
+    }
+
     #[allow(dead_code)]
     #[cfg(test)]
     pub fn subregions(mut self, subregions: Vec<Region>) -> Self {
@@ -735,6 +773,8 @@ impl RuntimeBuilder {
             shm_clients,
             #[cfg(feature = "unstable")]
             timestamp_callback,
+            #[cfg(feature = "unstable")]
+            interceptor_factories,
             #[cfg(test)]
             subregions,
             #[cfg(test)]
@@ -749,11 +789,28 @@ impl RuntimeBuilder {
 
         #[cfg(feature = "stats")]
         let stats = zenoh_stats::StatsRegistry::new(zid, whatami, &*crate::LONG_VERSION);
+        #[cfg(all(feature = "unstable", feature = "stats"))]
+        let hop_latency = HopLatencyAnalytics::new(&config, stats.hop_latency().clone())?;
+        #[cfg(feature = "stats")]
+        if unwrap_or_default!(config.stats().top().enabled()) {
+            let prefix_chunks = unwrap_or_default!(config.stats().top().prefix_chunks());
+            if prefix_chunks == 0 {
+                bail!("Invalid stats/top config: prefix_chunks must be greater than 0");
+            }
+            let max_keys = unwrap_or_default!(config.stats().top().max_keys());
+            stats.key_traffic().enable(prefix_chunks, max_keys);
+        }
+        #[cfg(feature = "stats")]
+        let metrics_scrape = scrape::MetricsScrapeConf::new(&config)?;
 
         let hlc = (*unwrap_or_default!(config.timestamping().enabled().get(whatami)))
             .then(|| Arc::new(HLCBuilder::new().with_id(uhlc::ID::from(&zid)).build()));
 
         let mut gateway_builder = GatewayBuilder::new(&config);
+        #[cfg(feature = "unstable")]
+        {
+            gateway_builder = gateway_builder.interceptor_factories(interceptor_factories);
+        }
 
         if let Some(hlc) = hlc.as_ref().cloned() {
             gateway_builder = gateway_builder.hlc(hlc.clone());
@@ -836,6 +893,8 @@ impl RuntimeBuilder {
                 namespace,
                 #[cfg(feature = "stats")]
                 stats,
+                #[cfg(all(feature = "unstable", feature = "stats"))]
+                hop_latency,
                 span,
             }),
         };
@@ -847,6 +906,13 @@ impl RuntimeBuilder {
             AdminSpace::start(&runtime).await;
         }
 
+        #[cfg(feature = "stats")]
+        if let Some(metrics_scrape) = metrics_scrape {
+            metrics_scrape.start(&runtime).await?;
+        }
+
+        runtime.spawn_access_control_watcher();
+
         // Start plugins
         #[cfg(feature = "plugins")]
         start_plugins(&runtime);
@@ -1013,6 +1079,41 @@ impl Runtime {
         self.state.get_cancellation_token()
     }
 
+    /// Registers a user-defined interceptor factory.
+    ///
+    /// The factory is applied after the built-in interceptors, both to the transports
+    /// established from now on and to the already established ones.
+    #[cfg(feature = "unstable")]
+    #[zenoh_macros::internal]
+    pub fn add_interceptor_factory(&self, factory: UserInterceptorFactory) -> ZResult<()> {
+        self.state.add_interceptor_factory(factory)
+    }
+
+    /// Reloads the access control policy whenever it is changed in the config,
+    /// swapping the interceptors of the established transports without closing them.
+    fn spawn_access_control_watcher(&self) {
+        let cfg_rx = self.state.config.subscribe();
+        let runtime = Runtime::downgrade(self);
+        self.spawn_abortable(async move {
+            while let Ok(change) = cfg_rx.recv_async().await {
+                if !is_access_control_key(&change) {
+                    continue;
+                }
+                let Some(runtime) = runtime.upgrade() else {
+                    break;
+                };
+                let config = runtime.config().lock().clone();
+                match runtime.router().tables.update_config(&config) {
+                    Ok(()) => tracing::info!("Access control policy reloaded"),
+                    Err(e) => tracing::error!(
+                        "Failed to reload access control policy, keeping the previous one: {}",
+                        e
+                    ),
+                }
+            }
+        });
+    }
+
     #[cfg(feature = "shared-memory")]
     #[zenoh_macros::unstable]
     #[allow(dead_code)]
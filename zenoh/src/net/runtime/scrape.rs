@@ -0,0 +1,445 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Embedded HTTP listener serving the metrics to Prometheus scrapers.
+//!
+//! Only `GET /metrics` is served, with one request per connection. In federation mode, the
+//! metrics of all the reachable nodes are queried on their adminspace on each scrape, and merged
+//! with the local ones, each sample being labeled with the `zid` of the node it comes from.
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::OnceCell,
+};
+use zenoh_config::{unwrap_or_default, Config};
+use zenoh_result::{bail, zerror, ZResult};
+
+use super::{adminspace::METRICS_ENCODING, DynamicRuntime, GenericRuntime, Runtime, WeakRuntime};
+use crate::api::{
+    query::{ConsolidationMode, QueryTarget},
+    sample::Locality,
+    session::Session,
+};
+
+pub(crate) const METRICS_PATH: &str = "/metrics";
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy)]
+struct ScrapeOptions {
+    per_transport: bool,
+    per_link: bool,
+    per_key: bool,
+    disconnected: bool,
+}
+
+impl ScrapeOptions {
+    /// Overrides the options with the query parameters of the scrape URL.
+    fn with_query(mut self, query: &str) -> Self {
+        for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+            let option = match key {
+                "per_transport" => &mut self.per_transport,
+                "per_link" => &mut self.per_link,
+                "per_key" => &mut self.per_key,
+                "disconnected" => &mut self.disconnected,
+                _ => continue,
+            };
+            match value {
+                "true" => *option = true,
+                "false" => *option = false,
+                _ => {}
+            }
+        }
+        self
+    }
+
+    fn encode(&self, runtime: &Runtime) -> String {
+        let mut metrics = String::new();
+        runtime
+            .stats()
+            .encode_metrics(
+                &mut metrics,
+                self.per_transport,
+                self.per_link,
+                self.disconnected,
+                self.per_key,
+            )
+            .expect("metrics should be encodable");
+        metrics
+    }
+
+    fn selector(&self) -> String {
+        format!(
+            "@/*/*/metrics?per_transport={};per_link={};per_key={};disconnected={};compression=false",
+            self.per_transport, self.per_link, self.per_key, self.disconnected
+        )
+    }
+}
+
+/// The configuration of the metrics scrape listener (`stats/scrape`).
+#[derive(Debug)]
+pub(crate) struct MetricsScrapeConf {
+    listen: SocketAddr,
+    options: ScrapeOptions,
+    federation_timeout: Option<Duration>,
+}
+
+impl MetricsScrapeConf {
+    /// Returns the configuration of the listener, or `None` if it is disabled.
+    pub(crate) fn new(config: &Config) -> ZResult<Option<Self>> {
+        let Some(listen) = config.stats().scrape().listen() else {
+            return Ok(None);
+        };
+        let listen = listen
+            .parse()
+            .map_err(|e| zerror!("Invalid stats/scrape config: listen '{listen}': {e}"))?;
+        let federation_timeout = unwrap_or_default!(config.stats().scrape().federation().enabled())
+            .then(|| {
+                Duration::from_millis(unwrap_or_default!(config
+                    .stats()
+                    .scrape()
+                    .federation()
+                    .timeout_ms()))
+            });
+        if federation_timeout.is_some_and(|t| t.is_zero()) {
+            bail!("Invalid stats/scrape config: federation timeout_ms must be greater than 0");
+        }
+        Ok(Some(Self {
+            listen,
+            options: ScrapeOptions {
+                per_transport: unwrap_or_default!(config.stats().scrape().per_transport()),
+                per_link: unwrap_or_default!(config.stats().scrape().per_link()),
+                per_key: unwrap_or_default!(config.stats().scrape().per_key()),
+                disconnected: unwrap_or_default!(config.stats().scrape().disconnected()),
+            },
+            federation_timeout,
+        }))
+    }
+
+    /// Binds the listener and spawns its task, which is aborted when the runtime is closed.
+    pub(crate) async fn start(self, runtime: &Runtime) -> ZResult<()> {
+        let listener = TcpListener::bind(self.listen).await.map_err(|e| {
+            zerror!(
+                "Unable to bind metrics scrape listener on {}: {e}",
+                self.listen
+            )
+        })?;
+        tracing::info!(
+            "Serving metrics on http://{}{METRICS_PATH}",
+            listener.local_addr().unwrap_or(self.listen)
+        );
+        let scraper = Scraper {
+            runtime: Runtime::downgrade(runtime),
+            options: self.options,
+            federation_timeout: self.federation_timeout,
+            session: OnceCell::new(),
+        };
+        runtime.spawn_abortable(async move {
+            let scraper = Arc::new(scraper);
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let Some(runtime) = scraper.runtime.upgrade() else {
+                            return;
+                        };
+                        let scraper = scraper.clone();
+                        runtime.spawn_abortable(async move {
+                            if let Err(e) = scraper.serve(stream).await {
+                                tracing::debug!("Metrics scrape request failed: {e}");
+                            }
+                        });
+                    }
+                    Err(e) => tracing::warn!("Unable to accept metrics scrape connection: {e}"),
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+struct Scraper {
+    runtime: WeakRuntime,
+    options: ScrapeOptions,
+    federation_timeout: Option<Duration>,
+    /// The session querying the metrics of the reachable nodes, opened on first federated scrape.
+    session: OnceCell<Session>,
+}
+
+impl Scraper {
+    async fn serve(&self, mut stream: TcpStream) -> ZResult<()> {
+        let request = tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream))
+            .await
+            .map_err(|_| zerror!("request timeout"))??;
+        let mut lines = request.lines();
+        let mut request_line = lines.next().unwrap_or_default().split(' ');
+        let (method, target) = (request_line.next(), request_line.next().unwrap_or_default());
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let gzip = lines.any(|line| {
+            line.split_once(':').is_some_and(|(name, value)| {
+                name.eq_ignore_ascii_case("accept-encoding") && value.contains("gzip")
+            })
+        });
+        if path != METRICS_PATH {
+            return write_response(&mut stream, "404 Not Found", None, false).await;
+        }
+        if method != Some("GET") {
+            return write_response(&mut stream, "405 Method Not Allowed", None, false).await;
+        }
+        let Some(runtime) = self.runtime.upgrade() else {
+            return write_response(&mut stream, "503 Service Unavailable", None, false).await;
+        };
+        let options = self.options.with_query(query);
+        let metrics = match self.federation_timeout {
+            Some(timeout) => self.federate(&runtime, options, timeout).await,
+            None => options.encode(&runtime),
+        };
+        write_response(&mut stream, "200 OK", Some(metrics), gzip).await
+    }
+
+    /// Queries the metrics of the reachable nodes, and merges them with the local ones.
+    async fn federate(
+        &self,
+        runtime: &Runtime,
+        options: ScrapeOptions,
+        timeout: Duration,
+    ) -> String {
+        let zid = runtime.zid().to_string();
+        let mut nodes = vec![(zid.clone(), options.encode(runtime))];
+        let session = self
+            .session
+            .get_or_init(|| async {
+                Session::init(
+                    GenericRuntime::from(DynamicRuntime::from(runtime.clone())),
+                    vec![],
+                    vec![],
+                )
+                .await
+            })
+            .await;
+        let replies = match session
+            .get(options.selector())
+            .target(QueryTarget::All)
+            .consolidation(ConsolidationMode::None)
+            .allowed_destination(Locality::Remote)
+            .timeout(timeout)
+            .await
+        {
+            Ok(replies) => replies,
+            Err(e) => {
+                tracing::warn!("Unable to query the metrics of reachable nodes: {e}");
+                return federate(&nodes);
+            }
+        };
+        while let Ok(reply) = replies.recv_async().await {
+            let Ok(sample) = reply.into_result() else {
+                continue;
+            };
+            // The key expression is `@/<zid>/<whatami>/metrics`
+            let Some(node) = sample.key_expr().as_str().split('/').nth(1) else {
+                continue;
+            };
+            if node == zid || nodes.iter().any(|(n, _)| n == node) {
+                continue;
+            }
+            let payload = sample.payload().to_bytes();
+            let metrics = if (sample.encoding().to_string()).contains("content-encoding=gzip") {
+                let mut metrics = String::new();
+                if let Err(e) = flate2::read::GzDecoder::new(&*payload).read_to_string(&mut metrics)
+                {
+                    tracing::warn!("Invalid metrics received from {node}: {e}");
+                    continue;
+                }
+                metrics
+            } else {
+                String::from_utf8_lossy(&payload).into_owned()
+            };
+            nodes.push((node.to_string(), metrics));
+        }
+        federate(&nodes)
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> ZResult<String> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        if request.len() > MAX_REQUEST_SIZE {
+            bail!("request too large");
+        }
+        let n = stream.read(&mut buffer).await?;
+        if n == 0 {
+            bail!("connection closed");
+        }
+        request.extend_from_slice(&buffer[..n]);
+    }
+    Ok(String::from_utf8_lossy(&request).into_owned())
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    body: Option<String>,
+    gzip: bool,
+) -> ZResult<()> {
+    let mut headers = format!("HTTP/1.1 {status}\r\nConnection: close\r\n");
+    let body = match body {
+        Some(body) if gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+            encoder.write_all(body.as_bytes())?;
+            headers.push_str("Content-Encoding: gzip\r\n");
+            headers.push_str(&format!("Content-Type: {METRICS_ENCODING}\r\n"));
+            encoder.finish()?
+        }
+        Some(body) => {
+            headers.push_str(&format!("Content-Type: {METRICS_ENCODING}\r\n"));
+            body.into_bytes()
+        }
+        None => Vec::new(),
+    };
+    headers.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+    stream.write_all(headers.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Merges the OpenMetrics exposition of several nodes, given with their zid.
+///
+/// Families are output once, with the descriptors of the first node exposing them, followed by
+/// the samples of all nodes labeled with their `zid`.
+fn federate(nodes: &[(String, String)]) -> String {
+    let mut families: Vec<(Vec<&str>, Vec<String>)> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (zid, metrics) in nodes {
+        // The families whose descriptors are taken from this node
+        let mut described = HashSet::new();
+        let mut family = None;
+        for line in metrics.lines().map(str::trim_end) {
+            if line.is_empty() || line == "# EOF" {
+                continue;
+            }
+            if let Some(descriptor) = line.strip_prefix("# ") {
+                let Some(name) = descriptor.split(' ').nth(1) else {
+                    continue;
+                };
+                let i = match index.get(name) {
+                    Some(i) => *i,
+                    None => {
+                        families.push(Default::default());
+                        index.insert(name, families.len() - 1);
+                        described.insert(families.len() - 1);
+                        families.len() - 1
+                    }
+                };
+                if described.contains(&i) {
+                    families[i].0.push(line);
+                }
+                family = Some(i);
+            } else if let Some(i) = family {
+                families[i].1.push(with_zid_label(line, zid));
+            }
+        }
+    }
+    let mut federated = String::new();
+    for (descriptors, samples) in &families {
+        for line in descriptors
+            .iter()
+            .copied()
+            .chain(samples.iter().map(String::as_str))
+        {
+            federated.push_str(line);
+            federated.push('\n');
+        }
+    }
+    federated.push_str("# EOF\n");
+    federated
+}
+
+/// Adds a `zid` label to an OpenMetrics sample line.
+fn with_zid_label(sample: &str, zid: &str) -> String {
+    let name_end = sample.find([' ', '{']).unwrap_or(sample.len());
+    let (name, rest) = sample.split_at(name_end);
+    match rest.strip_prefix('{') {
+        Some(labels) if labels.starts_with('}') => format!("{name}{{zid=\"{zid}\"{labels}"),
+        Some(labels) => format!("{name}{{zid=\"{zid}\",{labels}"),
+        None => format!("{name}{{zid=\"{zid}\"}}{rest}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn federate_metrics() {
+        let node1 = concat!(
+            "# HELP zenoh_build Zenoh build version.\n",
+            "# TYPE zenoh_build info\n",
+            "zenoh_build_info{version=\"1.0\"} 1\n",
+            "# TYPE zenoh_transports_opened gauge\n",
+            "zenoh_transports_opened 2\n",
+            "# EOF\n",
+        );
+        let node2 = concat!(
+            "# HELP zenoh_build Zenoh build version.\n",
+            "# TYPE zenoh_build info\n",
+            "zenoh_build_info{version=\"1.1\"} 1\n",
+            "# TYPE zenoh_rx_bytes counter\n",
+            "zenoh_rx_bytes_total{} 42\n",
+            "# EOF\n ",
+        );
+        let federated = federate(&[
+            ("a1".to_string(), node1.to_string()),
+            ("b2".to_string(), node2.to_string()),
+        ]);
+        assert_eq!(
+            federated,
+            concat!(
+                "# HELP zenoh_build Zenoh build version.\n",
+                "# TYPE zenoh_build info\n",
+                "zenoh_build_info{zid=\"a1\",version=\"1.0\"} 1\n",
+                "zenoh_build_info{zid=\"b2\",version=\"1.1\"} 1\n",
+                "# TYPE zenoh_transports_opened gauge\n",
+                "zenoh_transports_opened{zid=\"a1\"} 2\n",
+                "# TYPE zenoh_rx_bytes counter\n",
+                "zenoh_rx_bytes_total{zid=\"b2\"} 42\n",
+                "# EOF\n",
+            )
+        );
+    }
+
+    #[test]
+    fn scrape_options_query() {
+        let options = ScrapeOptions {
+            per_transport: true,
+            per_link: true,
+            per_key: true,
+            disconnected: false,
+        }
+        .with_query("per_key=false&disconnected=true&per_link=maybe&other=false");
+        assert!(options.per_transport);
+        assert!(options.per_link);
+        assert!(!options.per_key);
+        assert!(options.disconnected);
+    }
+}
@@ -505,6 +505,7 @@ impl MockFace {
                 ext_tstamp: None,
                 ext_nodeid: NodeIdType::DEFAULT,
                 ext_ts_stack: None,
+                ext_trace_context: None,
                 payload: PushBody::Put(Put {
                     payload: payload.into(),
                     ..Default::default()
@@ -526,6 +527,7 @@ impl MockFace {
             ext_budget: None,
             ext_timeout: None,
             ext_ts_stack: None,
+            ext_trace_context: None,
             payload: RequestBody::Query(Query::default()),
         });
     }
@@ -540,6 +542,7 @@ impl MockFace {
                 ext_tstamp: None,
                 ext_nodeid: NodeIdType::DEFAULT,
                 ext_ts_stack: None,
+                ext_trace_context: None,
                 payload: PushBody::Del(Del::default()),
             },
             Reliability::BestEffort,
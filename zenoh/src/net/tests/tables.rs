@@ -684,6 +684,7 @@ fn test_response_wireexpr() {
         ext_budget: None,
         ext_timeout: None,
         ext_ts_stack: None,
+        ext_trace_context: None,
     });
 
     route_send_response(
@@ -705,6 +706,7 @@ fn test_response_wireexpr() {
             ext_tstamp: None,
             ext_respid: None,
             ext_ts_stack: None,
+            ext_trace_context: None,
         },
     );
     assert_eq!(
@@ -752,6 +754,7 @@ fn test_response_wireexpr() {
             ext_tstamp: None,
             ext_respid: None,
             ext_ts_stack: None,
+            ext_trace_context: None,
         },
     );
     assert_eq!(
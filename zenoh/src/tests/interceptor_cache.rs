@@ -13,7 +13,7 @@
 //
 #![cfg(feature = "internal")]
 
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
 use zenoh_buffers::ZBuf;
 use zenoh_keyexpr::keyexpr;
@@ -21,9 +21,9 @@ use zenoh_protocol::{
     network::{NetworkBodyMut, NetworkMessageMut, Push},
     zenoh::PushBody,
 };
-use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
 
-use crate::net::routing::interceptor::*;
+use crate::net::{routing::interceptor::*, runtime::DynamicRuntime};
 
 #[derive(Clone)]
 struct TestInterceptorConf {
@@ -34,7 +34,7 @@ struct TestInterceptorConf {
 fn test_interceptor_factories(config: &Vec<TestInterceptorConf>) -> Vec<InterceptorFactory> {
     let mut res: Vec<InterceptorFactory> = vec![];
     for c in config {
-        res.push(Box::new(TestInterceptorFactory::new(c.clone())));
+        res.push(Arc::new(TestInterceptorFactory::new(c.clone())));
     }
     res
 }
@@ -71,7 +71,11 @@ impl InterceptorFactoryTrait for TestInterceptorFactory {
         None
     }
 
-    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
         None
     }
 }
@@ -88,13 +92,8 @@ impl TestInterceptor {
     }
 }
 
-impl InterceptorTrait for TestInterceptor {
-    fn compute_keyexpr_cache(&self, _key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
-        Some(Box::new(self.data.clone()))
-    }
-
-    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool {
-        let cache_hit = ctx.get_cache(msg).is_some();
+impl TestInterceptor {
+    fn set_payload(&self, msg: &mut NetworkMessageMut, cache_hit: bool) {
         if let NetworkBodyMut::Push(&mut Push {
             payload: PushBody::Put(ref mut p),
             ..
@@ -103,10 +102,55 @@ impl InterceptorTrait for TestInterceptor {
             let out = format!("Cache hit: {cache_hit}, data: {}", self.data);
             p.payload = ZBuf::from(out.as_bytes().to_owned());
         }
+    }
+}
+
+impl InterceptorTrait for TestInterceptor {
+    fn compute_keyexpr_cache(&self, _key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(self.data.clone()))
+    }
+
+    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &mut dyn InterceptorContext) -> bool {
+        let cache_hit = ctx.get_cache(msg).is_some();
+        self.set_payload(msg, cache_hit);
+        true
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl crate::interceptor::Interceptor for TestInterceptor {
+    fn compute_keyexpr_cache(&self, _key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(self.data.clone()))
+    }
+
+    fn intercept(
+        &self,
+        msg: &mut NetworkMessageMut,
+        ctx: &crate::interceptor::InterceptorContext,
+    ) -> bool {
+        let cache_hit = ctx.cache(msg).is_some();
+        self.set_payload(msg, cache_hit);
         true
     }
 }
 
+#[cfg(feature = "unstable")]
+impl crate::interceptor::InterceptorFactory for TestInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        _transport: &crate::interceptor::InterceptedTransport,
+    ) -> (
+        Option<crate::interceptor::IngressInterceptor>,
+        Option<crate::interceptor::EgressInterceptor>,
+    ) {
+        let interceptor = Box::new(TestInterceptor::new(&self.conf.data));
+        match self.conf.flow {
+            InterceptorFlow::Egress => (None, Some(interceptor)),
+            InterceptorFlow::Ingress => (Some(interceptor), None),
+        }
+    }
+}
+
 use std::{any::Any, time::Duration};
 
 use zenoh_config::{Config, InterceptorFlow, ZenohId};
@@ -422,3 +466,60 @@ async fn test_interceptors_cache_update_egress_then_ingress() {
         "Cache hit: true, data: 2"
     );
 }
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_interceptors_user_factory() {
+    let router_id = ZenohId::from_str("a2").unwrap();
+
+    init_log_from_env_or("error");
+    let (_config_router, router, config_client1, config_client2) =
+        open_router_and_client_configs(router_id).await;
+    tokio::time::sleep(SLEEP).await;
+    let session1 = ztimeout!(open(config_client1)).unwrap();
+    let session2 = ztimeout!(open(config_client2)).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    let sub = ztimeout!(session2.declare_subscriber("test/user")).unwrap();
+    let pub1 = ztimeout!(session1.declare_publisher("test/user")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    ztimeout!(pub1.put("some_data_1")).unwrap();
+
+    let msg1 = ztimeout!(sub.recv_async()).unwrap();
+    assert_eq!(
+        msg1.payload().try_to_string().unwrap().as_ref(),
+        "some_data_1"
+    );
+
+    // Registering a factory on a running router, the way a plugin does through its
+    // `DynamicRuntime`, applies to the already established transports
+    let runtime = DynamicRuntime::from(router.static_runtime().unwrap().clone());
+    runtime
+        .add_interceptor_factory(std::sync::Arc::new(TestInterceptorFactory::new(
+            TestInterceptorConf {
+                flow: InterceptorFlow::Ingress,
+                data: "user".to_string(),
+            },
+        )))
+        .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    ztimeout!(pub1.put("some_data_2")).unwrap();
+    let msg2 = ztimeout!(sub.recv_async()).unwrap();
+    assert_eq!(
+        msg2.payload().try_to_string().unwrap().as_ref(),
+        "Cache hit: true, data: user"
+    );
+
+    // Transports established afterwards get the user interceptors too
+    let session3 = ztimeout!(open(get_basic_client_config(
+        get_tcp_locator(&router).await
+    )))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+    ztimeout!(session3.put("test/user", "some_data_3")).unwrap();
+    let msg3 = ztimeout!(sub.recv_async()).unwrap();
+    assert_eq!(
+        msg3.payload().try_to_string().unwrap().as_ref(),
+        "Cache hit: false, data: user"
+    );
+}
@@ -17,6 +17,7 @@ use std::{
     any::Any,
     collections::HashMap,
     str::{self, FromStr},
+    sync::Arc,
 };
 
 use zenoh_buffers::ZBuf;
@@ -27,7 +28,7 @@ use zenoh_protocol::{
     network::{NetworkBodyMut, NetworkMessageMut, Push},
     zenoh::PushBody,
 };
-use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
+use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
 
 use crate::{
     net::{protocol::linkstate::LinkInfo, routing::interceptor::*},
@@ -44,7 +45,7 @@ fn link_trace_interceptor_factories(
 ) -> Vec<InterceptorFactory> {
     let mut res: Vec<InterceptorFactory> = vec![];
     for c in config {
-        res.push(Box::new(LinkTraceInterceptorFactory::new(c.clone())));
+        res.push(Arc::new(LinkTraceInterceptorFactory::new(c.clone())));
     }
     res
 }
@@ -87,7 +88,11 @@ impl InterceptorFactoryTrait for LinkTraceInterceptorFactory {
         None
     }
 
-    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+    fn new_peer_multicast(
+        &self,
+        _transport: &TransportMulticast,
+        _peer: &TransportPeer,
+    ) -> Option<IngressInterceptor> {
         None
     }
 }
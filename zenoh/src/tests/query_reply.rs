@@ -81,6 +81,8 @@ async fn test_reply_preserves_optimized_ke() {
         runtime: None,
         #[cfg(feature = "unstable")]
         query_ts_stack: None,
+        #[cfg(feature = "tracing-instrument")]
+        span: tracing::Span::none(),
     };
     let query = Query {
         inner: Arc::new(query_inner),
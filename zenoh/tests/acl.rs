@@ -20,6 +20,7 @@ use std::{
     time::Duration,
 };
 
+use sha3::Digest;
 use tokio::runtime::Handle;
 use zenoh::{config::WhatAmI, sample::SampleKind};
 use zenoh_config::Config;
@@ -47,6 +48,19 @@ async fn test_acl_pub_sub() {
     test_pub_sub_allow_then_deny().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_hot_reload() {
+    zenoh::init_log_from_env_or("error");
+    test_pub_sub_hot_reload().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_acl_audit() {
+    zenoh::init_log_from_env_or("error");
+    test_audit_file_and_adminspace().await;
+    test_audit_rotation().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn test_acl_get_queryable() {
     zenoh::init_log_from_env_or("error");
@@ -672,6 +686,378 @@ async fn test_pub_sub_deny_then_allow() {
     test_context.close().await;
 }
 
+async fn test_pub_sub_hot_reload() {
+    println!("test_pub_sub_hot_reload");
+    let mut test_context = TestSessions::new();
+
+    let rule = |permission: &str| {
+        format!(
+            r#"{{
+                "id": "r1",
+                "permission": "{permission}",
+                "flows": ["egress", "ingress"],
+                "messages": [
+                    "put",
+                    "declare_subscriber"
+                ],
+                "key_exprs": [
+                    "test/demo"
+                ],
+            }}"#
+        )
+    };
+
+    let mut config_router = get_basic_router_config().await;
+    config_router
+        .insert_json5(
+            "access_control",
+            &format!(
+                r#"{{
+                    "enabled": true,
+                    "default_permission": "deny",
+                    "rules": [{}],
+                    "subjects": [
+                        {{
+                            "id": "s1",
+                            "interfaces": [
+                                "lo", "lo0"
+                            ],
+                        }}
+                    ],
+                    "policies": [
+                        {{
+                            "rules": ["r1"],
+                            "subjects": ["s1"],
+                        }}
+                    ]
+                }}"#,
+                rule("allow")
+            ),
+        )
+        .unwrap();
+    println!("Opening router session");
+
+    let router_session = test_context.open_listener_with_cfg(config_router).await;
+    let (sub_session, pub_session) = get_client_sessions(&mut test_context).await;
+    {
+        let publisher = ztimeout!(pub_session.declare_publisher(KEY_EXPR)).unwrap();
+        let received_value = Arc::new(Mutex::new(String::new()));
+
+        let temp_recv_value = received_value.clone();
+        let subscriber = sub_session
+            .declare_subscriber(KEY_EXPR)
+            .callback(move |sample| {
+                let mut temp_value = zlock!(temp_recv_value);
+                *temp_value = sample.payload().try_to_string().unwrap().into_owned();
+            })
+            .await
+            .unwrap();
+
+        tokio::time::sleep(SLEEP).await;
+        publisher.put(VALUE).await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_value), VALUE);
+
+        // Revoke the rule without restarting the router
+        router_session
+            .config()
+            .insert_json5("access_control/rules", &format!("[{}]", rule("deny")))
+            .unwrap();
+        tokio::time::sleep(SLEEP).await;
+        *zlock!(received_value) = String::new();
+        publisher.put(VALUE).await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_ne!(*zlock!(received_value), VALUE);
+
+        // Grant it again over the same sessions
+        router_session
+            .config()
+            .insert_json5("access_control/rules", &format!("[{}]", rule("allow")))
+            .unwrap();
+        tokio::time::sleep(SLEEP).await;
+        publisher.put(VALUE).await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_value), VALUE);
+
+        // An invalid policy is rejected, leaving both the config and the enforced policy unchanged
+        let policies = router_session
+            .config()
+            .get("access_control/policies")
+            .unwrap();
+        assert!(router_session
+            .config()
+            .insert_json5(
+                "access_control/policies",
+                r#"[{ "rules": ["unknown"], "subjects": ["s1"] }]"#,
+            )
+            .is_err());
+        assert_eq!(
+            router_session
+                .config()
+                .get("access_control/policies")
+                .unwrap(),
+            policies
+        );
+        tokio::time::sleep(SLEEP).await;
+        *zlock!(received_value) = String::new();
+        publisher.put(VALUE).await.unwrap();
+        tokio::time::sleep(SLEEP).await;
+        assert_eq!(*zlock!(received_value), VALUE);
+
+        ztimeout!(subscriber.undeclare()).unwrap();
+    }
+    test_context.close().await;
+}
+
+async fn test_audit_file_and_adminspace() {
+    println!("test_audit_file_and_adminspace");
+    let mut test_context = TestSessions::new();
+    let audit_file =
+        std::env::temp_dir().join(format!("zenoh_acl_audit_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&audit_file);
+
+    let mut config_router = get_basic_router_config().await;
+    config_router
+        .insert_json5(
+            "access_control",
+            &format!(
+                r#"{{
+                    "enabled": true,
+                    "default_permission": "deny",
+                    "rules": [
+                        {{
+                            "id": "r1",
+                            "permission": "allow",
+                            "flows": ["egress", "ingress"],
+                            "messages": [
+                                "put",
+                                "declare_subscriber"
+                            ],
+                            "key_exprs": [
+                                "test/demo"
+                            ],
+                        }},
+                    ],
+                    "subjects": [
+                        {{
+                            "id": "s1",
+                            "interfaces": [
+                                "lo", "lo0"
+                            ],
+                        }}
+                    ],
+                    "policies": [
+                        {{
+                            "id": "p1",
+                            "rules": ["r1"],
+                            "subjects": ["s1"],
+                        }}
+                    ],
+                    "audit": {{
+                        "enabled": true,
+                        "log_allowed": true,
+                        "file": {:?},
+                    }},
+                }}"#,
+                audit_file.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+    config_router.adminspace.set_enabled(true).unwrap();
+    println!("Opening router session");
+
+    let router_session = test_context.open_listener_with_cfg(config_router).await;
+    let (sub_session, pub_session) = get_client_sessions(&mut test_context).await;
+    {
+        let subscriber = ztimeout!(sub_session.declare_subscriber(KEY_EXPR)).unwrap();
+        let denied_subscriber = ztimeout!(sub_session.declare_subscriber(KEY_EXPR2)).unwrap();
+        tokio::time::sleep(SLEEP).await;
+        ztimeout!(pub_session.put(KEY_EXPR, VALUE)).unwrap();
+        tokio::time::sleep(SLEEP).await;
+
+        let content = std::fs::read_to_string(&audit_file).unwrap();
+        let records = content
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        // Records are chained
+        let mut prev_hash =
+            "0000000000000000000000000000000000000000000000000000000000000000".to_string();
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record["seq"], i as u64 + 1);
+            assert_eq!(record["prev_hash"], prev_hash.as_str());
+            let mut unhashed = record.clone();
+            let hash = unhashed.as_object_mut().unwrap().remove("hash").unwrap();
+            let expected = sha3::Sha3_256::digest(unhashed.to_string().as_bytes())
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>();
+            assert_eq!(hash, expected.as_str());
+            prev_hash = expected;
+        }
+
+        let denied = records
+            .iter()
+            .find(|r| r["permission"] == "deny")
+            .expect("deny decision should be recorded");
+        assert_eq!(denied["key_expr"], KEY_EXPR2);
+        assert_eq!(denied["message"], "declare_subscriber");
+        assert_eq!(denied["flow"], "ingress");
+        assert_eq!(denied["zid"], sub_session.zid().to_string());
+        assert!(denied["rule"].is_null());
+        assert!(denied["policy"].is_null());
+
+        let allowed = records
+            .iter()
+            .find(|r| r["permission"] == "allow" && r["message"] == "put" && r["flow"] == "ingress")
+            .expect("allow decision should be recorded");
+        assert_eq!(allowed["key_expr"], KEY_EXPR);
+        assert_eq!(allowed["zid"], pub_session.zid().to_string());
+        assert_eq!(allowed["rule"], "r1");
+        assert_eq!(allowed["policy"], "p1");
+        assert_eq!(allowed["link_protocols"], serde_json::json!(["tcp"]));
+        assert!(allowed["interfaces"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|i| i == "lo" || i == "lo0"));
+
+        // The same records are served on the adminspace
+        let reply = ztimeout!(router_session.get(format!(
+            "@/{}/router/access_control/audit",
+            router_session.zid()
+        )))
+        .unwrap()
+        .recv_async()
+        .await
+        .unwrap();
+        let adminspace_records: serde_json::Value =
+            serde_json::from_str(&reply.result().unwrap().payload().try_to_string().unwrap())
+                .unwrap();
+        assert_eq!(
+            adminspace_records.as_array().unwrap()[..records.len()],
+            records[..]
+        );
+
+        // The audit file can only be set at startup
+        assert!(router_session
+            .config()
+            .insert_json5("access_control/audit/file", r#""/tmp/other.jsonl""#)
+            .is_err());
+
+        ztimeout!(subscriber.undeclare()).unwrap();
+        ztimeout!(denied_subscriber.undeclare()).unwrap();
+    }
+    test_context.close().await;
+    let _ = std::fs::remove_file(&audit_file);
+}
+
+async fn test_audit_rotation() {
+    println!("test_audit_rotation");
+    let mut test_context = TestSessions::new();
+    let audit_file = std::env::temp_dir().join(format!(
+        "zenoh_acl_audit_rotation_{}.jsonl",
+        std::process::id()
+    ));
+    let rotated = |i: usize| format!("{}.{i}", audit_file.to_str().unwrap());
+    let remove_files = || {
+        let _ = std::fs::remove_file(&audit_file);
+        for i in 1..=3 {
+            let _ = std::fs::remove_file(rotated(i));
+        }
+    };
+    remove_files();
+
+    let mut config_router = get_basic_router_config().await;
+    config_router
+        .insert_json5(
+            "access_control",
+            &format!(
+                r#"{{
+                    "enabled": true,
+                    "default_permission": "deny",
+                    "rules": [
+                        {{
+                            "id": "r1",
+                            "permission": "allow",
+                            "flows": ["egress", "ingress"],
+                            "messages": [
+                                "put",
+                                "declare_subscriber"
+                            ],
+                            "key_exprs": [
+                                "test/demo"
+                            ],
+                        }},
+                    ],
+                    "subjects": [
+                        {{
+                            "id": "s1",
+                            "interfaces": [
+                                "lo", "lo0"
+                            ],
+                        }}
+                    ],
+                    "policies": [
+                        {{
+                            "rules": ["r1"],
+                            "subjects": ["s1"],
+                        }}
+                    ],
+                    "audit": {{
+                        "enabled": true,
+                        "log_allowed": true,
+                        "file": {:?},
+                        "max_file_size": 1,
+                        "max_files": 2,
+                    }},
+                }}"#,
+                audit_file.to_str().unwrap()
+            ),
+        )
+        .unwrap();
+    println!("Opening router session");
+
+    let _router_session = test_context.open_listener_with_cfg(config_router).await;
+    let (sub_session, pub_session) = get_client_sessions(&mut test_context).await;
+    {
+        let subscriber = ztimeout!(sub_session.declare_subscriber(KEY_EXPR)).unwrap();
+        tokio::time::sleep(SLEEP).await;
+        for _ in 0..3 {
+            ztimeout!(pub_session.put(KEY_EXPR, VALUE)).unwrap();
+        }
+        tokio::time::sleep(SLEEP).await;
+
+        // Each record exceeds the maximum size, so that each file holds a single record
+        let read = |path: &str| {
+            let content = std::fs::read_to_string(path).unwrap();
+            let records = content
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(records.len(), 1);
+            records.into_iter().next().unwrap()
+        };
+        let current = read(audit_file.to_str().unwrap());
+        let previous = read(&rotated(1));
+        let oldest = read(&rotated(2));
+        assert!(!std::path::Path::new(&rotated(3)).exists());
+
+        // The chain is continued across rotations
+        assert_eq!(current["prev_hash"], previous["hash"]);
+        assert_eq!(previous["prev_hash"], oldest["hash"]);
+        assert_eq!(
+            current["seq"].as_u64(),
+            previous["seq"].as_u64().map(|s| s + 1)
+        );
+
+        ztimeout!(subscriber.undeclare()).unwrap();
+    }
+    test_context.close().await;
+    remove_files();
+}
+
 async fn test_get_qbl_deny() {
     println!("test_get_qbl_deny");
     let mut test_context = TestSessions::new();
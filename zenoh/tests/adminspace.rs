@@ -970,3 +970,95 @@ async fn test_adminspace_regression_1() {
     ztimeout!(router2.close()).unwrap();
     ztimeout!(router1.close()).unwrap();
 }
+
+#[cfg(feature = "stats")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_adminspace_stats_top() {
+    const TIMEOUT: Duration = Duration::from_secs(60);
+
+    zenoh_util::init_log_from_env_or("error");
+
+    let router = {
+        let mut c = zenoh_config::Config::default();
+        c.set_mode(Some(WhatAmI::Router)).unwrap();
+        c.listen
+            .endpoints
+            .set(vec!["tcp/127.0.0.1:0".parse::<EndPoint>().unwrap()])
+            .unwrap();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        c.adminspace.set_enabled(true).unwrap();
+        c.adminspace.permissions.set_read(true).unwrap();
+        c.insert_json5("stats/top", r#"{ enabled: true, prefix_chunks: 3 }"#)
+            .unwrap();
+        ztimeout!(zenoh::open(c)).unwrap()
+    };
+    let zid = router.zid();
+    let router_endpoint = get_locators_from_session(&router)
+        .await
+        .into_iter()
+        .find(|ep| ep.to_string().starts_with("tcp/"))
+        .unwrap();
+
+    let open_client = || {
+        let mut c = zenoh_config::Config::default();
+        c.set_mode(Some(WhatAmI::Client)).unwrap();
+        c.connect
+            .endpoints
+            .set(vec![router_endpoint.clone().into()])
+            .unwrap();
+        c.scouting.multicast.set_enabled(Some(false)).unwrap();
+        zenoh::open(c)
+    };
+    let client1 = ztimeout!(open_client()).unwrap();
+    let client2 = ztimeout!(open_client()).unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    for i in 0..10 {
+        ztimeout!(client1.put(format!("test/top/flood/{i}"), vec![0u8; 1000])).unwrap();
+    }
+    ztimeout!(client2.put("test/top/quiet", "data")).unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let get_top = |parameters: &'static str| {
+        let router = router.clone();
+        async move {
+            let reply = router
+                .get(format!("@/{zid}/router/stats/top{parameters}"))
+                .await
+                .unwrap()
+                .recv_async()
+                .await
+                .unwrap();
+            let sample = reply.result().unwrap();
+            assert_eq!(sample.encoding(), &zenoh::bytes::Encoding::APPLICATION_JSON);
+            serde_json::from_slice::<serde_json::Value>(&sample.payload().to_bytes()).unwrap()
+        }
+    };
+
+    let json = ztimeout!(get_top("?n=1"));
+    let publishers = json["publishers"].as_array().unwrap();
+    assert_eq!(publishers.len(), 1);
+    assert_eq!(publishers[0]["zid"], client1.zid().to_string());
+    assert_eq!(publishers[0]["rx_msgs"], 10);
+    assert_eq!(publishers[0]["rx_bytes"], 10_000);
+    assert_eq!(publishers[0]["keys"][0]["key"], "test/top/flood");
+    assert_eq!(json["keys"].as_array().unwrap().len(), 1);
+    assert_eq!(json["keys"][0]["key"], "test/top/flood");
+
+    let json = ztimeout!(get_top(""));
+    let publishers = json["publishers"].as_array().unwrap();
+    assert_eq!(publishers.len(), 2);
+    assert_eq!(publishers[1]["zid"], client2.zid().to_string());
+    assert_eq!(publishers[1]["keys"][0]["key"], "test/top/quiet");
+
+    let reply = ztimeout!(router.get(format!("@/{zid}/router/stats/top?n=x")))
+        .unwrap()
+        .recv_async()
+        .await
+        .unwrap();
+    assert!(reply.result().is_err());
+
+    ztimeout!(client2.close()).unwrap();
+    ztimeout!(client1.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
@@ -0,0 +1,175 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#![cfg(feature = "unstable")]
+
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use zenoh::{
+    config::ZenohId,
+    interceptor::{
+        EgressInterceptor, IngressInterceptor, InterceptedTransport, Interceptor,
+        InterceptorContext, InterceptorFactory, NetworkMessageMut,
+    },
+    key_expr::{keyexpr, KeyExpr},
+    sample::Sample,
+};
+use zenoh_core::ztimeout;
+use zenoh_test::{close_session, get_locators_from_session, TestSessions};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_secs(1);
+
+/// Drops the messages on the private key expressions.
+struct DropPrivate {
+    private: KeyExpr<'static>,
+}
+
+impl Interceptor for DropPrivate {
+    fn compute_keyexpr_cache(&self, key_expr: &keyexpr) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(self.private.intersects(key_expr)))
+    }
+
+    fn intercept(&self, msg: &mut NetworkMessageMut, ctx: &InterceptorContext) -> bool {
+        let is_private = match ctx.cache(msg) {
+            Some(cache) => *cache.downcast_ref::<bool>().unwrap(),
+            None => ctx
+                .key_expr(msg)
+                .is_some_and(|key_expr| self.private.intersects(&key_expr)),
+        };
+        !is_private
+    }
+}
+
+/// The peers of the intercepted transports, with the usernames they authenticated with.
+type Peers = Arc<Mutex<Vec<(ZenohId, Option<String>)>>>;
+
+/// Attaches a [`DropPrivate`] interceptor to the ingress of the unicast transports, recording
+/// the peers of these transports and the usernames they authenticated with.
+struct DropPrivateFactory {
+    peers: Peers,
+}
+
+impl InterceptorFactory for DropPrivateFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &InterceptedTransport,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        assert!(!transport.interfaces().is_empty());
+        self.peers.lock().unwrap().push((
+            *transport.transport().unwrap().zid(),
+            transport.username().map(String::from),
+        ));
+        let interceptor = DropPrivate {
+            private: KeyExpr::new("test/interceptor/private/**").unwrap(),
+        };
+        (Some(Box::new(interceptor)), None)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn interceptor_factory_on_open() {
+    zenoh::init_log_from_env_or("error");
+
+    let test_context = TestSessions::new();
+    let peers = Arc::new(Mutex::new(vec![]));
+    let config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    let sub_session = ztimeout!(
+        zenoh::open(config).with_interceptor_factory(DropPrivateFactory {
+            peers: peers.clone(),
+        })
+    )
+    .unwrap();
+    let locators = get_locators_from_session(&sub_session).await;
+    let pub_session = ztimeout!(zenoh::open(
+        test_context.get_connector_config_with_endpoint(locators)
+    ))
+    .unwrap();
+
+    let received = Arc::new(Mutex::new(vec![]));
+    let _sub = ztimeout!(sub_session
+        .declare_subscriber("test/interceptor/**")
+        .callback({
+            let received = received.clone();
+            move |sample: Sample| received.lock().unwrap().push(sample.key_expr().to_string())
+        }))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    for key_expr in ["test/interceptor/public/a", "test/interceptor/private/a"] {
+        for _ in 0..2 {
+            ztimeout!(pub_session.put(key_expr, "data")).unwrap();
+        }
+    }
+    tokio::time::sleep(SLEEP).await;
+
+    assert_eq!(*peers.lock().unwrap(), [(pub_session.zid(), None)]);
+    assert_eq!(
+        *received.lock().unwrap(),
+        ["test/interceptor/public/a", "test/interceptor/public/a"]
+    );
+
+    close_session(sub_session, pub_session).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn interceptor_factory_username() {
+    zenoh::init_log_from_env_or("error");
+
+    let credentials = std::env::temp_dir().join("interceptor_credentials.txt");
+    std::fs::write(&credentials, "client1name:client1passwd").unwrap();
+
+    let test_context = TestSessions::new();
+    let peers = Arc::new(Mutex::new(vec![]));
+    let mut config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    config
+        .insert_json5(
+            "transport/auth/usrpwd",
+            r#"{ user: "routername", password: "routerpasswd" }"#,
+        )
+        .unwrap();
+    config
+        .transport
+        .auth
+        .usrpwd
+        .set_dictionary_file(Some(credentials.to_string_lossy().into_owned()))
+        .unwrap();
+    let sub_session = ztimeout!(
+        zenoh::open(config).with_interceptor_factory(DropPrivateFactory {
+            peers: peers.clone(),
+        })
+    )
+    .unwrap();
+    let locators = get_locators_from_session(&sub_session).await;
+    let mut config = test_context.get_connector_config_with_endpoint(locators);
+    config
+        .insert_json5(
+            "transport/auth/usrpwd",
+            r#"{ user: "client1name", password: "client1passwd" }"#,
+        )
+        .unwrap();
+    let pub_session = ztimeout!(zenoh::open(config)).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    assert_eq!(
+        *peers.lock().unwrap(),
+        [(pub_session.zid(), Some("client1name".to_string()))]
+    );
+
+    close_session(sub_session, pub_session).await;
+}
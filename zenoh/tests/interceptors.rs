@@ -317,6 +317,82 @@ fn downsampling_by_protocol() {
     downsampling_by_protocol_impl(InterceptorFlow::Egress);
 }
 
+#[test]
+fn downsampling_state_kept_on_acl_reload() {
+    zenoh::init_log_from_env_or("error");
+
+    let ke: KeyExpr = "test/downsampling_acl_reload".try_into().unwrap();
+    let ds_config = DownsamplingItemConf {
+        id: None,
+        flows: Some(nev![InterceptorFlow::Ingress]),
+        interfaces: None,
+        link_protocols: None,
+        messages: nev![DataMessage::Put],
+        rules: nev![DownsamplingRuleConf {
+            key_expr: ke.clone().into(),
+            freq: 0.1,
+        }],
+    };
+    let deny_rule = |key_expr: &str| {
+        format!(
+            r#"[{{
+                "id": "deny",
+                "permission": "deny",
+                "flows": ["ingress"],
+                "messages": ["put"],
+                "key_exprs": ["{key_expr}"],
+            }}]"#
+        )
+    };
+
+    let mut test_context = TestSessions::new();
+    let mut config = build_listener_config(vec![ds_config], InterceptorFlow::Ingress, false);
+    config
+        .insert_json5(
+            "access_control",
+            &format!(
+                r#"{{
+                    "enabled": true,
+                    "default_permission": "allow",
+                    "rules": {},
+                    "subjects": [{{id: "all"}}],
+                    "policies": [{{rules: ["deny"], subjects: ["all"]}}],
+                }}"#,
+                deny_rule("denied/first")
+            ),
+        )
+        .unwrap();
+    let sub_session = test_context.open_listener_with_cfg_sync(config);
+    let counter = Arc::new(AtomicUsize::new(0));
+    let _sub = sub_session
+        .declare_subscriber(&ke)
+        .callback({
+            let counter = counter.clone();
+            move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .wait()
+        .unwrap();
+    let pub_session =
+        test_context.open_connector_with_cfg_sync(test_context.get_connector_config());
+    std::thread::sleep(std::time::Duration::from_millis(WARMUP_MS));
+
+    pub_session.put(&ke, "message").wait().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(WARMUP_MS));
+    // Only the access control interceptors are rebuilt, the downsampler keeps its state
+    sub_session
+        .config()
+        .insert_json5("access_control/rules", &deny_rule("denied/second"))
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(WARMUP_MS));
+    pub_session.put(&ke, "message").wait().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(WARMUP_MS));
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+    test_context.close_sync();
+}
+
 #[test]
 #[should_panic(expected = "unknown variant `down`")]
 fn downsampling_config_error_wrong_strategy() {
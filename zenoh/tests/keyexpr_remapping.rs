@@ -0,0 +1,472 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#![cfg(unix)]
+#![cfg(feature = "unstable")]
+
+use std::{
+    collections::BTreeSet,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use nonempty_collections::nev;
+use zenoh::{config::ZenohId, Session, Wait};
+use zenoh_config::{Config, KeyExprRemappingConf, KeyExprRemappingRuleConf};
+use zenoh_test::TestSessions;
+
+static DECLARATION_DELAY_MS: u64 = 250;
+static MESSAGES_DELAY_MS: u64 = 500;
+static LEGACY_ZID: &str = "1e6acc";
+
+fn remapping_config(zid: &str) -> KeyExprRemappingConf {
+    KeyExprRemappingConf {
+        id: Some("legacy".to_string()),
+        interfaces: None,
+        zids: Some(nev![ZenohId::from_str(zid).unwrap()]),
+        flows: None,
+        rules: nev![
+            KeyExprRemappingRuleConf {
+                remote: "robot1/sensors/**".parse().unwrap(),
+                local: "fleet/robot1/sensors/**".parse().unwrap(),
+            },
+            KeyExprRemappingRuleConf {
+                remote: "robot1/status".parse().unwrap(),
+                local: "fleet/robot1/status".parse().unwrap(),
+            }
+        ],
+    }
+}
+
+/// Opens a session with the given remapping configuration, and a "legacy" session connected to it.
+fn open_sessions(
+    test_context: &mut TestSessions,
+    remapping: Vec<KeyExprRemappingConf>,
+) -> (Session, Session) {
+    let mut config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    config.set_keyexpr_remapping(remapping).unwrap();
+    let session = test_context.open_listener_with_cfg_sync(config);
+    let mut legacy_config = test_context.get_connector_config();
+    legacy_config
+        .set_id(Some(ZenohId::from_str(LEGACY_ZID).unwrap()))
+        .unwrap();
+    let legacy_session = test_context.open_connector_with_cfg_sync(legacy_config);
+    (session, legacy_session)
+}
+
+fn collect_keys(
+    session: &Session,
+    key_expr: &str,
+) -> (Arc<Mutex<BTreeSet<String>>>, zenoh::pubsub::Subscriber<()>) {
+    let keys = Arc::new(Mutex::new(BTreeSet::new()));
+    let subscriber = session
+        .declare_subscriber(key_expr)
+        .callback({
+            let keys = keys.clone();
+            move |sample| {
+                keys.lock().unwrap().insert(sample.key_expr().to_string());
+            }
+        })
+        .wait()
+        .unwrap();
+    (keys, subscriber)
+}
+
+fn keys(keys: &[&str]) -> BTreeSet<String> {
+    keys.iter().map(|k| k.to_string()).collect()
+}
+
+#[test]
+fn keyexpr_remapping_pub_sub_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+    let (session, legacy_session) =
+        open_sessions(&mut test_context, vec![remapping_config(LEGACY_ZID)]);
+
+    // Ingress: remote keys are remapped to local keys
+    let (received, _sub) = collect_keys(&session, "**");
+    let (legacy_received, _legacy_sub) = collect_keys(&legacy_session, "**");
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+
+    let optimized = legacy_session
+        .declare_keyexpr("robot1/sensors/optimized")
+        .wait()
+        .unwrap();
+    legacy_session
+        .put("robot1/sensors/temp", "")
+        .wait()
+        .unwrap();
+    legacy_session.put(&optimized, "").wait().unwrap();
+    legacy_session
+        .put(optimized.join("nested").unwrap(), "")
+        .wait()
+        .unwrap();
+    legacy_session.put("robot1/status", "").wait().unwrap();
+    legacy_session
+        .put("robot1/status/nested", "")
+        .wait()
+        .unwrap();
+    legacy_session.put("other/key", "").wait().unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(
+        *received.lock().unwrap(),
+        keys(&[
+            "fleet/robot1/sensors/temp",
+            "fleet/robot1/sensors/optimized",
+            "fleet/robot1/sensors/optimized/nested",
+            "fleet/robot1/status",
+            "robot1/status/nested",
+            "other/key",
+        ])
+    );
+
+    // Egress: local keys are remapped to remote keys
+    legacy_received.lock().unwrap().clear();
+    session.put("fleet/robot1/sensors/hum", "").wait().unwrap();
+    session.put("fleet/robot1/status", "").wait().unwrap();
+    session.put("other/key2", "").wait().unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(
+        *legacy_received.lock().unwrap(),
+        keys(&["robot1/sensors/hum", "robot1/status", "other/key2"])
+    );
+
+    test_context.close_sync();
+}
+
+#[test]
+fn keyexpr_remapping_declarations_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+    let (session, legacy_session) =
+        open_sessions(&mut test_context, vec![remapping_config(LEGACY_ZID)]);
+
+    // Subscribers are declared in the key space of each node
+    let (received, _sub) = collect_keys(&session, "fleet/robot1/sensors/**");
+    let (legacy_received, _legacy_sub) = collect_keys(&legacy_session, "robot1/sensors/**");
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+    legacy_session
+        .put("robot1/sensors/temp", "")
+        .wait()
+        .unwrap();
+    session.put("fleet/robot1/sensors/hum", "").wait().unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(
+        *received.lock().unwrap(),
+        keys(&["fleet/robot1/sensors/temp", "fleet/robot1/sensors/hum"])
+    );
+    assert_eq!(
+        *legacy_received.lock().unwrap(),
+        keys(&["robot1/sensors/temp", "robot1/sensors/hum"])
+    );
+
+    // Liveliness tokens
+    let _token = legacy_session
+        .liveliness()
+        .declare_token("robot1/sensors/alive")
+        .wait()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+    let tokens = session
+        .liveliness()
+        .get("fleet/robot1/**")
+        .wait()
+        .unwrap()
+        .into_iter()
+        .map(|reply| reply.result().unwrap().key_expr().to_string())
+        .collect::<BTreeSet<_>>();
+    assert_eq!(tokens, keys(&["fleet/robot1/sensors/alive"]));
+
+    test_context.close_sync();
+}
+
+#[test]
+fn keyexpr_remapping_intersecting_wildcards_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+    let (session, legacy_session) =
+        open_sessions(&mut test_context, vec![remapping_config(LEGACY_ZID)]);
+
+    // A subscriber intersecting the remapped key space without being included in it is not
+    // remapped, so it only matches the keys outside of this key space
+    let (legacy_received, _legacy_sub) = collect_keys(&legacy_session, "*/sensors/temp");
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+    session.put("fleet/robot1/sensors/temp", "").wait().unwrap();
+    session.put("robot2/sensors/temp", "").wait().unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(
+        *legacy_received.lock().unwrap(),
+        keys(&["robot2/sensors/temp"])
+    );
+
+    test_context.close_sync();
+}
+
+#[test]
+fn keyexpr_remapping_query_reply_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+    let (session, legacy_session) =
+        open_sessions(&mut test_context, vec![remapping_config(LEGACY_ZID)]);
+
+    let _qbl = session
+        .declare_queryable("fleet/robot1/sensors/**")
+        .callback(|query| {
+            query
+                .reply("fleet/robot1/sensors/temp", "local")
+                .wait()
+                .unwrap();
+        })
+        .wait()
+        .unwrap();
+    let _legacy_qbl = legacy_session
+        .declare_queryable("robot1/sensors/**")
+        .callback(|query| {
+            query.reply("robot1/sensors/hum", "remote").wait().unwrap();
+        })
+        .wait()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+
+    let reply_keys = |session: &Session, selector: &str| {
+        session
+            .get(selector)
+            .wait()
+            .unwrap()
+            .into_iter()
+            .map(|reply| reply.result().unwrap().key_expr().to_string())
+            .collect::<BTreeSet<_>>()
+    };
+    assert_eq!(
+        reply_keys(&legacy_session, "robot1/sensors/**"),
+        keys(&["robot1/sensors/temp", "robot1/sensors/hum"])
+    );
+    assert_eq!(
+        reply_keys(&session, "fleet/robot1/sensors/**"),
+        keys(&["fleet/robot1/sensors/temp", "fleet/robot1/sensors/hum"])
+    );
+
+    test_context.close_sync();
+}
+
+#[test]
+fn keyexpr_remapping_other_zid_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+    let (session, legacy_session) = open_sessions(&mut test_context, vec![remapping_config("abc")]);
+
+    let (received, _sub) = collect_keys(&session, "**");
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+    legacy_session
+        .put("robot1/sensors/temp", "")
+        .wait()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(*received.lock().unwrap(), keys(&["robot1/sensors/temp"]));
+
+    test_context.close_sync();
+}
+
+#[test]
+fn keyexpr_remapping_access_control_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+    let mut config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    config
+        .set_keyexpr_remapping(vec![remapping_config(LEGACY_ZID)])
+        .unwrap();
+    // Access control rules are expressed in the key space of this node
+    config
+        .insert_json5(
+            "access_control",
+            r#"{
+                "enabled": true,
+                "default_permission": "allow",
+                "rules": [
+                    {
+                        "id": "deny_sensors",
+                        "permission": "deny",
+                        "flows": ["ingress"],
+                        "messages": ["put"],
+                        "key_exprs": ["fleet/robot1/sensors/**"],
+                    },
+                    {
+                        "id": "deny_status",
+                        "permission": "deny",
+                        "flows": ["egress"],
+                        "messages": ["put"],
+                        "key_exprs": ["fleet/robot1/status"],
+                    },
+                ],
+                "subjects": [{id: "all"}],
+                "policies": [
+                    {
+                        rules: ["deny_sensors", "deny_status"],
+                        subjects: ["all"],
+                    }
+                ],
+            }"#,
+        )
+        .unwrap();
+    let session = test_context.open_listener_with_cfg_sync(config);
+    let mut legacy_config = test_context.get_connector_config();
+    legacy_config
+        .set_id(Some(ZenohId::from_str(LEGACY_ZID).unwrap()))
+        .unwrap();
+    let legacy_session = test_context.open_connector_with_cfg_sync(legacy_config);
+
+    let (received, _sub) = collect_keys(&session, "**");
+    let (legacy_received, _legacy_sub) = collect_keys(&legacy_session, "**");
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+
+    // Ingress: access control applies to the remapped key expressions
+    let optimized = legacy_session
+        .declare_keyexpr("robot1/sensors/optimized")
+        .wait()
+        .unwrap();
+    legacy_session
+        .put("robot1/sensors/temp", "")
+        .wait()
+        .unwrap();
+    legacy_session.put(&optimized, "").wait().unwrap();
+    legacy_session.put("robot1/status", "").wait().unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(*received.lock().unwrap(), keys(&["fleet/robot1/status"]));
+
+    // Egress: access control applies to the key expressions before remapping
+    legacy_received.lock().unwrap().clear();
+    session.put("fleet/robot1/status", "").wait().unwrap();
+    session.put("fleet/robot1/sensors/hum", "").wait().unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(
+        *legacy_received.lock().unwrap(),
+        keys(&["robot1/sensors/hum"])
+    );
+
+    test_context.close_sync();
+}
+
+#[test]
+fn keyexpr_remapping_config_reload_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+    let mut config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    // The local key space is nested in the remote one, so that remapping a key twice is noticed
+    config
+        .set_keyexpr_remapping(vec![KeyExprRemappingConf {
+            id: None,
+            interfaces: None,
+            zids: Some(nev![ZenohId::from_str(LEGACY_ZID).unwrap()]),
+            flows: None,
+            rules: nev![KeyExprRemappingRuleConf {
+                remote: "sensors/**".parse().unwrap(),
+                local: "sensors/robot1/**".parse().unwrap(),
+            }],
+        }])
+        .unwrap();
+    let deny_rule = |key_expr: &str| {
+        format!(
+            r#"[{{
+                "id": "deny",
+                "permission": "deny",
+                "flows": ["ingress"],
+                "messages": ["put"],
+                "key_exprs": ["{key_expr}"],
+            }}]"#
+        )
+    };
+    config
+        .insert_json5(
+            "access_control",
+            &format!(
+                r#"{{
+                    "enabled": true,
+                    "default_permission": "allow",
+                    "rules": {},
+                    "subjects": [{{id: "all"}}],
+                    "policies": [{{rules: ["deny"], subjects: ["all"]}}],
+                }}"#,
+                deny_rule("denied/first")
+            ),
+        )
+        .unwrap();
+    let session = test_context.open_listener_with_cfg_sync(config);
+    let mut legacy_config = test_context.get_connector_config();
+    legacy_config
+        .set_id(Some(ZenohId::from_str(LEGACY_ZID).unwrap()))
+        .unwrap();
+    let legacy_session = test_context.open_connector_with_cfg_sync(legacy_config);
+
+    let (received, _sub) = collect_keys(&session, "**");
+    // The key expression of a subscription is declared to the remote node, which then
+    // publishes on it with its id
+    let (_, _legacy_sub) = collect_keys(&legacy_session, "sensors/temp");
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+
+    legacy_session.put("sensors/temp", "").wait().unwrap();
+    legacy_session.put("denied/first", "").wait().unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(*received.lock().unwrap(), keys(&["sensors/robot1/temp"]));
+
+    // The interceptors are rebuilt on reload, the key expressions declared before are still remapped
+    received.lock().unwrap().clear();
+    session
+        .config()
+        .insert_json5("access_control/rules", &deny_rule("denied/second"))
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    legacy_session.put("sensors/temp", "").wait().unwrap();
+    legacy_session.put("denied/first", "").wait().unwrap();
+    legacy_session.put("denied/second", "").wait().unwrap();
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+    assert_eq!(
+        *received.lock().unwrap(),
+        keys(&["sensors/robot1/temp", "denied/first"])
+    );
+
+    test_context.close_sync();
+}
+
+#[test]
+#[should_panic(expected = "'robot1/*/temp' is neither a key without wildcards")]
+fn keyexpr_remapping_config_error_wildcard() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    let mut remapping = remapping_config(LEGACY_ZID);
+    remapping.rules = nev![KeyExprRemappingRuleConf {
+        remote: "robot1/*/temp".parse().unwrap(),
+        local: "fleet/robot1/temp".parse().unwrap(),
+    }];
+    config.set_keyexpr_remapping(vec![remapping]).unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "maps a single key to a key space")]
+fn keyexpr_remapping_config_error_shape() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    let mut remapping = remapping_config(LEGACY_ZID);
+    remapping.rules = nev![KeyExprRemappingRuleConf {
+        remote: "robot1/**".parse().unwrap(),
+        local: "fleet/robot1".parse().unwrap(),
+    }];
+    config.set_keyexpr_remapping(vec![remapping]).unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
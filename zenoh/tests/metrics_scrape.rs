@@ -0,0 +1,103 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#![cfg(feature = "stats")]
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use zenoh::config::WhatAmI;
+use zenoh_test::{get_free_tcp_port, TestSessions};
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+/// Sends an HTTP request to the scrape listener, and returns the status line and the body.
+fn http_get(port: u16, target: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    write!(stream, "GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    (head.lines().next().unwrap().to_string(), body.to_string())
+}
+
+#[test]
+fn metrics_scrape_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+
+    let port = get_free_tcp_port();
+    let mut config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .insert_json5(
+            "stats/scrape",
+            &format!(r#"{{ listen: "127.0.0.1:{port}" }}"#),
+        )
+        .unwrap();
+    let router = test_context.open_listener_with_cfg_sync(config);
+
+    let (status, body) = http_get(port, "/metrics");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains(&format!("local_id=\"{}\"", router.zid())));
+    assert!(body.contains("zenoh_transports_opened"));
+    assert!(!body.contains("zid=\""));
+    assert!(body.ends_with("# EOF\n"));
+
+    let (status, _) = http_get(port, "/other");
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+
+    test_context.close_sync();
+}
+
+#[test]
+fn metrics_scrape_federation_test() {
+    zenoh::init_log_from_env_or("error");
+    let mut test_context = TestSessions::new();
+
+    let port = get_free_tcp_port();
+    let mut config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .insert_json5(
+            "stats/scrape",
+            &format!(r#"{{ listen: "127.0.0.1:{port}", federation: {{ enabled: true }} }}"#),
+        )
+        .unwrap();
+    let router = test_context.open_listener_with_cfg_sync(config);
+
+    let mut config = test_context.get_connector_config();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    config.adminspace.permissions.set_read(true).unwrap();
+    let client = test_context.open_connector_with_cfg_sync(config);
+    std::thread::sleep(SLEEP);
+
+    let (status, body) = http_get(port, "/metrics?per_key=false");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    for zid in [router.zid(), client.zid()] {
+        assert!(body.contains(&format!("zenoh_transports_opened{{zid=\"{zid}\",")));
+    }
+    assert_eq!(
+        body.matches("# TYPE zenoh_transports_opened ").count(),
+        1,
+        "{body}"
+    );
+    assert_eq!(body.matches("# EOF").count(), 1);
+
+    test_context.close_sync();
+}
@@ -0,0 +1,369 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#![cfg(unix)]
+#![cfg(feature = "unstable")]
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use nonempty_collections::{nev, NEVec};
+use zenoh::{bytes::Encoding, Wait};
+use zenoh_config::{
+    Config, DataMessage, InterceptorFlow, InterceptorLink, PayloadFilterAttachmentConf,
+    PayloadFilterConf,
+};
+use zenoh_test::TestSessions;
+
+static DECLARATION_DELAY_MS: u64 = 250;
+static MESSAGES_DELAY_MS: u64 = 1000;
+
+fn payload_filter_config(
+    flow: InterceptorFlow,
+    link_protocols: Option<NEVec<InterceptorLink>>,
+    messages: NEVec<DataMessage>,
+) -> PayloadFilterConf {
+    PayloadFilterConf {
+        id: Some("test".to_string()),
+        interfaces: None,
+        link_protocols,
+        flows: Some(nev![flow]),
+        messages,
+        key_exprs: nev!["test/payload_filter/telemetry/**".parse().unwrap()],
+        encodings: None,
+        attachment: None,
+    }
+}
+
+/// Serializes the entries as a map of strings, following the zenoh serialization format.
+fn map_attachment(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut bytes = vec![entries.len() as u8];
+    for (key, value) in entries {
+        bytes.push(key.len() as u8);
+        bytes.extend_from_slice(key.as_bytes());
+        bytes.push(value.len() as u8);
+        bytes.extend_from_slice(value.as_bytes());
+    }
+    bytes
+}
+
+/// A message published by the test: its label, key expression, encoding and attachment.
+type TestMessage = (&'static str, &'static str, Encoding, Option<Vec<u8>>);
+
+/// Publishes the given messages from a session to another one, `pf_config` being set on the
+/// publisher for the egress flow and on the subscriber for the ingress flow, and returns the
+/// labels of the messages received.
+fn payload_filter_put_test(
+    flow: InterceptorFlow,
+    pf_config: Vec<PayloadFilterConf>,
+    messages: Vec<TestMessage>,
+) -> HashSet<String> {
+    let mut test_context = TestSessions::new();
+    let mut sub_config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    if flow == InterceptorFlow::Ingress {
+        sub_config.set_payload_filter(pf_config.clone()).unwrap();
+    }
+    let sub_session = test_context.open_listener_with_cfg_sync(sub_config);
+    let mut pub_config = test_context.get_connector_config();
+    if flow == InterceptorFlow::Egress {
+        pub_config.set_payload_filter(pf_config).unwrap();
+    }
+    let pub_session = test_context.open_connector_with_cfg_sync(pub_config);
+
+    let received = Arc::new(Mutex::new(HashSet::new()));
+    let _sub = sub_session
+        .declare_subscriber("test/payload_filter/**")
+        .callback({
+            let received = received.clone();
+            move |sample| {
+                let label = sample.payload().try_to_string().unwrap().into_owned();
+                received.lock().unwrap().insert(label);
+            }
+        })
+        .wait()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+
+    for (label, key_expr, encoding, attachment) in messages {
+        let mut put = pub_session.put(key_expr, label).encoding(encoding);
+        if let Some(attachment) = attachment {
+            put = put.attachment(attachment);
+        }
+        put.wait().unwrap();
+    }
+    std::thread::sleep(Duration::from_millis(MESSAGES_DELAY_MS));
+
+    test_context.close_sync();
+    let received = received.lock().unwrap().clone();
+    received
+}
+
+fn encoding_messages() -> Vec<TestMessage> {
+    vec![
+        (
+            "json",
+            "test/payload_filter/telemetry/a",
+            Encoding::APPLICATION_JSON,
+            None,
+        ),
+        (
+            "json_schema",
+            "test/payload_filter/telemetry/a",
+            Encoding::APPLICATION_JSON.with_schema("v1"),
+            None,
+        ),
+        (
+            "text",
+            "test/payload_filter/telemetry/a",
+            Encoding::TEXT_PLAIN,
+            None,
+        ),
+        (
+            "other_text",
+            "test/payload_filter/other",
+            Encoding::TEXT_PLAIN,
+            None,
+        ),
+    ]
+}
+
+#[test]
+fn payload_filter_encoding_test() {
+    zenoh::init_log_from_env_or("error");
+
+    for flow in [InterceptorFlow::Ingress, InterceptorFlow::Egress] {
+        for link_protocols in [None, Some(nev![InterceptorLink::Tcp])] {
+            let mut config = payload_filter_config(flow, link_protocols, nev![DataMessage::Put]);
+            config.encodings = Some(nev!["application/json".to_string()]);
+            let received = payload_filter_put_test(flow, vec![config], encoding_messages());
+            assert_eq!(
+                received,
+                HashSet::from(["json", "json_schema", "other_text"].map(String::from)),
+                "flow: {flow:?}"
+            );
+        }
+
+        // Not applied on other link protocols
+        let mut config = payload_filter_config(
+            flow,
+            Some(nev![InterceptorLink::Udp]),
+            nev![DataMessage::Put],
+        );
+        config.encodings = Some(nev!["application/json".to_string()]);
+        let received = payload_filter_put_test(flow, vec![config], encoding_messages());
+        assert_eq!(received.len(), 4, "flow: {flow:?}");
+
+        // Not applied on other messages
+        let mut config = payload_filter_config(flow, None, nev![DataMessage::Query]);
+        config.encodings = Some(nev!["application/json".to_string()]);
+        let received = payload_filter_put_test(flow, vec![config], encoding_messages());
+        assert_eq!(received.len(), 4, "flow: {flow:?}");
+
+        // An encoding with schema only admits that schema
+        let mut config = payload_filter_config(flow, None, nev![DataMessage::Put]);
+        config.encodings = Some(nev!["application/json;v1".to_string()]);
+        let received = payload_filter_put_test(flow, vec![config], encoding_messages());
+        assert_eq!(
+            received,
+            HashSet::from(["json_schema", "other_text"].map(String::from)),
+            "flow: {flow:?}"
+        );
+    }
+}
+
+#[test]
+fn payload_filter_attachment_test() {
+    zenoh::init_log_from_env_or("error");
+
+    let ke = "test/payload_filter/telemetry/a";
+    let messages = || {
+        vec![
+            (
+                "tenant_a",
+                ke,
+                Encoding::default(),
+                Some(map_attachment(&[("source", "s1"), ("tenant", "a")])),
+            ),
+            (
+                "tenant_b",
+                ke,
+                Encoding::default(),
+                Some(map_attachment(&[("tenant", "b")])),
+            ),
+            (
+                "no_tenant",
+                ke,
+                Encoding::default(),
+                Some(map_attachment(&[("source", "s1")])),
+            ),
+            ("no_attachment", ke, Encoding::default(), None),
+            (
+                "not_a_map",
+                ke,
+                Encoding::default(),
+                Some(b"tenant".to_vec()),
+            ),
+        ]
+    };
+
+    for flow in [InterceptorFlow::Ingress, InterceptorFlow::Egress] {
+        let mut config = payload_filter_config(flow, None, nev![DataMessage::Put]);
+        config.attachment = Some(nev![PayloadFilterAttachmentConf {
+            key: "tenant".to_string(),
+            value: None,
+        }]);
+        let received = payload_filter_put_test(flow, vec![config], messages());
+        assert_eq!(
+            received,
+            HashSet::from(["tenant_a", "tenant_b"].map(String::from)),
+            "flow: {flow:?}"
+        );
+
+        let mut config = payload_filter_config(flow, None, nev![DataMessage::Put]);
+        config.attachment = Some(nev![PayloadFilterAttachmentConf {
+            key: "tenant".to_string(),
+            value: Some("a".to_string()),
+        }]);
+        let received = payload_filter_put_test(flow, vec![config], messages());
+        assert_eq!(
+            received,
+            HashSet::from(["tenant_a"].map(String::from)),
+            "flow: {flow:?}"
+        );
+    }
+}
+
+#[test]
+fn payload_filter_multiple_filters_test() {
+    zenoh::init_log_from_env_or("error");
+
+    // A message must be admitted by all the filters matching it
+    let mut encoding_config =
+        payload_filter_config(InterceptorFlow::Ingress, None, nev![DataMessage::Put]);
+    encoding_config.encodings = Some(nev!["application/json".to_string()]);
+    let mut attachment_config =
+        payload_filter_config(InterceptorFlow::Ingress, None, nev![DataMessage::Put]);
+    attachment_config.id = Some("test2".to_string());
+    attachment_config.attachment = Some(nev![PayloadFilterAttachmentConf {
+        key: "tenant".to_string(),
+        value: None,
+    }]);
+    let ke = "test/payload_filter/telemetry/a";
+    let tenant = || Some(map_attachment(&[("tenant", "a")]));
+    let received = payload_filter_put_test(
+        InterceptorFlow::Ingress,
+        vec![encoding_config, attachment_config],
+        vec![
+            ("json_tenant", ke, Encoding::APPLICATION_JSON, tenant()),
+            ("json", ke, Encoding::APPLICATION_JSON, None),
+            ("text_tenant", ke, Encoding::TEXT_PLAIN, tenant()),
+        ],
+    );
+    assert_eq!(received, HashSet::from(["json_tenant"].map(String::from)));
+}
+
+#[test]
+fn payload_filter_query_reply_test() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut test_context = TestSessions::new();
+    let mut qbl_config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    let mut query_config =
+        payload_filter_config(InterceptorFlow::Ingress, None, nev![DataMessage::Query]);
+    query_config.encodings = Some(nev!["application/json".to_string()]);
+    let mut reply_config =
+        payload_filter_config(InterceptorFlow::Egress, None, nev![DataMessage::Reply]);
+    reply_config.id = Some("test2".to_string());
+    reply_config.encodings = Some(nev!["application/json".to_string()]);
+    qbl_config
+        .set_payload_filter(vec![query_config, reply_config])
+        .unwrap();
+    let qbl_session = test_context.open_listener_with_cfg_sync(qbl_config);
+    let get_config = test_context.get_connector_config();
+    let get_session = test_context.open_connector_with_cfg_sync(get_config);
+
+    let _qbl = qbl_session
+        .declare_queryable("test/payload_filter/telemetry/**")
+        .callback(|query| {
+            let encoding = match query.parameters().as_str() {
+                "json" => Encoding::APPLICATION_JSON,
+                _ => Encoding::TEXT_PLAIN,
+            };
+            query
+                .reply(query.key_expr().clone(), "reply")
+                .encoding(encoding)
+                .wait()
+                .unwrap();
+        })
+        .wait()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+
+    let replies = |parameters: &str, encoding: Option<Encoding>| {
+        let mut get = get_session.get(format!("test/payload_filter/telemetry/a?{parameters}"));
+        if let Some(encoding) = encoding {
+            get = get.payload("query").encoding(encoding);
+        }
+        get.wait()
+            .unwrap()
+            .into_iter()
+            .filter(|reply| reply.result().is_ok())
+            .count()
+    };
+
+    // Queries without body are not subject to the encodings condition
+    assert_eq!(replies("json", None), 1);
+    assert_eq!(replies("json", Some(Encoding::APPLICATION_JSON)), 1);
+    assert_eq!(replies("json", Some(Encoding::TEXT_PLAIN)), 0);
+    assert_eq!(replies("text", Some(Encoding::APPLICATION_JSON)), 0);
+
+    test_context.close_sync();
+}
+
+#[test]
+#[should_panic(expected = "Invalid payload filter config: id 'REPEATED' is repeated")]
+fn payload_filter_config_error_repeated_id() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    let mut pf_config =
+        payload_filter_config(InterceptorFlow::Ingress, None, nev![DataMessage::Put]);
+    pf_config.id = Some("REPEATED".to_string());
+    pf_config.encodings = Some(nev!["application/json".to_string()]);
+    config
+        .set_payload_filter(vec![pf_config.clone(), pf_config])
+        .unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "filter 'test' has neither encodings nor attachment")]
+fn payload_filter_config_error_no_condition() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    config
+        .set_payload_filter(vec![payload_filter_config(
+            InterceptorFlow::Ingress,
+            None,
+            nev![DataMessage::Put],
+        )])
+        .unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
@@ -0,0 +1,306 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#![cfg(unix)]
+#![cfg(feature = "unstable")]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use nonempty_collections::nev;
+use zenoh::{qos::CongestionControl, Session, Wait};
+use zenoh_config::{
+    Config, DataMessage, InterceptorFlow, RateLimitAction, RateLimitItemConf, RateLimitRuleConf,
+};
+use zenoh_test::TestSessions;
+
+static MSG_COUNT: usize = 50;
+static MSGS_PER_SEC: u64 = 10;
+static DECLARATION_DELAY_MS: u64 = 500;
+static DROP_TIMEOUT_MS: u64 = 1000;
+static MAX_BURST_MS: u64 = 5_000;
+static DELAY_TIMEOUT_MS: u64 = 8_000;
+
+fn rate_limit_config(action: RateLimitAction, max_burst_ms: Option<u64>) -> RateLimitItemConf {
+    RateLimitItemConf {
+        id: Some("test".to_string()),
+        interfaces: None,
+        cert_common_names: None,
+        usernames: None,
+        link_protocols: None,
+        zids: None,
+        flows: Some(nev![InterceptorFlow::Ingress]),
+        messages: nev![DataMessage::Put],
+        rules: nev![RateLimitRuleConf {
+            key_expr: "test/rate_limit/limited/**".parse().unwrap(),
+            bytes_per_sec: None,
+            msgs_per_sec: Some(MSGS_PER_SEC),
+        }],
+        action,
+        max_burst_ms,
+    }
+}
+
+fn rate_limit_counters(session: &Session) -> serde_json::Value {
+    let counters = session
+        .get(format!("@/{}/peer/rate_limit", session.zid()))
+        .wait()
+        .unwrap()
+        .recv()
+        .unwrap()
+        .into_result()
+        .unwrap()
+        .payload()
+        .try_to_string()
+        .unwrap()
+        .into_owned();
+    serde_json::from_str(&counters).unwrap()
+}
+
+/// Publishes `MSG_COUNT` messages on both a limited and an unlimited key expression and
+/// returns the number of messages received on each, with the time it took to receive the
+/// last message on the limited key expression and the counters of the limiting rule.
+fn rate_limit_pub_sub_test(
+    rl_config: Vec<RateLimitItemConf>,
+    timeout: Duration,
+) -> (usize, usize, Duration, serde_json::Value) {
+    let mut test_context = TestSessions::new();
+    let mut sub_config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    sub_config.set_rate_limit(rl_config).unwrap();
+    sub_config.adminspace.set_enabled(true).unwrap();
+    let sub_session = test_context.open_listener_with_cfg_sync(sub_config);
+    let pub_config = test_context.get_connector_config();
+    let pub_session = test_context.open_connector_with_cfg_sync(pub_config);
+
+    let limited = Arc::new(AtomicUsize::new(0));
+    let unlimited = Arc::new(AtomicUsize::new(0));
+    let last_limited = Arc::new(Mutex::new(None));
+    let _sub_limited = sub_session
+        .declare_subscriber("test/rate_limit/limited/*")
+        .callback({
+            let limited = limited.clone();
+            let last_limited = last_limited.clone();
+            move |_| {
+                limited.fetch_add(1, Ordering::SeqCst);
+                *last_limited.lock().unwrap() = Some(Instant::now());
+            }
+        })
+        .wait()
+        .unwrap();
+    let _sub_unlimited = sub_session
+        .declare_subscriber("test/rate_limit/unlimited/*")
+        .callback({
+            let unlimited = unlimited.clone();
+            move |_| {
+                unlimited.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .wait()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+
+    let start = Instant::now();
+    for _ in 0..MSG_COUNT {
+        pub_session
+            .put("test/rate_limit/unlimited/data", "data")
+            .congestion_control(CongestionControl::Block)
+            .wait()
+            .unwrap();
+        pub_session
+            .put("test/rate_limit/limited/data", "data")
+            .congestion_control(CongestionControl::Block)
+            .wait()
+            .unwrap();
+    }
+    while limited.load(Ordering::SeqCst) < MSG_COUNT && start.elapsed() < timeout {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    let elapsed = last_limited
+        .lock()
+        .unwrap()
+        .map(|last| last - start)
+        .unwrap_or_default();
+
+    let counters = rate_limit_counters(&sub_session);
+    let rules = counters[0]["rules"][0].clone();
+    assert_eq!(counters[0]["id"], "test");
+    assert_eq!(counters[0]["flow"], "ingress");
+    assert_eq!(rules["key_expr"], "test/rate_limit/limited/**");
+    assert_eq!(
+        rules["passed_msgs"].as_u64().unwrap() + rules["dropped_msgs"].as_u64().unwrap(),
+        MSG_COUNT as u64
+    );
+    assert_eq!(
+        rules["passed_msgs"].as_u64().unwrap(),
+        limited.load(Ordering::SeqCst) as u64
+    );
+
+    // The budgets of a remote node are released when its transport is closed
+    pub_session.close().wait().unwrap();
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+    assert_eq!(rate_limit_counters(&sub_session), serde_json::json!([]));
+
+    test_context.close_sync();
+    (
+        limited.load(Ordering::SeqCst),
+        unlimited.load(Ordering::SeqCst),
+        elapsed,
+        rules,
+    )
+}
+
+#[test]
+fn rate_limit_drop() {
+    zenoh::init_log_from_env_or("error");
+    let (limited, unlimited, elapsed, _) = rate_limit_pub_sub_test(
+        vec![rate_limit_config(RateLimitAction::Drop, None)],
+        Duration::from_millis(DROP_TIMEOUT_MS),
+    );
+    assert_eq!(unlimited, MSG_COUNT);
+    // The bucket holds one second worth of budget at most
+    let max_expected = MSGS_PER_SEC as f64 * (1. + elapsed.as_secs_f64());
+    assert!(limited >= 1, "no message received");
+    assert!(
+        limited as f64 <= max_expected,
+        "received {limited} messages, expected at most {max_expected}"
+    );
+}
+
+#[test]
+fn rate_limit_delay() {
+    zenoh::init_log_from_env_or("error");
+    let (limited, unlimited, elapsed, rules) = rate_limit_pub_sub_test(
+        vec![rate_limit_config(
+            RateLimitAction::Delay,
+            Some(MAX_BURST_MS),
+        )],
+        Duration::from_millis(DELAY_TIMEOUT_MS),
+    );
+    assert_eq!(unlimited, MSG_COUNT);
+    // Messages exceeding the one second worth of budget are held back instead of dropped
+    assert_eq!(limited, MSG_COUNT);
+    assert_eq!(rules["dropped_msgs"], 0);
+    let delayed = rules["delayed_msgs"].as_u64().unwrap();
+    assert!(delayed > 0, "no message delayed");
+    // and sent at the budget rate
+    let min_elapsed = Duration::from_secs_f64((delayed - 1) as f64 / MSGS_PER_SEC as f64);
+    assert!(
+        elapsed >= min_elapsed,
+        "received {delayed} delayed messages in {elapsed:?}, expected at least {min_elapsed:?}"
+    );
+}
+
+#[test]
+fn rate_limit_overlapping_items() {
+    zenoh::init_log_from_env_or("error");
+
+    // The first item limits the whole test key space, the second one a narrower part of it
+    let mut wide = rate_limit_config(RateLimitAction::Drop, None);
+    wide.id = Some("wide".to_string());
+    wide.rules = nev![RateLimitRuleConf {
+        key_expr: "test/rate_limit/**".parse().unwrap(),
+        bytes_per_sec: None,
+        msgs_per_sec: Some(4),
+    }];
+    let mut narrow = rate_limit_config(RateLimitAction::Drop, None);
+    narrow.id = Some("narrow".to_string());
+    narrow.rules = nev![RateLimitRuleConf {
+        key_expr: "test/rate_limit/limited/**".parse().unwrap(),
+        bytes_per_sec: None,
+        msgs_per_sec: Some(2),
+    }];
+
+    let mut test_context = TestSessions::new();
+    let mut sub_config = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    sub_config.set_rate_limit(vec![wide, narrow]).unwrap();
+    let sub_session = test_context.open_listener_with_cfg_sync(sub_config);
+    let pub_config = test_context.get_connector_config();
+    let pub_session = test_context.open_connector_with_cfg_sync(pub_config);
+
+    let received = Arc::new(Mutex::new(vec![]));
+    let _sub = sub_session
+        .declare_subscriber("test/rate_limit/**")
+        .callback({
+            let received = received.clone();
+            move |sample| received.lock().unwrap().push(sample.key_expr().to_string())
+        })
+        .wait()
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(DECLARATION_DELAY_MS));
+
+    // The messages dropped by the second item do not use up the budget of the first one
+    for key_expr in [
+        "test/rate_limit/limited/data",
+        "test/rate_limit/limited/data",
+        "test/rate_limit/limited/data",
+        "test/rate_limit/limited/data",
+        "test/rate_limit/limited/data",
+        "test/rate_limit/unlimited/data",
+        "test/rate_limit/unlimited/data",
+    ] {
+        pub_session
+            .put(key_expr, "data")
+            .congestion_control(CongestionControl::Block)
+            .wait()
+            .unwrap();
+    }
+    std::thread::sleep(Duration::from_millis(DROP_TIMEOUT_MS));
+
+    assert_eq!(
+        *received.lock().unwrap(),
+        [
+            "test/rate_limit/limited/data",
+            "test/rate_limit/limited/data",
+            "test/rate_limit/unlimited/data",
+            "test/rate_limit/unlimited/data",
+        ]
+    );
+
+    test_context.close_sync();
+}
+
+#[test]
+#[should_panic(expected = "Invalid RateLimit config: id 'REPEATED' is repeated")]
+fn rate_limit_config_error_repeated_id() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    let mut item = rate_limit_config(RateLimitAction::Drop, None);
+    item.id = Some("REPEATED".to_string());
+    config.set_rate_limit(vec![item.clone(), item]).unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "has neither bytes_per_sec nor msgs_per_sec")]
+fn rate_limit_config_error_no_budget() {
+    zenoh::init_log_from_env_or("error");
+
+    let mut config = Config::default();
+    let mut item = rate_limit_config(RateLimitAction::Drop, None);
+    item.rules = nev![RateLimitRuleConf {
+        key_expr: "test/**".parse().unwrap(),
+        bytes_per_sec: None,
+        msgs_per_sec: None,
+    }];
+    config.set_rate_limit(vec![item]).unwrap();
+
+    zenoh::open(config).wait().unwrap();
+}
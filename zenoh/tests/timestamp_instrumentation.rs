@@ -1637,3 +1637,80 @@ async fn querier_get_routed_send_route_receive() {
 
     test_context.close().await;
 }
+
+// ─── Hop latency analytics ──────────────────────────────────────────────
+
+#[cfg(feature = "stats")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn hop_latency_analytics() {
+    use zenoh::Wait;
+
+    zenoh_util::init_log_from_env_or("error");
+    let ke = "test/ts_instr/hop_latency/query";
+    let instr = make_instrumentation(true, true, true);
+
+    let mut test_context = TestSessions::new();
+    let mut config_router = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    config_router.set_mode(Some(WhatAmI::Router)).unwrap();
+    let router = test_context.open_listener_with_cfg(config_router).await;
+    tokio::time::sleep(SLEEP).await;
+
+    let mut config_client1 = test_context.get_connector_config();
+    config_client1.set_mode(Some(WhatAmI::Client)).unwrap();
+    config_client1.adminspace.set_enabled(true).unwrap();
+    config_client1
+        .insert_json5(
+            "stats/latency",
+            r#"{ enabled: true, sampling: 1.0, prefix_chunks: 2 }"#,
+        )
+        .unwrap();
+    let client1 = test_context.open_connector_with_cfg(config_client1).await;
+
+    let mut config_client2 = test_context.get_connector_config();
+    config_client2.set_mode(Some(WhatAmI::Client)).unwrap();
+    let client2 = test_context.open_connector_with_cfg(config_client2).await;
+    tokio::time::sleep(SLEEP).await;
+
+    let _queryable = ztimeout!(client2
+        .declare_queryable(ke)
+        .callback(move |query| query.reply(ke, "data").wait().unwrap()))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // The reply carries the timestamp stack of the query, so the querier observes the whole
+    // round trip: client1 -> router -> client2 -> router -> client1
+    for _ in 0..3 {
+        let replies = ztimeout!(client1.get(ke).timestamp_instrumentation(instr)).unwrap();
+        let reply = ztimeout!(replies.recv_async()).unwrap();
+        assert!(reply.result().is_ok());
+    }
+
+    let zid1 = client1.zid();
+    let reply = ztimeout!(client1.get(format!("@/{zid1}/client/stats/latency")))
+        .unwrap()
+        .recv_async()
+        .await
+        .unwrap();
+    let json: serde_json::Value =
+        serde_json::from_slice(&reply.result().unwrap().payload().to_bytes()).unwrap();
+
+    let hops = json["hops"].as_array().unwrap();
+    let hop = |router: String| {
+        hops.iter()
+            .find(|hop| hop["router"] == router.as_str())
+            .unwrap_or_else(|| panic!("no hop for {router} in {json}"))
+    };
+    for zid in [router.zid(), client2.zid(), zid1] {
+        let hop = hop(zid.to_string());
+        assert_eq!(hop["source"], zid1.to_string());
+        assert_eq!(hop["prefix"], "test/ts_instr");
+        assert!(hop["count"].as_u64().unwrap() >= 3);
+    }
+    // Both directions between the querier and the router have been observed
+    let clock_offsets = json["clock_offsets"].as_array().unwrap();
+    assert!(clock_offsets.iter().any(|offset| {
+        offset["from"] == zid1.to_string() && offset["to"] == router.zid().to_string()
+    }));
+
+    test_context.close().await;
+}
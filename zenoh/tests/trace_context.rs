@@ -0,0 +1,182 @@
+//
+// Copyright (c) 2026 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+#![cfg(feature = "tracing-instrument")]
+
+use std::{
+    sync::{Arc, Mutex, Once},
+    time::Duration,
+};
+
+use opentelemetry::trace::{TraceContextExt, TraceId, TracerProvider};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use zenoh::{config::WhatAmI, query::ConsolidationMode, Session, Wait};
+use zenoh_test::TestSessions;
+
+const SLEEP: Duration = Duration::from_secs(1);
+
+/// Installs a global subscriber exporting `tracing` spans as OpenTelemetry spans, so that they
+/// carry a trace context. It must be global as callbacks are called from zenoh runtime threads.
+fn init_tracing() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let tracer = SdkTracerProvider::builder().build().tracer("zenoh-test");
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+        tracing::subscriber::set_global_default(subscriber).unwrap();
+    });
+}
+
+fn current_trace_id() -> TraceId {
+    tracing::Span::current()
+        .context()
+        .span()
+        .span_context()
+        .trace_id()
+}
+
+/// Opens a router and two clients connected to it.
+fn open_sessions(test_context: &mut TestSessions) -> (Session, Session) {
+    let mut config_router = test_context.get_listener_config("tcp/127.0.0.1:0", 1);
+    config_router.set_mode(Some(WhatAmI::Router)).unwrap();
+    let _router = test_context.open_listener_with_cfg_sync(config_router);
+
+    let mut config_client1 = test_context.get_connector_config();
+    config_client1.set_mode(Some(WhatAmI::Client)).unwrap();
+    let client1 = test_context.open_connector_with_cfg_sync(config_client1);
+
+    let mut config_client2 = test_context.get_connector_config();
+    config_client2.set_mode(Some(WhatAmI::Client)).unwrap();
+    let client2 = test_context.open_connector_with_cfg_sync(config_client2);
+
+    std::thread::sleep(SLEEP);
+    (client1, client2)
+}
+
+#[test]
+fn trace_context_put_test() {
+    init_tracing();
+    let mut test_context = TestSessions::new();
+    let (client1, client2) = open_sessions(&mut test_context);
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let _sub = client2
+        .declare_subscriber("test/trace_context/put")
+        .callback({
+            let received = received.clone();
+            move |_| received.lock().unwrap().push(current_trace_id())
+        })
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    let mut expected = Vec::new();
+    for _ in 0..2 {
+        let _span = tracing::info_span!("publish").entered();
+        expected.push(current_trace_id());
+        client1.put("test/trace_context/put", "").wait().unwrap();
+    }
+    std::thread::sleep(SLEEP);
+
+    assert_ne!(expected[0], TraceId::INVALID);
+    assert_ne!(expected[0], expected[1]);
+    assert_eq!(*received.lock().unwrap(), expected);
+
+    test_context.close_sync();
+}
+
+#[test]
+fn trace_context_get_callback_test() {
+    init_tracing();
+    let mut test_context = TestSessions::new();
+    let (client1, client2) = open_sessions(&mut test_context);
+
+    let queried = Arc::new(Mutex::new(Vec::new()));
+    let _qbl = client2
+        .declare_queryable("test/trace_context/get")
+        .callback({
+            let queried = queried.clone();
+            move |query| {
+                queried.lock().unwrap().push(current_trace_id());
+                query.reply(query.key_expr(), "").wait().unwrap();
+            }
+        })
+        .wait()
+        .unwrap();
+    std::thread::sleep(SLEEP);
+
+    let replied = Arc::new(Mutex::new(Vec::new()));
+    let expected = {
+        let _span = tracing::info_span!("query").entered();
+        client1
+            .get("test/trace_context/get")
+            .callback({
+                let replied = replied.clone();
+                move |_| replied.lock().unwrap().push(current_trace_id())
+            })
+            .wait()
+            .unwrap();
+        current_trace_id()
+    };
+    std::thread::sleep(SLEEP);
+
+    assert_ne!(expected, TraceId::INVALID);
+    assert_eq!(*queried.lock().unwrap(), vec![expected]);
+    assert_eq!(*replied.lock().unwrap(), vec![expected]);
+
+    test_context.close_sync();
+}
+
+#[test]
+fn trace_context_get_channel_test() {
+    init_tracing();
+    let mut test_context = TestSessions::new();
+    let (client1, client2) = open_sessions(&mut test_context);
+
+    // Replies sent outside of the queryable callback continue the trace of the query,
+    // and without consolidation, reply callbacks are called in the trace of the reply
+    let queryable = client2
+        .declare_queryable("test/trace_context/get")
+        .wait()
+        .unwrap();
+    std::thread::spawn(move || {
+        while let Ok(query) = queryable.recv() {
+            query.reply(query.key_expr(), "").wait().unwrap();
+        }
+    });
+    std::thread::sleep(SLEEP);
+
+    let replied = Arc::new(Mutex::new(Vec::new()));
+    let expected = {
+        let _span = tracing::info_span!("query").entered();
+        client1
+            .get("test/trace_context/get")
+            .consolidation(ConsolidationMode::None)
+            .callback({
+                let replied = replied.clone();
+                move |_| replied.lock().unwrap().push(current_trace_id())
+            })
+            .wait()
+            .unwrap();
+        current_trace_id()
+    };
+    std::thread::sleep(SLEEP);
+
+    assert_ne!(expected, TraceId::INVALID);
+    assert_eq!(*replied.lock().unwrap(), vec![expected]);
+
+    test_context.close_sync();
+}